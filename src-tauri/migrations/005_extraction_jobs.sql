@@ -0,0 +1,10 @@
+-- Migration 005: Resumable Highlight Extraction
+-- Checkpoints batch extraction progress so an interrupted job can resume
+
+CREATE TABLE IF NOT EXISTS extraction_jobs (
+    id TEXT PRIMARY KEY,
+    processed_review_ids TEXT NOT NULL DEFAULT '[]',  -- JSON array of review IDs done
+    total INTEGER NOT NULL DEFAULT 0,
+    created_at TEXT DEFAULT (datetime('now')),
+    updated_at TEXT DEFAULT (datetime('now'))
+);
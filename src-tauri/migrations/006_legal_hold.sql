@@ -0,0 +1,4 @@
+-- Migration 006: Legal Hold Flag
+-- Marks employees whose data is subject to litigation or investigation holds
+
+ALTER TABLE employees ADD COLUMN legal_hold INTEGER NOT NULL DEFAULT 0;
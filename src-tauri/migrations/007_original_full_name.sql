@@ -0,0 +1,4 @@
+-- Migration 007: Original Name Preservation
+-- Imports canonicalize full_name to "First Last"; keep the name exactly as it appeared in the source file
+
+ALTER TABLE employees ADD COLUMN original_full_name TEXT;
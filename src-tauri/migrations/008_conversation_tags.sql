@@ -0,0 +1,7 @@
+-- Migration 008: Conversation Tags & Archiving
+-- Supports multi-select tagging and archiving from the sidebar
+
+ALTER TABLE conversations ADD COLUMN tags TEXT NOT NULL DEFAULT '[]';  -- JSON array of tag strings
+ALTER TABLE conversations ADD COLUMN archived INTEGER NOT NULL DEFAULT 0;
+
+CREATE INDEX IF NOT EXISTS idx_conversations_archived ON conversations(archived);
@@ -0,0 +1,4 @@
+-- Migration 009: Conversation Memory Exclusion
+-- Lets users keep one-off or sensitive conversations out of memory retrieval
+
+ALTER TABLE conversations ADD COLUMN exclude_from_memory INTEGER NOT NULL DEFAULT 0;
@@ -0,0 +1,12 @@
+-- Migration 010: Review Cycle Events
+-- Audit trail of review cycle status transitions (opened, closed, reopened)
+
+CREATE TABLE IF NOT EXISTS review_cycle_events (
+    id TEXT PRIMARY KEY,
+    cycle_id TEXT NOT NULL,                -- No FK: the trail outlives a deleted cycle
+    old_status TEXT,                       -- NULL when the cycle was created
+    new_status TEXT NOT NULL,
+    created_at TEXT DEFAULT (datetime('now'))
+);
+
+CREATE INDEX IF NOT EXISTS idx_review_cycle_events_cycle ON review_cycle_events(cycle_id, created_at);
@@ -0,0 +1,14 @@
+-- Migration 011: Department Changes
+-- History of department moves, recorded when an employee's department is updated
+
+CREATE TABLE IF NOT EXISTS department_changes (
+    id TEXT PRIMARY KEY,
+    employee_id TEXT NOT NULL,
+    from_department TEXT,                  -- NULL when the employee had no department
+    to_department TEXT NOT NULL,
+    changed_at TEXT DEFAULT (datetime('now')),
+    FOREIGN KEY (employee_id) REFERENCES employees(id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_department_changes_employee ON department_changes(employee_id);
+CREATE INDEX IF NOT EXISTS idx_department_changes_date ON department_changes(changed_at);
@@ -0,0 +1,7 @@
+-- Migration 012: Audit Verification Snapshot
+-- Keep the query type and aggregates each answer was verified against, so
+-- past answers can be re-verified when the claim extraction improves
+
+ALTER TABLE audit_log ADD COLUMN context_snapshot TEXT;  -- JSON AuditContextSnapshot
+ALTER TABLE audit_log ADD COLUMN verification_status TEXT;
+ALTER TABLE audit_log ADD COLUMN verified_at TEXT;
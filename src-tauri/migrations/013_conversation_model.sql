@@ -0,0 +1,4 @@
+-- Migration 013: Conversation Model
+-- Remembers the Claude model chosen for a conversation; NULL uses the default
+
+ALTER TABLE conversations ADD COLUMN model TEXT;
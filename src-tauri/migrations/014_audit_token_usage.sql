@@ -0,0 +1,5 @@
+-- Migration 014: Audit Token Usage
+-- Token counts reported by the API for each answer, for cost tracking
+
+ALTER TABLE audit_log ADD COLUMN input_tokens INTEGER;
+ALTER TABLE audit_log ADD COLUMN output_tokens INTEGER;
@@ -0,0 +1,6 @@
+-- Migration 015: Summary Embeddings
+-- Cached embedding of each conversation summary (little-endian f32 vector)
+-- for semantic memory search; NULL until embedded, and cleared whenever the
+-- summary changes
+
+ALTER TABLE conversations ADD COLUMN summary_embedding BLOB;
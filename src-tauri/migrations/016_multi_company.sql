@@ -0,0 +1,9 @@
+-- Migration 016: Multiple Companies
+-- Employees belong to one legal entity (company row). Existing employees,
+-- and any created without a company, belong to the 'default' company.
+
+ALTER TABLE employees ADD COLUMN company_id TEXT DEFAULT 'default';
+
+UPDATE employees SET company_id = 'default' WHERE company_id IS NULL OR TRIM(company_id) = '';
+
+CREATE INDEX IF NOT EXISTS idx_employees_company ON employees(company_id);
@@ -0,0 +1,7 @@
+-- Migration 017: Pinned Conversations
+-- Pinned conversations sort above the rest of the sidebar. The archived
+-- flag already exists (008_conversation_tags.sql).
+
+ALTER TABLE conversations ADD COLUMN pinned INTEGER NOT NULL DEFAULT 0;
+
+CREATE INDEX IF NOT EXISTS idx_conversations_pinned ON conversations(pinned);
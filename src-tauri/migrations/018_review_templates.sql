@@ -0,0 +1,13 @@
+-- Migration 018: Review Templates
+-- Named sets of review sections (with prompts and required flags) that a
+-- review cycle can use to structure its reviews
+
+CREATE TABLE IF NOT EXISTS review_templates (
+    id TEXT PRIMARY KEY,
+    name TEXT NOT NULL UNIQUE,
+    description TEXT,
+    sections TEXT NOT NULL DEFAULT '[]',   -- JSON array of {section, prompt, required}
+    created_at TEXT DEFAULT (datetime('now'))
+);
+
+ALTER TABLE review_cycles ADD COLUMN template_id TEXT REFERENCES review_templates(id) ON DELETE SET NULL;
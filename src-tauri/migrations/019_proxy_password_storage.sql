@@ -0,0 +1,5 @@
+-- Migration 019: Proxy Password Storage
+-- The proxy password moved to file-based key storage (keyring.rs); drop any
+-- plaintext copy left in settings
+
+DELETE FROM settings WHERE key = 'proxy_password';
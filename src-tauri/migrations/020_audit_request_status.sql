@@ -0,0 +1,5 @@
+-- Migration 020: Audit Request Status
+-- Whether the answer streamed to completion or was cancelled by the user.
+-- A cancelled request was still sent to the API, so it is audited too.
+
+ALTER TABLE audit_log ADD COLUMN request_status TEXT NOT NULL DEFAULT 'completed';
@@ -6,6 +6,8 @@
 // - Filters are applied via parameterized queries
 // - Claude only emits intent + groupBy, never raw SQL
 
+use std::sync::LazyLock;
+
 use sqlx::Row;
 use thiserror::Error;
 
@@ -92,6 +94,44 @@ pub fn is_supported_combination(intent: ChartIntent, group_by: GroupBy) -> bool
 // SQL Templates
 // =============================================================================
 
+/// eNPS breakdown (Promoters/Passives/Detractors) over each active
+/// employee's latest response, picked the same way as the eNPS module does
+static ENPS_BREAKDOWN_SQL: LazyLock<String> = LazyLock::new(|| {
+    format!(
+        r#"
+        WITH latest_responses AS (
+            SELECT
+                er.employee_id,
+                er.score,
+                ROW_NUMBER() OVER (
+                    PARTITION BY er.employee_id
+                    ORDER BY {}
+                ) as rn
+            FROM enps_responses er
+            JOIN employees e ON er.employee_id = e.id
+            WHERE e.status = 'active'
+        )
+        SELECT
+            CASE
+                WHEN score >= 9 THEN 'Promoters'
+                WHEN score >= 7 THEN 'Passives'
+                ELSE 'Detractors'
+            END as label,
+            COUNT(*) as value,
+            CASE
+                WHEN score >= 9 THEN 1
+                WHEN score >= 7 THEN 2
+                ELSE 3
+            END as sort_order
+        FROM latest_responses
+        WHERE rn = 1
+        GROUP BY label
+        ORDER BY sort_order
+        "#,
+        crate::enps::latest_survey_order("er.survey_date")
+    )
+});
+
 /// Get the base SQL template for an intent + groupBy combination.
 /// These templates return (label, value) pairs for charting.
 fn get_sql_template(intent: ChartIntent, group_by: GroupBy) -> Option<&'static str> {
@@ -192,7 +232,10 @@ fn get_sql_template(intent: ChartIntent, group_by: GroupBy) -> Option<&'static s
                 SELECT
                     pr.employee_id,
                     pr.overall_rating,
-                    ROW_NUMBER() OVER (PARTITION BY pr.employee_id ORDER BY rc.end_date DESC) as rn
+                    ROW_NUMBER() OVER (
+                        PARTITION BY pr.employee_id
+                        ORDER BY COALESCE(date(rc.end_date), date(rc.start_date), rc.created_at) DESC
+                    ) as rn
                 FROM performance_ratings pr
                 JOIN review_cycles rc ON pr.review_cycle_id = rc.id
                 JOIN employees e ON pr.employee_id = e.id
@@ -220,35 +263,7 @@ fn get_sql_template(intent: ChartIntent, group_by: GroupBy) -> Option<&'static s
         ),
 
         // eNPS breakdown (Promoters/Passives/Detractors)
-        (ChartIntent::EnpsBreakdown, GroupBy::Status) => Some(
-            r#"
-            WITH latest_responses AS (
-                SELECT
-                    er.employee_id,
-                    er.score,
-                    ROW_NUMBER() OVER (PARTITION BY er.employee_id ORDER BY er.survey_date DESC) as rn
-                FROM enps_responses er
-                JOIN employees e ON er.employee_id = e.id
-                WHERE e.status = 'active'
-            )
-            SELECT
-                CASE
-                    WHEN score >= 9 THEN 'Promoters'
-                    WHEN score >= 7 THEN 'Passives'
-                    ELSE 'Detractors'
-                END as label,
-                COUNT(*) as value,
-                CASE
-                    WHEN score >= 9 THEN 1
-                    WHEN score >= 7 THEN 2
-                    ELSE 3
-                END as sort_order
-            FROM latest_responses
-            WHERE rn = 1
-            GROUP BY label
-            ORDER BY sort_order
-            "#,
-        ),
+        (ChartIntent::EnpsBreakdown, GroupBy::Status) => Some(ENPS_BREAKDOWN_SQL.as_str()),
 
         // Attrition by quarter (time series)
         (ChartIntent::AttritionAnalysis, GroupBy::Quarter) => Some(
@@ -516,6 +531,14 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_rating_template_uses_shared_cycle_order() {
+        // Static template, so it spells out the ordering instead of calling the helper
+        let sql = get_sql_template(ChartIntent::RatingDistribution, GroupBy::RatingBucket).unwrap();
+        let expected = format!("ORDER BY {} DESC", crate::performance_ratings::cycle_recency("rc"));
+        assert!(sql.contains(&expected));
+    }
+
     #[test]
     fn test_filter_clause_empty() {
         let filters = ChartFilters::default();
@@ -5,16 +5,25 @@
 // 1. Create audit entries after each Claude API interaction
 // 2. List/filter audit entries for review
 // 3. Export audit log to CSV format
+// 4. Re-verify past answers against the context snapshot stored with them
 //
-// Design: Audit entries are created AFTER streaming completes.
+// Design: Audit entries are created AFTER streaming completes or is cancelled.
 // Failures are logged but never block the chat flow.
 
+use std::collections::{HashMap, HashSet};
+
+use regex::Regex;
 use serde::{Deserialize, Serialize};
-use sqlx::FromRow;
+use sqlx::{FromRow, Row};
 use thiserror::Error;
 use uuid::Uuid;
 
+use crate::chat::TokenUsage;
+use crate::context::{OrgAggregates, QueryType, VerificationResult, VerificationStatus};
+use crate::csv_export::{CsvExportOptions, CsvWriter};
 use crate::db::DbPool;
+use crate::pdf_export::PdfWriter;
+use crate::pii::CompiledPiiPattern;
 
 // ============================================================================
 // Error Types
@@ -30,6 +39,8 @@ pub enum AuditError {
     InvalidInput(String),
     #[error("Export error: {0}")]
     ExportError(String),
+    #[error("{}", crate::db::READ_ONLY_MESSAGE)]
+    ReadOnly,
 }
 
 impl From<sqlx::Error> for AuditError {
@@ -38,6 +49,12 @@ impl From<sqlx::Error> for AuditError {
     }
 }
 
+impl From<crate::db::ReadOnly> for AuditError {
+    fn from(_: crate::db::ReadOnly) -> Self {
+        AuditError::ReadOnly
+    }
+}
+
 // Make AuditError serializable for Tauri commands
 impl serde::Serialize for AuditError {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
@@ -61,8 +78,18 @@ pub struct AuditEntry {
     pub response_text: String,
     pub context_used: Option<String>, // JSON array of employee IDs
     pub created_at: String,
+    /// Reported by the API; None for entries written before usage was tracked
+    pub input_tokens: Option<i64>,
+    pub output_tokens: Option<i64>,
+    /// `REQUEST_COMPLETED`, or `REQUEST_CANCELLED` when the user stopped the answer
+    pub request_status: String,
 }
 
+/// Stored `request_status` for an answer that streamed to the end
+pub const REQUEST_COMPLETED: &str = "completed";
+/// Stored `request_status` for an answer the user cancelled mid-stream
+pub const REQUEST_CANCELLED: &str = "cancelled";
+
 /// Lightweight audit entry for list display
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AuditListItem {
@@ -81,6 +108,37 @@ pub struct CreateAuditEntry {
     pub request_redacted: String,
     pub response_text: String,
     pub employee_ids_used: Vec<String>,
+    /// What the answer was verified against, kept for re-verification
+    #[serde(default)]
+    pub context_snapshot: Option<AuditContextSnapshot>,
+    #[serde(default)]
+    pub verification_status: Option<VerificationStatus>,
+    #[serde(default)]
+    pub usage: Option<TokenUsage>,
+    /// The stream was cancelled; response_text is what arrived before that
+    #[serde(default)]
+    pub cancelled: bool,
+}
+
+/// The verification inputs for one answer, stored as JSON with the entry
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuditContextSnapshot {
+    pub query_type: QueryType,
+    pub aggregates: Option<OrgAggregates>,
+}
+
+/// Outcome of re-verifying a batch of audit entries
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ReverifyReport {
+    pub entries_checked: usize,
+    pub reverified: usize,
+    /// Entries written before snapshots were stored
+    pub skipped_without_snapshot: usize,
+    /// Re-verified entries whose stored status changed
+    pub status_changed: usize,
+    /// Entries that couldn't be re-verified, as "<id>: <reason>"; the rest
+    /// of the batch still runs
+    pub errors: Vec<String>,
 }
 
 /// Filter options for listing/exporting audit entries
@@ -91,21 +149,85 @@ pub struct AuditFilter {
     pub end_date: Option<String>,   // ISO 8601 format
 }
 
+/// Options controlling what leaves the machine in an export
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuditExportOptions {
+    /// Run free-text columns and context_used through the PII scanner
+    pub scrub_pii: bool,
+    /// Replace known employee names with stable "[emp:<id>]" tokens, so an
+    /// external reviewer can't read names but staff can map them back
+    #[serde(default)]
+    pub pseudonymize_names: bool,
+}
+
+impl Default for AuditExportOptions {
+    fn default() -> Self {
+        Self {
+            scrub_pii: true,
+            pseudonymize_names: false,
+        }
+    }
+}
+
+/// How often an employee appeared in chat context
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct EmployeeMention {
+    pub employee_id: String,
+    pub full_name: String,
+    pub department: Option<String>,
+    /// Number of audited interactions whose context included this employee
+    pub mention_count: i64,
+    pub last_mentioned_at: String,
+}
+
 /// CSV export result
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ExportResult {
     pub csv_content: String,
     pub row_count: usize,
+    /// Suggested download name (timestamp only, never entry content)
+    pub filename: String,
+}
+
+/// PDF export result
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PdfExportResult {
+    pub pdf_data: Vec<u8>,
+    pub entry_count: usize,
+    pub page_count: usize,
+    /// Suggested download name (timestamp only, never entry content)
+    pub filename: String,
+}
+
+/// Dry-run preview of a retention purge
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PurgePreview {
+    pub cutoff: String,
+    pub entry_count: i64,
+    /// Pass back to `purge_audit_entries_before` to confirm. It encodes the
+    /// cutoff and count, so it stops matching if either changes.
+    pub confirmation_token: String,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PurgeResult {
+    pub cutoff: String,
+    pub deleted: i64,
+    /// The entry recording that this purge happened
+    pub meta_entry_id: String,
+}
+
+/// Responses longer than this many characters are cut short in the PDF
+const PDF_MAX_RESPONSE_CHARS: usize = 4000;
+
 // ============================================================================
 // Core Functions
 // ============================================================================
 
 /// Create a new audit log entry
 ///
-/// Called by frontend after streaming response completes.
-/// Employee IDs are serialized to JSON for storage.
+/// Called by the chat stream once the response completes, using the text
+/// assembled server-side. Employee IDs are serialized to JSON for storage.
 pub async fn create_audit_entry(
     pool: &DbPool,
     input: CreateAuditEntry,
@@ -120,11 +242,19 @@ pub async fn create_audit_entry(
             AuditError::InvalidInput(format!("Failed to serialize employee IDs: {}", e))
         })?)
     };
+    let context_snapshot = input
+        .context_snapshot
+        .as_ref()
+        .map(serde_json::to_string)
+        .transpose()
+        .map_err(|e| AuditError::InvalidInput(format!("Failed to serialize context snapshot: {}", e)))?;
+    let verification_status = input.verification_status.map(status_text);
 
     sqlx::query(
         r#"
-        INSERT INTO audit_log (id, conversation_id, request_redacted, response_text, context_used, created_at)
-        VALUES (?, ?, ?, ?, ?, datetime('now'))
+        INSERT INTO audit_log (id, conversation_id, request_redacted, response_text, context_used, created_at,
+            context_snapshot, verification_status, verified_at, input_tokens, output_tokens, request_status)
+        VALUES (?, ?, ?, ?, ?, datetime('now'), ?, ?, CASE WHEN ? IS NULL THEN NULL ELSE datetime('now') END, ?, ?, ?)
         "#,
     )
     .bind(&id)
@@ -132,6 +262,12 @@ pub async fn create_audit_entry(
     .bind(&input.request_redacted)
     .bind(&input.response_text)
     .bind(&context_used)
+    .bind(&context_snapshot)
+    .bind(&verification_status)
+    .bind(&verification_status)
+    .bind(input.usage.map(|u| u.input_tokens))
+    .bind(input.usage.map(|u| u.output_tokens))
+    .bind(if input.cancelled { REQUEST_CANCELLED } else { REQUEST_COMPLETED })
     .execute(pool)
     .await?;
 
@@ -142,7 +278,8 @@ pub async fn create_audit_entry(
 pub async fn get_audit_entry(pool: &DbPool, id: &str) -> Result<AuditEntry, AuditError> {
     let entry = sqlx::query_as::<_, AuditEntry>(
         r#"
-        SELECT id, conversation_id, request_redacted, response_text, context_used, created_at
+        SELECT id, conversation_id, request_redacted, response_text, context_used, created_at,
+            input_tokens, output_tokens, request_status
         FROM audit_log
         WHERE id = ?
         "#,
@@ -167,28 +304,12 @@ pub async fn list_audit_entries(
     let limit = limit.unwrap_or(50);
     let offset = offset.unwrap_or(0);
 
-    // Build dynamic query with filters
-    let mut conditions = vec!["1=1".to_string()];
-    let mut bindings: Vec<String> = vec![];
-
-    if let Some(conv_id) = &filter.conversation_id {
-        conditions.push("conversation_id = ?".to_string());
-        bindings.push(conv_id.clone());
-    }
-
-    if let Some(start) = &filter.start_date {
-        conditions.push("created_at >= ?".to_string());
-        bindings.push(start.clone());
-    }
-
-    if let Some(end) = &filter.end_date {
-        conditions.push("created_at <= ?".to_string());
-        bindings.push(end.clone());
-    }
+    let (conditions, bindings) = filter_conditions(&filter);
 
     let query = format!(
         r#"
-        SELECT id, conversation_id, request_redacted, response_text, context_used, created_at
+        SELECT id, conversation_id, request_redacted, response_text, context_used, created_at,
+            input_tokens, output_tokens, request_status
         FROM audit_log
         WHERE {}
         ORDER BY created_at DESC
@@ -238,24 +359,7 @@ pub async fn count_audit_entries(
 ) -> Result<i64, AuditError> {
     let filter = filter.unwrap_or_default();
 
-    // Build dynamic query with filters
-    let mut conditions = vec!["1=1".to_string()];
-    let mut bindings: Vec<String> = vec![];
-
-    if let Some(conv_id) = &filter.conversation_id {
-        conditions.push("conversation_id = ?".to_string());
-        bindings.push(conv_id.clone());
-    }
-
-    if let Some(start) = &filter.start_date {
-        conditions.push("created_at >= ?".to_string());
-        bindings.push(start.clone());
-    }
-
-    if let Some(end) = &filter.end_date {
-        conditions.push("created_at <= ?".to_string());
-        bindings.push(end.clone());
-    }
+    let (conditions, bindings) = filter_conditions(&filter);
 
     let query = format!(
         "SELECT COUNT(*) FROM audit_log WHERE {}",
@@ -271,38 +375,160 @@ pub async fn count_audit_entries(
     Ok(result.0)
 }
 
+/// Rank employees by how often they appeared in chat context
+///
+/// Counts audit entries whose `context_used` includes each employee, optionally
+/// only those created at or after `since` (ISO 8601). Employees who have since
+/// been deleted are left out. Ties break on most recent mention.
+pub async fn get_most_discussed_employees(
+    pool: &DbPool,
+    since: Option<String>,
+    limit: Option<usize>,
+) -> Result<Vec<EmployeeMention>, AuditError> {
+    let limit = limit.unwrap_or(10);
+
+    let rows = sqlx::query(
+        r#"
+        SELECT context_used, created_at FROM audit_log
+        WHERE context_used IS NOT NULL AND (? IS NULL OR created_at >= ?)
+        "#,
+    )
+    .bind(&since)
+    .bind(&since)
+    .fetch_all(pool)
+    .await?;
+
+    let entries: Vec<(String, String)> = rows
+        .iter()
+        .map(|row| (row.get("context_used"), row.get("created_at")))
+        .collect();
+    let tallies = tally_employee_mentions(&entries);
+
+    let mut mentions = Vec::with_capacity(tallies.len());
+    for (employee_id, (count, last_mentioned_at)) in tallies {
+        let employee = sqlx::query("SELECT full_name, department FROM employees WHERE id = ?")
+            .bind(&employee_id)
+            .fetch_optional(pool)
+            .await?;
+        if let Some(row) = employee {
+            mentions.push(EmployeeMention {
+                employee_id,
+                full_name: row.get("full_name"),
+                department: row.get("department"),
+                mention_count: count,
+                last_mentioned_at,
+            });
+        }
+    }
+
+    mentions.sort_by(|a, b| {
+        b.mention_count
+            .cmp(&a.mention_count)
+            .then_with(|| b.last_mentioned_at.cmp(&a.last_mentioned_at))
+            .then_with(|| a.full_name.cmp(&b.full_name))
+    });
+    mentions.truncate(limit);
+
+    Ok(mentions)
+}
+
+/// Count entries per employee ID from (context_used JSON, created_at) pairs.
+/// An ID listed twice in one entry counts once; malformed JSON is skipped.
+fn tally_employee_mentions(entries: &[(String, String)]) -> HashMap<String, (i64, String)> {
+    let mut tallies: HashMap<String, (i64, String)> = HashMap::new();
+
+    for (context_used, created_at) in entries {
+        let Ok(ids) = serde_json::from_str::<Vec<String>>(context_used) else {
+            continue;
+        };
+        let unique: HashSet<String> = ids.into_iter().collect();
+        for id in unique {
+            let tally = tallies.entry(id).or_insert_with(|| (0, created_at.clone()));
+            tally.0 += 1;
+            if *created_at > tally.1 {
+                tally.1 = created_at.clone();
+            }
+        }
+    }
+
+    tallies
+}
+
 /// Export audit log to CSV format
 ///
 /// Returns CSV content as a string for download.
 /// Response is truncated to first 500 chars to keep file size reasonable.
+/// PII scrubbing is on unless the options explicitly disable it.
+/// Delimiter, BOM, and line endings follow `csv_options` (comma/LF by default).
 pub async fn export_to_csv(
     pool: &DbPool,
     filter: Option<AuditFilter>,
+    options: Option<AuditExportOptions>,
+    csv_options: Option<CsvExportOptions>,
 ) -> Result<ExportResult, AuditError> {
     let filter = filter.unwrap_or_default();
+    let options = options.unwrap_or_default();
+    let csv_options = csv_options.unwrap_or_default();
+    csv_options.validate().map_err(AuditError::InvalidInput)?;
+
+    let entries = fetch_export_entries(pool, &filter).await?;
+    let pseudonymizer = load_pseudonymizer(pool, &options).await?;
+    let custom = crate::pii::load_custom_pii_patterns(pool).await;
+
+    Ok(ExportResult {
+        csv_content: build_csv(&entries, &options, &csv_options, pseudonymizer.as_ref(), &custom),
+        row_count: entries.len(),
+        filename: export_filename(chrono::Utc::now(), "csv"),
+    })
+}
 
-    // Build dynamic query with filters
-    let mut conditions = vec!["1=1".to_string()];
-    let mut bindings: Vec<String> = vec![];
-
-    if let Some(conv_id) = &filter.conversation_id {
-        conditions.push("conversation_id = ?".to_string());
-        bindings.push(conv_id.clone());
-    }
+/// Export audit log as a paginated PDF for auditors
+///
+/// A header block gives the company, date range, and entry count, followed by
+/// one section per entry with the redacted request and the response.
+/// Responses over `PDF_MAX_RESPONSE_CHARS` are truncated with a note. Uses the
+/// same filter, ordering, and scrubbing options as the CSV export.
+pub async fn export_to_pdf(
+    pool: &DbPool,
+    filter: Option<AuditFilter>,
+    options: Option<AuditExportOptions>,
+) -> Result<PdfExportResult, AuditError> {
+    let filter = filter.unwrap_or_default();
+    let options = options.unwrap_or_default();
+
+    let entries = fetch_export_entries(pool, &filter).await?;
+    let pseudonymizer = load_pseudonymizer(pool, &options).await?;
+    let custom = crate::pii::load_custom_pii_patterns(pool).await;
+    // No company profile yet just leaves the name out of the header
+    let company_name = crate::company::get_company(pool).await.ok().map(|c| c.name);
+
+    let now = chrono::Utc::now();
+    let (pdf_data, page_count) = build_pdf(
+        &entries,
+        &filter,
+        &options,
+        pseudonymizer.as_ref(),
+        &custom,
+        company_name.as_deref(),
+        now,
+    );
 
-    if let Some(start) = &filter.start_date {
-        conditions.push("created_at >= ?".to_string());
-        bindings.push(start.clone());
-    }
+    Ok(PdfExportResult {
+        pdf_data,
+        entry_count: entries.len(),
+        page_count,
+        filename: export_filename(now, "pdf"),
+    })
+}
 
-    if let Some(end) = &filter.end_date {
-        conditions.push("created_at <= ?".to_string());
-        bindings.push(end.clone());
-    }
+/// Entries matching the filter, newest first
+async fn fetch_export_entries(pool: &DbPool, filter: &AuditFilter) -> Result<Vec<AuditEntry>, AuditError> {
+    let (conditions, bindings) = filter_conditions(filter);
 
     let query = format!(
         r#"
-        SELECT id, conversation_id, request_redacted, response_text, context_used, created_at
+        SELECT id, conversation_id, request_redacted, response_text, context_used, created_at,
+            input_tokens, output_tokens, request_status
         FROM audit_log
         WHERE {}
         ORDER BY created_at DESC
@@ -315,42 +541,449 @@ pub async fn export_to_csv(
         sqlx_query = sqlx_query.bind(binding);
     }
 
-    let entries = sqlx_query.fetch_all(pool).await?;
-    let row_count = entries.len();
+    Ok(sqlx_query.fetch_all(pool).await?)
+}
 
-    // Build CSV content
-    let mut csv = String::new();
+async fn load_pseudonymizer(
+    pool: &DbPool,
+    options: &AuditExportOptions,
+) -> Result<Option<NamePseudonymizer>, AuditError> {
+    if !options.pseudonymize_names {
+        return Ok(None);
+    }
+    let roster: Vec<(String, String)> = sqlx::query_as("SELECT id, full_name FROM employees")
+        .fetch_all(pool)
+        .await?;
+    NamePseudonymizer::from_roster(&roster).map(Some)
+}
+
+/// Names are pseudonymized (when a pseudonymizer is given) before PII
+/// scrubbing, so names not on the roster are left to the scrubber. The
+/// scrubber applies the saved custom patterns alongside the built-ins.
+fn scrub_for_export(
+    text: &str,
+    options: &AuditExportOptions,
+    pseudonymizer: Option<&NamePseudonymizer>,
+    custom: &[CompiledPiiPattern],
+) -> String {
+    let text = match pseudonymizer {
+        Some(p) => p.apply(text),
+        None => text.to_string(),
+    };
+    if options.scrub_pii {
+        crate::pii::scan_and_redact_with(&text, custom).redacted_text
+    } else {
+        text
+    }
+}
+
+/// Render audit entries as CSV
+fn build_csv(
+    entries: &[AuditEntry],
+    options: &AuditExportOptions,
+    csv_options: &CsvExportOptions,
+    pseudonymizer: Option<&NamePseudonymizer>,
+    custom: &[CompiledPiiPattern],
+) -> String {
+    let scrub = |text: &str| scrub_for_export(text, options, pseudonymizer, custom);
+
+    let mut csv = CsvWriter::new(csv_options);
 
     // Header row
-    csv.push_str("id,timestamp,conversation_id,request_redacted,response_preview,employee_ids_used\n");
+    csv.write_row(&[
+        "id",
+        "timestamp",
+        "conversation_id",
+        "request_redacted",
+        "response_preview",
+        "employee_ids_used",
+    ]);
 
     // Data rows
-    for entry in &entries {
+    for entry in entries {
+        // Fall back to the raw column if it isn't the expected JSON array,
+        // so malformed context still goes through the scrubber
         let employee_ids = entry
             .context_used
             .as_ref()
-            .and_then(|json| serde_json::from_str::<Vec<String>>(json).ok())
-            .map(|ids| ids.join(";"))
+            .map(|json| {
+                serde_json::from_str::<Vec<String>>(json)
+                    .map(|ids| ids.join(";"))
+                    .unwrap_or_else(|_| json.clone())
+            })
             .unwrap_or_default();
 
-        csv.push_str(&format!(
-            "{},{},{},{},{},{}\n",
-            escape_csv(&entry.id),
-            escape_csv(&entry.created_at),
-            escape_csv(&entry.conversation_id.clone().unwrap_or_default()),
-            escape_csv(&entry.request_redacted),
-            escape_csv(&truncate_preview(&entry.response_text, 500)),
-            escape_csv(&employee_ids),
-        ));
+        csv.write_row(&[
+            entry.id.clone(),
+            entry.created_at.clone(),
+            entry.conversation_id.clone().unwrap_or_default(),
+            scrub(&entry.request_redacted),
+            truncate_preview(&scrub(&entry.response_text), 500),
+            scrub(&employee_ids),
+        ]);
     }
 
-    Ok(ExportResult { csv_content: csv, row_count })
+    csv.finish()
+}
+
+/// Render audit entries as a PDF, returning the bytes and page count
+fn build_pdf(
+    entries: &[AuditEntry],
+    filter: &AuditFilter,
+    options: &AuditExportOptions,
+    pseudonymizer: Option<&NamePseudonymizer>,
+    custom: &[CompiledPiiPattern],
+    company_name: Option<&str>,
+    generated_at: chrono::DateTime<chrono::Utc>,
+) -> (Vec<u8>, usize) {
+    let scrub = |text: &str| scrub_for_export(text, options, pseudonymizer, custom);
+
+    let date_range = match (&filter.start_date, &filter.end_date) {
+        (Some(start), Some(end)) => format!("{} to {}", start, end),
+        (Some(start), None) => format!("From {}", start),
+        (None, Some(end)) => format!("Through {}", end),
+        (None, None) => "All dates".to_string(),
+    };
+
+    let mut pdf = PdfWriter::new();
+    pdf.heading("Audit Log Export");
+    pdf.paragraph(&format!("Company: {}", company_name.unwrap_or("(not set)")));
+    pdf.paragraph(&format!("Date range: {}", date_range));
+    if let Some(conversation_id) = &filter.conversation_id {
+        pdf.paragraph(&format!("Conversation: {}", conversation_id));
+    }
+    pdf.paragraph(&format!("Generated: {}", generated_at.format("%Y-%m-%d %H:%M UTC")));
+    pdf.paragraph(&format!("Entries: {}", entries.len()));
+    pdf.paragraph(&format!(
+        "PII scrubbing: {}",
+        if options.scrub_pii { "on" } else { "off" }
+    ));
+
+    let total = entries.len();
+    for (index, entry) in entries.iter().enumerate() {
+        pdf.blank_line();
+        pdf.label(&format!("Entry {} of {}", index + 1, total));
+        pdf.paragraph(&format!("Timestamp: {}", entry.created_at));
+        pdf.paragraph(&format!("Entry ID: {}", entry.id));
+        if let Some(conversation_id) = &entry.conversation_id {
+            pdf.paragraph(&format!("Conversation: {}", conversation_id));
+        }
+        pdf.label("Request:");
+        pdf.paragraph(&scrub(&entry.request_redacted));
+        pdf.label("Response:");
+        let response = scrub(&entry.response_text);
+        let response = response.trim();
+        if response.chars().count() > PDF_MAX_RESPONSE_CHARS {
+            let cut: String = response.chars().take(PDF_MAX_RESPONSE_CHARS).collect();
+            pdf.paragraph(&format!("{}...(truncated)", cut.trim_end()));
+        } else {
+            pdf.paragraph(response);
+        }
+    }
+
+    let page_count = pdf.page_count().max(1);
+    (pdf.finish(), page_count)
+}
+
+/// Roster names per compiled pattern, so a large roster stays under the
+/// regex size limit
+const PSEUDONYM_NAMES_PER_PATTERN: usize = 500;
+
+/// Token for a name shared by more than one employee
+const AMBIGUOUS_NAME_TOKEN: &str = "[emp:ambiguous]";
+
+/// Replaces employee full names with "[emp:<id>]" tokens using the roster.
+/// Matching is case-insensitive on whole words and tolerates any run of
+/// whitespace between name parts. Names shared by more than one employee
+/// become `AMBIGUOUS_NAME_TOKEN` rather than being linked to the wrong person.
+struct NamePseudonymizer {
+    /// Longest names first, across and within patterns
+    patterns: Vec<Regex>,
+    /// None for a name shared by several employees
+    ids_by_name: HashMap<String, Option<String>>,
+}
+
+impl NamePseudonymizer {
+    /// Fails rather than returning a pseudonymizer that would let names
+    /// through
+    fn from_roster(roster: &[(String, String)]) -> Result<Self, AuditError> {
+        let mut ids_by_name: HashMap<String, Option<String>> = HashMap::new();
+        for (id, full_name) in roster {
+            let key = normalize_name(full_name);
+            if key.is_empty() {
+                continue;
+            }
+            let entry = ids_by_name.entry(key).or_insert_with(|| Some(id.clone()));
+            if entry.as_ref().is_some_and(|prev| prev != id) {
+                *entry = None;
+            }
+        }
+
+        // Longest names first so "Sarah Chen-Lopez" wins over "Sarah Chen"
+        let mut names: Vec<&String> = ids_by_name.keys().collect();
+        names.sort_by(|a, b| b.len().cmp(&a.len()).then(a.cmp(b)));
+        let patterns = names
+            .chunks(PSEUDONYM_NAMES_PER_PATTERN)
+            .map(|chunk| {
+                let alternatives: Vec<String> = chunk
+                    .iter()
+                    .map(|name| name.split(' ').map(regex::escape).collect::<Vec<_>>().join(r"\s+"))
+                    .collect();
+                Regex::new(&format!(r"(?i)\b(?:{})\b", alternatives.join("|")))
+                    .map_err(|e| AuditError::ExportError(format!("Failed to build name pattern: {}", e)))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Self { patterns, ids_by_name })
+    }
+
+    fn apply(&self, text: &str) -> String {
+        let mut text = text.to_string();
+        for pattern in &self.patterns {
+            text = pattern
+                .replace_all(&text, |caps: &regex::Captures| {
+                    let matched = &caps[0];
+                    match self.ids_by_name.get(&normalize_name(matched)) {
+                        Some(Some(id)) => format!("[emp:{}]", id),
+                        Some(None) => AMBIGUOUS_NAME_TOKEN.to_string(),
+                        None => matched.to_string(),
+                    }
+                })
+                .into_owned();
+        }
+        text
+    }
+}
+
+/// Lowercase and collapse whitespace so roster names and matches compare equal
+fn normalize_name(name: &str) -> String {
+    name.split_whitespace().collect::<Vec<_>>().join(" ").to_lowercase()
+}
+
+/// Suggested export filename built only from the export time
+fn export_filename(now: chrono::DateTime<chrono::Utc>, extension: &str) -> String {
+    format!("audit_log_{}.{}", now.format("%Y-%m-%d_%H%M"), extension)
+}
+
+// ============================================================================
+// Retention
+// ============================================================================
+
+/// Same layout as SQLite's datetime('now'), which fills created_at
+fn cutoff_text(cutoff: chrono::DateTime<chrono::Utc>) -> String {
+    cutoff.format("%Y-%m-%d %H:%M:%S").to_string()
+}
+
+fn purge_token(cutoff: &str, count: i64) -> String {
+    format!("PURGE {} BEFORE {}", count, cutoff)
+}
+
+/// Count entries created before `cutoff` without deleting anything. Entries
+/// with an unparseable created_at are never counted or purged.
+pub async fn count_audit_entries_before(
+    pool: &DbPool,
+    cutoff: chrono::DateTime<chrono::Utc>,
+) -> Result<PurgePreview, AuditError> {
+    let cutoff = cutoff_text(cutoff);
+    let (count,): (i64,) =
+        sqlx::query_as("SELECT COUNT(*) FROM audit_log WHERE datetime(created_at) < datetime(?)")
+            .bind(&cutoff)
+            .fetch_one(pool)
+            .await?;
+
+    Ok(PurgePreview {
+        confirmation_token: purge_token(&cutoff, count),
+        cutoff,
+        entry_count: count,
+    })
+}
+
+/// Delete entries created before `cutoff` and record the purge as a new
+/// audit entry, in one transaction.
+///
+/// `confirmation_token` must be the token from `count_audit_entries_before`
+/// for the same cutoff; if entries were added or removed since the preview
+/// it no longer matches and nothing is deleted. Cutoffs in the future are
+/// rejected.
+pub async fn purge_audit_entries_before(
+    pool: &DbPool,
+    cutoff: chrono::DateTime<chrono::Utc>,
+    confirmation_token: &str,
+) -> Result<PurgeResult, AuditError> {
+    if cutoff > chrono::Utc::now() {
+        return Err(AuditError::InvalidInput("Purge cutoff can't be in the future".to_string()));
+    }
+    let cutoff = cutoff_text(cutoff);
+
+    let mut tx = pool.begin().await?;
+    let (count,): (i64,) =
+        sqlx::query_as("SELECT COUNT(*) FROM audit_log WHERE datetime(created_at) < datetime(?)")
+            .bind(&cutoff)
+            .fetch_one(&mut *tx)
+            .await?;
+    if confirmation_token != purge_token(&cutoff, count) {
+        return Err(AuditError::InvalidInput(format!(
+            "Confirmation token doesn't match: {} entries are before {}. Preview the purge again.",
+            count, cutoff
+        )));
+    }
+
+    let deleted = sqlx::query("DELETE FROM audit_log WHERE datetime(created_at) < datetime(?)")
+        .bind(&cutoff)
+        .execute(&mut *tx)
+        .await?
+        .rows_affected() as i64;
+
+    let meta_entry_id = Uuid::new_v4().to_string();
+    sqlx::query("INSERT INTO audit_log (id, request_redacted, response_text) VALUES (?, ?, ?)")
+        .bind(&meta_entry_id)
+        .bind("[retention] Audit log purge")
+        .bind(format!("Purged {} audit entries created before {} UTC", deleted, cutoff))
+        .execute(&mut *tx)
+        .await?;
+    tx.commit().await?;
+
+    eprintln!("[Audit] Purged {} entries before {}", deleted, cutoff);
+
+    Ok(PurgeResult {
+        cutoff,
+        deleted,
+        meta_entry_id,
+    })
+}
+
+// ============================================================================
+// Re-verification
+// ============================================================================
+
+/// Re-run answer verification for one entry using its stored snapshot, and
+/// store the new status
+pub async fn reverify_audit_entry(
+    pool: &DbPool,
+    audit_id: &str,
+) -> Result<VerificationResult, AuditError> {
+    let row: Option<(String, Option<String>)> =
+        sqlx::query_as("SELECT response_text, context_snapshot FROM audit_log WHERE id = ?")
+            .bind(audit_id)
+            .fetch_optional(pool)
+            .await?;
+    let (response_text, snapshot) = row.ok_or_else(|| AuditError::NotFound(audit_id.to_string()))?;
+    let snapshot = snapshot.ok_or_else(|| {
+        AuditError::InvalidInput(format!("Audit entry {} has no context snapshot", audit_id))
+    })?;
+
+    let result = verify_snapshot(&response_text, &snapshot)?;
+    store_verification_status(pool, audit_id, result.overall_status).await?;
+    Ok(result)
+}
+
+/// Re-verify every entry matching the filter. Entries without a snapshot
+/// are counted and left alone; an entry that fails (e.g. a corrupt snapshot)
+/// is reported in `errors` and keeps its old status.
+pub async fn reverify_audit_log(
+    pool: &DbPool,
+    filter: Option<AuditFilter>,
+) -> Result<ReverifyReport, AuditError> {
+    let filter = filter.unwrap_or_default();
+    let (conditions, bindings) = filter_conditions(&filter);
+
+    let query = format!(
+        "SELECT id, response_text, context_snapshot, verification_status FROM audit_log WHERE {} ORDER BY created_at",
+        conditions.join(" AND ")
+    );
+    let mut sqlx_query = sqlx::query_as::<_, (String, String, Option<String>, Option<String>)>(&query);
+    for binding in &bindings {
+        sqlx_query = sqlx_query.bind(binding);
+    }
+    let rows = sqlx_query.fetch_all(pool).await?;
+
+    let mut report = ReverifyReport {
+        entries_checked: rows.len(),
+        ..Default::default()
+    };
+    for (id, response_text, snapshot, old_status) in rows {
+        let Some(snapshot) = snapshot else {
+            report.skipped_without_snapshot += 1;
+            continue;
+        };
+        let result = match verify_snapshot(&response_text, &snapshot) {
+            Ok(result) => result,
+            Err(e) => {
+                report.errors.push(format!("{}: {}", id, e));
+                continue;
+            }
+        };
+        if let Err(e) = store_verification_status(pool, &id, result.overall_status).await {
+            report.errors.push(format!("{}: {}", id, e));
+            continue;
+        }
+        if old_status.as_deref() != Some(status_text(result.overall_status).as_str()) {
+            report.status_changed += 1;
+        }
+        report.reverified += 1;
+    }
+
+    Ok(report)
+}
+
+fn verify_snapshot(response_text: &str, snapshot_json: &str) -> Result<VerificationResult, AuditError> {
+    let snapshot: AuditContextSnapshot = serde_json::from_str(snapshot_json)
+        .map_err(|e| AuditError::InvalidInput(format!("Invalid context snapshot: {}", e)))?;
+    Ok(crate::context::verify_response(
+        response_text,
+        snapshot.aggregates.as_ref(),
+        snapshot.query_type,
+    ))
+}
+
+async fn store_verification_status(
+    pool: &DbPool,
+    audit_id: &str,
+    status: VerificationStatus,
+) -> Result<(), AuditError> {
+    sqlx::query("UPDATE audit_log SET verification_status = ?, verified_at = datetime('now') WHERE id = ?")
+        .bind(status_text(status))
+        .bind(audit_id)
+        .execute(pool)
+        .await?;
+    Ok(())
 }
 
 // ============================================================================
 // Helper Functions
 // ============================================================================
 
+/// Stored form of a verification status (its serialized variant name)
+fn status_text(status: VerificationStatus) -> String {
+    serde_json::to_value(status)
+        .ok()
+        .and_then(|v| v.as_str().map(str::to_string))
+        .unwrap_or_default()
+}
+
+/// WHERE conditions and bind values for an audit filter
+fn filter_conditions(filter: &AuditFilter) -> (Vec<String>, Vec<String>) {
+    let mut conditions = vec!["1=1".to_string()];
+    let mut bindings: Vec<String> = vec![];
+
+    if let Some(conv_id) = &filter.conversation_id {
+        conditions.push("conversation_id = ?".to_string());
+        bindings.push(conv_id.clone());
+    }
+
+    if let Some(start) = &filter.start_date {
+        conditions.push("created_at >= ?".to_string());
+        bindings.push(start.clone());
+    }
+
+    if let Some(end) = &filter.end_date {
+        conditions.push("created_at <= ?".to_string());
+        bindings.push(end.clone());
+    }
+
+    (conditions, bindings)
+}
+
 /// Truncate text to a preview length, adding ellipsis if truncated
 fn truncate_preview(text: &str, max_len: usize) -> String {
     let trimmed = text.trim();
@@ -361,20 +994,6 @@ fn truncate_preview(text: &str, max_len: usize) -> String {
     }
 }
 
-/// Escape a string for CSV format
-///
-/// Wraps in quotes if contains comma, quote, or newline.
-/// Doubles any internal quotes.
-fn escape_csv(s: &str) -> String {
-    let needs_quoting = s.contains(',') || s.contains('"') || s.contains('\n') || s.contains('\r');
-
-    if needs_quoting {
-        format!("\"{}\"", s.replace('"', "\"\""))
-    } else {
-        s.to_string()
-    }
-}
-
 // ============================================================================
 // Tests
 // ============================================================================
@@ -405,26 +1024,6 @@ mod tests {
         assert_eq!(result, "Hello world");
     }
 
-    #[test]
-    fn test_escape_csv_simple() {
-        assert_eq!(escape_csv("hello"), "hello");
-    }
-
-    #[test]
-    fn test_escape_csv_with_comma() {
-        assert_eq!(escape_csv("hello, world"), "\"hello, world\"");
-    }
-
-    #[test]
-    fn test_escape_csv_with_quotes() {
-        assert_eq!(escape_csv("say \"hello\""), "\"say \"\"hello\"\"\"");
-    }
-
-    #[test]
-    fn test_escape_csv_with_newline() {
-        assert_eq!(escape_csv("line1\nline2"), "\"line1\nline2\"");
-    }
-
     #[test]
     fn test_audit_error_serialization() {
         let err = AuditError::NotFound("test-id".to_string());
@@ -447,6 +1046,10 @@ mod tests {
             request_redacted: "What is Sarah's rating?".to_string(),
             response_text: "Sarah has a rating of 4.2".to_string(),
             employee_ids_used: vec!["emp-1".to_string(), "emp-2".to_string()],
+            context_snapshot: None,
+            verification_status: None,
+            usage: None,
+            cancelled: false,
         };
 
         // Verify serialization works
@@ -466,4 +1069,467 @@ mod tests {
         assert_eq!(parsed.len(), 3);
         assert_eq!(parsed[0], "emp-1");
     }
+
+    fn make_entry(context_used: Option<&str>, response: &str) -> AuditEntry {
+        AuditEntry {
+            id: "audit-1".to_string(),
+            conversation_id: Some("conv-1".to_string()),
+            request_redacted: "Tell me about Sarah".to_string(),
+            response_text: response.to_string(),
+            context_used: context_used.map(|s| s.to_string()),
+            created_at: "2024-01-01 10:00:00".to_string(),
+            input_tokens: None,
+            output_tokens: None,
+            request_status: REQUEST_COMPLETED.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_build_csv_scrubs_pii_in_context_used() {
+        let entries = vec![make_entry(
+            Some(r#"["emp-1","123-45-6789"]"#),
+            "Her card is 4111 1111 1111 1111",
+        )];
+
+        let csv = build_csv(&entries, &AuditExportOptions { scrub_pii: true, ..Default::default() }, &CsvExportOptions::default(), None, &[]);
+        assert!(!csv.contains("123-45-6789"));
+        assert!(!csv.contains("4111 1111 1111 1111"));
+        assert!(csv.contains("[SSN_REDACTED]"));
+        assert!(csv.contains("emp-1"));
+    }
+
+    #[test]
+    fn test_build_csv_scrubs_custom_pii_patterns() {
+        let custom = crate::pii::compile_custom_patterns(&[crate::pii::CustomPiiPattern {
+            label: "Employee badge".to_string(),
+            regex: r"EMP-\d{6}".to_string(),
+        }])
+        .unwrap();
+        let entries = vec![make_entry(None, "Badge EMP-123456 was reissued")];
+
+        let csv = build_csv(&entries, &AuditExportOptions::default(), &CsvExportOptions::default(), None, &custom);
+        assert!(!csv.contains("EMP-123456"));
+        assert!(csv.contains("[EMPLOYEE_BADGE_REDACTED]"));
+    }
+
+    #[test]
+    fn test_build_csv_scrubs_malformed_context_used() {
+        let entries = vec![make_entry(Some("SSN 123-45-6789"), "ok")];
+        let csv = build_csv(&entries, &AuditExportOptions::default(), &CsvExportOptions::default(), None, &[]);
+        assert!(!csv.contains("123-45-6789"));
+    }
+
+    #[test]
+    fn test_build_csv_without_scrubbing_keeps_text() {
+        let entries = vec![make_entry(Some(r#"["123-45-6789"]"#), "ok")];
+        let csv = build_csv(&entries, &AuditExportOptions { scrub_pii: false, ..Default::default() }, &CsvExportOptions::default(), None, &[]);
+        assert!(csv.contains("123-45-6789"));
+    }
+
+    #[test]
+    fn test_build_csv_semicolon_crlf() {
+        let entries = vec![make_entry(Some(r#"["emp-1","emp-2"]"#), "Yes, she did")];
+        let csv_options = CsvExportOptions {
+            delimiter: ';',
+            line_ending: crate::csv_export::CsvLineEnding::Crlf,
+            include_bom: false,
+        };
+        let csv = build_csv(&entries, &AuditExportOptions { scrub_pii: false, ..Default::default() }, &csv_options, None, &[]);
+
+        let mut lines = csv.split("\r\n");
+        assert!(lines.next().unwrap().starts_with("id;timestamp;"));
+        let row = lines.next().unwrap();
+        // Commas no longer force quoting; the ';'-joined id list does
+        assert!(row.contains(";Yes, she did;"));
+        assert!(row.ends_with(";\"emp-1;emp-2\""));
+        assert!(!csv.contains("\r\n\n") && !csv.replace("\r\n", "").contains('\n'));
+    }
+
+    #[test]
+    fn test_export_options_default_scrubs() {
+        assert!(AuditExportOptions::default().scrub_pii);
+        assert!(!AuditExportOptions::default().pseudonymize_names);
+    }
+
+    fn roster() -> Vec<(String, String)> {
+        [("abc123", "Sarah Chen"), ("def456", "Marcus Johnson"), ("x1", "Alex Kim"), ("x2", "Alex Kim")]
+            .iter()
+            .map(|(id, name)| (id.to_string(), name.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_pseudonymizer_replaces_known_names() {
+        let p = NamePseudonymizer::from_roster(&roster()).unwrap();
+        assert_eq!(
+            p.apply("Compare sarah  chen with Marcus Johnson's rating"),
+            "Compare [emp:abc123] with [emp:def456]'s rating"
+        );
+        // Partial words are left alone; a name two employees share gets a
+        // generic token
+        assert_eq!(p.apply("Sarah Chenoweth and Alex Kim"), "Sarah Chenoweth and [emp:ambiguous]");
+    }
+
+    #[test]
+    fn test_pseudonymizer_covers_large_rosters() {
+        let roster: Vec<(String, String)> = (0..2_000)
+            .map(|i| (format!("id{}", i), format!("Person{} Longsurname{}", i, i)))
+            .collect();
+        let p = NamePseudonymizer::from_roster(&roster).unwrap();
+        assert!(p.patterns.len() > 1);
+        assert_eq!(
+            p.apply("person0 longsurname0 met Person1999 Longsurname1999"),
+            "[emp:id0] met [emp:id1999]"
+        );
+    }
+
+    #[test]
+    fn test_build_csv_pseudonymizes_then_scrubs() {
+        let p = NamePseudonymizer::from_roster(&roster()).unwrap();
+        let mut entry = make_entry(Some(r#"["abc123"]"#), "Sarah Chen and Jane Doe (SSN 123-45-6789)");
+        entry.request_redacted = "How is Sarah Chen doing?".to_string();
+        let options = AuditExportOptions {
+            scrub_pii: true,
+            pseudonymize_names: true,
+        };
+
+        let csv = build_csv(&[entry.clone()], &options, &CsvExportOptions::default(), Some(&p), &[]);
+        assert!(!csv.contains("Sarah Chen"));
+        assert!(csv.contains("How is [emp:abc123] doing?"));
+        // Unknown names pass through; PII around them is still scrubbed
+        assert!(csv.contains("Jane Doe"));
+        assert!(!csv.contains("123-45-6789"));
+
+        let unscrubbed = AuditExportOptions {
+            scrub_pii: false,
+            pseudonymize_names: true,
+        };
+        let csv = build_csv(&[entry], &unscrubbed, &CsvExportOptions::default(), Some(&p), &[]);
+        assert!(csv.contains("123-45-6789"));
+        assert!(csv.contains("[emp:abc123] and Jane Doe"));
+    }
+
+    #[test]
+    fn test_export_filename_has_no_pii() {
+        let now = chrono::DateTime::parse_from_rfc3339("2024-03-05T14:07:09Z")
+            .unwrap()
+            .with_timezone(&chrono::Utc);
+        let filename = export_filename(now, "csv");
+        assert_eq!(filename, "audit_log_2024-03-05_1407.csv");
+        assert!(crate::pii::scan_for_pii(&filename).is_empty());
+    }
+
+    async fn seed_audit(pool: &DbPool, id: &str, context_used: Option<&str>, created_at: &str) {
+        sqlx::query(
+            "INSERT INTO audit_log (id, request_redacted, response_text, context_used, created_at) VALUES (?, 'q', 'a', ?, ?)",
+        )
+        .bind(id)
+        .bind(context_used)
+        .bind(created_at)
+        .execute(pool)
+        .await
+        .unwrap();
+    }
+
+    async fn seed_employee(pool: &DbPool, id: &str, name: &str) {
+        sqlx::query("INSERT INTO employees (id, email, full_name, department) VALUES (?, ?, ?, 'Engineering')")
+            .bind(id)
+            .bind(format!("{}@example.com", id))
+            .bind(name)
+            .execute(pool)
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_most_discussed_employees_ranking() {
+        let pool = crate::db::test_pool().await;
+        seed_employee(&pool, "e1", "Alice Park").await;
+        seed_employee(&pool, "e2", "Ben Ortiz").await;
+        seed_employee(&pool, "e3", "Cara Diaz").await;
+
+        seed_audit(&pool, "a1", Some(r#"["e1","e2"]"#), "2024-01-10 09:00:00").await;
+        seed_audit(&pool, "a2", Some(r#"["e1","e1"]"#), "2024-02-01 09:00:00").await;
+        seed_audit(&pool, "a3", Some(r#"["e1","e3"]"#), "2024-03-01 09:00:00").await;
+        seed_audit(&pool, "a4", Some(r#"["e3","ghost"]"#), "2024-03-05 09:00:00").await;
+        seed_audit(&pool, "a5", None, "2024-03-06 09:00:00").await;
+        seed_audit(&pool, "a6", Some("not json"), "2024-03-07 09:00:00").await;
+
+        let top = get_most_discussed_employees(&pool, None, None).await.unwrap();
+        let ranked: Vec<(&str, i64)> = top.iter().map(|m| (m.employee_id.as_str(), m.mention_count)).collect();
+        // Duplicate IDs within an entry count once; deleted employees are dropped
+        assert_eq!(ranked, vec![("e1", 3), ("e3", 2), ("e2", 1)]);
+        assert_eq!(top[0].full_name, "Alice Park");
+        assert_eq!(top[0].last_mentioned_at, "2024-03-01 09:00:00");
+        assert_eq!(top[1].last_mentioned_at, "2024-03-05 09:00:00");
+
+        let limited = get_most_discussed_employees(&pool, None, Some(1)).await.unwrap();
+        assert_eq!(limited.len(), 1);
+        assert_eq!(limited[0].employee_id, "e1");
+    }
+
+    #[tokio::test]
+    async fn test_most_discussed_employees_since_window() {
+        let pool = crate::db::test_pool().await;
+        seed_employee(&pool, "e1", "Alice Park").await;
+        seed_employee(&pool, "e2", "Ben Ortiz").await;
+
+        seed_audit(&pool, "a1", Some(r#"["e1"]"#), "2024-01-10 09:00:00").await;
+        seed_audit(&pool, "a2", Some(r#"["e1"]"#), "2024-01-20 09:00:00").await;
+        seed_audit(&pool, "a3", Some(r#"["e2"]"#), "2024-03-01 09:00:00").await;
+
+        let top = get_most_discussed_employees(&pool, Some("2024-02-01".to_string()), None)
+            .await
+            .unwrap();
+        assert_eq!(top.len(), 1);
+        assert_eq!((top[0].employee_id.as_str(), top[0].mention_count), ("e2", 1));
+    }
+
+    fn snapshot_aggregates(total_employees: i64) -> OrgAggregates {
+        use crate::context::{AttritionStats, EnpsAggregate, RatingDistribution};
+        OrgAggregates {
+            total_employees,
+            active_count: total_employees,
+            terminated_count: 0,
+            on_leave_count: 0,
+            by_department: vec![],
+            avg_rating: None,
+            rating_distribution: RatingDistribution::default(),
+            employees_with_no_rating: 0,
+            enps: EnpsAggregate {
+                score: 0,
+                promoters: 0,
+                passives: 0,
+                detractors: 0,
+                total_responses: 0,
+                response_rate: 0.0,
+            },
+            attrition: AttritionStats::default(),
+            tenure: Default::default(),
+            diversity: None,
+            missing_sections: vec![],
+        }
+    }
+
+    async fn insert_entry(pool: &DbPool, response: &str, snapshot: Option<AuditContextSnapshot>) -> String {
+        create_audit_entry(
+            pool,
+            CreateAuditEntry {
+                conversation_id: None,
+                request_redacted: "How many employees do we have?".to_string(),
+                response_text: response.to_string(),
+                employee_ids_used: vec![],
+                context_snapshot: snapshot,
+                verification_status: Some(VerificationStatus::Unverified),
+                usage: None,
+                cancelled: false,
+            },
+        )
+        .await
+        .unwrap()
+        .id
+    }
+
+    async fn stored_status(pool: &DbPool, id: &str) -> Option<String> {
+        sqlx::query_scalar("SELECT verification_status FROM audit_log WHERE id = ?")
+            .bind(id)
+            .fetch_one(pool)
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_cancelled_entries_record_request_status() {
+        let pool = crate::db::test_pool().await;
+        let completed = insert_entry(&pool, "Done.", None).await;
+        let cancelled = create_audit_entry(
+            &pool,
+            CreateAuditEntry {
+                conversation_id: None,
+                request_redacted: "Summarize the team".to_string(),
+                response_text: "The team".to_string(),
+                employee_ids_used: vec![],
+                context_snapshot: None,
+                verification_status: None,
+                usage: None,
+                cancelled: true,
+            },
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(cancelled.request_status, REQUEST_CANCELLED);
+        assert_eq!(cancelled.response_text, "The team");
+        let completed = get_audit_entry(&pool, &completed).await.unwrap();
+        assert_eq!(completed.request_status, REQUEST_COMPLETED);
+    }
+
+    #[tokio::test]
+    async fn test_reverify_entry_uses_snapshot() {
+        let pool = crate::db::test_pool().await;
+        // The snapshot says 100 employees, whatever the live tables hold now
+        let snapshot = AuditContextSnapshot {
+            query_type: QueryType::Aggregate,
+            aggregates: Some(snapshot_aggregates(100)),
+        };
+        let id = insert_entry(&pool, "You currently have 100 employees in total.", Some(snapshot)).await;
+        assert_eq!(stored_status(&pool, &id).await.as_deref(), Some("Unverified"));
+
+        let result = reverify_audit_entry(&pool, &id).await.unwrap();
+        assert_eq!(result.overall_status, VerificationStatus::Verified);
+        assert_eq!(stored_status(&pool, &id).await.as_deref(), Some("Verified"));
+
+        assert!(matches!(reverify_audit_entry(&pool, "missing").await, Err(AuditError::NotFound(_))));
+    }
+
+    #[tokio::test]
+    async fn test_reverify_audit_log_skips_entries_without_snapshot() {
+        let pool = crate::db::test_pool().await;
+        let snapshot = AuditContextSnapshot {
+            query_type: QueryType::Aggregate,
+            aggregates: Some(snapshot_aggregates(100)),
+        };
+        let verified = insert_entry(&pool, "You currently have 100 employees in total.", Some(snapshot)).await;
+        let legacy = insert_entry(&pool, "You currently have 100 employees in total.", None).await;
+
+        let report = reverify_audit_log(&pool, None).await.unwrap();
+        assert_eq!(report.entries_checked, 2);
+        assert_eq!(report.reverified, 1);
+        assert_eq!(report.skipped_without_snapshot, 1);
+        assert_eq!(report.status_changed, 1);
+        assert_eq!(stored_status(&pool, &verified).await.as_deref(), Some("Verified"));
+        assert_eq!(stored_status(&pool, &legacy).await.as_deref(), Some("Unverified"));
+        assert!(matches!(reverify_audit_entry(&pool, &legacy).await, Err(AuditError::InvalidInput(_))));
+
+        // Nothing changes on a second pass
+        let again = reverify_audit_log(&pool, None).await.unwrap();
+        assert_eq!(again.status_changed, 0);
+    }
+
+    #[tokio::test]
+    async fn test_reverify_audit_log_continues_past_bad_snapshot() {
+        let pool = crate::db::test_pool().await;
+        let snapshot = AuditContextSnapshot {
+            query_type: QueryType::Aggregate,
+            aggregates: Some(snapshot_aggregates(100)),
+        };
+        let corrupt = insert_entry(&pool, "You currently have 100 employees in total.", Some(snapshot.clone())).await;
+        sqlx::query("UPDATE audit_log SET context_snapshot = '{not json' WHERE id = ?")
+            .bind(&corrupt)
+            .execute(&pool)
+            .await
+            .unwrap();
+        let good = insert_entry(&pool, "You currently have 100 employees in total.", Some(snapshot)).await;
+
+        let report = reverify_audit_log(&pool, None).await.unwrap();
+        assert_eq!(report.entries_checked, 2);
+        assert_eq!(report.reverified, 1);
+        assert_eq!(report.errors.len(), 1);
+        assert!(report.errors[0].starts_with(&format!("{}: ", corrupt)), "{}", report.errors[0]);
+        assert_eq!(stored_status(&pool, &good).await.as_deref(), Some("Verified"));
+        assert_eq!(stored_status(&pool, &corrupt).await.as_deref(), Some("Unverified"));
+    }
+
+    #[tokio::test]
+    async fn test_export_to_pdf_matches_filtered_count() {
+        let pool = crate::db::test_pool().await;
+        crate::company::upsert_company(
+            &pool,
+            crate::company::UpsertCompany {
+                id: None,
+                name: "Acme Corp".to_string(),
+                state: "CA".to_string(),
+                industry: None,
+            },
+        )
+        .await
+        .unwrap();
+        seed_audit(&pool, "old", None, "2024-01-05 10:00:00").await;
+        seed_audit(&pool, "mid", None, "2024-03-10 10:00:00").await;
+        sqlx::query(
+            "INSERT INTO audit_log (id, request_redacted, response_text, created_at) VALUES ('long', 'Summarize (all) reviews', ?, '2024-03-20 10:00:00')",
+        )
+        .bind("word ".repeat(2000))
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        let filter = AuditFilter {
+            conversation_id: None,
+            start_date: Some("2024-03-01".to_string()),
+            end_date: None,
+        };
+        let result = export_to_pdf(&pool, Some(filter.clone()), None).await.unwrap();
+        let expected = count_audit_entries(&pool, Some(filter)).await.unwrap() as usize;
+
+        assert!(result.pdf_data.starts_with(b"%PDF"));
+        assert_eq!(result.entry_count, expected);
+        assert_eq!(expected, 2);
+        assert!(result.filename.ends_with(".pdf"));
+
+        let text = String::from_utf8_lossy(&result.pdf_data);
+        let sections = Regex::new(r"\(Entry \d+ of \d+\)").unwrap().find_iter(&text).count();
+        assert_eq!(sections, expected);
+        assert!(text.contains(&format!("(Entries: {})", expected)));
+        assert!(text.contains("(Company: Acme Corp)"));
+        assert!(text.contains("(Date range: From 2024-03-01)"));
+        assert!(text.contains(r"(Summarize \(all\) reviews)"));
+        // The 10,000-character response is cut short, which takes more than one page
+        assert!(text.contains(r"word...\(truncated\))"));
+        assert!(result.page_count > 1);
+        assert!(text.contains(&format!("/Count {}", result.page_count)));
+    }
+
+    #[tokio::test]
+    async fn test_purge_audit_entries_before() {
+        let pool = crate::db::test_pool().await;
+        seed_audit(&pool, "2021", None, "2021-06-01 09:00:00").await;
+        seed_audit(&pool, "2022", None, "2022-12-31 23:59:59").await;
+        seed_audit(&pool, "boundary", None, "2023-01-01 00:00:00").await;
+        seed_audit(&pool, "2024", None, "2024-03-15 12:00:00").await;
+        seed_audit(&pool, "garbled", None, "not a date").await;
+
+        let cutoff = chrono::DateTime::parse_from_rfc3339("2023-01-01T00:00:00Z")
+            .unwrap()
+            .with_timezone(&chrono::Utc);
+        let preview = count_audit_entries_before(&pool, cutoff).await.unwrap();
+        assert_eq!(preview.entry_count, 2);
+
+        // A wrong or stale token deletes nothing
+        assert!(matches!(
+            purge_audit_entries_before(&pool, cutoff, "yes").await,
+            Err(AuditError::InvalidInput(_))
+        ));
+        seed_audit(&pool, "late-import", None, "2020-01-01 00:00:00").await;
+        assert!(matches!(
+            purge_audit_entries_before(&pool, cutoff, &preview.confirmation_token).await,
+            Err(AuditError::InvalidInput(_))
+        ));
+        assert_eq!(count_audit_entries(&pool, None).await.unwrap(), 6);
+
+        let preview = count_audit_entries_before(&pool, cutoff).await.unwrap();
+        let result = purge_audit_entries_before(&pool, cutoff, &preview.confirmation_token)
+            .await
+            .unwrap();
+        assert_eq!(result.deleted, preview.entry_count);
+        assert_eq!(result.deleted, 3);
+
+        let mut remaining: Vec<String> = sqlx::query_scalar("SELECT id FROM audit_log")
+            .fetch_all(&pool)
+            .await
+            .unwrap();
+        remaining.sort();
+        let mut expected = vec!["2024".to_string(), "boundary".to_string(), "garbled".to_string(), result.meta_entry_id.clone()];
+        expected.sort();
+        assert_eq!(remaining, expected);
+
+        let meta = get_audit_entry(&pool, &result.meta_entry_id).await.unwrap();
+        assert!(meta.response_text.contains("Purged 3 audit entries created before 2023-01-01 00:00:00"));
+
+        let future = chrono::Utc::now() + chrono::Duration::days(1);
+        assert!(matches!(
+            purge_audit_entries_before(&pool, future, "").await,
+            Err(AuditError::InvalidInput(_))
+        ));
+    }
 }
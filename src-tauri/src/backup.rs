@@ -9,12 +9,13 @@ use aes_gcm::{
     aead::{Aead, KeyInit, OsRng},
     Aes256Gcm, Nonce,
 };
-use argon2::{password_hash::SaltString, Argon2, PasswordHasher};
+use argon2::{password_hash::SaltString, Algorithm, Argon2, Params, PasswordHasher, Version};
 use chrono::{DateTime, Utc};
 use flate2::{read::GzDecoder, write::GzEncoder, Compression};
 use rand::RngCore;
 use serde::{Deserialize, Serialize};
-use sqlx::{Row, SqlitePool};
+use sqlx::{Row, Sqlite, SqlitePool, Transaction};
+use std::collections::HashSet;
 use std::io::{Read, Write};
 use thiserror::Error;
 
@@ -44,6 +45,18 @@ pub enum BackupError {
 
     #[error("Compression error: {0}")]
     Compression(String),
+
+    #[error("Password is too weak: {feedback}")]
+    WeakPassword { feedback: String },
+
+    #[error("Invalid table selection: {0}")]
+    InvalidSelection(String),
+
+    #[error("Restoring would delete {0} employee(s) under legal hold that are not in this backup")]
+    LegalHoldMissing(usize),
+
+    #[error("{}", crate::db::READ_ONLY_MESSAGE)]
+    ReadOnly,
 }
 
 impl From<sqlx::Error> for BackupError {
@@ -52,37 +65,136 @@ impl From<sqlx::Error> for BackupError {
     }
 }
 
+impl From<crate::db::ReadOnly> for BackupError {
+    fn from(_: crate::db::ReadOnly) -> Self {
+        BackupError::ReadOnly
+    }
+}
+
 // ============================================================================
 // Constants
 // ============================================================================
 
 /// Current backup format version
-const BACKUP_VERSION: &str = "1.0";
+const BACKUP_VERSION: &str = "1.1";
+
+/// Versions this build can read. "1.0" predates the KDF header.
+const SUPPORTED_VERSIONS: &[&str] = &["1.0", BACKUP_VERSION];
 
 /// Minimum password length
 const MIN_PASSWORD_LENGTH: usize = 8;
 
+/// Default minimum strength score (0-4) a backup password must reach
+pub const DEFAULT_MIN_PASSWORD_SCORE: u8 = 3;
+
+/// Passwords (and their letters-only forms) that are rejected outright
+const COMMON_PASSWORDS: &[&str] = &[
+    "password", "passw", "qwerty", "qwertyuiop", "letmein", "welcome", "admin",
+    "iloveyou", "monkey", "dragon", "football", "baseball", "sunshine", "master",
+    "abc", "abcdefgh", "trustno", "changeme", "secret", "hrcommand", "backup",
+];
+
 /// Salt length for Argon2
 const SALT_LENGTH: usize = 16;
 
 /// Nonce length for AES-GCM
 const NONCE_LENGTH: usize = 12;
 
+/// Argon2id parameters for new backups. Pinned rather than taken from
+/// `Argon2::default()` so a change in the crate's defaults can't leave older
+/// backups undecryptable.
+const KDF_PARAMS: KdfParams = KdfParams {
+    memory_kib: 19_456,
+    iterations: 2,
+    parallelism: 1,
+};
+
+/// The argon2 0.5 defaults, used for version "1.0" backups, which carry no
+/// KDF header
+const LEGACY_KDF_PARAMS: KdfParams = KdfParams {
+    memory_kib: 19_456,
+    iterations: 2,
+    parallelism: 1,
+};
+
+/// Upper bounds on the costs read from a file header, so a crafted backup
+/// can't make restore hash for minutes or exhaust memory (1 GiB)
+const MAX_KDF_MEMORY_KIB: u32 = 1_048_576;
+const MAX_KDF_ITERATIONS: u32 = 16;
+const MAX_KDF_PARALLELISM: u32 = 16;
+
+/// Marks an encrypted blob that starts with its KDF parameters
+const KDF_HEADER_MAGIC: &[u8; 4] = b"HRK1";
+
+/// Magic plus memory, iterations and parallelism as little-endian u32s
+const KDF_HEADER_LENGTH: usize = 16;
+
 // ============================================================================
 // Backup Metadata & Results
 // ============================================================================
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct TableCounts {
     pub employees: usize,
     pub conversations: usize,
     pub company: usize,
     pub settings: usize,
     pub audit_log: usize,
+    #[serde(default)]
+    pub review_templates: usize,
     pub review_cycles: usize,
+    #[serde(default)]
+    pub review_cycle_events: usize,
     pub performance_ratings: usize,
     pub performance_reviews: usize,
     pub enps_responses: usize,
+    #[serde(default)]
+    pub department_changes: usize,
+}
+
+impl TableCounts {
+    fn of(tables: &BackupTables) -> TableCounts {
+        TableCounts {
+            employees: tables.employees.len(),
+            conversations: tables.conversations.len(),
+            company: tables.company.len(),
+            settings: tables.settings.len(),
+            audit_log: tables.audit_log.len(),
+            review_templates: tables.review_templates.len(),
+            review_cycles: tables.review_cycles.len(),
+            review_cycle_events: tables.review_cycle_events.len(),
+            performance_ratings: tables.performance_ratings.len(),
+            performance_reviews: tables.performance_reviews.len(),
+            enps_responses: tables.enps_responses.len(),
+            department_changes: tables.department_changes.len(),
+        }
+    }
+
+    /// Per-table difference, for rows offered minus rows written
+    fn minus(&self, other: &TableCounts) -> TableCounts {
+        TableCounts {
+            employees: self.employees.saturating_sub(other.employees),
+            conversations: self.conversations.saturating_sub(other.conversations),
+            company: self.company.saturating_sub(other.company),
+            settings: self.settings.saturating_sub(other.settings),
+            audit_log: self.audit_log.saturating_sub(other.audit_log),
+            review_templates: self.review_templates.saturating_sub(other.review_templates),
+            review_cycles: self.review_cycles.saturating_sub(other.review_cycles),
+            review_cycle_events: self.review_cycle_events.saturating_sub(other.review_cycle_events),
+            performance_ratings: self.performance_ratings.saturating_sub(other.performance_ratings),
+            performance_reviews: self.performance_reviews.saturating_sub(other.performance_reviews),
+            enps_responses: self.enps_responses.saturating_sub(other.enps_responses),
+            department_changes: self.department_changes.saturating_sub(other.department_changes),
+        }
+    }
+}
+
+/// Argon2id cost parameters a backup's key was derived with
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KdfParams {
+    pub memory_kib: u32,
+    pub iterations: u32,
+    pub parallelism: u32,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -91,6 +203,13 @@ pub struct BackupMetadata {
     pub created_at: DateTime<Utc>,
     pub app_version: String,
     pub table_counts: TableCounts,
+    /// Set on incremental backups: only rows written at or after this time are included
+    #[serde(default)]
+    pub incremental_since: Option<DateTime<Utc>>,
+    /// KDF parameters the file was encrypted with (absent before version 1.1).
+    /// Informational: decryption reads them from the unencrypted file header.
+    #[serde(default)]
+    pub kdf_params: Option<KdfParams>,
 }
 
 #[derive(Debug, Serialize)]
@@ -103,10 +222,105 @@ pub struct ExportResult {
     pub table_counts: TableCounts,
 }
 
+/// Options controlling how a backup is exported
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct BackupExportOptions {
+    /// Minimum password strength score (0-4); defaults to `DEFAULT_MIN_PASSWORD_SCORE`
+    #[serde(default)]
+    pub min_password_score: Option<u8>,
+}
+
+/// Estimated strength of a backup password
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct PasswordStrength {
+    /// 0 = very weak, 1 = weak, 2 = fair, 3 = strong, 4 = very strong
+    pub score: u8,
+    /// Suggestions for making the password stronger (empty when nothing to improve)
+    pub feedback: Vec<String>,
+}
+
+/// A backed-up table, for restoring a subset of a backup
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TableName {
+    Company,
+    Settings,
+    ReviewTemplates,
+    ReviewCycles,
+    ReviewCycleEvents,
+    Employees,
+    PerformanceRatings,
+    PerformanceReviews,
+    EnpsResponses,
+    DepartmentChanges,
+    Conversations,
+    AuditLog,
+}
+
+impl TableName {
+    /// Every table, in FK-safe restore order (parent → child)
+    pub const ALL: [TableName; 12] = [
+        TableName::Company,
+        TableName::Settings,
+        TableName::ReviewTemplates,
+        TableName::ReviewCycles,
+        TableName::ReviewCycleEvents,
+        TableName::Employees,
+        TableName::PerformanceRatings,
+        TableName::PerformanceReviews,
+        TableName::EnpsResponses,
+        TableName::DepartmentChanges,
+        TableName::Conversations,
+        TableName::AuditLog,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TableName::Company => "company",
+            TableName::Settings => "settings",
+            TableName::ReviewTemplates => "review_templates",
+            TableName::ReviewCycles => "review_cycles",
+            TableName::ReviewCycleEvents => "review_cycle_events",
+            TableName::Employees => "employees",
+            TableName::PerformanceRatings => "performance_ratings",
+            TableName::PerformanceReviews => "performance_reviews",
+            TableName::EnpsResponses => "enps_responses",
+            TableName::DepartmentChanges => "department_changes",
+            TableName::Conversations => "conversations",
+            TableName::AuditLog => "audit_log",
+        }
+    }
+
+    /// Tables this one has foreign keys into
+    fn parents(&self) -> &'static [TableName] {
+        match self {
+            TableName::PerformanceRatings | TableName::PerformanceReviews => {
+                &[TableName::Employees, TableName::ReviewCycles]
+            }
+            TableName::ReviewCycles => &[TableName::ReviewTemplates],
+            TableName::EnpsResponses | TableName::DepartmentChanges => &[TableName::Employees],
+            TableName::AuditLog => &[TableName::Conversations],
+            _ => &[],
+        }
+    }
+}
+
+/// Options controlling what an import restores
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ImportOptions {
+    /// Tables to clear and restore; the rest are left untouched. Empty
+    /// restores every table.
+    #[serde(default)]
+    pub tables: Vec<TableName>,
+}
+
 #[derive(Debug, Serialize)]
 pub struct ImportResult {
     /// Count of records restored per table
     pub restored_counts: TableCounts,
+    /// Backup records left out per table (merge imports only): they collided
+    /// with existing rows or their parent was missing
+    pub skipped_counts: TableCounts,
     /// Any warnings encountered during import
     pub warnings: Vec<String>,
 }
@@ -134,6 +348,12 @@ pub struct EmployeeRow {
     pub ethnicity: Option<String>,
     pub termination_date: Option<String>,
     pub termination_reason: Option<String>,
+    #[serde(default)]
+    pub legal_hold: bool,
+    #[serde(default)]
+    pub original_full_name: Option<String>,
+    #[serde(default)]
+    pub company_id: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -144,6 +364,20 @@ pub struct ConversationRow {
     pub messages_json: String,
     pub created_at: Option<String>,
     pub updated_at: Option<String>,
+    #[serde(default = "empty_json_array")]
+    pub tags: String,
+    #[serde(default)]
+    pub archived: bool,
+    #[serde(default)]
+    pub pinned: bool,
+    #[serde(default)]
+    pub exclude_from_memory: bool,
+    #[serde(default)]
+    pub model: Option<String>,
+}
+
+fn empty_json_array() -> String {
+    "[]".to_string()
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -170,6 +404,19 @@ pub struct AuditLogRow {
     pub response_text: String,
     pub context_used: Option<String>,
     pub created_at: Option<String>,
+    #[serde(default)]
+    pub context_snapshot: Option<String>,
+    #[serde(default)]
+    pub verification_status: Option<String>,
+    #[serde(default)]
+    pub verified_at: Option<String>,
+    #[serde(default)]
+    pub input_tokens: Option<i64>,
+    #[serde(default)]
+    pub output_tokens: Option<i64>,
+    /// None in backups written before request status was recorded
+    #[serde(default)]
+    pub request_status: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -181,6 +428,26 @@ pub struct ReviewCycleRow {
     pub end_date: String,
     pub status: String,
     pub created_at: Option<String>,
+    #[serde(default)]
+    pub template_id: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReviewCycleEventRow {
+    pub id: String,
+    pub cycle_id: String,
+    pub old_status: Option<String>,
+    pub new_status: String,
+    pub created_at: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReviewTemplateRow {
+    pub id: String,
+    pub name: String,
+    pub description: Option<String>,
+    pub sections: String,
+    pub created_at: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -225,6 +492,15 @@ pub struct EnpsRow {
     pub created_at: Option<String>,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DepartmentChangeRow {
+    pub id: String,
+    pub employee_id: String,
+    pub from_department: Option<String>,
+    pub to_department: String,
+    pub changed_at: Option<String>,
+}
+
 // ============================================================================
 // Backup Data Structure
 // ============================================================================
@@ -236,10 +512,16 @@ pub struct BackupTables {
     pub company: Vec<CompanyRow>,
     pub settings: Vec<SettingsRow>,
     pub audit_log: Vec<AuditLogRow>,
+    #[serde(default)]
+    pub review_templates: Vec<ReviewTemplateRow>,
     pub review_cycles: Vec<ReviewCycleRow>,
+    #[serde(default)]
+    pub review_cycle_events: Vec<ReviewCycleEventRow>,
     pub performance_ratings: Vec<PerformanceRatingRow>,
     pub performance_reviews: Vec<PerformanceReviewRow>,
     pub enps_responses: Vec<EnpsRow>,
+    #[serde(default)]
+    pub department_changes: Vec<DepartmentChangeRow>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -253,9 +535,10 @@ pub struct BackupData {
 // ============================================================================
 
 /// Derive a 256-bit key from password using Argon2id
-fn derive_key(password: &str, salt: &[u8]) -> Result<[u8; 32], BackupError> {
-    // Use Argon2id with reasonable parameters for desktop app
-    let argon2 = Argon2::default();
+fn derive_key(password: &str, salt: &[u8], kdf: KdfParams) -> Result<[u8; 32], BackupError> {
+    let params = Params::new(kdf.memory_kib, kdf.iterations, kdf.parallelism, None)
+        .map_err(|e| BackupError::Encryption(format!("Invalid KDF parameters: {}", e)))?;
+    let argon2 = Argon2::new(Algorithm::Argon2id, Version::V0x13, params);
 
     // Convert salt to SaltString format
     let salt_string = SaltString::encode_b64(salt)
@@ -291,7 +574,7 @@ fn encrypt_data(data: &[u8], password: &str) -> Result<Vec<u8>, BackupError> {
     OsRng.fill_bytes(&mut nonce_bytes);
 
     // Derive key from password
-    let key = derive_key(password, &salt)?;
+    let key = derive_key(password, &salt, KDF_PARAMS)?;
 
     // Create cipher and encrypt
     let cipher = Aes256Gcm::new_from_slice(&key)
@@ -302,8 +585,13 @@ fn encrypt_data(data: &[u8], password: &str) -> Result<Vec<u8>, BackupError> {
         .encrypt(nonce, data)
         .map_err(|e| BackupError::Encryption(format!("Encryption error: {}", e)))?;
 
-    // Concatenate salt + nonce + ciphertext
-    let mut result = Vec::with_capacity(SALT_LENGTH + NONCE_LENGTH + ciphertext.len());
+    // Concatenate KDF header + salt + nonce + ciphertext
+    let mut result =
+        Vec::with_capacity(KDF_HEADER_LENGTH + SALT_LENGTH + NONCE_LENGTH + ciphertext.len());
+    result.extend_from_slice(KDF_HEADER_MAGIC);
+    result.extend_from_slice(&KDF_PARAMS.memory_kib.to_le_bytes());
+    result.extend_from_slice(&KDF_PARAMS.iterations.to_le_bytes());
+    result.extend_from_slice(&KDF_PARAMS.parallelism.to_le_bytes());
     result.extend_from_slice(&salt);
     result.extend_from_slice(&nonce_bytes);
     result.extend_from_slice(&ciphertext);
@@ -312,8 +600,13 @@ fn encrypt_data(data: &[u8], password: &str) -> Result<Vec<u8>, BackupError> {
 }
 
 /// Decrypt data with AES-256-GCM
-/// Expects: [salt: 16 bytes][nonce: 12 bytes][ciphertext]
+/// Expects: [KDF header: 16 bytes][salt: 16 bytes][nonce: 12 bytes][ciphertext]
+/// The header is "HRK1" followed by memory (KiB), iterations and parallelism
+/// as little-endian u32s; legacy blobs start straight with the salt.
 fn decrypt_data(encrypted: &[u8], password: &str) -> Result<Vec<u8>, BackupError> {
+    // Read the KDF header; files without one use the legacy parameters
+    let (kdf, encrypted) = split_kdf_header(encrypted)?;
+
     // Validate minimum length
     if encrypted.len() < SALT_LENGTH + NONCE_LENGTH + 16 {
         return Err(BackupError::InvalidBackup);
@@ -325,7 +618,7 @@ fn decrypt_data(encrypted: &[u8], password: &str) -> Result<Vec<u8>, BackupError
     let ciphertext = &encrypted[SALT_LENGTH + NONCE_LENGTH..];
 
     // Derive key from password
-    let key = derive_key(password, salt)?;
+    let key = derive_key(password, salt, kdf)?;
 
     // Create cipher and decrypt
     let cipher = Aes256Gcm::new_from_slice(&key)
@@ -341,6 +634,44 @@ fn decrypt_data(encrypted: &[u8], password: &str) -> Result<Vec<u8>, BackupError
 // Compression Helpers
 // ============================================================================
 
+/// Split the KDF parameters off the front of an encrypted blob. Blobs written
+/// before the header existed start straight with the salt and get
+/// `LEGACY_KDF_PARAMS`.
+fn split_kdf_header(encrypted: &[u8]) -> Result<(KdfParams, &[u8]), BackupError> {
+    if encrypted.len() < KDF_HEADER_LENGTH || &encrypted[..4] != KDF_HEADER_MAGIC {
+        return Ok((LEGACY_KDF_PARAMS, encrypted));
+    }
+
+    let field = |i: usize| {
+        let start = 4 + i * 4;
+        u32::from_le_bytes(encrypted[start..start + 4].try_into().unwrap())
+    };
+    let kdf = KdfParams {
+        memory_kib: field(0),
+        iterations: field(1),
+        parallelism: field(2),
+    };
+    if kdf.memory_kib > MAX_KDF_MEMORY_KIB
+        || kdf.iterations > MAX_KDF_ITERATIONS
+        || kdf.parallelism > MAX_KDF_PARALLELISM
+    {
+        return Err(BackupError::InvalidBackup);
+    }
+
+    Ok((kdf, &encrypted[KDF_HEADER_LENGTH..]))
+}
+
+/// Reject backups from a format version this build can't read
+fn check_version(metadata: &BackupMetadata) -> Result<(), BackupError> {
+    if SUPPORTED_VERSIONS.contains(&metadata.version.as_str()) {
+        return Ok(());
+    }
+    Err(BackupError::VersionMismatch {
+        expected: BACKUP_VERSION.to_string(),
+        found: metadata.version.clone(),
+    })
+}
+
 /// Compress data using gzip
 fn compress_data(data: &[u8]) -> Result<Vec<u8>, BackupError> {
     let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
@@ -362,18 +693,116 @@ fn decompress_data(compressed: &[u8]) -> Result<Vec<u8>, BackupError> {
     Ok(decompressed)
 }
 
+// ============================================================================
+// Password Strength
+// ============================================================================
+
+/// Estimate password strength from length and character-class diversity.
+///
+/// Length earns up to 3 points (8/12/16+ chars) and each character class
+/// beyond the first (lower, upper, digit, symbol) earns 1, capped at 4.
+/// Common passwords and repeated or sequential runs score 0.
+pub fn estimate_password_strength(password: &str) -> PasswordStrength {
+    let mut feedback = Vec::new();
+    let length = password.chars().count();
+
+    let has_lower = password.chars().any(|c| c.is_lowercase());
+    let has_upper = password.chars().any(|c| c.is_uppercase());
+    let has_digit = password.chars().any(|c| c.is_ascii_digit());
+    let has_symbol = password.chars().any(|c| !c.is_alphanumeric());
+    let classes = [has_lower, has_upper, has_digit, has_symbol]
+        .iter()
+        .filter(|&&present| present)
+        .count();
+
+    let length_points: u8 = match length {
+        0..=7 => 0,
+        8..=11 => 1,
+        12..=15 => 2,
+        _ => 3,
+    };
+    let diversity_points = classes.saturating_sub(1) as u8;
+
+    if length < 12 {
+        feedback.push("Use at least 12 characters; a short phrase of unrelated words works well.".to_string());
+    }
+    if !has_upper || !has_lower {
+        feedback.push("Mix uppercase and lowercase letters.".to_string());
+    }
+    if !has_digit {
+        feedback.push("Add a number.".to_string());
+    }
+    if !has_symbol {
+        feedback.push("Add a symbol such as ! or #.".to_string());
+    }
+
+    let lower = password.to_lowercase();
+    let letters: String = lower.chars().filter(|c| c.is_alphabetic()).collect();
+    if COMMON_PASSWORDS.contains(&lower.as_str()) || COMMON_PASSWORDS.contains(&letters.as_str()) {
+        return PasswordStrength {
+            score: 0,
+            feedback: vec!["This is a commonly used password; choose something unique.".to_string()],
+        };
+    }
+    if is_repeated_or_sequential(&lower) {
+        return PasswordStrength {
+            score: 0,
+            feedback: vec!["Avoid repeated or sequential characters like \"aaaa\" or \"1234\".".to_string()],
+        };
+    }
+
+    PasswordStrength {
+        score: (length_points + diversity_points).min(4),
+        feedback,
+    }
+}
+
+/// True when the whole password is one repeated character or a single
+/// ascending/descending run (e.g. "11111111", "abcdefgh", "87654321")
+fn is_repeated_or_sequential(password: &str) -> bool {
+    let chars: Vec<u32> = password.chars().map(|c| c as u32).collect();
+    if chars.len() < 2 {
+        return false;
+    }
+    let steps: Vec<i64> = chars.windows(2).map(|w| w[1] as i64 - w[0] as i64).collect();
+    let first = steps[0];
+    first.abs() <= 1 && steps.iter().all(|&s| s == first)
+}
+
+/// Reject passwords below the absolute length floor or the minimum strength score
+fn check_password_strength(password: &str, min_score: u8) -> Result<(), BackupError> {
+    if password.len() < MIN_PASSWORD_LENGTH {
+        return Err(BackupError::Encryption(format!(
+            "Password must be at least {} characters",
+            MIN_PASSWORD_LENGTH
+        )));
+    }
+
+    let strength = estimate_password_strength(password);
+    if strength.score < min_score {
+        return Err(BackupError::WeakPassword {
+            feedback: strength.feedback.join(" "),
+        });
+    }
+
+    Ok(())
+}
+
 // ============================================================================
 // Database Fetch Functions
 // ============================================================================
 
-async fn fetch_employees(pool: &SqlitePool) -> Result<Vec<EmployeeRow>, BackupError> {
+async fn fetch_employees(pool: &SqlitePool, since: Option<&str>) -> Result<Vec<EmployeeRow>, BackupError> {
     let rows = sqlx::query(
         r#"SELECT
             id, email, full_name, department, job_title, manager_id,
             hire_date, work_state, status, extra_fields, created_at, updated_at,
-            date_of_birth, gender, ethnicity, termination_date, termination_reason
-        FROM employees"#
+            date_of_birth, gender, ethnicity, termination_date, termination_reason,
+            legal_hold, original_full_name, company_id
+        FROM employees
+        WHERE ?1 IS NULL OR datetime(COALESCE(updated_at, created_at)) >= datetime(?1)"#
     )
+    .bind(since)
     .fetch_all(pool)
     .await?;
 
@@ -397,14 +826,19 @@ async fn fetch_employees(pool: &SqlitePool) -> Result<Vec<EmployeeRow>, BackupEr
             ethnicity: row.get("ethnicity"),
             termination_date: row.get("termination_date"),
             termination_reason: row.get("termination_reason"),
+            legal_hold: row.get("legal_hold"),
+            original_full_name: row.get("original_full_name"),
+            company_id: row.get("company_id"),
         })
         .collect())
 }
 
-async fn fetch_conversations(pool: &SqlitePool) -> Result<Vec<ConversationRow>, BackupError> {
+async fn fetch_conversations(pool: &SqlitePool, since: Option<&str>) -> Result<Vec<ConversationRow>, BackupError> {
     let rows = sqlx::query(
-        r#"SELECT id, title, summary, messages_json, created_at, updated_at FROM conversations"#
+        r#"SELECT id, title, summary, messages_json, created_at, updated_at, tags, archived, pinned, exclude_from_memory, model FROM conversations
+        WHERE ?1 IS NULL OR datetime(COALESCE(updated_at, created_at)) >= datetime(?1)"#
     )
+    .bind(since)
     .fetch_all(pool)
     .await?;
 
@@ -417,14 +851,21 @@ async fn fetch_conversations(pool: &SqlitePool) -> Result<Vec<ConversationRow>,
             messages_json: row.get("messages_json"),
             created_at: row.get("created_at"),
             updated_at: row.get("updated_at"),
+            tags: row.get("tags"),
+            archived: row.get("archived"),
+            pinned: row.get("pinned"),
+            exclude_from_memory: row.get("exclude_from_memory"),
+            model: row.get("model"),
         })
         .collect())
 }
 
-async fn fetch_company(pool: &SqlitePool) -> Result<Vec<CompanyRow>, BackupError> {
+async fn fetch_company(pool: &SqlitePool, since: Option<&str>) -> Result<Vec<CompanyRow>, BackupError> {
     let rows = sqlx::query(
-        r#"SELECT id, name, state, industry, created_at FROM company"#
+        r#"SELECT id, name, state, industry, created_at FROM company
+        WHERE ?1 IS NULL OR datetime(created_at) >= datetime(?1)"#
     )
+    .bind(since)
     .fetch_all(pool)
     .await?;
 
@@ -440,15 +881,18 @@ async fn fetch_company(pool: &SqlitePool) -> Result<Vec<CompanyRow>, BackupError
         .collect())
 }
 
-async fn fetch_settings(pool: &SqlitePool) -> Result<Vec<SettingsRow>, BackupError> {
+async fn fetch_settings(pool: &SqlitePool, since: Option<&str>) -> Result<Vec<SettingsRow>, BackupError> {
     let rows = sqlx::query(
-        r#"SELECT key, value, updated_at FROM settings"#
+        r#"SELECT key, value, updated_at FROM settings
+        WHERE ?1 IS NULL OR datetime(updated_at) >= datetime(?1)"#
     )
+    .bind(since)
     .fetch_all(pool)
     .await?;
 
     Ok(rows
         .iter()
+        .filter(|row| !crate::settings::SECRET_KEYS.contains(&row.get::<String, _>("key").as_str()))
         .map(|row| SettingsRow {
             key: row.get("key"),
             value: row.get("value"),
@@ -457,11 +901,15 @@ async fn fetch_settings(pool: &SqlitePool) -> Result<Vec<SettingsRow>, BackupErr
         .collect())
 }
 
-async fn fetch_audit_log(pool: &SqlitePool) -> Result<Vec<AuditLogRow>, BackupError> {
+async fn fetch_audit_log(pool: &SqlitePool, since: Option<&str>) -> Result<Vec<AuditLogRow>, BackupError> {
     let rows = sqlx::query(
-        r#"SELECT id, conversation_id, request_redacted, response_text, context_used, created_at
-        FROM audit_log"#
+        r#"SELECT id, conversation_id, request_redacted, response_text, context_used, created_at,
+            context_snapshot, verification_status, verified_at, input_tokens, output_tokens,
+            request_status
+        FROM audit_log
+        WHERE ?1 IS NULL OR datetime(created_at) >= datetime(?1)"#
     )
+    .bind(since)
     .fetch_all(pool)
     .await?;
 
@@ -474,14 +922,23 @@ async fn fetch_audit_log(pool: &SqlitePool) -> Result<Vec<AuditLogRow>, BackupEr
             response_text: row.get("response_text"),
             context_used: row.get("context_used"),
             created_at: row.get("created_at"),
+            context_snapshot: row.get("context_snapshot"),
+            verification_status: row.get("verification_status"),
+            verified_at: row.get("verified_at"),
+            input_tokens: row.get("input_tokens"),
+            output_tokens: row.get("output_tokens"),
+            request_status: row.get("request_status"),
         })
         .collect())
 }
 
-async fn fetch_review_cycles(pool: &SqlitePool) -> Result<Vec<ReviewCycleRow>, BackupError> {
+async fn fetch_review_cycles(pool: &SqlitePool, since: Option<&str>) -> Result<Vec<ReviewCycleRow>, BackupError> {
     let rows = sqlx::query(
-        r#"SELECT id, name, cycle_type, start_date, end_date, status, created_at FROM review_cycles"#
+        r#"SELECT id, name, cycle_type, start_date, end_date, status, created_at, template_id
+        FROM review_cycles
+        WHERE ?1 IS NULL OR datetime(created_at) >= datetime(?1)"#
     )
+    .bind(since)
     .fetch_all(pool)
     .await?;
 
@@ -495,18 +952,70 @@ async fn fetch_review_cycles(pool: &SqlitePool) -> Result<Vec<ReviewCycleRow>, B
             end_date: row.get("end_date"),
             status: row.get("status"),
             created_at: row.get("created_at"),
+            template_id: row.get("template_id"),
+        })
+        .collect())
+}
+
+async fn fetch_review_cycle_events(
+    pool: &SqlitePool,
+    since: Option<&str>,
+) -> Result<Vec<ReviewCycleEventRow>, BackupError> {
+    let rows = sqlx::query(
+        r#"SELECT id, cycle_id, old_status, new_status, created_at FROM review_cycle_events
+        WHERE ?1 IS NULL OR datetime(created_at) >= datetime(?1)"#
+    )
+    .bind(since)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(rows
+        .iter()
+        .map(|row| ReviewCycleEventRow {
+            id: row.get("id"),
+            cycle_id: row.get("cycle_id"),
+            old_status: row.get("old_status"),
+            new_status: row.get("new_status"),
+            created_at: row.get("created_at"),
+        })
+        .collect())
+}
+
+async fn fetch_review_templates(
+    pool: &SqlitePool,
+    since: Option<&str>,
+) -> Result<Vec<ReviewTemplateRow>, BackupError> {
+    let rows = sqlx::query(
+        r#"SELECT id, name, description, sections, created_at FROM review_templates
+        WHERE ?1 IS NULL OR datetime(created_at) >= datetime(?1)"#
+    )
+    .bind(since)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(rows
+        .iter()
+        .map(|row| ReviewTemplateRow {
+            id: row.get("id"),
+            name: row.get("name"),
+            description: row.get("description"),
+            sections: row.get("sections"),
+            created_at: row.get("created_at"),
         })
         .collect())
 }
 
 async fn fetch_performance_ratings(
     pool: &SqlitePool,
+    since: Option<&str>,
 ) -> Result<Vec<PerformanceRatingRow>, BackupError> {
     let rows = sqlx::query(
         r#"SELECT id, employee_id, review_cycle_id, overall_rating, goals_rating,
             competencies_rating, reviewer_id, rating_date, created_at, updated_at
-        FROM performance_ratings"#
+        FROM performance_ratings
+        WHERE ?1 IS NULL OR datetime(COALESCE(updated_at, created_at)) >= datetime(?1)"#
     )
+    .bind(since)
     .fetch_all(pool)
     .await?;
 
@@ -529,13 +1038,16 @@ async fn fetch_performance_ratings(
 
 async fn fetch_performance_reviews(
     pool: &SqlitePool,
+    since: Option<&str>,
 ) -> Result<Vec<PerformanceReviewRow>, BackupError> {
     let rows = sqlx::query(
         r#"SELECT id, employee_id, review_cycle_id, strengths, areas_for_improvement,
             accomplishments, goals_next_period, manager_comments, self_assessment,
             reviewer_id, review_date, created_at, updated_at
-        FROM performance_reviews"#
+        FROM performance_reviews
+        WHERE ?1 IS NULL OR datetime(COALESCE(updated_at, created_at)) >= datetime(?1)"#
     )
+    .bind(since)
     .fetch_all(pool)
     .await?;
 
@@ -559,11 +1071,13 @@ async fn fetch_performance_reviews(
         .collect())
 }
 
-async fn fetch_enps_responses(pool: &SqlitePool) -> Result<Vec<EnpsRow>, BackupError> {
+async fn fetch_enps_responses(pool: &SqlitePool, since: Option<&str>) -> Result<Vec<EnpsRow>, BackupError> {
     let rows = sqlx::query(
         r#"SELECT id, employee_id, score, survey_date, survey_name, feedback_text, created_at
-        FROM enps_responses"#
+        FROM enps_responses
+        WHERE ?1 IS NULL OR datetime(created_at) >= datetime(?1)"#
     )
+    .bind(since)
     .fetch_all(pool)
     .await?;
 
@@ -581,106 +1095,478 @@ async fn fetch_enps_responses(pool: &SqlitePool) -> Result<Vec<EnpsRow>, BackupE
         .collect())
 }
 
-/// Fetch all tables for backup
-async fn fetch_all_tables(pool: &SqlitePool) -> Result<BackupTables, BackupError> {
+async fn fetch_department_changes(
+    pool: &SqlitePool,
+    since: Option<&str>,
+) -> Result<Vec<DepartmentChangeRow>, BackupError> {
+    let rows = sqlx::query(
+        r#"SELECT id, employee_id, from_department, to_department, changed_at
+        FROM department_changes
+        WHERE ?1 IS NULL OR datetime(changed_at) >= datetime(?1)"#
+    )
+    .bind(since)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(rows
+        .iter()
+        .map(|row| DepartmentChangeRow {
+            id: row.get("id"),
+            employee_id: row.get("employee_id"),
+            from_department: row.get("from_department"),
+            to_department: row.get("to_department"),
+            changed_at: row.get("changed_at"),
+        })
+        .collect())
+}
+
+/// Fetch tables for backup: every row when `since` is None, otherwise rows
+/// written at or after `since` (SQLite datetime text). Rows are matched on updated_at, falling back to
+/// created_at; tables without updated_at (company, audit_log, review_templates,
+/// review_cycles, review_cycle_events, enps_responses) only pick up rows
+/// created in the window, and department_changes rows recorded in it.
+async fn fetch_tables_since(
+    pool: &SqlitePool,
+    since: Option<&str>,
+) -> Result<BackupTables, BackupError> {
     Ok(BackupTables {
-        employees: fetch_employees(pool).await?,
-        conversations: fetch_conversations(pool).await?,
-        company: fetch_company(pool).await?,
-        settings: fetch_settings(pool).await?,
-        audit_log: fetch_audit_log(pool).await?,
-        review_cycles: fetch_review_cycles(pool).await?,
-        performance_ratings: fetch_performance_ratings(pool).await?,
-        performance_reviews: fetch_performance_reviews(pool).await?,
-        enps_responses: fetch_enps_responses(pool).await?,
+        employees: fetch_employees(pool, since).await?,
+        conversations: fetch_conversations(pool, since).await?,
+        company: fetch_company(pool, since).await?,
+        settings: fetch_settings(pool, since).await?,
+        audit_log: fetch_audit_log(pool, since).await?,
+        review_templates: fetch_review_templates(pool, since).await?,
+        review_cycles: fetch_review_cycles(pool, since).await?,
+        review_cycle_events: fetch_review_cycle_events(pool, since).await?,
+        performance_ratings: fetch_performance_ratings(pool, since).await?,
+        performance_reviews: fetch_performance_reviews(pool, since).await?,
+        enps_responses: fetch_enps_responses(pool, since).await?,
+        department_changes: fetch_department_changes(pool, since).await?,
     })
 }
 
+async fn fetch_legal_hold_ids(pool: &SqlitePool) -> Result<HashSet<String>, BackupError> {
+    let ids: Vec<String> = sqlx::query_scalar("SELECT id FROM employees WHERE legal_hold = 1")
+        .fetch_all(pool)
+        .await?;
+    Ok(ids.into_iter().collect())
+}
+
+/// Keep the first row for each key and drop later repeats, recording one
+/// warning per duplicated key
+fn dedupe_rows<T>(table: &str, rows: &mut Vec<T>, key: impl Fn(&T) -> &str, warnings: &mut Vec<String>) {
+    let mut seen: HashSet<String> = HashSet::new();
+    let mut dropped: Vec<(String, usize)> = Vec::new();
+
+    rows.retain(|row| {
+        let k = key(row);
+        if seen.insert(k.to_string()) {
+            return true;
+        }
+        match dropped.iter_mut().find(|(id, _)| id == k) {
+            Some((_, count)) => *count += 1,
+            None => dropped.push((k.to_string(), 1)),
+        }
+        false
+    });
+
+    for (id, count) in dropped {
+        warnings.push(format!(
+            "{}: '{}' appears {} times in the backup; kept the first row and skipped {}",
+            table,
+            id,
+            count + 1,
+            count
+        ));
+    }
+}
+
+/// Remove rows whose primary key repeats within a table (e.g. from a
+/// hand-merged backup), which would otherwise abort the restore on a unique
+/// constraint partway through. Returns a warning per duplicated key.
+fn dedupe_backup_tables(tables: &mut BackupTables) -> Vec<String> {
+    let mut warnings = Vec::new();
+    dedupe_rows("company", &mut tables.company, |r| &r.id, &mut warnings);
+    dedupe_rows("settings", &mut tables.settings, |r| &r.key, &mut warnings);
+    dedupe_rows("review_templates", &mut tables.review_templates, |r| &r.id, &mut warnings);
+    dedupe_rows("review_cycles", &mut tables.review_cycles, |r| &r.id, &mut warnings);
+    dedupe_rows("review_cycle_events", &mut tables.review_cycle_events, |r| &r.id, &mut warnings);
+    dedupe_rows("employees", &mut tables.employees, |r| &r.id, &mut warnings);
+    dedupe_rows("performance_ratings", &mut tables.performance_ratings, |r| &r.id, &mut warnings);
+    dedupe_rows("performance_reviews", &mut tables.performance_reviews, |r| &r.id, &mut warnings);
+    dedupe_rows("enps_responses", &mut tables.enps_responses, |r| &r.id, &mut warnings);
+    dedupe_rows("department_changes", &mut tables.department_changes, |r| &r.id, &mut warnings);
+    dedupe_rows("conversations", &mut tables.conversations, |r| &r.id, &mut warnings);
+    dedupe_rows("audit_log", &mut tables.audit_log, |r| &r.id, &mut warnings);
+    warnings
+}
+
 // ============================================================================
 // Database Clear Functions (FK-safe order: child → parent)
 // ============================================================================
 
-/// Clear all tables in FK-safe order for import
-/// Order: enps_responses → performance_reviews → performance_ratings → audit_log
-///        → conversations → employees → review_cycles → settings → company
-pub async fn clear_all_tables(pool: &SqlitePool) -> Result<(), BackupError> {
-    // Child tables first (those with foreign keys)
-    sqlx::query("DELETE FROM enps_responses")
-        .execute(pool)
-        .await?;
-    sqlx::query("DELETE FROM performance_reviews")
-        .execute(pool)
-        .await?;
-    sqlx::query("DELETE FROM performance_ratings")
-        .execute(pool)
-        .await?;
-    sqlx::query("DELETE FROM audit_log").execute(pool).await?;
+/// Clear the selected tables for import in FK-safe order (child → parent)
+/// Order: audit_log → conversations → department_changes → enps_responses
+///        → performance_reviews → performance_ratings → employees
+///        → review_cycle_events → review_cycles → review_templates → settings
+///        → company
+async fn clear_tables(tx: &mut Transaction<'_, Sqlite>, selected: &HashSet<TableName>) -> Result<(), BackupError> {
+    for table in TableName::ALL.iter().rev().filter(|t| selected.contains(t)) {
+        sqlx::query(&format!("DELETE FROM {}", table.as_str()))
+            .execute(&mut **tx)
+            .await?;
+
+        // Rebuild FTS indexes to drop any orphaned entries. (Deleting from the
+        // FTS table first would corrupt it: the table's delete trigger needs
+        // each row's index entry to still exist.)
+        let fts = match table {
+            TableName::Conversations => Some("conversations_fts"),
+            TableName::PerformanceReviews => Some("performance_reviews_fts"),
+            _ => None,
+        };
+        if let Some(fts) = fts {
+            sqlx::query(&format!("INSERT INTO {fts}({fts}) VALUES('rebuild')"))
+                .execute(&mut **tx)
+                .await?;
+        }
+    }
 
-    // Also clear FTS tables to avoid orphaned entries
-    sqlx::query("DELETE FROM conversations_fts")
-        .execute(pool)
-        .await?;
-    sqlx::query("DELETE FROM performance_reviews_fts")
-        .execute(pool)
-        .await?;
+    Ok(())
+}
 
-    sqlx::query("DELETE FROM conversations")
-        .execute(pool)
-        .await?;
-    sqlx::query("DELETE FROM employees").execute(pool).await?;
-    sqlx::query("DELETE FROM review_cycles")
-        .execute(pool)
+/// Refuse a selection that would clear a parent table while keeping children
+/// that reference it; deleting the parent rows would cascade into (or be
+/// blocked by) the unselected table.
+fn check_selection(selected: &HashSet<TableName>) -> Result<(), BackupError> {
+    for parent in selected {
+        let kept_children: Vec<&str> = TableName::ALL
+            .iter()
+            .filter(|child| child.parents().contains(parent) && !selected.contains(child))
+            .map(|child| child.as_str())
+            .collect();
+        if !kept_children.is_empty() {
+            return Err(BackupError::InvalidSelection(format!(
+                "restoring {} would also clear {}; select {} too",
+                parent.as_str(),
+                kept_children.join(", "),
+                if kept_children.len() == 1 { "it" } else { "them" }
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// Empty every table in `tables` that isn't selected
+fn retain_selected(tables: &mut BackupTables, selected: &HashSet<TableName>) {
+    for table in TableName::ALL.iter().filter(|t| !selected.contains(t)) {
+        match table {
+            TableName::Company => tables.company.clear(),
+            TableName::Settings => tables.settings.clear(),
+            TableName::ReviewTemplates => tables.review_templates.clear(),
+            TableName::ReviewCycles => tables.review_cycles.clear(),
+            TableName::ReviewCycleEvents => tables.review_cycle_events.clear(),
+            TableName::Employees => tables.employees.clear(),
+            TableName::PerformanceRatings => tables.performance_ratings.clear(),
+            TableName::PerformanceReviews => tables.performance_reviews.clear(),
+            TableName::EnpsResponses => tables.enps_responses.clear(),
+            TableName::DepartmentChanges => tables.department_changes.clear(),
+            TableName::Conversations => tables.conversations.clear(),
+            TableName::AuditLog => tables.audit_log.clear(),
+        }
+    }
+}
+
+/// IDs a restored child row may reference in `parent`: the backup's rows when
+/// the parent is being restored too (plus existing rows if nothing is
+/// cleared), otherwise whatever the database already holds
+async fn available_parent_ids(
+    tx: &mut Transaction<'_, Sqlite>,
+    parent: TableName,
+    backup_ids: Vec<String>,
+    selected: &HashSet<TableName>,
+    incremental: bool,
+) -> Result<HashSet<String>, BackupError> {
+    let mut ids: HashSet<String> = HashSet::new();
+    if selected.contains(&parent) {
+        ids.extend(backup_ids);
+        if !incremental {
+            return Ok(ids);
+        }
+    }
+    let existing: Vec<String> = sqlx::query_scalar(&format!("SELECT id FROM {}", parent.as_str()))
+        .fetch_all(&mut **tx)
         .await?;
-    sqlx::query("DELETE FROM settings").execute(pool).await?;
-    sqlx::query("DELETE FROM company").execute(pool).await?;
+    ids.extend(existing);
+    Ok(ids)
+}
 
-    Ok(())
+/// For a partial restore, skip rows whose required parent won't exist and
+/// clear optional references (template, reviewer, conversation) that would dangle,
+/// with a warning for each table affected
+async fn drop_orphaned_rows(
+    tx: &mut Transaction<'_, Sqlite>,
+    tables: &mut BackupTables,
+    selected: &HashSet<TableName>,
+    incremental: bool,
+) -> Result<Vec<String>, BackupError> {
+    let mut warnings = Vec::new();
+
+    if selected.contains(&TableName::ReviewCycles) {
+        let template_ids: Vec<String> = tables.review_templates.iter().map(|t| t.id.clone()).collect();
+        let templates =
+            available_parent_ids(tx, TableName::ReviewTemplates, template_ids, selected, incremental).await?;
+        let mut cleared = 0;
+        for cycle in &mut tables.review_cycles {
+            if cycle.template_id.as_ref().is_some_and(|t| !templates.contains(t)) {
+                cycle.template_id = None;
+                cleared += 1;
+            }
+        }
+        push_cleared_warning(&mut warnings, "review_cycles", cleared, "review template");
+    }
+
+    let needs_employees = [
+        TableName::PerformanceRatings,
+        TableName::PerformanceReviews,
+        TableName::EnpsResponses,
+        TableName::DepartmentChanges,
+    ]
+    .iter()
+    .any(|t| selected.contains(t));
+    if needs_employees {
+        let employee_ids: Vec<String> = tables.employees.iter().map(|e| e.id.clone()).collect();
+        let employees = available_parent_ids(tx, TableName::Employees, employee_ids, selected, incremental).await?;
+        let cycle_ids: Vec<String> = tables.review_cycles.iter().map(|c| c.id.clone()).collect();
+        let cycles = available_parent_ids(tx, TableName::ReviewCycles, cycle_ids, selected, incremental).await?;
+
+        let before = tables.performance_ratings.len();
+        tables
+            .performance_ratings
+            .retain(|r| employees.contains(&r.employee_id) && cycles.contains(&r.review_cycle_id));
+        push_skipped_warning(&mut warnings, "performance_ratings", before - tables.performance_ratings.len(), "employee or review cycle");
+        let mut cleared = 0;
+        for rating in &mut tables.performance_ratings {
+            if rating.reviewer_id.as_ref().is_some_and(|r| !employees.contains(r)) {
+                rating.reviewer_id = None;
+                cleared += 1;
+            }
+        }
+        push_cleared_warning(&mut warnings, "performance_ratings", cleared, "reviewer");
+
+        let before = tables.performance_reviews.len();
+        tables
+            .performance_reviews
+            .retain(|r| employees.contains(&r.employee_id) && cycles.contains(&r.review_cycle_id));
+        push_skipped_warning(&mut warnings, "performance_reviews", before - tables.performance_reviews.len(), "employee or review cycle");
+        let mut cleared = 0;
+        for review in &mut tables.performance_reviews {
+            if review.reviewer_id.as_ref().is_some_and(|r| !employees.contains(r)) {
+                review.reviewer_id = None;
+                cleared += 1;
+            }
+        }
+        push_cleared_warning(&mut warnings, "performance_reviews", cleared, "reviewer");
+
+        let before = tables.enps_responses.len();
+        tables.enps_responses.retain(|r| employees.contains(&r.employee_id));
+        push_skipped_warning(&mut warnings, "enps_responses", before - tables.enps_responses.len(), "employee");
+
+        let before = tables.department_changes.len();
+        tables.department_changes.retain(|r| employees.contains(&r.employee_id));
+        push_skipped_warning(&mut warnings, "department_changes", before - tables.department_changes.len(), "employee");
+    }
+
+    if selected.contains(&TableName::AuditLog) {
+        let conversation_ids: Vec<String> = tables.conversations.iter().map(|c| c.id.clone()).collect();
+        let conversations =
+            available_parent_ids(tx, TableName::Conversations, conversation_ids, selected, incremental).await?;
+        let mut cleared = 0;
+        for entry in &mut tables.audit_log {
+            if entry.conversation_id.as_ref().is_some_and(|c| !conversations.contains(c)) {
+                entry.conversation_id = None;
+                cleared += 1;
+            }
+        }
+        push_cleared_warning(&mut warnings, "audit_log", cleared, "conversation");
+    }
+
+    Ok(warnings)
+}
+
+fn push_skipped_warning(warnings: &mut Vec<String>, table: &str, skipped: usize, parent: &str) {
+    if skipped > 0 {
+        warnings.push(format!(
+            "{}: skipped {} row(s) whose {} isn't in the database or the selected tables",
+            table, skipped, parent
+        ));
+    }
+}
+
+fn push_cleared_warning(warnings: &mut Vec<String>, table: &str, cleared: usize, parent: &str) {
+    if cleared > 0 {
+        warnings.push(format!(
+            "{}: cleared {} reference(s) to a {} that isn't in the database or the selected tables",
+            table, cleared, parent
+        ));
+    }
 }
 
 // ============================================================================
 // Database Restore Functions (FK-safe order: parent → child)
 // ============================================================================
 
-async fn restore_company(pool: &SqlitePool, rows: &[CompanyRow]) -> Result<usize, BackupError> {
+const COMPANY_COLUMNS: &[&str] = &[
+    "id", "name", "state", "industry", "created_at",
+];
+const SETTINGS_COLUMNS: &[&str] = &[
+    "key", "value", "updated_at",
+];
+const REVIEW_TEMPLATES_COLUMNS: &[&str] = &[
+    "id", "name", "description", "sections", "created_at",
+];
+const REVIEW_CYCLES_COLUMNS: &[&str] = &[
+    "id", "name", "cycle_type", "start_date", "end_date", "status", "created_at",
+    "template_id",
+];
+// No FK to review_cycles: the trail outlives a deleted cycle, so events
+// restore without their cycle
+const REVIEW_CYCLE_EVENTS_COLUMNS: &[&str] = &[
+    "id", "cycle_id", "old_status", "new_status", "created_at",
+];
+const EMPLOYEES_COLUMNS: &[&str] = &[
+    "id", "email", "full_name", "department", "job_title", "manager_id", "hire_date",
+    "work_state", "status", "extra_fields", "created_at", "updated_at", "date_of_birth",
+    "gender", "ethnicity", "termination_date", "termination_reason", "legal_hold",
+    "original_full_name", "company_id",
+];
+const PERFORMANCE_RATINGS_COLUMNS: &[&str] = &[
+    "id", "employee_id", "review_cycle_id", "overall_rating", "goals_rating",
+    "competencies_rating", "reviewer_id", "rating_date", "created_at", "updated_at",
+];
+const PERFORMANCE_REVIEWS_COLUMNS: &[&str] = &[
+    "id", "employee_id", "review_cycle_id", "strengths", "areas_for_improvement",
+    "accomplishments", "goals_next_period", "manager_comments", "self_assessment",
+    "reviewer_id", "review_date", "created_at", "updated_at",
+];
+const ENPS_RESPONSES_COLUMNS: &[&str] = &[
+    "id", "employee_id", "score", "survey_date", "survey_name", "feedback_text",
+    "created_at",
+];
+const DEPARTMENT_CHANGES_COLUMNS: &[&str] = &[
+    "id", "employee_id", "from_department", "to_department", "changed_at",
+];
+const CONVERSATIONS_COLUMNS: &[&str] = &[
+    "id", "title", "summary", "messages_json", "created_at", "updated_at", "tags",
+    "archived", "pinned", "exclude_from_memory", "model",
+];
+const AUDIT_LOG_COLUMNS: &[&str] = &[
+    "id", "conversation_id", "request_redacted", "response_text", "context_used",
+    "created_at", "context_snapshot", "verification_status", "verified_at",
+    "input_tokens", "output_tokens", "request_status",
+];
+
+/// How restored rows treat rows already in the database
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum WriteMode {
+    /// Plain INSERT into cleared tables
+    Insert,
+    /// Incremental restore: update the existing row with the same key
+    Upsert,
+    /// Merge: keep the existing row and skip the backup's
+    SkipExisting,
+}
+
+/// INSERT for a restored row. Incremental restores upsert on `key` instead,
+/// updating rows that already exist and leaving everything else in place.
+/// (Not INSERT OR REPLACE: that deletes the old row first, which would fire
+/// ON DELETE CASCADE on children and skip the FTS update triggers.)
+/// Merges skip a row that collides with any unique key (id, email, one rating
+/// per employee and cycle); unlike INSERT OR IGNORE, NOT NULL and CHECK
+/// failures still raise.
+fn insert_sql(table: &str, key: &str, columns: &[&str], mode: WriteMode) -> String {
+    let placeholders = vec!["?"; columns.len()].join(", ");
+    let mut sql = format!(
+        "INSERT INTO {} ({}) VALUES ({})",
+        table,
+        columns.join(", "),
+        placeholders
+    );
+    if mode == WriteMode::SkipExisting {
+        sql.push_str(" ON CONFLICT DO NOTHING");
+    }
+    if mode == WriteMode::Upsert {
+        let updates: Vec<String> = columns
+            .iter()
+            .filter(|c| **c != key)
+            .map(|c| format!("{c} = excluded.{c}"))
+            .collect();
+        sql.push_str(&format!(" ON CONFLICT({}) DO UPDATE SET {}", key, updates.join(", ")));
+    }
+    sql
+}
+
+async fn restore_company(tx: &mut Transaction<'_, Sqlite>, rows: &[CompanyRow], mode: WriteMode) -> Result<usize, BackupError> {
+    let sql = insert_sql("company", "id", COMPANY_COLUMNS, mode);
+    let mut written = 0;
     for row in rows {
-        sqlx::query(
-            r#"INSERT INTO company (id, name, state, industry, created_at)
-            VALUES (?, ?, ?, ?, ?)"#,
-        )
+        written += sqlx::query(&sql)
         .bind(&row.id)
         .bind(&row.name)
         .bind(&row.state)
         .bind(&row.industry)
         .bind(&row.created_at)
-        .execute(pool)
-        .await?;
+        .execute(&mut **tx)
+        .await?
+        .rows_affected() as usize;
     }
-    Ok(rows.len())
+    Ok(written)
 }
 
-async fn restore_settings(pool: &SqlitePool, rows: &[SettingsRow]) -> Result<usize, BackupError> {
+async fn restore_settings(tx: &mut Transaction<'_, Sqlite>, rows: &[SettingsRow], mode: WriteMode) -> Result<usize, BackupError> {
+    let sql = insert_sql("settings", "key", SETTINGS_COLUMNS, mode);
+    let mut written = 0;
     for row in rows {
-        sqlx::query(
-            r#"INSERT INTO settings (key, value, updated_at) VALUES (?, ?, ?)"#,
-        )
+        written += sqlx::query(&sql)
         .bind(&row.key)
         .bind(&row.value)
         .bind(&row.updated_at)
-        .execute(pool)
-        .await?;
+        .execute(&mut **tx)
+        .await?
+        .rows_affected() as usize;
     }
-    Ok(rows.len())
+    Ok(written)
+}
+
+async fn restore_review_templates(
+    tx: &mut Transaction<'_, Sqlite>,
+    rows: &[ReviewTemplateRow],
+    mode: WriteMode,
+) -> Result<usize, BackupError> {
+    let sql = insert_sql("review_templates", "id", REVIEW_TEMPLATES_COLUMNS, mode);
+    let mut written = 0;
+    for row in rows {
+        written += sqlx::query(&sql)
+        .bind(&row.id)
+        .bind(&row.name)
+        .bind(&row.description)
+        .bind(&row.sections)
+        .bind(&row.created_at)
+        .execute(&mut **tx)
+        .await?
+        .rows_affected() as usize;
+    }
+    Ok(written)
 }
 
 async fn restore_review_cycles(
-    pool: &SqlitePool,
+    tx: &mut Transaction<'_, Sqlite>,
     rows: &[ReviewCycleRow],
+    mode: WriteMode,
 ) -> Result<usize, BackupError> {
+    let sql = insert_sql("review_cycles", "id", REVIEW_CYCLES_COLUMNS, mode);
+    let mut written = 0;
     for row in rows {
-        sqlx::query(
-            r#"INSERT INTO review_cycles (id, name, cycle_type, start_date, end_date, status, created_at)
-            VALUES (?, ?, ?, ?, ?, ?, ?)"#,
-        )
+        written += sqlx::query(&sql)
         .bind(&row.id)
         .bind(&row.name)
         .bind(&row.cycle_type)
@@ -688,24 +1574,44 @@ async fn restore_review_cycles(
         .bind(&row.end_date)
         .bind(&row.status)
         .bind(&row.created_at)
-        .execute(pool)
-        .await?;
+        .bind(&row.template_id)
+        .execute(&mut **tx)
+        .await?
+        .rows_affected() as usize;
     }
-    Ok(rows.len())
+    Ok(written)
+}
+
+async fn restore_review_cycle_events(
+    tx: &mut Transaction<'_, Sqlite>,
+    rows: &[ReviewCycleEventRow],
+    mode: WriteMode,
+) -> Result<usize, BackupError> {
+    let sql = insert_sql("review_cycle_events", "id", REVIEW_CYCLE_EVENTS_COLUMNS, mode);
+    let mut written = 0;
+    for row in rows {
+        written += sqlx::query(&sql)
+        .bind(&row.id)
+        .bind(&row.cycle_id)
+        .bind(&row.old_status)
+        .bind(&row.new_status)
+        .bind(&row.created_at)
+        .execute(&mut **tx)
+        .await?
+        .rows_affected() as usize;
+    }
+    Ok(written)
 }
 
 async fn restore_employees(
-    pool: &SqlitePool,
+    tx: &mut Transaction<'_, Sqlite>,
     rows: &[EmployeeRow],
+    mode: WriteMode,
 ) -> Result<usize, BackupError> {
+    let sql = insert_sql("employees", "id", EMPLOYEES_COLUMNS, mode);
+    let mut written = 0;
     for row in rows {
-        sqlx::query(
-            r#"INSERT INTO employees (
-                id, email, full_name, department, job_title, manager_id,
-                hire_date, work_state, status, extra_fields, created_at, updated_at,
-                date_of_birth, gender, ethnicity, termination_date, termination_reason
-            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)"#,
-        )
+        written += sqlx::query(&sql)
         .bind(&row.id)
         .bind(&row.email)
         .bind(&row.full_name)
@@ -723,23 +1629,26 @@ async fn restore_employees(
         .bind(&row.ethnicity)
         .bind(&row.termination_date)
         .bind(&row.termination_reason)
-        .execute(pool)
-        .await?;
+        .bind(row.legal_hold)
+        .bind(&row.original_full_name)
+        // Backups from before multi-company support belong to the primary company
+        .bind(row.company_id.as_deref().unwrap_or(crate::company::DEFAULT_COMPANY_ID))
+        .execute(&mut **tx)
+        .await?
+        .rows_affected() as usize;
     }
-    Ok(rows.len())
+    Ok(written)
 }
 
 async fn restore_performance_ratings(
-    pool: &SqlitePool,
+    tx: &mut Transaction<'_, Sqlite>,
     rows: &[PerformanceRatingRow],
+    mode: WriteMode,
 ) -> Result<usize, BackupError> {
+    let sql = insert_sql("performance_ratings", "id", PERFORMANCE_RATINGS_COLUMNS, mode);
+    let mut written = 0;
     for row in rows {
-        sqlx::query(
-            r#"INSERT INTO performance_ratings (
-                id, employee_id, review_cycle_id, overall_rating, goals_rating,
-                competencies_rating, reviewer_id, rating_date, created_at, updated_at
-            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)"#,
-        )
+        written += sqlx::query(&sql)
         .bind(&row.id)
         .bind(&row.employee_id)
         .bind(&row.review_cycle_id)
@@ -750,24 +1659,22 @@ async fn restore_performance_ratings(
         .bind(&row.rating_date)
         .bind(&row.created_at)
         .bind(&row.updated_at)
-        .execute(pool)
-        .await?;
+        .execute(&mut **tx)
+        .await?
+        .rows_affected() as usize;
     }
-    Ok(rows.len())
+    Ok(written)
 }
 
 async fn restore_performance_reviews(
-    pool: &SqlitePool,
+    tx: &mut Transaction<'_, Sqlite>,
     rows: &[PerformanceReviewRow],
+    mode: WriteMode,
 ) -> Result<usize, BackupError> {
+    let sql = insert_sql("performance_reviews", "id", PERFORMANCE_REVIEWS_COLUMNS, mode);
+    let mut written = 0;
     for row in rows {
-        sqlx::query(
-            r#"INSERT INTO performance_reviews (
-                id, employee_id, review_cycle_id, strengths, areas_for_improvement,
-                accomplishments, goals_next_period, manager_comments, self_assessment,
-                reviewer_id, review_date, created_at, updated_at
-            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)"#,
-        )
+        written += sqlx::query(&sql)
         .bind(&row.id)
         .bind(&row.employee_id)
         .bind(&row.review_cycle_id)
@@ -781,22 +1688,22 @@ async fn restore_performance_reviews(
         .bind(&row.review_date)
         .bind(&row.created_at)
         .bind(&row.updated_at)
-        .execute(pool)
-        .await?;
+        .execute(&mut **tx)
+        .await?
+        .rows_affected() as usize;
     }
-    Ok(rows.len())
+    Ok(written)
 }
 
 async fn restore_enps_responses(
-    pool: &SqlitePool,
+    tx: &mut Transaction<'_, Sqlite>,
     rows: &[EnpsRow],
+    mode: WriteMode,
 ) -> Result<usize, BackupError> {
+    let sql = insert_sql("enps_responses", "id", ENPS_RESPONSES_COLUMNS, mode);
+    let mut written = 0;
     for row in rows {
-        sqlx::query(
-            r#"INSERT INTO enps_responses (
-                id, employee_id, score, survey_date, survey_name, feedback_text, created_at
-            ) VALUES (?, ?, ?, ?, ?, ?, ?)"#,
-        )
+        written += sqlx::query(&sql)
         .bind(&row.id)
         .bind(&row.employee_id)
         .bind(row.score)
@@ -804,73 +1711,177 @@ async fn restore_enps_responses(
         .bind(&row.survey_name)
         .bind(&row.feedback_text)
         .bind(&row.created_at)
-        .execute(pool)
-        .await?;
+        .execute(&mut **tx)
+        .await?
+        .rows_affected() as usize;
     }
-    Ok(rows.len())
+    Ok(written)
+}
+
+async fn restore_department_changes(
+    tx: &mut Transaction<'_, Sqlite>,
+    rows: &[DepartmentChangeRow],
+    mode: WriteMode,
+) -> Result<usize, BackupError> {
+    let sql = insert_sql("department_changes", "id", DEPARTMENT_CHANGES_COLUMNS, mode);
+    let mut written = 0;
+    for row in rows {
+        written += sqlx::query(&sql)
+        .bind(&row.id)
+        .bind(&row.employee_id)
+        .bind(&row.from_department)
+        .bind(&row.to_department)
+        .bind(&row.changed_at)
+        .execute(&mut **tx)
+        .await?
+        .rows_affected() as usize;
+    }
+    Ok(written)
 }
 
 async fn restore_conversations(
-    pool: &SqlitePool,
+    tx: &mut Transaction<'_, Sqlite>,
     rows: &[ConversationRow],
+    mode: WriteMode,
 ) -> Result<usize, BackupError> {
+    let sql = insert_sql("conversations", "id", CONVERSATIONS_COLUMNS, mode);
+    let mut written = 0;
     for row in rows {
-        sqlx::query(
-            r#"INSERT INTO conversations (id, title, summary, messages_json, created_at, updated_at)
-            VALUES (?, ?, ?, ?, ?, ?)"#,
-        )
+        written += sqlx::query(&sql)
         .bind(&row.id)
         .bind(&row.title)
         .bind(&row.summary)
         .bind(&row.messages_json)
         .bind(&row.created_at)
         .bind(&row.updated_at)
-        .execute(pool)
-        .await?;
+        .bind(&row.tags)
+        .bind(row.archived)
+        .bind(row.pinned)
+        .bind(row.exclude_from_memory)
+        .bind(&row.model)
+        .execute(&mut **tx)
+        .await?
+        .rows_affected() as usize;
     }
-    Ok(rows.len())
+    Ok(written)
 }
 
 async fn restore_audit_log(
-    pool: &SqlitePool,
+    tx: &mut Transaction<'_, Sqlite>,
     rows: &[AuditLogRow],
+    mode: WriteMode,
 ) -> Result<usize, BackupError> {
+    let sql = insert_sql("audit_log", "id", AUDIT_LOG_COLUMNS, mode);
+    let mut written = 0;
     for row in rows {
-        sqlx::query(
-            r#"INSERT INTO audit_log (id, conversation_id, request_redacted, response_text, context_used, created_at)
-            VALUES (?, ?, ?, ?, ?, ?)"#,
-        )
+        written += sqlx::query(&sql)
         .bind(&row.id)
         .bind(&row.conversation_id)
         .bind(&row.request_redacted)
         .bind(&row.response_text)
         .bind(&row.context_used)
         .bind(&row.created_at)
-        .execute(pool)
-        .await?;
+        .bind(&row.context_snapshot)
+        .bind(&row.verification_status)
+        .bind(&row.verified_at)
+        .bind(row.input_tokens)
+        .bind(row.output_tokens)
+        .bind(row.request_status.as_deref().unwrap_or(crate::audit::REQUEST_COMPLETED))
+        .execute(&mut **tx)
+        .await?
+        .rows_affected() as usize;
     }
-    Ok(rows.len())
+    Ok(written)
 }
 
 /// Restore all tables in FK-safe order
-/// Order: company → settings → review_cycles → employees → performance_ratings
-///        → performance_reviews → enps_responses → conversations → audit_log
+/// Order: company → settings → review_templates → review_cycles
+///        → review_cycle_events → employees → performance_ratings
+///        → performance_reviews → enps_responses → department_changes
+///        → conversations → audit_log
+///
+/// With `upsert` (incremental backups) the same order applies, but a row's
+/// parent may not be in the backup at all: a rating changed inside the window
+/// while its employee and review cycle did not. Those parents must already be
+/// in the database, which holds when an incremental backup is applied on top
+/// of the full backup it was taken after. Applied to an empty database, such
+/// rows fail their foreign key and the import stops.
 async fn restore_all_tables(
-    pool: &SqlitePool,
+    tx: &mut Transaction<'_, Sqlite>,
     tables: &BackupTables,
+    upsert: bool,
 ) -> Result<TableCounts, BackupError> {
+    let mode = if upsert { WriteMode::Upsert } else { WriteMode::Insert };
     Ok(TableCounts {
-        company: restore_company(pool, &tables.company).await?,
-        settings: restore_settings(pool, &tables.settings).await?,
-        review_cycles: restore_review_cycles(pool, &tables.review_cycles).await?,
-        employees: restore_employees(pool, &tables.employees).await?,
-        performance_ratings: restore_performance_ratings(pool, &tables.performance_ratings)
+        company: restore_company(tx, &tables.company, mode).await?,
+        settings: restore_settings(tx, &tables.settings, mode).await?,
+        review_templates: restore_review_templates(tx, &tables.review_templates, mode).await?,
+        review_cycles: restore_review_cycles(tx, &tables.review_cycles, mode).await?,
+        review_cycle_events: restore_review_cycle_events(tx, &tables.review_cycle_events, mode).await?,
+        employees: restore_employees(tx, &tables.employees, mode).await?,
+        performance_ratings: restore_performance_ratings(tx, &tables.performance_ratings, mode)
             .await?,
-        performance_reviews: restore_performance_reviews(pool, &tables.performance_reviews)
+        performance_reviews: restore_performance_reviews(tx, &tables.performance_reviews, mode)
             .await?,
-        enps_responses: restore_enps_responses(pool, &tables.enps_responses).await?,
-        conversations: restore_conversations(pool, &tables.conversations).await?,
-        audit_log: restore_audit_log(pool, &tables.audit_log).await?,
+        enps_responses: restore_enps_responses(tx, &tables.enps_responses, mode).await?,
+        department_changes: restore_department_changes(tx, &tables.department_changes, mode).await?,
+        conversations: restore_conversations(tx, &tables.conversations, mode).await?,
+        audit_log: restore_audit_log(tx, &tables.audit_log, mode).await?,
+    })
+}
+
+/// Merge tables into existing data, same order as `restore_all_tables`.
+/// Parents are written first so children are checked against what actually
+/// landed: a backup employee skipped for colliding with an existing email
+/// takes its ratings, reviews, eNPS responses and department history with it
+/// (with a warning), and cycles and audit entries lose links to templates and
+/// conversations that didn't make it.
+async fn merge_all_tables(
+    tx: &mut Transaction<'_, Sqlite>,
+    tables: &mut BackupTables,
+    warnings: &mut Vec<String>,
+) -> Result<TableCounts, BackupError> {
+    let mode = WriteMode::SkipExisting;
+    let company = restore_company(tx, &tables.company, mode).await?;
+    let settings = restore_settings(tx, &tables.settings, mode).await?;
+    let review_templates = restore_review_templates(tx, &tables.review_templates, mode).await?;
+    warnings.extend(drop_orphaned_rows(tx, tables, &[TableName::ReviewCycles].into(), true).await?);
+    let review_cycles = restore_review_cycles(tx, &tables.review_cycles, mode).await?;
+    let review_cycle_events = restore_review_cycle_events(tx, &tables.review_cycle_events, mode).await?;
+    let employees = restore_employees(tx, &tables.employees, mode).await?;
+
+    // An empty selection for the parent tables makes drop_orphaned_rows check
+    // against the database alone, which now holds every parent that landed
+    let children: HashSet<TableName> = [
+        TableName::PerformanceRatings,
+        TableName::PerformanceReviews,
+        TableName::EnpsResponses,
+        TableName::DepartmentChanges,
+    ]
+    .into();
+    warnings.extend(drop_orphaned_rows(tx, tables, &children, true).await?);
+    let performance_ratings = restore_performance_ratings(tx, &tables.performance_ratings, mode).await?;
+    let performance_reviews = restore_performance_reviews(tx, &tables.performance_reviews, mode).await?;
+    let enps_responses = restore_enps_responses(tx, &tables.enps_responses, mode).await?;
+    let department_changes = restore_department_changes(tx, &tables.department_changes, mode).await?;
+
+    let conversations = restore_conversations(tx, &tables.conversations, mode).await?;
+    warnings.extend(drop_orphaned_rows(tx, tables, &[TableName::AuditLog].into(), true).await?);
+    let audit_log = restore_audit_log(tx, &tables.audit_log, mode).await?;
+
+    Ok(TableCounts {
+        employees,
+        conversations,
+        company,
+        settings,
+        audit_log,
+        review_templates,
+        review_cycles,
+        review_cycle_events,
+        performance_ratings,
+        performance_reviews,
+        enps_responses,
+        department_changes,
     })
 }
 
@@ -879,36 +1890,61 @@ async fn restore_all_tables(
 // ============================================================================
 
 /// Export all database tables to an encrypted backup
-pub async fn export_backup(pool: &SqlitePool, password: &str) -> Result<ExportResult, BackupError> {
-    // Validate password length
-    if password.len() < MIN_PASSWORD_LENGTH {
-        return Err(BackupError::Encryption(format!(
-            "Password must be at least {} characters",
-            MIN_PASSWORD_LENGTH
-        )));
-    }
+///
+/// The password must be at least 8 characters and reach the minimum strength
+/// score (`options.min_password_score`, default 3).
+///
+/// Employees under legal hold are always included: the backup is encrypted
+/// and a restore replaces every table, so leaving them out would erase their
+/// records on restore. Shareable exports (the roster export) exclude them.
+pub async fn export_backup(
+    pool: &SqlitePool,
+    password: &str,
+    options: Option<BackupExportOptions>,
+) -> Result<ExportResult, BackupError> {
+    export_tables(pool, password, options.unwrap_or_default(), None).await
+}
 
-    // Fetch all data
-    let tables = fetch_all_tables(pool).await?;
+/// Export only rows written at or after `since` (by updated_at, or created_at
+/// where a table has no updated_at) to an encrypted incremental backup.
+///
+/// Deletions aren't captured, and the result only restores on top of the
+/// full backup it follows; see `restore_all_tables` for the FK implications.
+/// Password rules match `export_backup` with default options.
+pub async fn export_incremental_backup(
+    pool: &SqlitePool,
+    password: &str,
+    since: DateTime<Utc>,
+) -> Result<ExportResult, BackupError> {
+    export_tables(pool, password, BackupExportOptions::default(), Some(since)).await
+}
+
+async fn export_tables(
+    pool: &SqlitePool,
+    password: &str,
+    options: BackupExportOptions,
+    since: Option<DateTime<Utc>>,
+) -> Result<ExportResult, BackupError> {
+    // Validate password length and strength
+    check_password_strength(
+        password,
+        options.min_password_score.unwrap_or(DEFAULT_MIN_PASSWORD_SCORE),
+    )?;
+
+    // Fetch all data, or just the rows in the incremental window
+    let since_text = since.map(|t| t.format("%Y-%m-%d %H:%M:%S").to_string());
+    let tables = fetch_tables_since(pool, since_text.as_deref()).await?;
 
     // Build metadata
-    let table_counts = TableCounts {
-        employees: tables.employees.len(),
-        conversations: tables.conversations.len(),
-        company: tables.company.len(),
-        settings: tables.settings.len(),
-        audit_log: tables.audit_log.len(),
-        review_cycles: tables.review_cycles.len(),
-        performance_ratings: tables.performance_ratings.len(),
-        performance_reviews: tables.performance_reviews.len(),
-        enps_responses: tables.enps_responses.len(),
-    };
+    let table_counts = TableCounts::of(&tables);
 
     let metadata = BackupMetadata {
         version: BACKUP_VERSION.to_string(),
         created_at: Utc::now(),
         app_version: env!("CARGO_PKG_VERSION").to_string(),
         table_counts: table_counts.clone(),
+        incremental_since: since,
+        kdf_params: Some(KDF_PARAMS),
     };
 
     let backup_data = BackupData { metadata, tables };
@@ -925,7 +1961,8 @@ pub async fn export_backup(pool: &SqlitePool, password: &str) -> Result<ExportRe
 
     // Generate filename
     let timestamp = Utc::now().format("%Y%m%d_%H%M%S");
-    let filename = format!("hrcommand_backup_{}.hrbackup", timestamp);
+    let kind = if since.is_some() { "incremental" } else { "backup" };
+    let filename = format!("hrcommand_{}_{}.hrbackup", kind, timestamp);
 
     Ok(ExportResult {
         encrypted_data: encrypted,
@@ -934,67 +1971,132 @@ pub async fn export_backup(pool: &SqlitePool, password: &str) -> Result<ExportRe
     })
 }
 
-/// Validate a backup file and return its metadata (without importing)
-pub fn validate_backup(encrypted_data: &[u8], password: &str) -> Result<BackupMetadata, BackupError> {
-    // Decrypt
-    let compressed = decrypt_data(encrypted_data, password)?;
-
-    // Decompress
-    let json = decompress_data(&compressed)?;
-
-    // Parse
-    let backup_data: BackupData = serde_json::from_slice(&json)
-        .map_err(|_| BackupError::InvalidBackup)?;
+/// Re-encrypt a backup under a new password. The payload is decrypted with
+/// the old password and sealed again with a fresh salt and nonce; the
+/// compressed backup inside is passed through unchanged. The new password
+/// must meet the default strength requirement.
+pub fn rotate_backup_password(
+    encrypted_data: &[u8],
+    old_password: &str,
+    new_password: &str,
+) -> Result<Vec<u8>, BackupError> {
+    check_password_strength(new_password, DEFAULT_MIN_PASSWORD_SCORE)?;
 
-    // Check version compatibility
-    if backup_data.metadata.version != BACKUP_VERSION {
-        return Err(BackupError::VersionMismatch {
-            expected: BACKUP_VERSION.to_string(),
-            found: backup_data.metadata.version,
-        });
-    }
+    let compressed = decrypt_data(encrypted_data, old_password)?;
+    encrypt_data(&compressed, new_password)
+}
 
-    Ok(backup_data.metadata)
+/// Validate a backup file and return its metadata (without importing)
+pub fn validate_backup(encrypted_data: &[u8], password: &str) -> Result<BackupMetadata, BackupError> {
+    Ok(open_backup(encrypted_data, password)?.metadata)
 }
 
-/// Import data from an encrypted backup, replacing all existing data
+/// Import data from an encrypted backup, replacing all existing data.
+/// Incremental backups are merged instead: their rows are upserted and rows
+/// outside the backup's window are kept.
+///
+/// `options.tables` limits the import to the named tables; the rest are left
+/// untouched. A selection that clears a parent but not its children is
+/// refused, and rows whose parents are neither selected nor already in the
+/// database are skipped with a warning. A restore that would delete an
+/// employee under legal hold who is missing from the backup is refused.
 pub async fn import_backup(
     pool: &SqlitePool,
     encrypted_data: &[u8],
     password: &str,
+    options: Option<ImportOptions>,
 ) -> Result<ImportResult, BackupError> {
-    // Decrypt
-    let compressed = decrypt_data(encrypted_data, password)?;
+    let mut backup_data = open_backup(encrypted_data, password)?;
 
-    // Decompress
-    let json = decompress_data(&compressed)?;
+    // Checked before clearing so a bad backup never leaves a half-restored database
+    let mut warnings = dedupe_backup_tables(&mut backup_data.tables);
 
-    // Parse
-    let backup_data: BackupData = serde_json::from_slice(&json)
-        .map_err(|_| BackupError::InvalidBackup)?;
+    let incremental = backup_data.metadata.incremental_since.is_some();
 
-    // Check version compatibility
-    if backup_data.metadata.version != BACKUP_VERSION {
-        return Err(BackupError::VersionMismatch {
-            expected: BACKUP_VERSION.to_string(),
-            found: backup_data.metadata.version,
-        });
+    let requested = options.unwrap_or_default().tables;
+    let selected: HashSet<TableName> = if requested.is_empty() {
+        TableName::ALL.into_iter().collect()
+    } else {
+        requested.into_iter().collect()
+    };
+    if selected.len() < TableName::ALL.len() && !incremental {
+        check_selection(&selected)?;
+    }
+    // Older backups left held employees out; a full restore of one would
+    // delete them along with their records
+    if !incremental && selected.contains(&TableName::Employees) {
+        let in_backup: HashSet<&str> = backup_data.tables.employees.iter().map(|e| e.id.as_str()).collect();
+        let missing = fetch_legal_hold_ids(pool)
+            .await?
+            .iter()
+            .filter(|id| !in_backup.contains(id.as_str()))
+            .count();
+        if missing > 0 {
+            return Err(BackupError::LegalHoldMissing(missing));
+        }
     }
 
-    let warnings = Vec::new();
+    // One transaction for the clear and restore: if any row fails, dropping
+    // `tx` rolls everything back and the existing data survives
+    let mut tx = pool.begin().await?;
 
-    // Clear existing data
-    clear_all_tables(pool).await?;
+    if selected.len() < TableName::ALL.len() {
+        retain_selected(&mut backup_data.tables, &selected);
+        warnings.extend(drop_orphaned_rows(&mut tx, &mut backup_data.tables, &selected, incremental).await?);
+    }
+
+    // Clear existing data (a full backup replaces the selected tables)
+    if !incremental {
+        clear_tables(&mut tx, &selected).await?;
+    }
 
     // Restore all tables
-    let restored_counts = restore_all_tables(pool, &backup_data.tables).await?;
+    let restored_counts = restore_all_tables(&mut tx, &backup_data.tables, incremental).await?;
+
+    tx.commit().await?;
+
+    Ok(ImportResult {
+        restored_counts,
+        skipped_counts: TableCounts::default(),
+        warnings,
+    })
+}
+
+/// Import a backup on top of existing data without clearing anything. Rows
+/// that collide with an existing row (same id, or a unique value such as an
+/// employee's email) are skipped, as are ratings, reviews and eNPS responses
+/// whose employee or review cycle isn't in the database after the merge.
+/// Existing rows always win, including settings and the company profile.
+pub async fn import_backup_merge(
+    pool: &SqlitePool,
+    encrypted_data: &[u8],
+    password: &str,
+) -> Result<ImportResult, BackupError> {
+    let mut backup_data = open_backup(encrypted_data, password)?;
+    let mut warnings = dedupe_backup_tables(&mut backup_data.tables);
+    let offered = TableCounts::of(&backup_data.tables);
+
+    let mut tx = pool.begin().await?;
+    let restored_counts = merge_all_tables(&mut tx, &mut backup_data.tables, &mut warnings).await?;
+    tx.commit().await?;
 
     Ok(ImportResult {
+        skipped_counts: offered.minus(&restored_counts),
         restored_counts,
         warnings,
     })
 }
 
+/// Decrypt, decompress and parse a backup, checking its version
+fn open_backup(encrypted_data: &[u8], password: &str) -> Result<BackupData, BackupError> {
+    let compressed = decrypt_data(encrypted_data, password)?;
+    let json = decompress_data(&compressed)?;
+    let backup_data: BackupData = serde_json::from_slice(&json)
+        .map_err(|_| BackupError::InvalidBackup)?;
+    check_version(&backup_data.metadata)?;
+    Ok(backup_data)
+}
+
 // ============================================================================
 // Tests
 // ============================================================================
@@ -1045,10 +2147,13 @@ mod tests {
             company: vec![],
             settings: vec![],
             audit_log: vec![],
+            review_templates: vec![],
             review_cycles: vec![],
+            review_cycle_events: vec![],
             performance_ratings: vec![],
             performance_reviews: vec![],
             enps_responses: vec![],
+            department_changes: vec![],
         };
 
         // Can't test export_backup directly without async runtime, but we can verify
@@ -1056,6 +2161,56 @@ mod tests {
         assert_eq!(MIN_PASSWORD_LENGTH, 8);
     }
 
+    #[test]
+    fn test_password_strength_scores() {
+        let cases = [
+            ("password", false),
+            ("Password1!", false),
+            ("12345678", false),
+            ("aaaaaaaaaaaa", false),
+            ("kittens1", false),
+            ("P@ss12!xZ", true),
+            ("Blue-Harbor-Lantern", true),
+            ("correcthorsebatterystaple", true),
+        ];
+
+        for (password, should_pass) in cases {
+            let result = check_password_strength(password, DEFAULT_MIN_PASSWORD_SCORE);
+            assert_eq!(result.is_ok(), should_pass, "unexpected result for {:?}", password);
+            if let Err(BackupError::WeakPassword { feedback }) = result {
+                assert!(!feedback.is_empty(), "missing feedback for {:?}", password);
+            }
+        }
+    }
+
+    #[test]
+    fn test_password_strength_feedback() {
+        let common = estimate_password_strength("password");
+        assert_eq!(common.score, 0);
+        assert!(common.feedback[0].contains("commonly used"));
+
+        let weak = estimate_password_strength("kittens1");
+        assert!(weak.feedback.iter().any(|f| f.contains("12 characters")));
+        assert!(weak.feedback.iter().any(|f| f.contains("symbol")));
+        assert!(weak.feedback.iter().any(|f| f.contains("uppercase")));
+
+        let strong = estimate_password_strength("P@ss12!xZ-Harbor");
+        assert_eq!(strong.score, 4);
+        assert!(strong.feedback.is_empty());
+    }
+
+    #[test]
+    fn test_password_floor_and_configurable_minimum() {
+        // Length floor applies regardless of the configured minimum
+        assert!(matches!(
+            check_password_strength("Ab1!", 0),
+            Err(BackupError::Encryption(_))
+        ));
+        // A lower minimum accepts a fair password the default rejects
+        assert!(check_password_strength("kittens1", DEFAULT_MIN_PASSWORD_SCORE).is_err());
+        assert!(check_password_strength("kittens1", 2).is_ok());
+    }
+
     #[test]
     fn test_invalid_backup_data() {
         let garbage = vec![0u8; 100];
@@ -1068,6 +2223,457 @@ mod tests {
         ));
     }
 
+    async fn seed_employee(pool: &SqlitePool, id: &str, manager_id: Option<&str>, legal_hold: bool) {
+        sqlx::query(
+            "INSERT INTO employees (id, email, full_name, manager_id, legal_hold) VALUES (?, ?, ?, ?, ?)",
+        )
+        .bind(id)
+        .bind(format!("{}@example.com", id))
+        .bind(format!("Employee {}", id))
+        .bind(manager_id)
+        .bind(legal_hold)
+        .execute(pool)
+        .await
+        .unwrap();
+    }
+
+    async fn seed_legal_hold_fixture(pool: &SqlitePool) {
+        seed_employee(pool, "held", None, true).await;
+        seed_employee(pool, "free", Some("held"), false).await;
+        sqlx::query("INSERT INTO review_cycles (id, name, cycle_type, start_date, end_date) VALUES ('c1', '2024', 'annual', '2024-01-01', '2024-12-31')")
+            .execute(pool)
+            .await
+            .unwrap();
+        sqlx::query("INSERT INTO performance_ratings (id, employee_id, review_cycle_id, overall_rating, reviewer_id) VALUES ('r1', 'held', 'c1', 3.0, NULL), ('r2', 'free', 'c1', 4.0, 'held')")
+            .execute(pool)
+            .await
+            .unwrap();
+        sqlx::query("INSERT INTO enps_responses (id, employee_id, score, survey_date) VALUES ('e1', 'held', 9, '2024-06-01'), ('e2', 'free', 7, '2024-06-01')")
+            .execute(pool)
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_backup_round_trip_keeps_legal_hold_records() {
+        let password = "testpassword123";
+        let pool = crate::db::test_pool().await;
+        seed_legal_hold_fixture(&pool).await;
+
+        let backup = export_backup(&pool, password, None).await.unwrap();
+        assert_eq!(backup.table_counts.employees, 2);
+        assert_eq!(backup.table_counts.enps_responses, 2);
+
+        import_backup(&pool, &backup.encrypted_data, password, None).await.unwrap();
+        let held: bool = sqlx::query_scalar("SELECT legal_hold FROM employees WHERE id = 'held'")
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+        assert!(held);
+        let ratings: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM performance_ratings WHERE employee_id = 'held'")
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+        assert_eq!(ratings, 1);
+        let reviewer: Option<String> = sqlx::query_scalar("SELECT reviewer_id FROM performance_ratings WHERE id = 'r2'")
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+        assert_eq!(reviewer.as_deref(), Some("held"));
+        let enps: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM enps_responses WHERE employee_id = 'held'")
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+        assert_eq!(enps, 1);
+    }
+
+    #[tokio::test]
+    async fn test_import_refuses_to_delete_held_employee_missing_from_backup() {
+        let password = "testpassword123";
+        let source = crate::db::test_pool().await;
+        seed_employee(&source, "free", None, false).await;
+        let backup = export_backup(&source, password, None).await.unwrap();
+
+        let target = crate::db::test_pool().await;
+        seed_legal_hold_fixture(&target).await;
+        let result = import_backup(&target, &backup.encrypted_data, password, None).await;
+        assert!(matches!(result, Err(BackupError::LegalHoldMissing(1))), "{:?}", result.map(|r| r.restored_counts));
+
+        let held: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM enps_responses WHERE employee_id = 'held'")
+            .fetch_one(&target)
+            .await
+            .unwrap();
+        assert_eq!(held, 1);
+    }
+
+    #[tokio::test]
+    async fn test_import_skips_duplicate_ids_with_warning() {
+        let pool = crate::db::test_pool().await;
+        seed_legal_hold_fixture(&pool).await;
+
+        let mut tables = fetch_tables_since(&pool, None).await.unwrap();
+        let mut dup = tables.employees.iter().find(|e| e.id == "free").unwrap().clone();
+        dup.full_name = "Hand-merged copy".to_string();
+        tables.employees.push(dup.clone());
+        tables.employees.push(dup);
+
+        let backup = BackupData {
+            metadata: BackupMetadata {
+                version: BACKUP_VERSION.to_string(),
+                created_at: Utc::now(),
+                app_version: "test".to_string(),
+                table_counts: TableCounts::default(),
+                incremental_since: None,
+                kdf_params: None,
+            },
+            tables,
+        };
+        let json = serde_json::to_vec(&backup).unwrap();
+        let encrypted = encrypt_data(&compress_data(&json).unwrap(), "testpassword123").unwrap();
+
+        let target = crate::db::test_pool().await;
+        let result = import_backup(&target, &encrypted, "testpassword123", None).await.unwrap();
+
+        assert_eq!(result.warnings.len(), 1);
+        assert!(result.warnings[0].starts_with("employees: 'free' appears 3 times"));
+        assert_eq!(result.restored_counts.employees, 2);
+        assert_eq!(result.restored_counts.performance_ratings, 2);
+
+        let name: String = sqlx::query_scalar("SELECT full_name FROM employees WHERE id = 'free'")
+            .fetch_one(&target)
+            .await
+            .unwrap();
+        assert_eq!(name, "Employee free");
+    }
+
+    #[tokio::test]
+    async fn test_incremental_backup_round_trip() {
+        let password = "testpassword123";
+        let pool = crate::db::test_pool().await;
+        for id in ["a", "b", "c"] {
+            seed_employee(&pool, id, None, false).await;
+        }
+        sqlx::query("UPDATE employees SET created_at = '2024-01-01 00:00:00', updated_at = '2024-01-01 00:00:00'")
+            .execute(&pool)
+            .await
+            .unwrap();
+        let full = export_backup(&pool, password, None).await.unwrap();
+
+        // After the cutoff: one employee edited, one hired under an unchanged manager
+        sqlx::query("UPDATE employees SET full_name = 'Renamed b', updated_at = '2025-03-01 12:00:00' WHERE id = 'b'")
+            .execute(&pool)
+            .await
+            .unwrap();
+        seed_employee(&pool, "d", Some("a"), false).await;
+
+        let since = DateTime::parse_from_rfc3339("2025-01-01T00:00:00Z").unwrap().with_timezone(&Utc);
+        let incremental = export_incremental_backup(&pool, password, since).await.unwrap();
+        assert_eq!(incremental.table_counts.employees, 2);
+        assert!(incremental.filename.starts_with("hrcommand_incremental_"));
+        let metadata = validate_backup(&incremental.encrypted_data, password).unwrap();
+        assert_eq!(metadata.incremental_since, Some(since));
+
+        // Restore the full backup, then layer the incremental on top
+        let target = crate::db::test_pool().await;
+        import_backup(&target, &full.encrypted_data, password, None).await.unwrap();
+        seed_employee(&target, "local", None, false).await;
+        let result = import_backup(&target, &incremental.encrypted_data, password, None).await.unwrap();
+        assert_eq!(result.restored_counts.employees, 2);
+
+        let rows: Vec<(String, String)> = sqlx::query_as("SELECT id, full_name FROM employees ORDER BY id")
+            .fetch_all(&target)
+            .await
+            .unwrap();
+        let names: Vec<(&str, &str)> = rows.iter().map(|(id, name)| (id.as_str(), name.as_str())).collect();
+        assert_eq!(
+            names,
+            vec![
+                ("a", "Employee a"),
+                ("b", "Renamed b"),
+                ("c", "Employee c"),
+                ("d", "Employee d"),
+                ("local", "Employee local"),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_incremental_backup_includes_held_employee_records() {
+        let pool = crate::db::test_pool().await;
+        seed_legal_hold_fixture(&pool).await;
+        sqlx::query("UPDATE employees SET created_at = '2024-01-01 00:00:00', updated_at = '2024-01-01 00:00:00'")
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        // Only the ratings and eNPS responses fall inside the window
+        let since = DateTime::parse_from_rfc3339("2025-01-01T00:00:00Z").unwrap().with_timezone(&Utc);
+        let result = export_incremental_backup(&pool, "testpassword123", since).await.unwrap();
+        assert_eq!(result.table_counts.employees, 0);
+        assert_eq!(result.table_counts.performance_ratings, 2);
+        assert_eq!(result.table_counts.enps_responses, 2);
+    }
+
+    #[test]
+    fn test_upsert_sql_updates_non_key_columns() {
+        let sql = insert_sql("settings", "key", SETTINGS_COLUMNS, WriteMode::Upsert);
+        assert_eq!(
+            sql,
+            "INSERT INTO settings (key, value, updated_at) VALUES (?, ?, ?) ON CONFLICT(key) DO UPDATE SET value = excluded.value, updated_at = excluded.updated_at"
+        );
+        assert!(!insert_sql("settings", "key", SETTINGS_COLUMNS, WriteMode::Insert).contains("ON CONFLICT"));
+        assert!(insert_sql("settings", "key", SETTINGS_COLUMNS, WriteMode::SkipExisting).ends_with(" ON CONFLICT DO NOTHING"));
+    }
+
+    async fn seed_conversation_with_audit(pool: &SqlitePool, id: &str, title: &str) {
+        sqlx::query("INSERT INTO conversations (id, title, messages_json) VALUES (?, ?, '[]')")
+            .bind(id)
+            .bind(title)
+            .execute(pool)
+            .await
+            .unwrap();
+        sqlx::query("INSERT INTO audit_log (id, conversation_id, request_redacted, response_text) VALUES (?, ?, 'q', 'a')")
+            .bind(format!("audit-{}", id))
+            .bind(id)
+            .execute(pool)
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_selective_import_leaves_other_tables_untouched() {
+        let password = "testpassword123";
+        let source = crate::db::test_pool().await;
+        seed_legal_hold_fixture(&source).await;
+        seed_conversation_with_audit(&source, "conv-backup", "From backup").await;
+        let backup = export_backup(&source, password, None).await.unwrap();
+
+        let target = crate::db::test_pool().await;
+        seed_employee(&target, "current", None, false).await;
+        seed_conversation_with_audit(&target, "conv-current", "Current").await;
+
+        let options = ImportOptions { tables: vec![TableName::Conversations, TableName::AuditLog] };
+        let result = import_backup(&target, &backup.encrypted_data, password, Some(options)).await.unwrap();
+        assert!(result.warnings.is_empty(), "{:?}", result.warnings);
+        assert_eq!(result.restored_counts.conversations, 1);
+        assert_eq!(result.restored_counts.audit_log, 1);
+        assert_eq!(result.restored_counts.employees, 0);
+
+        let conversations: Vec<String> = sqlx::query_scalar("SELECT id FROM conversations")
+            .fetch_all(&target)
+            .await
+            .unwrap();
+        assert_eq!(conversations, vec!["conv-backup"]);
+        let employees: Vec<String> = sqlx::query_scalar("SELECT id FROM employees")
+            .fetch_all(&target)
+            .await
+            .unwrap();
+        assert_eq!(employees, vec!["current"]);
+    }
+
+    #[tokio::test]
+    async fn test_selective_import_skips_rows_with_missing_parents() {
+        let password = "testpassword123";
+        let source = crate::db::test_pool().await;
+        seed_legal_hold_fixture(&source).await;
+        let backup = export_backup(&source, password, None).await.unwrap();
+
+        // Target has the review cycle and one of the two employees
+        let target = crate::db::test_pool().await;
+        seed_employee(&target, "free", None, false).await;
+        sqlx::query("INSERT INTO review_cycles (id, name, cycle_type, start_date, end_date) VALUES ('c1', '2024', 'annual', '2024-01-01', '2024-12-31')")
+            .execute(&target)
+            .await
+            .unwrap();
+
+        let options = ImportOptions { tables: vec![TableName::PerformanceRatings] };
+        let result = import_backup(&target, &backup.encrypted_data, password, Some(options)).await.unwrap();
+        assert_eq!(result.restored_counts.performance_ratings, 1);
+        assert_eq!(result.warnings.len(), 2, "{:?}", result.warnings);
+        assert!(result.warnings[0].starts_with("performance_ratings: skipped 1 row(s)"));
+        assert!(result.warnings[1].starts_with("performance_ratings: cleared 1 reference(s) to a reviewer"));
+
+        let reviewer: Option<String> = sqlx::query_scalar("SELECT reviewer_id FROM performance_ratings WHERE id = 'r2'")
+            .fetch_one(&target)
+            .await
+            .unwrap();
+        assert_eq!(reviewer, None);
+    }
+
+    #[tokio::test]
+    async fn test_selective_import_refuses_parent_without_children() {
+        let password = "testpassword123";
+        let source = crate::db::test_pool().await;
+        seed_legal_hold_fixture(&source).await;
+        let backup = export_backup(&source, password, None).await.unwrap();
+
+        let target = crate::db::test_pool().await;
+        seed_employee(&target, "current", None, false).await;
+
+        let options = ImportOptions { tables: vec![TableName::Employees, TableName::EnpsResponses] };
+        let result = import_backup(&target, &backup.encrypted_data, password, Some(options)).await;
+        match result {
+            Err(BackupError::InvalidSelection(message)) => {
+                assert!(message.contains("performance_ratings, performance_reviews"), "{}", message);
+            }
+            other => panic!("expected InvalidSelection, got {:?}", other.map(|r| r.restored_counts)),
+        }
+
+        let employees: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM employees")
+            .fetch_one(&target)
+            .await
+            .unwrap();
+        assert_eq!(employees, 1);
+    }
+
+    #[tokio::test]
+    async fn test_failed_import_rolls_back() {
+        let password = "testpassword123";
+        let pool = crate::db::test_pool().await;
+        seed_legal_hold_fixture(&pool).await;
+        seed_conversation_with_audit(&pool, "conv-1", "Existing").await;
+
+        // A rating for an employee that doesn't exist fails its foreign key
+        // after company, cycles and employees have already been restored
+        let mut tables = fetch_tables_since(&pool, None).await.unwrap();
+        tables.performance_ratings[0].employee_id = "ghost".to_string();
+        let backup = BackupData {
+            metadata: BackupMetadata {
+                version: BACKUP_VERSION.to_string(),
+                created_at: Utc::now(),
+                app_version: "test".to_string(),
+                table_counts: TableCounts::default(),
+                incremental_since: None,
+                kdf_params: None,
+            },
+            tables,
+        };
+        let json = serde_json::to_vec(&backup).unwrap();
+        let encrypted = encrypt_data(&compress_data(&json).unwrap(), password).unwrap();
+
+        async fn counts(pool: &SqlitePool) -> Vec<i64> {
+            let mut counts = Vec::new();
+            for table in TableName::ALL {
+                let count: i64 = sqlx::query_scalar(&format!("SELECT COUNT(*) FROM {}", table.as_str()))
+                    .fetch_one(pool)
+                    .await
+                    .unwrap();
+                counts.push(count);
+            }
+            counts
+        }
+        let before = counts(&pool).await;
+
+        let result = import_backup(&pool, &encrypted, password, None).await;
+        assert!(matches!(result, Err(BackupError::Database(_))));
+        assert_eq!(counts(&pool).await, before);
+
+        // Search index is intact too
+        let hits: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM conversations_fts WHERE conversations_fts MATCH 'Existing'")
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+        assert_eq!(hits, 1);
+    }
+
+    #[tokio::test]
+    async fn test_rotate_backup_password() {
+        let pool = crate::db::test_pool().await;
+        seed_legal_hold_fixture(&pool).await;
+        let backup = export_backup(&pool, "testpassword123", None).await.unwrap();
+
+        let rotated = rotate_backup_password(&backup.encrypted_data, "testpassword123", "Rotated-Passw0rd!").unwrap();
+        assert_ne!(rotated, backup.encrypted_data);
+        let salt_and_nonce = KDF_HEADER_LENGTH..KDF_HEADER_LENGTH + SALT_LENGTH + NONCE_LENGTH;
+        assert_ne!(rotated[salt_and_nonce.clone()], backup.encrypted_data[salt_and_nonce]);
+
+        let metadata = validate_backup(&rotated, "Rotated-Passw0rd!").unwrap();
+        assert_eq!(metadata.table_counts.employees, backup.table_counts.employees);
+        assert!(matches!(
+            validate_backup(&rotated, "testpassword123"),
+            Err(BackupError::InvalidPassword)
+        ));
+
+        let target = crate::db::test_pool().await;
+        let result = import_backup(&target, &rotated, "Rotated-Passw0rd!", None).await.unwrap();
+        assert_eq!(result.restored_counts.employees, 2);
+    }
+
+    #[test]
+    fn test_rotate_rejects_wrong_old_or_weak_new_password() {
+        let encrypted = encrypt_data(&compress_data(b"{}").unwrap(), "testpassword123").unwrap();
+
+        assert!(matches!(
+            rotate_backup_password(&encrypted, "wrongpassword1", "Rotated-Passw0rd!"),
+            Err(BackupError::InvalidPassword)
+        ));
+        assert!(matches!(
+            rotate_backup_password(&encrypted, "testpassword123", "password"),
+            Err(BackupError::WeakPassword { .. })
+        ));
+    }
+
+    /// A version "1.0" backup (one company row, password "legacy-backup-pw")
+    /// written before the KDF header, with `Argon2::default()` key derivation
+    const LEGACY_BACKUP_HEX: &str = concat!(
+        "8fe278c6fc5d3b0e6b5f7697e24cfe45085f4270b89011fd7b7025d3436b2a5c0c2accf60db992ff49c7002a6dc9b713",
+        "b23de131ccb23d24d0bfe0ec02d5e9175d28a2558ea0486daa3adec014526c388cf5b1893371d133694962ceebfa570d",
+        "29befd6f4db5b2ea0457a5ce5685fe7fd8b8d08ceb875d9ec9ff7b648e93cbe29abc6030634fb295dfe7058c316a760a",
+        "cddb622a24b74dc18257ef0144e1b48c37b74008ba8fcdd4be9c73964a531cadc044730069ec4e487443e7abe7ef3d7f",
+        "1ccfd91ec832220a2885438130726e436c89becdc8846f4022ace1db78bd15122c141670b250663685c3a00d34782642",
+        "26292ca5b199a6a6c5019d9a5dc4131337751d108247ec9cf25e019aa37a6a7d6c7472a0c943b3fba5d60f205c1bd317",
+        "ca11b9dc1de3f7d01700f68b7666fe400a237b4180bde3ed1b217315fcc3b9",
+    );
+
+    fn decode_hex(hex: &str) -> Vec<u8> {
+        (0..hex.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).unwrap())
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_legacy_backup_uses_default_kdf_params() {
+        let legacy = decode_hex(LEGACY_BACKUP_HEX);
+        assert_ne!(&legacy[..4], KDF_HEADER_MAGIC);
+
+        let metadata = validate_backup(&legacy, "legacy-backup-pw").unwrap();
+        assert_eq!(metadata.version, "1.0");
+        assert_eq!(metadata.kdf_params, None);
+        assert_eq!(metadata.table_counts.company, 1);
+
+        let pool = crate::db::test_pool().await;
+        import_backup(&pool, &legacy, "legacy-backup-pw", None).await.unwrap();
+        let name: String = sqlx::query_scalar("SELECT name FROM company")
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+        assert_eq!(name, "Legacy Co");
+    }
+
+    #[tokio::test]
+    async fn test_new_backups_record_kdf_params() {
+        let pool = crate::db::test_pool().await;
+        let backup = export_backup(&pool, "testpassword123", None).await.unwrap();
+        assert_eq!(&backup.encrypted_data[..4], KDF_HEADER_MAGIC);
+
+        let (kdf, _) = split_kdf_header(&backup.encrypted_data).unwrap();
+        assert_eq!(kdf, KDF_PARAMS);
+        let metadata = validate_backup(&backup.encrypted_data, "testpassword123").unwrap();
+        assert_eq!(metadata.version, BACKUP_VERSION);
+        assert_eq!(metadata.kdf_params, Some(KDF_PARAMS));
+    }
+
+    #[test]
+    fn test_kdf_header_rejects_excessive_costs() {
+        let blob = encrypt_data(b"data", "testpassword123").unwrap();
+        for (field, limit) in [(4, MAX_KDF_MEMORY_KIB), (8, MAX_KDF_ITERATIONS), (12, MAX_KDF_PARALLELISM)] {
+            let mut tampered = blob.clone();
+            tampered[field..field + 4].copy_from_slice(&(limit + 1).to_le_bytes());
+            assert!(matches!(decrypt_data(&tampered, "testpassword123"), Err(BackupError::InvalidBackup)));
+        }
+    }
+
     #[test]
     fn test_table_counts_serialization() {
         let counts = TableCounts {
@@ -1076,10 +2682,13 @@ mod tests {
             company: 1,
             settings: 5,
             audit_log: 200,
+            review_templates: 2,
             review_cycles: 3,
+            review_cycle_events: 6,
             performance_ratings: 300,
             performance_reviews: 300,
             enps_responses: 600,
+            department_changes: 12,
         };
 
         let json = serde_json::to_string(&counts).unwrap();
@@ -1088,4 +2697,198 @@ mod tests {
         assert_eq!(parsed.employees, 100);
         assert_eq!(parsed.enps_responses, 600);
     }
+
+    #[tokio::test]
+    async fn test_merge_import_keeps_existing_rows() {
+        let password = "testpassword123";
+        let source = crate::db::test_pool().await;
+        seed_legal_hold_fixture(&source).await;
+        seed_conversation_with_audit(&source, "conv-backup", "From backup").await;
+        seed_conversation_with_audit(&source, "conv-shared", "Their copy").await;
+        let backup = export_backup(&source, password, None).await.unwrap();
+
+        let target = crate::db::test_pool().await;
+        seed_employee(&target, "current", None, false).await;
+        seed_conversation_with_audit(&target, "conv-current", "Current").await;
+        seed_conversation_with_audit(&target, "conv-shared", "My copy").await;
+        sqlx::query("INSERT INTO review_cycles (id, name, cycle_type, start_date, end_date) VALUES ('c1', 'Mine', 'annual', '2024-01-01', '2024-12-31')")
+            .execute(&target)
+            .await
+            .unwrap();
+
+        let result = import_backup_merge(&target, &backup.encrypted_data, password).await.unwrap();
+        assert!(result.warnings.is_empty(), "{:?}", result.warnings);
+        assert_eq!(result.restored_counts.employees, 2);
+        assert_eq!(result.restored_counts.performance_ratings, 2);
+        assert_eq!(result.restored_counts.enps_responses, 2);
+        assert_eq!(result.restored_counts.conversations, 1);
+        assert_eq!(result.restored_counts.audit_log, 1);
+        assert_eq!(result.skipped_counts.review_cycles, 1);
+        assert_eq!(result.skipped_counts.conversations, 1);
+        assert_eq!(result.skipped_counts.audit_log, 1);
+        assert_eq!(result.skipped_counts.employees, 0);
+
+        let titles: Vec<String> = sqlx::query_scalar("SELECT title FROM conversations ORDER BY id")
+            .fetch_all(&target)
+            .await
+            .unwrap();
+        assert_eq!(titles, vec!["From backup", "Current", "My copy"]);
+        let cycle_name: String = sqlx::query_scalar("SELECT name FROM review_cycles WHERE id = 'c1'")
+            .fetch_one(&target)
+            .await
+            .unwrap();
+        assert_eq!(cycle_name, "Mine");
+        let employees: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM employees")
+            .fetch_one(&target)
+            .await
+            .unwrap();
+        assert_eq!(employees, 3);
+    }
+
+    #[tokio::test]
+    async fn test_merge_import_skips_children_of_missing_employees() {
+        let password = "testpassword123";
+        let source = crate::db::test_pool().await;
+        seed_legal_hold_fixture(&source).await;
+        let backup = export_backup(&source, password, None).await.unwrap();
+
+        // Same email as the backup's "free" employee under a different id, so
+        // the backup's employee is skipped and its ratings would dangle
+        let target = crate::db::test_pool().await;
+        sqlx::query("INSERT INTO employees (id, email, full_name) VALUES ('mine', 'free@example.com', 'Local Copy')")
+            .execute(&target)
+            .await
+            .unwrap();
+
+        let result = import_backup_merge(&target, &backup.encrypted_data, password).await.unwrap();
+        assert_eq!(result.restored_counts.employees, 1);
+        assert_eq!(result.skipped_counts.employees, 1);
+        assert_eq!(result.restored_counts.review_cycles, 1);
+        assert_eq!(result.skipped_counts.performance_ratings, 1);
+        assert_eq!(result.skipped_counts.enps_responses, 1);
+        assert!(result.warnings.iter().any(|w| w.starts_with("performance_ratings: skipped 1")), "{:?}", result.warnings);
+        assert!(result.warnings.iter().any(|w| w.starts_with("enps_responses: skipped 1")), "{:?}", result.warnings);
+
+        let ratings: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM performance_ratings")
+            .fetch_one(&target)
+            .await
+            .unwrap();
+        assert_eq!(ratings, 1);
+    }
+
+    async fn seed_templated_cycle(pool: &SqlitePool, template_id: &str, template_name: &str) {
+        sqlx::query("INSERT INTO review_templates (id, name, sections) VALUES (?, ?, '[{\"section\":\"strengths\",\"prompt\":null,\"required\":true}]')")
+            .bind(template_id)
+            .bind(template_name)
+            .execute(pool)
+            .await
+            .unwrap();
+        sqlx::query("INSERT INTO review_cycles (id, name, cycle_type, start_date, end_date, template_id) VALUES ('c-t', 'Templated', 'annual', '2024-01-01', '2024-12-31', ?)")
+            .bind(template_id)
+            .execute(pool)
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_backup_round_trips_review_templates() {
+        let password = "testpassword123";
+        let source = crate::db::test_pool().await;
+        seed_templated_cycle(&source, "t1", "Annual").await;
+        let backup = export_backup(&source, password, None).await.unwrap();
+        assert_eq!(backup.table_counts.review_templates, 1);
+
+        let target = crate::db::test_pool().await;
+        let result = import_backup(&target, &backup.encrypted_data, password, None).await.unwrap();
+        assert_eq!(result.restored_counts.review_templates, 1);
+
+        let (name, template_id): (String, Option<String>) = sqlx::query_as(
+            "SELECT t.name, c.template_id FROM review_cycles c JOIN review_templates t ON t.id = c.template_id",
+        )
+        .fetch_one(&target)
+        .await
+        .unwrap();
+        assert_eq!(name, "Annual");
+        assert_eq!(template_id.as_deref(), Some("t1"));
+    }
+
+    #[tokio::test]
+    async fn test_merge_import_clears_link_to_skipped_template() {
+        let password = "testpassword123";
+        let source = crate::db::test_pool().await;
+        seed_templated_cycle(&source, "t1", "Annual").await;
+        let backup = export_backup(&source, password, None).await.unwrap();
+
+        // Same template name under another id, so the backup's template is skipped
+        let target = crate::db::test_pool().await;
+        sqlx::query("INSERT INTO review_templates (id, name) VALUES ('mine', 'Annual')")
+            .execute(&target)
+            .await
+            .unwrap();
+
+        let result = import_backup_merge(&target, &backup.encrypted_data, password).await.unwrap();
+        assert_eq!(result.skipped_counts.review_templates, 1);
+        assert_eq!(result.restored_counts.review_cycles, 1);
+        assert!(result.warnings.iter().any(|w| w.starts_with("review_cycles: cleared 1 reference(s) to a review template")), "{:?}", result.warnings);
+
+        let template_id: Option<String> = sqlx::query_scalar("SELECT template_id FROM review_cycles WHERE id = 'c-t'")
+            .fetch_one(&target)
+            .await
+            .unwrap();
+        assert_eq!(template_id, None);
+    }
+
+    #[tokio::test]
+    async fn test_backup_round_trips_department_changes() {
+        let password = "testpassword123";
+        let source = crate::db::test_pool().await;
+        seed_legal_hold_fixture(&source).await;
+        sqlx::query("INSERT INTO department_changes (id, employee_id, from_department, to_department) VALUES ('d1', 'free', 'Sales', 'Marketing'), ('d2', 'held', NULL, 'Legal')")
+            .execute(&source)
+            .await
+            .unwrap();
+        let backup = export_backup(&source, password, None).await.unwrap();
+        assert_eq!(backup.table_counts.department_changes, 2);
+
+        let target = crate::db::test_pool().await;
+        let result = import_backup(&target, &backup.encrypted_data, password, None).await.unwrap();
+        assert_eq!(result.restored_counts.department_changes, 2);
+
+        let moves: Vec<(String, Option<String>, String)> =
+            sqlx::query_as("SELECT employee_id, from_department, to_department FROM department_changes ORDER BY id")
+                .fetch_all(&target)
+                .await
+                .unwrap();
+        assert_eq!(
+            moves,
+            vec![
+                ("free".to_string(), Some("Sales".to_string()), "Marketing".to_string()),
+                ("held".to_string(), None, "Legal".to_string()),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_backup_round_trips_review_cycle_events() {
+        let password = "testpassword123";
+        let source = crate::db::test_pool().await;
+        seed_legal_hold_fixture(&source).await;
+        sqlx::query("INSERT INTO review_cycle_events (id, cycle_id, old_status, new_status) VALUES ('ev1', 'c1', NULL, 'active'), ('ev2', 'c1', 'active', 'closed'), ('ev3', 'gone', NULL, 'active')")
+            .execute(&source)
+            .await
+            .unwrap();
+        let backup = export_backup(&source, password, None).await.unwrap();
+        assert_eq!(backup.table_counts.review_cycle_events, 3);
+
+        // Events for a deleted cycle restore too
+        let target = crate::db::test_pool().await;
+        let result = import_backup(&target, &backup.encrypted_data, password, None).await.unwrap();
+        assert_eq!(result.restored_counts.review_cycle_events, 3);
+
+        let statuses: Vec<String> = sqlx::query_scalar("SELECT new_status FROM review_cycle_events WHERE cycle_id = 'c1' ORDER BY id")
+            .fetch_all(&target)
+            .await
+            .unwrap();
+        assert_eq!(statuses, vec!["active", "closed"]);
+    }
 }
@@ -1,7 +1,11 @@
 // HR Command Center - Bulk Import Module
-// Direct database inserts for test data with predefined IDs
+// Direct database inserts for test data with predefined IDs.
+// Each batch runs in one transaction: by default any failing row rolls the
+// whole batch back; with `continue_on_error` failing rows are skipped and
+// reported while the rest commit.
 
 use std::collections::HashSet;
+use std::sync::atomic::AtomicBool;
 
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
@@ -14,6 +18,10 @@ pub enum ImportError {
     Database(String),
     #[error("Validation error: {0}")]
     Validation(String),
+    #[error("Row {} ({}) failed: {}. Nothing was imported.", .0.index, .0.id, .0.reason)]
+    RowFailed(BulkImportError),
+    #[error("{}", crate::db::READ_ONLY_MESSAGE)]
+    ReadOnly,
 }
 
 impl From<sqlx::Error> for ImportError {
@@ -22,10 +30,91 @@ impl From<sqlx::Error> for ImportError {
     }
 }
 
+impl From<crate::db::ReadOnly> for ImportError {
+    fn from(_: crate::db::ReadOnly) -> Self {
+        ImportError::ReadOnly
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BulkImportResult {
     pub inserted: usize,
+    /// "<id>: <reason>" for each skipped row (best-effort imports only)
     pub errors: Vec<String>,
+    /// The same failures with their position in the input
+    pub row_errors: Vec<BulkImportError>,
+}
+
+/// A row that failed to import
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BulkImportError {
+    /// Zero-based position in the submitted batch
+    pub index: usize,
+    pub id: String,
+    pub reason: String,
+}
+
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+pub struct BulkImportOptions {
+    /// Skip failing rows and report them instead of rolling back the batch
+    #[serde(default)]
+    pub continue_on_error: bool,
+}
+
+/// Tracks row outcomes for one batch and decides whether a failure aborts it
+struct Batch {
+    options: BulkImportOptions,
+    inserted: usize,
+    row_errors: Vec<BulkImportError>,
+}
+
+impl Batch {
+    fn new(options: BulkImportOptions) -> Self {
+        Self {
+            options,
+            inserted: 0,
+            row_errors: Vec::new(),
+        }
+    }
+
+    /// Record a failed row: an error that aborts the batch unless
+    /// `continue_on_error` is set
+    fn fail(&mut self, index: usize, id: &str, reason: impl std::fmt::Display) -> Result<(), ImportError> {
+        let error = BulkImportError {
+            index,
+            id: id.to_string(),
+            reason: reason.to_string(),
+        };
+        if !self.options.continue_on_error {
+            return Err(ImportError::RowFailed(error));
+        }
+        self.row_errors.push(error);
+        Ok(())
+    }
+
+    /// Record an insert result, returning whether the row went in
+    fn record<T, E: std::fmt::Display>(
+        &mut self,
+        index: usize,
+        id: &str,
+        result: Result<T, E>,
+    ) -> Result<bool, ImportError> {
+        match result {
+            Ok(_) => {
+                self.inserted += 1;
+                Ok(true)
+            }
+            Err(e) => self.fail(index, id, e).map(|_| false),
+        }
+    }
+
+    fn finish(self) -> BulkImportResult {
+        BulkImportResult {
+            inserted: self.inserted,
+            errors: self.row_errors.iter().map(|e| format!("{}: {}", e.id, e.reason)).collect(),
+            row_errors: self.row_errors,
+        }
+    }
 }
 
 // ============================================================================
@@ -115,11 +204,17 @@ pub async fn clear_all_data(pool: &DbPool) -> Result<(), ImportError> {
 pub async fn import_review_cycles(
     pool: &DbPool,
     cycles: Vec<ImportReviewCycle>,
+    options: BulkImportOptions,
 ) -> Result<BulkImportResult, ImportError> {
-    let mut inserted = 0;
-    let mut errors = Vec::new();
+    let mut tx = pool.begin().await?;
+    let mut batch = Batch::new(options);
+
+    for (index, cycle) in cycles.into_iter().enumerate() {
+        if let Err(e) = crate::review_cycles::validate_cycle_dates(&cycle.start_date, &cycle.end_date) {
+            batch.fail(index, &cycle.id, e)?;
+            continue;
+        }
 
-    for cycle in cycles {
         let result = sqlx::query(
             r#"
             INSERT INTO review_cycles (id, name, cycle_type, start_date, end_date, status)
@@ -132,41 +227,47 @@ pub async fn import_review_cycles(
         .bind(&cycle.start_date)
         .bind(&cycle.end_date)
         .bind(&cycle.status)
-        .execute(pool)
+        .execute(&mut *tx)
         .await;
-
-        match result {
-            Ok(_) => inserted += 1,
-            Err(e) => errors.push(format!("{}: {}", cycle.id, e)),
-        }
+        batch.record(index, &cycle.id, result)?;
     }
 
-    Ok(BulkImportResult { inserted, errors })
+    tx.commit().await?;
+    Ok(batch.finish())
 }
 
-/// Import employees with predefined IDs (preserves foreign key references)
+/// Import employees with predefined IDs (preserves foreign key references).
+/// Names are canonicalized like the regular import, keeping the original.
 pub async fn import_employees_bulk(
     pool: &DbPool,
     employees: Vec<ImportEmployee>,
+    options: BulkImportOptions,
 ) -> Result<BulkImportResult, ImportError> {
-    let mut inserted = 0;
-    let mut errors = Vec::new();
-
-    for emp in employees {
+    let name_format = crate::employees::load_name_format(pool).await;
+    let mut tx = pool.begin().await?;
+    let mut batch = Batch::new(options);
+
+    for (index, emp) in employees.into_iter().enumerate() {
+        if let Err(e) = crate::employees::validate_employee_fields(Some(&emp.email), emp.hire_date.as_deref()) {
+            batch.fail(index, &emp.id, e)?;
+            continue;
+        }
         let status = emp.status.unwrap_or_else(|| "active".to_string());
+        let full_name = crate::employees::normalize_display_name(&emp.full_name, name_format);
 
         let result = sqlx::query(
             r#"
             INSERT INTO employees (
-                id, email, full_name, department, job_title, manager_id,
+                id, email, full_name, original_full_name, department, job_title, manager_id,
                 hire_date, work_state, status, date_of_birth, gender, ethnicity,
                 termination_date, termination_reason
-            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
             "#,
         )
         .bind(&emp.id)
         .bind(&emp.email)
-        .bind(&emp.full_name)
+        .bind(&full_name)
+        .bind(emp.full_name.trim())
         .bind(&emp.department)
         .bind(&emp.job_title)
         .bind(&emp.manager_id)
@@ -178,27 +279,25 @@ pub async fn import_employees_bulk(
         .bind(&emp.ethnicity)
         .bind(&emp.termination_date)
         .bind(&emp.termination_reason)
-        .execute(pool)
+        .execute(&mut *tx)
         .await;
-
-        match result {
-            Ok(_) => inserted += 1,
-            Err(e) => errors.push(format!("{}: {}", emp.id, e)),
-        }
+        batch.record(index, &emp.id, result)?;
     }
 
-    Ok(BulkImportResult { inserted, errors })
+    tx.commit().await?;
+    Ok(batch.finish())
 }
 
 /// Import performance ratings with predefined IDs
 pub async fn import_ratings_bulk(
     pool: &DbPool,
     ratings: Vec<ImportRating>,
+    options: BulkImportOptions,
 ) -> Result<BulkImportResult, ImportError> {
-    let mut inserted = 0;
-    let mut errors = Vec::new();
+    let mut tx = pool.begin().await?;
+    let mut batch = Batch::new(options);
 
-    for rating in ratings {
+    for (index, rating) in ratings.into_iter().enumerate() {
         let result = sqlx::query(
             r#"
             INSERT INTO performance_ratings (
@@ -215,31 +314,29 @@ pub async fn import_ratings_bulk(
         .bind(rating.goals_rating)
         .bind(rating.competency_rating)
         .bind(&rating.submitted_at)
-        .execute(pool)
+        .execute(&mut *tx)
         .await;
-
-        match result {
-            Ok(_) => inserted += 1,
-            Err(e) => errors.push(format!("{}: {}", rating.id, e)),
-        }
+        batch.record(index, &rating.id, result)?;
     }
 
-    Ok(BulkImportResult { inserted, errors })
+    tx.commit().await?;
+    Ok(batch.finish())
 }
 
 /// Import performance reviews with predefined IDs
 pub async fn import_reviews_bulk(
     pool: &DbPool,
     reviews: Vec<ImportReview>,
+    options: BulkImportOptions,
 ) -> Result<BulkImportResult, ImportError> {
-    let mut inserted = 0;
-    let mut errors = Vec::new();
+    let mut tx = pool.begin().await?;
+    let mut batch = Batch::new(options);
 
     // Track inserted reviews and affected employees for auto-extraction
     let mut inserted_review_ids: Vec<String> = Vec::new();
     let mut affected_employee_ids: HashSet<String> = HashSet::new();
 
-    for review in reviews {
+    for (index, review) in reviews.into_iter().enumerate() {
         let result = sqlx::query(
             r#"
             INSERT INTO performance_reviews (
@@ -257,18 +354,14 @@ pub async fn import_reviews_bulk(
         .bind(&review.accomplishments)
         .bind(&review.manager_comments)
         .bind(&review.submitted_at)
-        .execute(pool)
+        .execute(&mut *tx)
         .await;
-
-        match result {
-            Ok(_) => {
-                inserted += 1;
-                inserted_review_ids.push(review.id.clone());
-                affected_employee_ids.insert(review.employee_id.clone());
-            }
-            Err(e) => errors.push(format!("{}: {}", review.id, e)),
+        if batch.record(index, &review.id, result)? {
+            inserted_review_ids.push(review.id.clone());
+            affected_employee_ids.insert(review.employee_id.clone());
         }
     }
+    tx.commit().await?;
 
     // Auto-trigger: Extract highlights and regenerate summaries in background
     // Fire-and-forget pattern - don't block the import response
@@ -277,7 +370,7 @@ pub async fn import_reviews_bulk(
         let employee_ids: Vec<String> = affected_employee_ids.into_iter().collect();
         tokio::spawn(async move {
             // Batch extract with rate limiting (100ms between API calls)
-            if let Err(e) = crate::highlights::extract_highlights_batch(&pool_clone, inserted_review_ids).await {
+            if let Err(e) = crate::highlights::extract_highlights_batch(&pool_clone, inserted_review_ids, None, &AtomicBool::new(false), |_| {}).await {
                 eprintln!("[Auto-extract batch] Failed: {}", e);
             }
             // Regenerate summaries for all affected employees
@@ -289,18 +382,19 @@ pub async fn import_reviews_bulk(
         });
     }
 
-    Ok(BulkImportResult { inserted, errors })
+    Ok(batch.finish())
 }
 
 /// Import eNPS responses with predefined IDs
 pub async fn import_enps_bulk(
     pool: &DbPool,
     responses: Vec<ImportEnps>,
+    options: BulkImportOptions,
 ) -> Result<BulkImportResult, ImportError> {
-    let mut inserted = 0;
-    let mut errors = Vec::new();
+    let mut tx = pool.begin().await?;
+    let mut batch = Batch::new(options);
 
-    for enps in responses {
+    for (index, enps) in responses.into_iter().enumerate() {
         let result = sqlx::query(
             r#"
             INSERT INTO enps_responses (
@@ -314,16 +408,13 @@ pub async fn import_enps_bulk(
         .bind(&enps.survey_name)
         .bind(enps.score)
         .bind(&enps.feedback_text)
-        .execute(pool)
+        .execute(&mut *tx)
         .await;
-
-        match result {
-            Ok(_) => inserted += 1,
-            Err(e) => errors.push(format!("{}: {}", enps.id, e)),
-        }
+        batch.record(index, &enps.id, result)?;
     }
 
-    Ok(BulkImportResult { inserted, errors })
+    tx.commit().await?;
+    Ok(batch.finish())
 }
 
 // ============================================================================
@@ -473,3 +564,94 @@ pub async fn verify_integrity(pool: &DbPool) -> Result<Vec<IntegrityCheckResult>
 
     Ok(results)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn employee(id: &str, email: &str) -> ImportEmployee {
+        ImportEmployee {
+            id: id.to_string(),
+            email: email.to_string(),
+            full_name: format!("Employee {}", id),
+            department: Some("Engineering".to_string()),
+            job_title: None,
+            manager_id: None,
+            hire_date: Some("2022-03-01".to_string()),
+            work_state: None,
+            status: None,
+            date_of_birth: None,
+            gender: None,
+            ethnicity: None,
+            termination_date: None,
+            termination_reason: None,
+        }
+    }
+
+    async fn employee_count(pool: &DbPool) -> i64 {
+        sqlx::query_scalar("SELECT COUNT(*) FROM employees").fetch_one(pool).await.unwrap()
+    }
+
+    fn batch_with_duplicate() -> Vec<ImportEmployee> {
+        vec![
+            employee("e1", "one@example.com"),
+            employee("e2", "two@example.com"),
+            // Same primary key as the first row
+            employee("e1", "again@example.com"),
+            employee("e4", "four@example.com"),
+        ]
+    }
+
+    #[tokio::test]
+    async fn test_failed_row_rolls_back_whole_batch() {
+        let pool = crate::db::test_pool().await;
+
+        let err = import_employees_bulk(&pool, batch_with_duplicate(), BulkImportOptions::default())
+            .await
+            .unwrap_err();
+        let ImportError::RowFailed(row) = &err else {
+            panic!("expected a row failure, got {:?}", err);
+        };
+        assert_eq!((row.index, row.id.as_str()), (2, "e1"));
+        assert!(row.reason.contains("UNIQUE"), "{}", row.reason);
+        assert!(err.to_string().contains("Nothing was imported"));
+        assert_eq!(employee_count(&pool).await, 0);
+
+        // Validation failures abort the batch the same way
+        let rows = vec![employee("e1", "one@example.com"), employee("e2", "not-an-email")];
+        let err = import_employees_bulk(&pool, rows, BulkImportOptions::default()).await.unwrap_err();
+        assert!(matches!(err, ImportError::RowFailed(BulkImportError { index: 1, .. })), "{:?}", err);
+        assert_eq!(employee_count(&pool).await, 0);
+    }
+
+    #[tokio::test]
+    async fn test_continue_on_error_reports_failed_rows() {
+        let pool = crate::db::test_pool().await;
+        let options = BulkImportOptions { continue_on_error: true };
+
+        let mut rows = batch_with_duplicate();
+        rows.push(employee("e5", "not-an-email"));
+        let result = import_employees_bulk(&pool, rows, options).await.unwrap();
+        assert_eq!(result.inserted, 3);
+        assert_eq!(result.row_errors.iter().map(|e| e.index).collect::<Vec<_>>(), vec![2, 4]);
+        assert_eq!(result.errors.len(), 2);
+        assert!(result.errors[1].starts_with("e5: "), "{}", result.errors[1]);
+        assert_eq!(employee_count(&pool).await, 3);
+    }
+
+    #[tokio::test]
+    async fn test_bulk_import_canonicalizes_names() {
+        let pool = crate::db::test_pool().await;
+        let mut row = employee("e1", "one@example.com");
+        row.full_name = "Chen,  Sarah".to_string();
+        import_employees_bulk(&pool, vec![row], BulkImportOptions::default()).await.unwrap();
+
+        let (full_name, original): (String, Option<String>) =
+            sqlx::query_as("SELECT full_name, original_full_name FROM employees WHERE id = 'e1'")
+                .fetch_one(&pool)
+                .await
+                .unwrap();
+        assert_eq!(full_name, "Sarah Chen");
+        assert_eq!(original.as_deref(), Some("Chen,  Sarah"));
+    }
+}
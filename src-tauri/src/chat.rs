@@ -1,20 +1,34 @@
 // HR Command Center - Claude API Integration
 // Handles communication with the Anthropic Messages API
 
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+
+use tokio::sync::Notify;
+
 use futures::StreamExt;
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
 use tauri::{AppHandle, Emitter};
 use thiserror::Error;
+use uuid::Uuid;
 
+use crate::audit;
 use crate::context::{estimate_tokens, get_max_conversation_tokens};
+use crate::db::DbPool;
 use crate::keyring;
+use crate::network::{self, NetworkConfig, RetryPolicy};
 
 const ANTHROPIC_API_URL: &str = "https://api.anthropic.com/v1/messages";
 const ANTHROPIC_VERSION: &str = "2023-06-01";
 const MODEL: &str = "claude-sonnet-4-20250514";
 const MAX_TOKENS: u32 = 4096;
 
+/// Default cap on a single message's estimated tokens (`max_message_tokens`
+/// setting). Leaves room under the conversation budget for the system prompt.
+pub const DEFAULT_MAX_MESSAGE_TOKENS: usize = 100_000;
+
 #[derive(Error, Debug)]
 pub enum ChatError {
     #[error("API key not configured")]
@@ -25,8 +39,20 @@ pub enum ChatError {
     RequestError(String),
     #[error("API returned error: {0}")]
     ApiError(String),
+    /// Non-success HTTP response; request_id comes from the `request-id`
+    /// header and is what Anthropic support asks for
+    #[error("API returned error (HTTP {status}{}): {message}", request_id.as_ref().map(|id| format!(", request id: {}", id)).unwrap_or_default())]
+    Api {
+        status: u16,
+        request_id: Option<String>,
+        message: String,
+    },
     #[error("Failed to parse response: {0}")]
     ParseError(String),
+    #[error("Message is too long (about {estimated_tokens} tokens; the limit is {cap}). Split it into smaller parts and send them one at a time.")]
+    MessageTooLong { estimated_tokens: usize, cap: usize },
+    #[error("Unknown model: {0}")]
+    UnknownModel(String),
 }
 
 impl From<keyring::KeyringError> for ChatError {
@@ -44,6 +70,12 @@ impl From<reqwest::Error> for ChatError {
     }
 }
 
+impl From<network::NetworkError> for ChatError {
+    fn from(err: network::NetworkError) -> Self {
+        ChatError::RequestError(err.to_string())
+    }
+}
+
 // Make ChatError serializable for Tauri commands
 impl serde::Serialize for ChatError {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
@@ -127,7 +159,7 @@ pub enum StreamEvent {
     #[serde(rename = "content_block_stop")]
     ContentBlockStop { index: u32 },
     #[serde(rename = "message_delta")]
-    MessageDelta { delta: MessageDeltaData, usage: Option<UsageDelta> },
+    MessageDelta { usage: Option<UsageDelta> },
     #[serde(rename = "message_stop")]
     MessageStop,
     #[serde(rename = "ping")]
@@ -138,8 +170,15 @@ pub enum StreamEvent {
 
 #[derive(Debug, Deserialize)]
 pub struct StreamMessageStart {
-    pub id: String,
     pub model: String,
+    #[serde(default)]
+    pub usage: Option<StreamStartUsage>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct StreamStartUsage {
+    #[serde(default)]
+    pub input_tokens: u32,
 }
 
 #[derive(Debug, Deserialize)]
@@ -150,11 +189,6 @@ pub struct TextDelta {
     pub text: String,
 }
 
-#[derive(Debug, Deserialize)]
-pub struct MessageDeltaData {
-    pub stop_reason: Option<String>,
-}
-
 #[derive(Debug, Deserialize)]
 pub struct UsageDelta {
     pub output_tokens: u32,
@@ -177,6 +211,14 @@ pub struct ChatResponse {
     pub output_tokens: u32,
 }
 
+/// Token counts for one response, as reported by the API.
+/// Emitted as the "chat-stream-usage" event once a stream finishes.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TokenUsage {
+    pub input_tokens: u32,
+    pub output_tokens: u32,
+}
+
 /// Event emitted to frontend during streaming
 #[derive(Debug, Clone, Serialize)]
 pub struct StreamChunk {
@@ -187,6 +229,464 @@ pub struct StreamChunk {
     pub verification: Option<crate::context::VerificationResult>,
 }
 
+/// Audit details for a streamed exchange. The response text itself is
+/// assembled server-side from the stream, never taken from the client.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct StreamAuditInput {
+    pub conversation_id: Option<String>,
+    pub request_redacted: String,
+    pub employee_ids_used: Vec<String>,
+    /// Persona the system prompt was built with (SystemPromptResult.persona_id)
+    #[serde(default)]
+    pub persona_id: Option<String>,
+}
+
+/// Per-turn details for an assistant message. Stored alongside the message
+/// in messages_json; every field is optional so older messages still parse.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct TurnMetadata {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub model: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub persona_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub input_tokens: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub output_tokens: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub verification_status: Option<crate::context::VerificationStatus>,
+}
+
+/// "chat-complete" event, emitted after the final chunk once the assembled
+/// response has been verified and audited
+#[derive(Debug, Clone, Serialize)]
+pub struct ChatComplete {
+    /// None if writing the audit entry failed (logged, never blocks chat)
+    pub audit_entry_id: Option<String>,
+    pub verification: Option<crate::context::VerificationResult>,
+    /// Attach to the assistant message before persisting the conversation
+    pub turn: TurnMetadata,
+}
+
+/// "chat-stream-started" and "chat-stream-cancelled" events
+#[derive(Debug, Clone, Serialize)]
+pub struct StreamStatus {
+    pub stream_id: String,
+}
+
+// ============================================================================
+// Stream Cancellation
+// ============================================================================
+
+/// A stream's cancel flag, which can also be awaited
+#[derive(Debug, Default)]
+struct CancelSignal {
+    cancelled: AtomicBool,
+    notify: Notify,
+}
+
+impl CancelSignal {
+    fn cancel(&self) {
+        self.cancelled.store(true, Ordering::SeqCst);
+        self.notify.notify_waiters();
+    }
+
+    fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::SeqCst)
+    }
+
+    /// Resolves once `cancel` has been called
+    async fn cancelled(&self) {
+        loop {
+            // Created before the check, so a cancel in between still wakes it
+            let notified = self.notify.notified();
+            if self.is_cancelled() {
+                return;
+            }
+            notified.await;
+        }
+    }
+}
+
+/// Cancellation signals for in-flight streamed responses, keyed by stream
+/// id, managed as app state
+#[derive(Debug, Default)]
+pub struct ChatStreams {
+    streams: Mutex<HashMap<String, Arc<CancelSignal>>>,
+}
+
+impl ChatStreams {
+    /// Register a new stream; it is removed again when the guard drops
+    fn register(&self) -> StreamRegistration<'_> {
+        let id = Uuid::new_v4().to_string();
+        let cancel = Arc::new(CancelSignal::default());
+        self.lock().insert(id.clone(), cancel.clone());
+        StreamRegistration { streams: self, id, cancel }
+    }
+
+    /// Ask a stream to stop. Returns false if it has already finished.
+    pub fn cancel(&self, stream_id: &str) -> bool {
+        match self.lock().get(stream_id) {
+            Some(cancel) => {
+                cancel.cancel();
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, HashMap<String, Arc<CancelSignal>>> {
+        self.streams.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+/// A stream's entry in `ChatStreams` for as long as it runs
+struct StreamRegistration<'a> {
+    streams: &'a ChatStreams,
+    id: String,
+    cancel: Arc<CancelSignal>,
+}
+
+impl Drop for StreamRegistration<'_> {
+    fn drop(&mut self) {
+        self.streams.lock().remove(&self.id);
+    }
+}
+
+// ============================================================================
+// Stream Processing
+// ============================================================================
+
+/// Something to emit to the frontend while processing the SSE stream
+#[derive(Debug)]
+enum StreamOutput {
+    /// "chat-stream" event
+    Chunk(StreamChunk),
+    /// "chat-correction" event, sent after the final chunk
+    Correction(crate::context::ChatCorrection),
+    /// "chat-stream-usage" event, sent last when the API reported usage
+    Usage(TokenUsage),
+}
+
+/// Turns raw SSE bytes into frontend events, buffering partial events
+/// and accumulating the full response for post-stream verification
+struct StreamProcessor {
+    buffer: String,
+    full_response: String, // V2.1.4: Accumulate for verification
+    aggregates: Option<crate::context::OrgAggregates>,
+    query_type: Option<crate::context::QueryType>,
+    /// Set once message_stop has been seen
+    finished: bool,
+    verification: Option<crate::context::VerificationResult>,
+    /// From message_start
+    model: Option<String>,
+    input_tokens: Option<u32>,
+    /// From message_delta (cumulative for the message)
+    output_tokens: Option<u32>,
+}
+
+impl StreamProcessor {
+    fn new(
+        aggregates: Option<crate::context::OrgAggregates>,
+        query_type: Option<crate::context::QueryType>,
+    ) -> Self {
+        Self {
+            buffer: String::new(),
+            full_response: String::new(),
+            aggregates,
+            query_type,
+            finished: false,
+            verification: None,
+            model: None,
+            input_tokens: None,
+            output_tokens: None,
+        }
+    }
+
+    /// Feed the next piece of the stream; returns events completed by it
+    fn push(&mut self, data: &str) -> Result<Vec<StreamOutput>, ChatError> {
+        self.buffer.push_str(data);
+        let mut outputs = Vec::new();
+
+        // Process complete SSE events (lines ending with \n\n)
+        while let Some(pos) = self.buffer.find("\n\n") {
+            let event_data = self.buffer[..pos].to_string();
+            self.buffer = self.buffer[pos + 2..].to_string();
+
+            // Parse SSE event
+            for line in event_data.lines() {
+                let Some(data) = line.strip_prefix("data: ") else {
+                    continue;
+                };
+                let Ok(event) = serde_json::from_str::<StreamEvent>(data) else {
+                    continue;
+                };
+                match event {
+                    StreamEvent::MessageStart { message } => {
+                        self.model = Some(message.model);
+                        self.input_tokens = message.usage.map(|u| u.input_tokens);
+                    }
+                    StreamEvent::MessageDelta { usage: Some(usage) } => {
+                        self.output_tokens = Some(usage.output_tokens);
+                    }
+                    StreamEvent::ContentBlockDelta { delta, .. } => {
+                        self.full_response.push_str(&delta.text);
+                        outputs.push(StreamOutput::Chunk(StreamChunk {
+                            chunk: delta.text,
+                            done: false,
+                            verification: None,
+                        }));
+                    }
+                    StreamEvent::MessageStop => {
+                        // V2.1.4: Verify response if we have aggregates
+                        let verification = self.query_type.map(|qt| {
+                            crate::context::verify_response(
+                                &self.full_response,
+                                self.aggregates.as_ref(),
+                                qt,
+                            )
+                        });
+                        let correction = verification
+                            .as_ref()
+                            .and_then(crate::context::build_chat_correction);
+
+                        self.finished = true;
+                        self.verification = verification.clone();
+
+                        // Signal completion with verification result
+                        outputs.push(StreamOutput::Chunk(StreamChunk {
+                            chunk: String::new(),
+                            done: true,
+                            verification,
+                        }));
+                        if let Some(correction) = correction {
+                            outputs.push(StreamOutput::Correction(correction));
+                        }
+                        if let Some(usage) = self.usage() {
+                            outputs.push(StreamOutput::Usage(usage));
+                        }
+                    }
+                    StreamEvent::Error { error } => {
+                        return Err(ChatError::ApiError(error.message));
+                    }
+                    _ => {} // Ignore other events
+                }
+            }
+        }
+
+        Ok(outputs)
+    }
+
+    /// Usage from message_start and message_delta, if either was reported
+    fn usage(&self) -> Option<TokenUsage> {
+        if self.input_tokens.is_none() && self.output_tokens.is_none() {
+            return None;
+        }
+        Some(TokenUsage {
+            input_tokens: self.input_tokens.unwrap_or(0),
+            output_tokens: self.output_tokens.unwrap_or(0),
+        })
+    }
+}
+
+/// Run a byte stream through the processor, handing each output to `emit`.
+/// Waiting for the next network chunk also waits on `cancel`, so a stalled
+/// stream can still be stopped; once cancelled, nothing more is emitted and
+/// the stream is dropped, closing the connection. Returns true if the stream
+/// was cancelled.
+async fn consume_stream<S, B, E>(
+    mut stream: S,
+    processor: &mut StreamProcessor,
+    cancel: &CancelSignal,
+    mut emit: impl FnMut(StreamOutput),
+) -> Result<bool, ChatError>
+where
+    S: futures::Stream<Item = Result<B, E>> + Unpin,
+    B: AsRef<[u8]>,
+    E: std::fmt::Display,
+{
+    let mut pending: Vec<u8> = Vec::new();
+    loop {
+        let chunk_result = tokio::select! {
+            biased;
+            _ = cancel.cancelled() => return Ok(true),
+            next = stream.next() => match next {
+                Some(chunk_result) => chunk_result,
+                None => break,
+            },
+        };
+        let chunk = chunk_result.map_err(|e| ChatError::RequestError(e.to_string()))?;
+        pending.extend_from_slice(chunk.as_ref());
+
+        // Hold back a multi-byte character split across network chunks
+        let complete = match std::str::from_utf8(&pending) {
+            Err(e) if e.error_len().is_none() => e.valid_up_to(),
+            _ => pending.len(),
+        };
+        let text = String::from_utf8_lossy(&pending[..complete]).into_owned();
+        pending.drain(..complete);
+
+        for output in processor.push(&text)? {
+            emit(output);
+        }
+    }
+    Ok(false)
+}
+
+/// Write the audit entry for a stream from the server-side text. A
+/// cancelled stream is audited with the text that arrived before the cancel
+/// and no context snapshot, so the partial answer is not re-verified.
+async fn audit_stream(
+    pool: &DbPool,
+    processor: &StreamProcessor,
+    input: StreamAuditInput,
+    cancelled: bool,
+) -> Option<String> {
+    let context_snapshot = processor
+        .query_type
+        .filter(|_| !cancelled)
+        .map(|query_type| audit::AuditContextSnapshot {
+            query_type,
+            aggregates: processor.aggregates.clone(),
+        });
+    let entry = audit::create_audit_entry(
+        pool,
+        audit::CreateAuditEntry {
+            conversation_id: input.conversation_id,
+            request_redacted: input.request_redacted,
+            response_text: processor.full_response.clone(),
+            employee_ids_used: input.employee_ids_used,
+            context_snapshot,
+            verification_status: processor.verification.as_ref().map(|v| v.overall_status),
+            usage: processor.usage(),
+            cancelled,
+        },
+    )
+    .await;
+
+    match entry {
+        Ok(entry) => Some(entry.id),
+        Err(e) => {
+            eprintln!("[Audit] Failed to create entry: {}", e);
+            None
+        }
+    }
+}
+
+/// Write the audit entry for a finished stream
+async fn complete_stream(
+    pool: &DbPool,
+    processor: StreamProcessor,
+    mut input: StreamAuditInput,
+) -> ChatComplete {
+    let persona_id = input.persona_id.take();
+    let audit_entry_id = audit_stream(pool, &processor, input, false).await;
+
+    let turn = TurnMetadata {
+        model: processor.model,
+        persona_id,
+        input_tokens: processor.input_tokens,
+        output_tokens: processor.output_tokens,
+        verification_status: processor.verification.as_ref().map(|v| v.overall_status),
+    };
+
+    ChatComplete {
+        audit_entry_id,
+        verification: processor.verification,
+        turn,
+    }
+}
+
+// ============================================================================
+// Model Selection
+// ============================================================================
+
+/// A model users can pick for a conversation
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ModelInfo {
+    pub id: String,
+    pub display_name: String,
+    /// Maximum input tokens
+    pub context_window: u32,
+    pub is_default: bool,
+}
+
+/// Models offered for chat: (id, display name, context window)
+const AVAILABLE_MODELS: &[(&str, &str, u32)] = &[
+    (MODEL, "Claude Sonnet 4", 200_000),
+    ("claude-opus-4-20250514", "Claude Opus 4", 200_000),
+    ("claude-3-7-sonnet-20250219", "Claude Sonnet 3.7", 200_000),
+    ("claude-3-5-haiku-20241022", "Claude Haiku 3.5", 200_000),
+];
+
+/// Models offered for chat, default first
+pub fn list_available_models() -> Vec<ModelInfo> {
+    AVAILABLE_MODELS
+        .iter()
+        .map(|(id, display_name, context_window)| ModelInfo {
+            id: id.to_string(),
+            display_name: display_name.to_string(),
+            context_window: *context_window,
+            is_default: *id == MODEL,
+        })
+        .collect()
+}
+
+pub fn is_available_model(model: &str) -> bool {
+    AVAILABLE_MODELS.iter().any(|(id, _, _)| *id == model)
+}
+
+/// The model to call: the requested one if it is on the allowlist, or the
+/// default when none was requested
+fn resolve_model(model: Option<&str>) -> Result<&'static str, ChatError> {
+    match model {
+        None => Ok(MODEL),
+        Some(requested) => AVAILABLE_MODELS
+            .iter()
+            .map(|(id, _, _)| *id)
+            .find(|id| *id == requested)
+            .ok_or_else(|| ChatError::UnknownModel(requested.to_string())),
+    }
+}
+
+// ============================================================================
+// Model Pricing
+// ============================================================================
+
+/// USD per million tokens for a model
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ModelPricing {
+    pub input_per_million: f64,
+    pub output_per_million: f64,
+}
+
+impl ModelPricing {
+    pub fn cost_usd(&self, input_tokens: u64, output_tokens: u64) -> f64 {
+        (input_tokens as f64 * self.input_per_million
+            + output_tokens as f64 * self.output_per_million)
+            / 1_000_000.0
+    }
+}
+
+/// Published API prices, matched by model-id prefix (dated ids share a family price)
+const MODEL_PRICING: &[(&str, ModelPricing)] = &[
+    ("claude-opus-4", ModelPricing { input_per_million: 15.0, output_per_million: 75.0 }),
+    ("claude-sonnet-4", ModelPricing { input_per_million: 3.0, output_per_million: 15.0 }),
+    ("claude-3-7-sonnet", ModelPricing { input_per_million: 3.0, output_per_million: 15.0 }),
+    ("claude-3-5-sonnet", ModelPricing { input_per_million: 3.0, output_per_million: 15.0 }),
+    ("claude-3-5-haiku", ModelPricing { input_per_million: 0.8, output_per_million: 4.0 }),
+];
+
+/// Pricing for a model id; unknown models are priced like the default model
+pub fn model_pricing(model: &str) -> ModelPricing {
+    MODEL_PRICING
+        .iter()
+        .find(|(prefix, _)| model.starts_with(prefix))
+        .or_else(|| MODEL_PRICING.iter().find(|(prefix, _)| MODEL.starts_with(prefix)))
+        .map(|(_, pricing)| *pricing)
+        .unwrap_or(ModelPricing { input_per_million: 3.0, output_per_million: 15.0 })
+}
+
 // ============================================================================
 // Conversation Trimming
 // ============================================================================
@@ -243,24 +743,112 @@ pub fn trim_conversation_to_budget(
     result
 }
 
+/// Reject any single message over `cap` estimated tokens. Trimming can drop
+/// old history but never the latest message, so an oversized paste would
+/// otherwise reach the API and fail there.
+pub fn check_message_lengths(messages: &[ChatMessage], cap: usize) -> Result<(), ChatError> {
+    match messages
+        .iter()
+        .map(|m| estimate_tokens(&m.content))
+        .find(|tokens| *tokens > cap)
+    {
+        Some(estimated_tokens) => Err(ChatError::MessageTooLong { estimated_tokens, cap }),
+        None => Ok(()),
+    }
+}
+
+/// Read the `max_message_tokens` setting, falling back to the default
+pub async fn load_max_message_tokens(pool: &DbPool) -> usize {
+    crate::settings::get_setting(pool, crate::settings::SettingKey::MaxMessageTokens.as_str())
+        .await
+        .ok()
+        .flatten()
+        .and_then(|v| v.trim().parse::<usize>().ok())
+        .filter(|cap| *cap > 0)
+        .unwrap_or(DEFAULT_MAX_MESSAGE_TOKENS)
+}
+
 // ============================================================================
 // API Client
 // ============================================================================
 
-/// Send a message to Claude and get a response (non-streaming)
+/// POST a Messages API request, retrying transient failures
+async fn send_api_request(
+    client: &Client,
+    api_key: &str,
+    request: &MessageRequest,
+    retry: &RetryPolicy,
+) -> Result<reqwest::Response, ChatError> {
+    let outcome = network::send_with_retry(retry, || {
+        client
+            .post(ANTHROPIC_API_URL)
+            .header("x-api-key", api_key)
+            .header("anthropic-version", ANTHROPIC_VERSION)
+            .header("content-type", "application/json")
+            .json(request)
+    })
+    .await;
+    if outcome.retries > 0 {
+        eprintln!("[Chat] Request needed {} retries", outcome.retries);
+    }
+    Ok(outcome.result?)
+}
+
+/// Turn a non-success response into `ChatError::Api`, logging the details
+async fn error_from_response(response: reqwest::Response) -> ChatError {
+    let status = response.status().as_u16();
+    let request_id = response
+        .headers()
+        .get("request-id")
+        .and_then(|v| v.to_str().ok())
+        .map(str::to_string);
+    let body = response.text().await.unwrap_or_default();
+
+    let error = api_error(status, request_id, &body);
+    eprintln!("[Chat] {}", error);
+    error
+}
+
+/// Build `ChatError::Api` from a status, request id, and error body.
+/// The message keeps the "error_type: message" shape the UI matches on.
+fn api_error(status: u16, request_id: Option<String>, body: &str) -> ChatError {
+    let message = match serde_json::from_str::<ApiErrorResponse>(body) {
+        Ok(api_error) => format!("{}: {}", api_error.error.error_type, api_error.error.message),
+        Err(_) => body.to_string(),
+    };
+    ChatError::Api {
+        status,
+        request_id,
+        message,
+    }
+}
+
+/// Send a message to Claude and get a response (non-streaming), using
+/// `model` if given or the default model otherwise. Requests go through
+/// the configured proxy; connection failures, 429 and 5xx responses are
+/// retried per its retry policy. A message estimated above
+/// `max_message_tokens` is refused: pass `load_max_message_tokens` for
+/// user-written messages, or `DEFAULT_MAX_MESSAGE_TOKENS` for prompts the app
+/// assembles itself (summaries, titles), which the user can't split.
 pub async fn send_message(
     messages: Vec<ChatMessage>,
     system_prompt: Option<String>,
+    model: Option<&str>,
+    net: &NetworkConfig,
+    max_message_tokens: usize,
 ) -> Result<ChatResponse, ChatError> {
+    let model = resolve_model(model)?;
+
     // Get API key from Keychain
     let api_key = keyring::get_api_key()?;
 
     // Trim conversation to fit within token budget (silently drops oldest messages)
     let trimmed_messages = trim_conversation_to_budget(messages, &system_prompt);
+    check_message_lengths(&trimmed_messages, max_message_tokens)?;
 
     // Build the request
     let request = MessageRequest {
-        model: MODEL.to_string(),
+        model: model.to_string(),
         max_tokens: MAX_TOKENS,
         messages: trimmed_messages
             .into_iter()
@@ -274,34 +862,12 @@ pub async fn send_message(
     };
 
     // Create HTTP client and send request
-    let client = Client::new();
-    let response = client
-        .post(ANTHROPIC_API_URL)
-        .header("x-api-key", &api_key)
-        .header("anthropic-version", ANTHROPIC_VERSION)
-        .header("content-type", "application/json")
-        .json(&request)
-        .send()
-        .await?;
+    let client = net.client()?;
+    let response = send_api_request(&client, &api_key, &request, &net.retry).await?;
 
     // Check for HTTP errors
-    let status = response.status();
-    if !status.is_success() {
-        let error_text = response.text().await.unwrap_or_default();
-
-        // Try to parse as API error
-        if let Ok(api_error) = serde_json::from_str::<ApiErrorResponse>(&error_text) {
-            return Err(ChatError::ApiError(format!(
-                "{}: {}",
-                api_error.error.error_type, api_error.error.message
-            )));
-        }
-
-        return Err(ChatError::ApiError(format!(
-            "HTTP {}: {}",
-            status.as_u16(),
-            error_text
-        )));
+    if !response.status().is_success() {
+        return Err(error_from_response(response).await);
     }
 
     // Parse successful response
@@ -336,22 +902,49 @@ pub async fn send_message(
 ///
 /// V2.1.4: Now accepts optional aggregates and query_type for answer verification.
 /// When provided, verifies numeric claims in the response against ground truth.
+/// Mismatched aggregate claims are also sent as a "chat-correction" event.
+///
+/// The full response is assembled here and audited once the stream finishes,
+/// followed by a "chat-complete" event carrying the audit entry id.
+/// A stream that ends before message_stop is not audited.
+///
+/// A "chat-stream-started" event carries the stream id before the request is
+/// sent; pass it to `ChatStreams::cancel` to stop the response. A cancelled
+/// stream emits "chat-stream-cancelled" instead of completing. The prompt
+/// has already reached the API by then, so it is audited with a cancelled
+/// request status and whatever text arrived. Returns the stream id.
+///
+/// `model` must be one of `list_available_models`; None uses the default.
+#[allow(clippy::too_many_arguments)]
 pub async fn send_message_streaming(
     app: AppHandle,
+    pool: &DbPool,
+    streams: &ChatStreams,
+    audit_input: StreamAuditInput,
     messages: Vec<ChatMessage>,
     system_prompt: Option<String>,
     aggregates: Option<crate::context::OrgAggregates>,
     query_type: Option<crate::context::QueryType>,
-) -> Result<(), ChatError> {
+    model: Option<&str>,
+) -> Result<String, ChatError> {
+    let model = resolve_model(model)?;
+
     // Get API key
     let api_key = keyring::get_api_key()?;
 
     // Trim conversation to fit within token budget (silently drops oldest messages)
     let trimmed_messages = trim_conversation_to_budget(messages, &system_prompt);
+    check_message_lengths(&trimmed_messages, load_max_message_tokens(pool).await)?;
+
+    let registration = streams.register();
+    let status = StreamStatus {
+        stream_id: registration.id.clone(),
+    };
+    let _ = app.emit("chat-stream-started", status.clone());
 
     // Build the request with streaming enabled
     let request = MessageRequest {
-        model: MODEL.to_string(),
+        model: model.to_string(),
         max_tokens: MAX_TOKENS,
         messages: trimmed_messages
             .into_iter()
@@ -364,90 +957,42 @@ pub async fn send_message_streaming(
         stream: Some(true),
     };
 
-    // Create HTTP client and send request
-    let client = Client::new();
-    let response = client
-        .post(ANTHROPIC_API_URL)
-        .header("x-api-key", &api_key)
-        .header("anthropic-version", ANTHROPIC_VERSION)
-        .header("content-type", "application/json")
-        .json(&request)
-        .send()
-        .await?;
+    // Create HTTP client and send request. Only the initial request is
+    // retried; once events are streaming, a failure ends the stream.
+    let net = NetworkConfig::load(pool).await;
+    let client = net.client()?;
+    let response = send_api_request(&client, &api_key, &request, &net.retry).await?;
 
     // Check for HTTP errors
-    let status = response.status();
-    if !status.is_success() {
-        let error_text = response.text().await.unwrap_or_default();
-        if let Ok(api_error) = serde_json::from_str::<ApiErrorResponse>(&error_text) {
-            return Err(ChatError::ApiError(format!(
-                "{}: {}",
-                api_error.error.error_type, api_error.error.message
-            )));
-        }
-        return Err(ChatError::ApiError(format!("HTTP {}: {}", status.as_u16(), error_text)));
+    if !response.status().is_success() {
+        return Err(error_from_response(response).await);
     }
 
     // Process SSE stream
-    let mut stream = response.bytes_stream();
-    let mut buffer = String::new();
-    let mut full_response = String::new(); // V2.1.4: Accumulate for verification
-
-    while let Some(chunk_result) = stream.next().await {
-        let chunk = chunk_result.map_err(|e| ChatError::RequestError(e.to_string()))?;
-        let chunk_str = String::from_utf8_lossy(&chunk);
-        buffer.push_str(&chunk_str);
-
-        // Process complete SSE events (lines ending with \n\n)
-        while let Some(pos) = buffer.find("\n\n") {
-            let event_data = buffer[..pos].to_string();
-            buffer = buffer[pos + 2..].to_string();
-
-            // Parse SSE event
-            for line in event_data.lines() {
-                if let Some(data) = line.strip_prefix("data: ") {
-                    if let Ok(event) = serde_json::from_str::<StreamEvent>(data) {
-                        match event {
-                            StreamEvent::ContentBlockDelta { delta, .. } => {
-                                // V2.1.4: Accumulate for verification
-                                full_response.push_str(&delta.text);
-
-                                // Emit text chunk to frontend
-                                let _ = app.emit("chat-stream", StreamChunk {
-                                    chunk: delta.text,
-                                    done: false,
-                                    verification: None,
-                                });
-                            }
-                            StreamEvent::MessageStop => {
-                                // V2.1.4: Verify response if we have aggregates
-                                let verification = query_type.map(|qt| {
-                                    crate::context::verify_response(
-                                        &full_response,
-                                        aggregates.as_ref(),
-                                        qt,
-                                    )
-                                });
-
-                                // Signal completion with verification result
-                                let _ = app.emit("chat-stream", StreamChunk {
-                                    chunk: String::new(),
-                                    done: true,
-                                    verification,
-                                });
-                            }
-                            StreamEvent::Error { error } => {
-                                return Err(ChatError::ApiError(error.message));
-                            }
-                            _ => {} // Ignore other events
-                        }
-                    }
-                }
+    let mut processor = StreamProcessor::new(aggregates, query_type);
+    let cancelled = registration.cancel.is_cancelled()
+        || consume_stream(response.bytes_stream(), &mut processor, &registration.cancel, |output| match output {
+            StreamOutput::Chunk(chunk) => {
+                let _ = app.emit("chat-stream", chunk);
             }
-        }
+            StreamOutput::Correction(correction) => {
+                let _ = app.emit("chat-correction", correction);
+            }
+            StreamOutput::Usage(usage) => {
+                let _ = app.emit("chat-stream-usage", usage);
+            }
+        })
+        .await?;
+
+    if cancelled {
+        audit_stream(pool, &processor, audit_input, true).await;
+        let _ = app.emit("chat-stream-cancelled", status.clone());
+    } else if processor.finished {
+        let complete = complete_stream(pool, processor, audit_input).await;
+        let _ = app.emit("chat-complete", complete);
     }
 
-    Ok(())
+    Ok(status.stream_id)
 }
 
 #[cfg(test)]
@@ -559,4 +1104,420 @@ mod tests {
         // First message should still be OLDEST (no trimming needed)
         assert_eq!(trimmed[0].content, "OLDEST");
     }
+
+    // ========================================
+    // Stream Processing Tests
+    // ========================================
+
+    use crate::context::{
+        AttritionStats, ClaimType, EnpsAggregate, OrgAggregates, QueryType, RatingDistribution,
+    };
+
+    fn make_aggregates(total_employees: i64) -> OrgAggregates {
+        OrgAggregates {
+            total_employees,
+            active_count: total_employees,
+            terminated_count: 0,
+            on_leave_count: 0,
+            by_department: vec![],
+            avg_rating: None,
+            rating_distribution: RatingDistribution::default(),
+            employees_with_no_rating: 0,
+            enps: EnpsAggregate {
+                score: 0,
+                promoters: 0,
+                passives: 0,
+                detractors: 0,
+                total_responses: 0,
+                response_rate: 0.0,
+            },
+            attrition: AttritionStats::default(),
+            tenure: Default::default(),
+            diversity: None,
+            missing_sections: vec![],
+        }
+    }
+
+    /// Build a canned SSE stream with the given text deltas
+    fn canned_stream(deltas: &[&str]) -> String {
+        let mut sse = String::from(
+            "event: message_start\ndata: {\"type\":\"message_start\",\"message\":{\"id\":\"msg_1\",\"model\":\"test\"}}\n\n",
+        );
+        for text in deltas {
+            sse.push_str(&format!(
+                "event: content_block_delta\ndata: {{\"type\":\"content_block_delta\",\"index\":0,\"delta\":{{\"type\":\"text_delta\",\"text\":{}}}}}\n\n",
+                serde_json::to_string(text).unwrap()
+            ));
+        }
+        sse.push_str("event: message_stop\ndata: {\"type\":\"message_stop\"}\n\n");
+        sse
+    }
+
+    /// Feed the stream in small pieces to exercise partial-event buffering
+    fn run_stream(sse: &str, agg: OrgAggregates, query_type: QueryType) -> Vec<StreamOutput> {
+        let mut processor = StreamProcessor::new(Some(agg), Some(query_type));
+        let chars: Vec<char> = sse.chars().collect();
+        chars
+            .chunks(7)
+            .flat_map(|piece| processor.push(&piece.iter().collect::<String>()).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn test_stream_mismatched_headcount_emits_correction() {
+        let sse = canned_stream(&["You currently have ", "95 employees", " across the company."]);
+        let outputs = run_stream(&sse, make_aggregates(100), QueryType::Aggregate);
+
+        let text: String = outputs
+            .iter()
+            .filter_map(|o| match o {
+                StreamOutput::Chunk(c) if !c.done => Some(c.chunk.as_str()),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(text, "You currently have 95 employees across the company.");
+
+        // Done chunk comes first, then the correction
+        assert!(matches!(outputs[outputs.len() - 2], StreamOutput::Chunk(ref c) if c.done));
+        let StreamOutput::Correction(correction) = outputs.last().unwrap() else {
+            panic!("expected a correction event");
+        };
+        assert_eq!(correction.corrections.len(), 1);
+        let fix = &correction.corrections[0];
+        assert_eq!(fix.claim_type, ClaimType::TotalHeadcount);
+        assert_eq!((fix.claimed, fix.actual), (95.0, 100.0));
+        assert_eq!(fix.message, "Claude said 95 employees; your data shows 100 employees.");
+    }
+
+    #[test]
+    fn test_stream_matching_headcount_has_no_correction() {
+        let sse = canned_stream(&["You have 100 employees."]);
+        let outputs = run_stream(&sse, make_aggregates(100), QueryType::Aggregate);
+
+        assert!(!outputs.iter().any(|o| matches!(o, StreamOutput::Correction(_))));
+        assert!(matches!(outputs.last(), Some(StreamOutput::Chunk(c)) if c.done && c.verification.is_some()));
+    }
+
+    #[test]
+    fn test_stream_non_aggregate_query_has_no_correction() {
+        let sse = canned_stream(&["Sarah manages 95 employees."]);
+        let outputs = run_stream(&sse, make_aggregates(100), QueryType::Individual);
+
+        assert!(!outputs.iter().any(|o| matches!(o, StreamOutput::Correction(_))));
+    }
+
+    #[test]
+    fn test_stream_error_event() {
+        let mut processor = StreamProcessor::new(None, None);
+        let result = processor.push(
+            "event: error\ndata: {\"type\":\"error\",\"error\":{\"type\":\"overloaded_error\",\"message\":\"Overloaded\"}}\n\n",
+        );
+        assert!(matches!(result, Err(ChatError::ApiError(m)) if m == "Overloaded"));
+    }
+
+    #[tokio::test]
+    async fn test_consumed_stream_is_assembled_and_audited() {
+        let pool = crate::db::test_pool().await;
+        let deltas = ["You currently have ", "95 employees", " — héllo."];
+        let sse = canned_stream(&deltas);
+
+        // Mock network stream one byte at a time, splitting events and the
+        // multi-byte characters
+        let pieces: Vec<Result<Vec<u8>, String>> =
+            sse.as_bytes().chunks(1).map(|b| Ok(b.to_vec())).collect();
+        let mut processor = StreamProcessor::new(Some(make_aggregates(100)), Some(QueryType::Aggregate));
+        let mut emitted = String::new();
+        let cancelled = consume_stream(futures::stream::iter(pieces), &mut processor, &CancelSignal::default(), |output| {
+            if let StreamOutput::Chunk(c) = output {
+                emitted.push_str(&c.chunk);
+            }
+        })
+        .await
+        .unwrap();
+
+        assert!(!cancelled);
+        assert!(processor.finished);
+        let complete = complete_stream(
+            &pool,
+            processor,
+            StreamAuditInput {
+                conversation_id: None,
+                request_redacted: "How many employees?".to_string(),
+                employee_ids_used: vec!["emp-1".to_string()],
+                persona_id: Some("jordan".to_string()),
+            },
+        )
+        .await;
+
+        let entry = audit::get_audit_entry(&pool, complete.audit_entry_id.as_deref().unwrap())
+            .await
+            .unwrap();
+        assert_eq!(entry.response_text, deltas.concat());
+        assert_eq!(entry.response_text, emitted);
+        assert_eq!(entry.request_redacted, "How many employees?");
+        assert_eq!(entry.context_used.as_deref(), Some(r#"["emp-1"]"#));
+        assert!(complete.verification.is_some());
+
+        // Canned stream carries no usage, so tokens stay unset
+        assert_eq!(complete.turn.model.as_deref(), Some("test"));
+        assert_eq!(complete.turn.persona_id.as_deref(), Some("jordan"));
+        assert_eq!(complete.turn.input_tokens, None);
+        assert_eq!(
+            complete.turn.verification_status,
+            Some(crate::context::VerificationStatus::PartialMatch)
+        );
+    }
+
+    #[test]
+    fn test_stream_records_model_and_token_usage() {
+        let sse = concat!(
+            "event: message_start\n",
+            "data: {\"type\":\"message_start\",\"message\":{\"id\":\"msg_1\",\"model\":\"claude-test\",\"usage\":{\"input_tokens\":1200,\"output_tokens\":1}}}\n\n",
+            "event: content_block_delta\n",
+            "data: {\"type\":\"content_block_delta\",\"index\":0,\"delta\":{\"type\":\"text_delta\",\"text\":\"Hi\"}}\n\n",
+            "event: message_delta\n",
+            "data: {\"type\":\"message_delta\",\"delta\":{\"stop_reason\":\"end_turn\"},\"usage\":{\"output_tokens\":42}}\n\n",
+            "event: message_stop\n",
+            "data: {\"type\":\"message_stop\"}\n\n",
+        );
+        let mut processor = StreamProcessor::new(None, None);
+        processor.push(sse).unwrap();
+
+        assert!(processor.finished);
+        assert_eq!(processor.model.as_deref(), Some("claude-test"));
+        assert_eq!(processor.input_tokens, Some(1200));
+        assert_eq!(processor.output_tokens, Some(42));
+    }
+
+    #[tokio::test]
+    async fn test_stream_without_message_stop_is_not_finished() {
+        let sse = canned_stream(&["partial"]);
+        let truncated = sse.split("event: message_stop").next().unwrap().to_string();
+        let mut processor = StreamProcessor::new(None, None);
+        let pieces: Vec<Result<String, String>> = vec![Ok(truncated)];
+        consume_stream(futures::stream::iter(pieces), &mut processor, &CancelSignal::default(), |_| {})
+            .await
+            .unwrap();
+
+        assert!(!processor.finished);
+        assert_eq!(processor.full_response, "partial");
+    }
+
+    #[test]
+    fn test_api_error_captures_request_id_and_body() {
+        let body = r#"{"type":"error","error":{"type":"overloaded_error","message":"Overloaded"}}"#;
+        let error = api_error(529, Some("req_011CabcXYZ".to_string()), body);
+
+        let ChatError::Api { status, ref request_id, ref message } = error else {
+            panic!("expected ChatError::Api, got {:?}", error);
+        };
+        assert_eq!(status, 529);
+        assert_eq!(request_id.as_deref(), Some("req_011CabcXYZ"));
+        assert_eq!(message, "overloaded_error: Overloaded");
+        assert_eq!(
+            error.to_string(),
+            "API returned error (HTTP 529, request id: req_011CabcXYZ): overloaded_error: Overloaded"
+        );
+    }
+
+    #[test]
+    fn test_api_error_non_json_body_without_request_id() {
+        let error = api_error(502, None, "Bad Gateway");
+        assert!(matches!(
+            &error,
+            ChatError::Api { status: 502, request_id: None, message } if message == "Bad Gateway"
+        ));
+        assert_eq!(error.to_string(), "API returned error (HTTP 502): Bad Gateway");
+    }
+
+    // ========================================
+    // Message Length Tests
+    // ========================================
+
+    fn user_message(chars: usize) -> ChatMessage {
+        ChatMessage {
+            role: "user".to_string(),
+            content: "a".repeat(chars),
+        }
+    }
+
+    #[test]
+    fn test_message_just_under_cap_is_allowed() {
+        // 4 chars per token: 400 chars is exactly 100 tokens
+        let messages = vec![user_message(40), user_message(400)];
+        assert!(check_message_lengths(&messages, 100).is_ok());
+    }
+
+    #[test]
+    fn test_message_just_over_cap_is_rejected() {
+        let messages = vec![user_message(40), user_message(401)];
+        match check_message_lengths(&messages, 100) {
+            Err(ChatError::MessageTooLong { estimated_tokens, cap }) => {
+                assert_eq!(estimated_tokens, 101);
+                assert_eq!(cap, 100);
+            }
+            other => panic!("expected MessageTooLong, got {:?}", other),
+        }
+        let err = check_message_lengths(&messages, 100).unwrap_err().to_string();
+        assert!(err.contains("Split it"));
+    }
+
+    #[tokio::test]
+    async fn test_load_max_message_tokens() {
+        let pool = crate::db::test_pool().await;
+        assert_eq!(load_max_message_tokens(&pool).await, DEFAULT_MAX_MESSAGE_TOKENS);
+
+        crate::settings::set_setting(&pool, "max_message_tokens", "2000").await.unwrap();
+        assert_eq!(load_max_message_tokens(&pool).await, 2000);
+
+        crate::settings::set_setting(&pool, "max_message_tokens", "0").await.unwrap();
+        assert_eq!(load_max_message_tokens(&pool).await, DEFAULT_MAX_MESSAGE_TOKENS);
+    }
+
+    #[tokio::test]
+    async fn test_cancelled_stream_stops_emitting() {
+        let sse = canned_stream(&["one ", "two ", "three"]);
+        let pieces: Vec<Result<String, String>> = sse
+            .split_inclusive("\n\n")
+            .map(|event| Ok(event.to_string()))
+            .collect();
+        let mut processor = StreamProcessor::new(None, None);
+        let cancel = CancelSignal::default();
+        let mut emitted = Vec::new();
+
+        let cancelled = consume_stream(futures::stream::iter(pieces), &mut processor, &cancel, |output| {
+            if let StreamOutput::Chunk(c) = output {
+                if c.chunk == "one " {
+                    cancel.cancel();
+                }
+                emitted.push(c.chunk);
+            }
+        })
+        .await
+        .unwrap();
+
+        assert!(cancelled);
+        assert!(!processor.finished);
+        assert_eq!(emitted, vec!["one ".to_string()]);
+
+        // The prompt was already sent, so the partial answer is audited
+        let pool = crate::db::test_pool().await;
+        let input = StreamAuditInput {
+            conversation_id: None,
+            request_redacted: "Count to three".to_string(),
+            employee_ids_used: vec![],
+            persona_id: None,
+        };
+        let id = audit_stream(&pool, &processor, input, true).await.unwrap();
+        let entry = audit::get_audit_entry(&pool, &id).await.unwrap();
+        assert_eq!(entry.request_status, audit::REQUEST_CANCELLED);
+        assert_eq!(entry.response_text, "one ");
+    }
+
+    #[tokio::test]
+    async fn test_stalled_stream_can_be_cancelled() {
+        let sse = canned_stream(&["one "]);
+        let first: Vec<Result<String, String>> = vec![Ok(sse.split_inclusive("\n\n").take(2).collect())];
+        // Delivers the first events, then never yields another chunk
+        let stalled = futures::stream::iter(first).chain(futures::stream::pending());
+        let mut processor = StreamProcessor::new(None, None);
+        let cancel = CancelSignal::default();
+        let mut emitted = Vec::new();
+
+        let (cancelled, _) = futures::join!(
+            consume_stream(Box::pin(stalled), &mut processor, &cancel, |output| {
+                if let StreamOutput::Chunk(c) = output {
+                    emitted.push(c.chunk);
+                }
+            }),
+            async {
+                tokio::task::yield_now().await;
+                cancel.cancel();
+            },
+        );
+
+        assert!(cancelled.unwrap());
+        assert!(!processor.finished);
+        assert_eq!(emitted, vec!["one ".to_string()]);
+    }
+
+    #[test]
+    fn test_stream_registry_cancels_only_running_streams() {
+        let streams = ChatStreams::default();
+        let registration = streams.register();
+        let id = registration.id.clone();
+
+        assert!(streams.cancel(&id));
+        assert!(registration.cancel.is_cancelled());
+
+        drop(registration);
+        assert!(!streams.cancel(&id));
+        assert!(!streams.cancel("unknown"));
+    }
+
+    #[test]
+    fn test_resolve_model_uses_allowlist() {
+        assert_eq!(resolve_model(None).unwrap(), MODEL);
+        assert_eq!(resolve_model(Some("claude-3-5-haiku-20241022")).unwrap(), "claude-3-5-haiku-20241022");
+        assert!(matches!(
+            resolve_model(Some("gpt-4o")),
+            Err(ChatError::UnknownModel(m)) if m == "gpt-4o"
+        ));
+
+        let models = list_available_models();
+        assert_eq!(models[0].id, MODEL);
+        assert!(models[0].is_default);
+        assert_eq!(models.iter().filter(|m| m.is_default).count(), 1);
+        assert!(models.iter().all(|m| m.context_window > 0 && is_available_model(&m.id)));
+    }
+
+    /// Recorded Messages API stream, including the events the processor ignores
+    const RECORDED_SSE: &str = concat!(
+        "event: message_start\n",
+        "data: {\"type\":\"message_start\",\"message\":{\"id\":\"msg_01XFDUDYJgAACzvnptvVoYEL\",\"type\":\"message\",\"role\":\"assistant\",\"content\":[],\"model\":\"claude-sonnet-4-20250514\",\"stop_reason\":null,\"stop_sequence\":null,\"usage\":{\"input_tokens\":2517,\"output_tokens\":1}}}\n\n",
+        "event: content_block_start\n",
+        "data: {\"type\":\"content_block_start\",\"index\":0,\"content_block\":{\"type\":\"text\",\"text\":\"\"}}\n\n",
+        "event: ping\n",
+        "data: {\"type\":\"ping\"}\n\n",
+        "event: content_block_delta\n",
+        "data: {\"type\":\"content_block_delta\",\"index\":0,\"delta\":{\"type\":\"text_delta\",\"text\":\"Engineering has \"}}\n\n",
+        "event: content_block_delta\n",
+        "data: {\"type\":\"content_block_delta\",\"index\":0,\"delta\":{\"type\":\"text_delta\",\"text\":\"the most open roles.\"}}\n\n",
+        "event: content_block_stop\n",
+        "data: {\"type\":\"content_block_stop\",\"index\":0}\n\n",
+        "event: message_delta\n",
+        "data: {\"type\":\"message_delta\",\"delta\":{\"stop_reason\":\"end_turn\",\"stop_sequence\":null},\"usage\":{\"output_tokens\":15}}\n\n",
+        "event: message_stop\n",
+        "data: {\"type\":\"message_stop\"}\n\n",
+    );
+
+    #[tokio::test]
+    async fn test_recorded_stream_usage_survives_chunk_boundaries() {
+        let pool = crate::db::test_pool().await;
+        let expected = TokenUsage { input_tokens: 2517, output_tokens: 15 };
+
+        for size in [1, 7, 64, RECORDED_SSE.len()] {
+            let pieces: Vec<Result<Vec<u8>, String>> =
+                RECORDED_SSE.as_bytes().chunks(size).map(|b| Ok(b.to_vec())).collect();
+            let mut processor = StreamProcessor::new(None, None);
+            let mut usage_events = Vec::new();
+            consume_stream(futures::stream::iter(pieces), &mut processor, &CancelSignal::default(), |output| {
+                if let StreamOutput::Usage(usage) = output {
+                    usage_events.push(usage);
+                }
+            })
+            .await
+            .unwrap();
+
+            assert_eq!(usage_events, vec![expected], "chunk size {}", size);
+            assert_eq!(processor.full_response, "Engineering has the most open roles.");
+
+            let complete = complete_stream(&pool, processor, StreamAuditInput::default()).await;
+            let entry = audit::get_audit_entry(&pool, complete.audit_entry_id.as_deref().unwrap())
+                .await
+                .unwrap();
+            assert_eq!(entry.input_tokens, Some(2517));
+            assert_eq!(entry.output_tokens, Some(15));
+        }
+    }
 }
@@ -1,5 +1,7 @@
 // HR Command Center - Company Module
-// CRUD operations for company profile (single-row table)
+// CRUD operations for company profiles. The 'default' row is the primary
+// profile; additional rows are other legal entities, and each employee
+// belongs to one of them via employees.company_id.
 // Company state = HQ/incorporation state (legal jurisdiction)
 // Employee work states are tracked separately per employee
 
@@ -9,8 +11,12 @@ use thiserror::Error;
 
 use crate::db::DbPool;
 
-/// Default ID for the single company row
-const COMPANY_ID: &str = "default";
+/// ID of the primary company row, and of the company employees belong to
+/// unless assigned elsewhere
+pub const DEFAULT_COMPANY_ID: &str = "default";
+
+/// Longest accepted company ID
+const MAX_COMPANY_ID_LEN: usize = 40;
 
 #[derive(Error, Debug, Serialize)]
 pub enum CompanyError {
@@ -20,6 +26,8 @@ pub enum CompanyError {
     NotFound,
     #[error("Validation error: {0}")]
     Validation(String),
+    #[error("{}", crate::db::READ_ONLY_MESSAGE)]
+    ReadOnly,
 }
 
 impl From<sqlx::Error> for CompanyError {
@@ -28,6 +36,12 @@ impl From<sqlx::Error> for CompanyError {
     }
 }
 
+impl From<crate::db::ReadOnly> for CompanyError {
+    fn from(_: crate::db::ReadOnly) -> Self {
+        CompanyError::ReadOnly
+    }
+}
+
 /// Company profile from the database
 #[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
 pub struct Company {
@@ -38,9 +52,13 @@ pub struct Company {
     pub created_at: String,
 }
 
-/// Input for creating or updating the company profile
+/// Input for creating or updating a company profile
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct UpsertCompany {
+    /// Which company to write; the primary ('default') profile when omitted.
+    /// Lowercase letters, digits, '-' and '_'.
+    #[serde(default)]
+    pub id: Option<String>,
     pub name: String,
     pub state: String,
     pub industry: Option<String>,
@@ -59,28 +77,48 @@ pub struct StateCount {
     pub count: i64,
 }
 
-/// Check if a company profile exists
+/// Check if the primary company profile exists
 pub async fn has_company(pool: &DbPool) -> Result<bool, CompanyError> {
     let row: Option<(i64,)> = sqlx::query_as("SELECT COUNT(*) FROM company WHERE id = ?")
-        .bind(COMPANY_ID)
+        .bind(DEFAULT_COMPANY_ID)
         .fetch_optional(pool)
         .await?;
 
     Ok(row.map(|(count,)| count > 0).unwrap_or(false))
 }
 
-/// Get the company profile
+/// Get the primary company profile
 pub async fn get_company(pool: &DbPool) -> Result<Company, CompanyError> {
+    get_company_by_id(pool, DEFAULT_COMPANY_ID).await
+}
+
+/// Get one company profile by ID
+pub async fn get_company_by_id(pool: &DbPool, id: &str) -> Result<Company, CompanyError> {
     sqlx::query_as::<_, Company>("SELECT * FROM company WHERE id = ?")
-        .bind(COMPANY_ID)
+        .bind(id)
         .fetch_optional(pool)
         .await?
         .ok_or(CompanyError::NotFound)
 }
 
-/// Create or update the company profile (upsert)
+/// All company profiles, the primary one first, then by name
+pub async fn list_companies(pool: &DbPool) -> Result<Vec<Company>, CompanyError> {
+    let companies = sqlx::query_as::<_, Company>(
+        "SELECT * FROM company ORDER BY CASE WHEN id = ? THEN 0 ELSE 1 END, name COLLATE NOCASE, id",
+    )
+    .bind(DEFAULT_COMPANY_ID)
+    .fetch_all(pool)
+    .await?;
+    Ok(companies)
+}
+
+/// Create or update a company profile (upsert)
 pub async fn upsert_company(pool: &DbPool, input: UpsertCompany) -> Result<Company, CompanyError> {
     // Validate inputs
+    let id = match input.id.as_deref().map(str::trim) {
+        None | Some("") => DEFAULT_COMPANY_ID.to_string(),
+        Some(id) => validate_company_id(id)?,
+    };
     let name = input.name.trim();
     let state = input.state.trim().to_uppercase();
 
@@ -114,15 +152,28 @@ pub async fn upsert_company(pool: &DbPool, input: UpsertCompany) -> Result<Compa
         ))
         "#,
     )
-    .bind(COMPANY_ID)
+    .bind(&id)
     .bind(name)
     .bind(&state)
     .bind(&input.industry)
-    .bind(COMPANY_ID)
+    .bind(&id)
     .execute(pool)
     .await?;
 
-    get_company(pool).await
+    get_company_by_id(pool, &id).await
+}
+
+/// Company IDs are stored on every employee row, so keep them short slugs
+fn validate_company_id(id: &str) -> Result<String, CompanyError> {
+    let id = id.to_lowercase();
+    let valid_chars = id.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+    if !valid_chars || id.len() > MAX_COMPANY_ID_LEN {
+        return Err(CompanyError::Validation(format!(
+            "Company ID must be at most {} lowercase letters, digits, '-' or '_'",
+            MAX_COMPANY_ID_LEN
+        )));
+    }
+    Ok(id)
 }
 
 /// Get summary of employee work states (operational footprint)
@@ -164,6 +215,40 @@ fn is_valid_us_state(code: &str) -> bool {
     US_STATES.contains(&code)
 }
 
+/// US state codes paired with their full names
+pub const US_STATE_NAMES: [(&str, &str); 50] = [
+    ("AL", "Alabama"), ("AK", "Alaska"), ("AZ", "Arizona"), ("AR", "Arkansas"),
+    ("CA", "California"), ("CO", "Colorado"), ("CT", "Connecticut"), ("DE", "Delaware"),
+    ("FL", "Florida"), ("GA", "Georgia"), ("HI", "Hawaii"), ("ID", "Idaho"),
+    ("IL", "Illinois"), ("IN", "Indiana"), ("IA", "Iowa"), ("KS", "Kansas"),
+    ("KY", "Kentucky"), ("LA", "Louisiana"), ("ME", "Maine"), ("MD", "Maryland"),
+    ("MA", "Massachusetts"), ("MI", "Michigan"), ("MN", "Minnesota"), ("MS", "Mississippi"),
+    ("MO", "Missouri"), ("MT", "Montana"), ("NE", "Nebraska"), ("NV", "Nevada"),
+    ("NH", "New Hampshire"), ("NJ", "New Jersey"), ("NM", "New Mexico"), ("NY", "New York"),
+    ("NC", "North Carolina"), ("ND", "North Dakota"), ("OH", "Ohio"), ("OK", "Oklahoma"),
+    ("OR", "Oregon"), ("PA", "Pennsylvania"), ("RI", "Rhode Island"), ("SC", "South Carolina"),
+    ("SD", "South Dakota"), ("TN", "Tennessee"), ("TX", "Texas"), ("UT", "Utah"),
+    ("VT", "Vermont"), ("VA", "Virginia"), ("WA", "Washington"), ("WV", "West Virginia"),
+    ("WI", "Wisconsin"), ("WY", "Wyoming"),
+];
+
+/// Normalize a work_state value ("CA", "ca", "California") to its 2-letter code
+pub fn normalize_us_state(raw: &str) -> Option<&'static str> {
+    let trimmed = raw.trim();
+    US_STATE_NAMES
+        .iter()
+        .find(|(code, name)| code.eq_ignore_ascii_case(trimmed) || name.eq_ignore_ascii_case(trimmed))
+        .map(|(code, _)| *code)
+}
+
+/// Full name for a 2-letter state code
+pub fn us_state_name(code: &str) -> Option<&'static str> {
+    US_STATE_NAMES
+        .iter()
+        .find(|(c, _)| c.eq_ignore_ascii_case(code.trim()))
+        .map(|(_, name)| *name)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -177,4 +262,74 @@ mod tests {
         assert!(!is_valid_us_state("California"));
         assert!(!is_valid_us_state("ca")); // Must be uppercase
     }
+
+    #[test]
+    fn test_normalize_us_state() {
+        assert_eq!(normalize_us_state("CA"), Some("CA"));
+        assert_eq!(normalize_us_state(" ca "), Some("CA"));
+        assert_eq!(normalize_us_state("California"), Some("CA"));
+        assert_eq!(normalize_us_state("new york"), Some("NY"));
+        assert_eq!(normalize_us_state("Calif"), None);
+        assert_eq!(us_state_name("wv"), Some("West Virginia"));
+    }
+
+    fn profile(id: Option<&str>, name: &str, state: &str) -> UpsertCompany {
+        UpsertCompany {
+            id: id.map(str::to_string),
+            name: name.to_string(),
+            state: state.to_string(),
+            industry: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_multiple_companies() {
+        let pool = crate::db::test_pool().await;
+        upsert_company(&pool, profile(Some("Zeta-Labs"), "Zeta Labs", "ny")).await.unwrap();
+        upsert_company(&pool, profile(None, "Acme", "CA")).await.unwrap();
+        upsert_company(&pool, profile(Some("beta"), "Beta Co", "TX")).await.unwrap();
+
+        let ids: Vec<String> = list_companies(&pool).await.unwrap().into_iter().map(|c| c.id).collect();
+        assert_eq!(ids, vec!["default", "beta", "zeta-labs"]);
+        assert_eq!(get_company(&pool).await.unwrap().name, "Acme");
+        assert_eq!(get_company_by_id(&pool, "zeta-labs").await.unwrap().state, "NY");
+        assert!(matches!(
+            upsert_company(&pool, profile(Some("bad id!"), "Bad", "CA")).await,
+            Err(CompanyError::Validation(_))
+        ));
+
+        // Employees default to the primary company and must name a real one
+        use crate::employees::{self, CreateEmployee, EmployeeFilter};
+        let new_employee = |email: &str, company_id: Option<&str>| CreateEmployee {
+            email: email.to_string(),
+            full_name: "Test Person".to_string(),
+            department: None,
+            job_title: None,
+            manager_id: None,
+            hire_date: None,
+            work_state: None,
+            status: None,
+            date_of_birth: None,
+            gender: None,
+            ethnicity: None,
+            termination_date: None,
+            termination_reason: None,
+            extra_fields: None,
+            company_id: company_id.map(str::to_string),
+        };
+        let primary = employees::create_employee(&pool, new_employee("a@example.com", None)).await.unwrap();
+        assert_eq!(primary.company_id.as_deref(), Some(DEFAULT_COMPANY_ID));
+        employees::create_employee(&pool, new_employee("b@example.com", Some("beta"))).await.unwrap();
+        assert!(matches!(
+            employees::create_employee(&pool, new_employee("c@example.com", Some("nope"))).await,
+            Err(employees::EmployeeError::Validation(_))
+        ));
+
+        let filter = EmployeeFilter { company_id: Some("beta".to_string()), ..Default::default() };
+        let beta = employees::list_employees(&pool, filter, None, None).await.unwrap();
+        assert_eq!(beta.total, 1);
+        assert_eq!(beta.employees[0].email, "b@example.com");
+        let all = employees::list_employees(&pool, EmployeeFilter::default(), None, None).await.unwrap();
+        assert_eq!(all.total, 2);
+    }
 }
@@ -6,12 +6,14 @@
 // 2. List conversations for sidebar display
 // 3. Search conversations using FTS5
 // 4. Generate titles for new conversations
+// 5. Export a conversation as Markdown
 
 use serde::{Deserialize, Serialize};
-use sqlx::FromRow;
+use sqlx::{FromRow, Row};
 use thiserror::Error;
 
 use crate::db::DbPool;
+use crate::network::NetworkConfig;
 
 // ============================================================================
 // Error Types
@@ -25,6 +27,8 @@ pub enum ConversationError {
     NotFound(String),
     #[error("Invalid input: {0}")]
     InvalidInput(String),
+    #[error("{}", crate::db::READ_ONLY_MESSAGE)]
+    ReadOnly,
 }
 
 impl From<sqlx::Error> for ConversationError {
@@ -33,6 +37,12 @@ impl From<sqlx::Error> for ConversationError {
     }
 }
 
+impl From<crate::db::ReadOnly> for ConversationError {
+    fn from(_: crate::db::ReadOnly) -> Self {
+        ConversationError::ReadOnly
+    }
+}
+
 // Make ConversationError serializable for Tauri commands
 impl serde::Serialize for ConversationError {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
@@ -56,6 +66,14 @@ pub struct Conversation {
     pub messages_json: String,
     pub created_at: String,
     pub updated_at: String,
+    pub tags: String, // JSON array of tag strings
+    pub archived: bool,
+    /// Listed above unpinned conversations (unless archived)
+    pub pinned: bool,
+    /// Never surfaced by memory retrieval
+    pub exclude_from_memory: bool,
+    /// Claude model chosen for this conversation; None uses the default
+    pub model: Option<String>,
 }
 
 /// Lightweight conversation item for sidebar list
@@ -68,6 +86,9 @@ pub struct ConversationListItem {
     pub first_message_preview: Option<String>,
     pub created_at: String,
     pub updated_at: String,
+    pub tags: String, // JSON array of tag strings
+    pub pinned: bool,
+    pub archived: bool,
 }
 
 /// Input for creating a conversation
@@ -84,6 +105,9 @@ pub struct UpdateConversation {
     pub title: Option<String>,
     pub messages_json: Option<String>,
     pub summary: Option<String>,
+    pub exclude_from_memory: Option<bool>,
+    /// One of `chat::list_available_models`; an empty string resets to the default
+    pub model: Option<String>,
 }
 
 // ============================================================================
@@ -121,7 +145,7 @@ pub async fn get_conversation(
 ) -> Result<Conversation, ConversationError> {
     let conversation = sqlx::query_as::<_, Conversation>(
         r#"
-        SELECT id, title, summary, messages_json, created_at, updated_at
+        SELECT id, title, summary, messages_json, created_at, updated_at, tags, archived, pinned, exclude_from_memory, model
         FROM conversations
         WHERE id = ?
         "#,
@@ -141,6 +165,12 @@ pub async fn update_conversation(
     id: &str,
     input: UpdateConversation,
 ) -> Result<Conversation, ConversationError> {
+    if let Some(model) = input.model.as_deref().filter(|m| !m.is_empty()) {
+        if !crate::chat::is_available_model(model) {
+            return Err(ConversationError::InvalidInput(format!("Unknown model: {}", model)));
+        }
+    }
+
     // Build dynamic UPDATE query based on provided fields
     let mut set_clauses = vec!["updated_at = datetime('now')".to_string()];
     let mut bindings: Vec<String> = vec![];
@@ -156,10 +186,25 @@ pub async fn update_conversation(
     }
 
     if let Some(summary) = &input.summary {
+        // The cached embedding belongs to the old summary
         set_clauses.push("summary = ?".to_string());
+        set_clauses.push("summary_embedding = NULL".to_string());
         bindings.push(summary.clone());
     }
 
+    if let Some(exclude) = input.exclude_from_memory {
+        set_clauses.push(format!("exclude_from_memory = {}", i32::from(exclude)));
+    }
+
+    match input.model.as_deref() {
+        Some("") => set_clauses.push("model = NULL".to_string()),
+        Some(model) => {
+            set_clauses.push("model = ?".to_string());
+            bindings.push(model.to_string());
+        }
+        None => {}
+    }
+
     let query = format!(
         "UPDATE conversations SET {} WHERE id = ?",
         set_clauses.join(", ")
@@ -176,7 +221,7 @@ pub async fn update_conversation(
 
     if result.rows_affected() == 0 {
         // Conversation doesn't exist - create it
-        return create_conversation(
+        let created = create_conversation(
             pool,
             CreateConversation {
                 id: id.to_string(),
@@ -184,7 +229,19 @@ pub async fn update_conversation(
                 messages_json: input.messages_json,
             },
         )
-        .await;
+        .await?;
+
+        return match input.model.filter(|m| !m.is_empty()) {
+            Some(model) => {
+                sqlx::query("UPDATE conversations SET model = ? WHERE id = ?")
+                    .bind(model)
+                    .bind(id)
+                    .execute(pool)
+                    .await?;
+                get_conversation(pool, id).await
+            }
+            None => Ok(created),
+        };
     }
 
     get_conversation(pool, id).await
@@ -192,11 +249,16 @@ pub async fn update_conversation(
 
 /// List conversations for sidebar display
 ///
-/// Returns lightweight items sorted by updated_at (most recent first)
+/// Returns lightweight items sorted by updated_at (most recent first).
+/// Archived conversations are left out (search still finds them).
+///
+/// Pinned conversations come first, then the rest by recency. Archived
+/// conversations (pinned or not) are left out unless `include_archived`.
 pub async fn list_conversations(
     pool: &DbPool,
     limit: i64,
     offset: i64,
+    include_archived: bool,
 ) -> Result<Vec<ConversationListItem>, ConversationError> {
     // Use a subquery to count messages and extract first message preview
     let conversations = sqlx::query_as::<_, ConversationListItem>(
@@ -212,13 +274,18 @@ pub async fn list_conversations(
                 ELSE NULL
             END as first_message_preview,
             created_at,
-            updated_at
+            updated_at,
+            tags,
+            pinned,
+            archived
         FROM conversations
         WHERE json_array_length(messages_json) > 0
-        ORDER BY updated_at DESC
+          AND (? OR archived = 0)
+        ORDER BY (pinned = 1 AND archived = 0) DESC, updated_at DESC
         LIMIT ? OFFSET ?
         "#,
     )
+    .bind(include_archived)
     .bind(limit)
     .bind(offset)
     .fetch_all(pool)
@@ -260,7 +327,10 @@ pub async fn search_conversations(
                 ELSE NULL
             END as first_message_preview,
             c.created_at,
-            c.updated_at
+            c.updated_at,
+            c.tags,
+            c.pinned,
+            c.archived
         FROM conversations c
         INNER JOIN conversations_fts fts ON c.rowid = fts.rowid
         WHERE conversations_fts MATCH ?
@@ -312,12 +382,34 @@ pub async fn delete_conversation(
     Ok(())
 }
 
+/// Pin or unpin a conversation. Setting the current state again is a no-op.
+pub async fn set_conversation_pinned(pool: &DbPool, id: &str, pinned: bool) -> Result<(), ConversationError> {
+    set_flag(pool, id, "UPDATE conversations SET pinned = ? WHERE id = ?", pinned).await
+}
+
+/// Archive or unarchive a conversation. An archived conversation keeps its
+/// pin but drops out of the default list.
+pub async fn set_conversation_archived(pool: &DbPool, id: &str, archived: bool) -> Result<(), ConversationError> {
+    set_flag(pool, id, "UPDATE conversations SET archived = ? WHERE id = ?", archived).await
+}
+
+/// Run a flag update; `updated_at` is left alone so pinning doesn't reorder
+/// the list by recency
+async fn set_flag(pool: &DbPool, id: &str, sql: &str, value: bool) -> Result<(), ConversationError> {
+    let result = sqlx::query(sql).bind(value).bind(id).execute(pool).await?;
+    if result.rows_affected() == 0 {
+        return Err(ConversationError::NotFound(id.to_string()));
+    }
+    Ok(())
+}
+
 /// Get total count of conversations (for pagination)
 pub async fn count_conversations(pool: &DbPool) -> Result<i64, ConversationError> {
     let result: (i64,) = sqlx::query_as(
         r#"
         SELECT COUNT(*) FROM conversations
         WHERE json_array_length(messages_json) > 0
+          AND archived = 0
         "#,
     )
     .fetch_one(pool)
@@ -326,6 +418,119 @@ pub async fn count_conversations(pool: &DbPool) -> Result<i64, ConversationError
     Ok(result.0)
 }
 
+// ============================================================================
+// Bulk Operations
+// ============================================================================
+
+/// Action applied to every conversation in a bulk request
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum ConversationOp {
+    AddTag(String),
+    RemoveTag(String),
+    Archive,
+    Delete,
+}
+
+/// A conversation the bulk operation couldn't be applied to
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BatchFailure {
+    pub id: String,
+    pub error: String,
+}
+
+/// Per-id outcome of a bulk operation
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct BatchResult {
+    pub succeeded: Vec<String>,
+    pub failed: Vec<BatchFailure>,
+}
+
+/// Apply one operation to many conversations in a single transaction
+///
+/// Unknown ids are reported in `failed` and skipped; the rest still apply.
+/// A database error rolls back the whole batch and is returned as `Err`, so
+/// a batch is never left half-applied. Deletes also remove the conversation's
+/// audit log entries; the FTS index is kept in sync by the delete trigger.
+pub async fn bulk_conversation_op(
+    pool: &DbPool,
+    ids: Vec<String>,
+    op: ConversationOp,
+) -> Result<BatchResult, ConversationError> {
+    let op = match op {
+        ConversationOp::AddTag(tag) => ConversationOp::AddTag(normalize_tag(&tag)?),
+        ConversationOp::RemoveTag(tag) => ConversationOp::RemoveTag(normalize_tag(&tag)?),
+        other => other,
+    };
+
+    let mut result = BatchResult::default();
+    let mut tx = pool.begin().await?;
+
+    for id in ids {
+        let existing = sqlx::query("SELECT tags FROM conversations WHERE id = ?")
+            .bind(&id)
+            .fetch_optional(&mut *tx)
+            .await?;
+        let Some(row) = existing else {
+            result.failed.push(BatchFailure {
+                error: ConversationError::NotFound(id.clone()).to_string(),
+                id,
+            });
+            continue;
+        };
+
+        match &op {
+            ConversationOp::AddTag(tag) | ConversationOp::RemoveTag(tag) => {
+                let tags_json: String = row.get("tags");
+                let mut tags: Vec<String> = serde_json::from_str(&tags_json).unwrap_or_default();
+                if matches!(op, ConversationOp::AddTag(_)) {
+                    if !tags.contains(tag) {
+                        tags.push(tag.clone());
+                    }
+                } else {
+                    tags.retain(|t| t != tag);
+                }
+                let tags_json = serde_json::to_string(&tags)
+                    .map_err(|e| ConversationError::InvalidInput(e.to_string()))?;
+                sqlx::query("UPDATE conversations SET tags = ? WHERE id = ?")
+                    .bind(&tags_json)
+                    .bind(&id)
+                    .execute(&mut *tx)
+                    .await?;
+            }
+            ConversationOp::Archive => {
+                sqlx::query("UPDATE conversations SET archived = 1 WHERE id = ?")
+                    .bind(&id)
+                    .execute(&mut *tx)
+                    .await?;
+            }
+            ConversationOp::Delete => {
+                sqlx::query("DELETE FROM audit_log WHERE conversation_id = ?")
+                    .bind(&id)
+                    .execute(&mut *tx)
+                    .await?;
+                sqlx::query("DELETE FROM conversations WHERE id = ?")
+                    .bind(&id)
+                    .execute(&mut *tx)
+                    .await?;
+            }
+        }
+
+        result.succeeded.push(id);
+    }
+
+    tx.commit().await?;
+    Ok(result)
+}
+
+/// Trim a tag and reject empty ones
+fn normalize_tag(tag: &str) -> Result<String, ConversationError> {
+    let tag = tag.trim();
+    if tag.is_empty() {
+        return Err(ConversationError::InvalidInput("Tag cannot be empty".to_string()));
+    }
+    Ok(tag.to_string())
+}
+
 // ============================================================================
 // Title Generation
 // ============================================================================
@@ -339,15 +544,15 @@ Just respond with the title, nothing else."#;
 /// Generate a title for a conversation using Claude
 ///
 /// Takes the first user message and generates a 3-5 word title
-pub async fn generate_title(first_message: &str) -> Result<String, ConversationError> {
-    use crate::chat::{send_message, ChatMessage};
+pub async fn generate_title(first_message: &str, net: &NetworkConfig) -> Result<String, ConversationError> {
+    use crate::chat::{send_message, ChatMessage, DEFAULT_MAX_MESSAGE_TOKENS};
 
     let messages = vec![ChatMessage {
         role: "user".to_string(),
         content: format!("Generate a title for: {}", first_message),
     }];
 
-    let response = send_message(messages, Some(TITLE_SYSTEM_PROMPT.to_string()))
+    let response = send_message(messages, Some(TITLE_SYSTEM_PROMPT.to_string()), None, net, DEFAULT_MAX_MESSAGE_TOKENS)
         .await
         .map_err(|e| ConversationError::Database(format!("Title generation failed: {}", e)))?;
 
@@ -371,8 +576,8 @@ pub async fn generate_title(first_message: &str) -> Result<String, ConversationE
 /// Generate a title from the first message (fallback: truncation)
 ///
 /// Tries Claude first, falls back to simple truncation if that fails
-pub async fn generate_title_with_fallback(first_message: &str) -> String {
-    match generate_title(first_message).await {
+pub async fn generate_title_with_fallback(first_message: &str, net: &NetworkConfig) -> String {
+    match generate_title(first_message, net).await {
         Ok(title) => title,
         Err(_) => {
             // Fallback: truncate first message
@@ -386,6 +591,76 @@ pub async fn generate_title_with_fallback(first_message: &str) -> String {
     }
 }
 
+// ============================================================================
+// Markdown Export
+// ============================================================================
+
+/// Placeholder for messages that can't be read back
+const UNREADABLE_MESSAGE: &str = "_[This message could not be read]_";
+
+/// Render a conversation as Markdown for pasting into a doc or ticket:
+/// the title and created date, then each message under a bold role label.
+/// Assistant turns are labelled with the persona that wrote them (falling
+/// back to the current persona). Message text is copied verbatim, so code
+/// fences survive; malformed messages_json becomes a placeholder.
+pub async fn export_conversation_markdown(pool: &DbPool, id: &str) -> Result<String, ConversationError> {
+    let conversation = get_conversation(pool, id).await?;
+    let default_persona: Option<String> =
+        crate::settings::load_typed_setting(pool, crate::settings::SettingKey::Persona).await;
+    Ok(render_markdown(&conversation, default_persona.as_deref()))
+}
+
+fn render_markdown(conversation: &Conversation, default_persona: Option<&str>) -> String {
+    let title = conversation
+        .title
+        .as_deref()
+        .map(str::trim)
+        .filter(|t| !t.is_empty())
+        .unwrap_or("Untitled conversation");
+    let created = conversation.created_at.get(..10).unwrap_or(&conversation.created_at);
+    let mut out = format!("# {}\n\n_Created {}_\n", title, created);
+
+    let messages = match serde_json::from_str::<Vec<serde_json::Value>>(&conversation.messages_json) {
+        Ok(messages) => messages,
+        Err(e) => {
+            eprintln!("[Conversations] Unreadable messages in {}: {}", conversation.id, e);
+            out.push_str(&format!("\n{}\n", UNREADABLE_MESSAGE));
+            return out;
+        }
+    };
+
+    for message in &messages {
+        let role = message.get("role").and_then(|r| r.as_str());
+        let content = message.get("content").and_then(|c| c.as_str());
+        let label = match role {
+            Some("user") => "You",
+            Some("assistant") => {
+                let persona = message.get("persona_id").and_then(|p| p.as_str()).or(default_persona);
+                crate::context::get_persona(persona).name
+            }
+            _ => "Unknown",
+        };
+        out.push_str(&format!("\n**{}:**\n\n", label));
+        match content {
+            Some(content) => {
+                let content = content.trim_end();
+                out.push_str(content);
+                out.push('\n');
+                // Close a fence the message left open so it can't swallow
+                // the messages after it
+                if content.lines().filter(|l| l.trim_start().starts_with("```")).count() % 2 == 1 {
+                    out.push_str("```\n");
+                }
+            }
+            None => {
+                out.push_str(UNREADABLE_MESSAGE);
+                out.push('\n');
+            }
+        }
+    }
+    out
+}
+
 // ============================================================================
 // Helper Functions
 // ============================================================================
@@ -482,4 +757,278 @@ mod tests {
         // At ~4 chars per token, should be under 100 tokens
         assert!(prompt_len < 400, "Title system prompt too long: {} chars", prompt_len);
     }
+
+    async fn seed_conversation(pool: &DbPool, id: &str, text: &str) {
+        let messages = serde_json::json!([{ "role": "user", "content": text }]).to_string();
+        create_conversation(
+            pool,
+            CreateConversation {
+                id: id.to_string(),
+                title: Some(format!("Chat {}", id)),
+                messages_json: Some(messages),
+            },
+        )
+        .await
+        .unwrap();
+    }
+
+    async fn seed_audit(pool: &DbPool, conversation_id: &str) {
+        sqlx::query(
+            "INSERT INTO audit_log (id, conversation_id, request_redacted, response_text) VALUES (?, ?, 'q', 'a')",
+        )
+        .bind(format!("audit-{}", conversation_id))
+        .bind(conversation_id)
+        .execute(pool)
+        .await
+        .unwrap();
+    }
+
+    fn ids(list: &[&str]) -> Vec<String> {
+        list.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[tokio::test]
+    async fn test_bulk_tag_mixed_batch_reports_missing_id() {
+        let pool = crate::db::test_pool().await;
+        seed_conversation(&pool, "c1", "attrition in sales").await;
+        seed_conversation(&pool, "c2", "hiring plan").await;
+
+        let result = bulk_conversation_op(
+            &pool,
+            ids(&["c1", "missing", "c2"]),
+            ConversationOp::AddTag(" retention ".to_string()),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(result.succeeded, ids(&["c1", "c2"]));
+        assert_eq!(result.failed.len(), 1);
+        assert_eq!(result.failed[0].id, "missing");
+        assert!(result.failed[0].error.contains("not found"));
+        assert_eq!(get_conversation(&pool, "c1").await.unwrap().tags, r#"["retention"]"#);
+
+        // Adding again is idempotent; removing clears it
+        bulk_conversation_op(&pool, ids(&["c1"]), ConversationOp::AddTag("retention".to_string()))
+            .await
+            .unwrap();
+        assert_eq!(get_conversation(&pool, "c1").await.unwrap().tags, r#"["retention"]"#);
+        bulk_conversation_op(&pool, ids(&["c1"]), ConversationOp::RemoveTag("retention".to_string()))
+            .await
+            .unwrap();
+        assert_eq!(get_conversation(&pool, "c1").await.unwrap().tags, "[]");
+    }
+
+    #[tokio::test]
+    async fn test_bulk_archive_hides_from_list() {
+        let pool = crate::db::test_pool().await;
+        seed_conversation(&pool, "c1", "attrition in sales").await;
+        seed_conversation(&pool, "c2", "hiring plan").await;
+
+        bulk_conversation_op(&pool, ids(&["c2"]), ConversationOp::Archive).await.unwrap();
+
+        let listed = list_conversations(&pool, 50, 0, false).await.unwrap();
+        assert_eq!(listed.iter().map(|c| c.id.as_str()).collect::<Vec<_>>(), vec!["c1"]);
+        assert_eq!(count_conversations(&pool).await.unwrap(), 1);
+        assert!(get_conversation(&pool, "c2").await.unwrap().archived);
+    }
+
+    #[tokio::test]
+    async fn test_bulk_delete_cleans_audit_and_fts() {
+        let pool = crate::db::test_pool().await;
+        seed_conversation(&pool, "c1", "attrition in sales").await;
+        seed_conversation(&pool, "c2", "attrition in support").await;
+        seed_audit(&pool, "c1").await;
+
+        let result = bulk_conversation_op(&pool, ids(&["c1", "missing"]), ConversationOp::Delete)
+            .await
+            .unwrap();
+        assert_eq!(result.succeeded, ids(&["c1"]));
+        assert_eq!(result.failed.len(), 1);
+
+        assert!(matches!(get_conversation(&pool, "c1").await, Err(ConversationError::NotFound(_))));
+        let audit_rows: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM audit_log")
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+        assert_eq!(audit_rows, 0);
+
+        let found = search_conversations(&pool, "attrition", 10).await.unwrap();
+        assert_eq!(found.iter().map(|c| c.id.as_str()).collect::<Vec<_>>(), vec!["c2"]);
+    }
+
+    #[tokio::test]
+    async fn test_bulk_op_rolls_back_on_database_error() {
+        let pool = crate::db::test_pool().await;
+        seed_conversation(&pool, "c1", "attrition in sales").await;
+        seed_conversation(&pool, "c2", "hiring plan").await;
+        sqlx::query(
+            "CREATE TRIGGER block_c2 BEFORE DELETE ON conversations WHEN OLD.id = 'c2' BEGIN SELECT RAISE(ABORT, 'blocked'); END",
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        let result = bulk_conversation_op(&pool, ids(&["c1", "c2"]), ConversationOp::Delete).await;
+        assert!(matches!(result, Err(ConversationError::Database(_))));
+
+        // c1 was deleted earlier in the batch, but the rollback restores it
+        assert!(get_conversation(&pool, "c1").await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_bulk_op_rejects_empty_tag() {
+        let pool = crate::db::test_pool().await;
+        let result = bulk_conversation_op(&pool, ids(&["c1"]), ConversationOp::AddTag("  ".to_string())).await;
+        assert!(matches!(result, Err(ConversationError::InvalidInput(_))));
+    }
+
+    #[tokio::test]
+    async fn test_update_conversation_persists_model() {
+        let pool = crate::db::test_pool().await;
+        let set_model = |model: &str| UpdateConversation {
+            title: None,
+            messages_json: None,
+            summary: None,
+            exclude_from_memory: None,
+            model: Some(model.to_string()),
+        };
+
+        // Upserting a new conversation keeps the model too
+        let created = update_conversation(&pool, "c1", set_model("claude-3-5-haiku-20241022")).await.unwrap();
+        assert_eq!(created.model.as_deref(), Some("claude-3-5-haiku-20241022"));
+
+        let err = update_conversation(&pool, "c1", set_model("gpt-4o")).await.unwrap_err();
+        assert!(matches!(err, ConversationError::InvalidInput(_)));
+        assert_eq!(
+            get_conversation(&pool, "c1").await.unwrap().model.as_deref(),
+            Some("claude-3-5-haiku-20241022")
+        );
+
+        let reset = update_conversation(&pool, "c1", set_model("")).await.unwrap();
+        assert_eq!(reset.model, None);
+    }
+
+    async fn seed_dated(pool: &DbPool, id: &str, updated_at: &str) {
+        seed_conversation(pool, id, "notes").await;
+        sqlx::query("UPDATE conversations SET updated_at = ? WHERE id = ?")
+            .bind(updated_at)
+            .bind(id)
+            .execute(pool)
+            .await
+            .unwrap();
+    }
+
+    fn listed_ids(items: &[ConversationListItem]) -> Vec<&str> {
+        items.iter().map(|c| c.id.as_str()).collect()
+    }
+
+    #[tokio::test]
+    async fn test_pinned_conversations_list_first() {
+        let pool = crate::db::test_pool().await;
+        seed_dated(&pool, "old", "2024-01-01 09:00:00").await;
+        seed_dated(&pool, "mid", "2024-02-01 09:00:00").await;
+        seed_dated(&pool, "new", "2024-03-01 09:00:00").await;
+
+        set_conversation_pinned(&pool, "old", true).await.unwrap();
+        // Pinning twice is harmless
+        set_conversation_pinned(&pool, "old", true).await.unwrap();
+
+        let listed = list_conversations(&pool, 50, 0, false).await.unwrap();
+        assert_eq!(listed_ids(&listed), vec!["old", "new", "mid"]);
+        assert!(listed[0].pinned && !listed[1].pinned);
+        // Pinning doesn't touch updated_at
+        assert_eq!(listed[0].updated_at, "2024-01-01 09:00:00");
+
+        set_conversation_pinned(&pool, "old", false).await.unwrap();
+        let listed = list_conversations(&pool, 50, 0, false).await.unwrap();
+        assert_eq!(listed_ids(&listed), vec!["new", "mid", "old"]);
+
+        assert!(matches!(
+            set_conversation_pinned(&pool, "missing", true).await,
+            Err(ConversationError::NotFound(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_archived_conversations_excluded_by_default() {
+        let pool = crate::db::test_pool().await;
+        seed_dated(&pool, "kept", "2024-01-01 09:00:00").await;
+        seed_dated(&pool, "pinned_archived", "2024-02-01 09:00:00").await;
+        seed_dated(&pool, "archived", "2024-03-01 09:00:00").await;
+
+        set_conversation_pinned(&pool, "pinned_archived", true).await.unwrap();
+        set_conversation_archived(&pool, "pinned_archived", true).await.unwrap();
+        set_conversation_archived(&pool, "archived", true).await.unwrap();
+
+        let listed = list_conversations(&pool, 50, 0, false).await.unwrap();
+        assert_eq!(listed_ids(&listed), vec!["kept"]);
+
+        // With archived included, an archived pin no longer sorts first
+        let all = list_conversations(&pool, 50, 0, true).await.unwrap();
+        assert_eq!(listed_ids(&all), vec!["archived", "pinned_archived", "kept"]);
+        let item = all.iter().find(|c| c.id == "pinned_archived").unwrap();
+        assert!(item.pinned && item.archived);
+    }
+
+    #[tokio::test]
+    async fn test_export_markdown_keeps_order_and_code_fences() {
+        let pool = crate::db::test_pool().await;
+        let answer = "Use this query:\n\n```sql\nSELECT * FROM employees\nWHERE status = 'active';\n```\n\nThat lists **active** staff.";
+        let messages = serde_json::json!([
+            { "id": "1", "role": "user", "content": "How do I list active employees?", "timestamp": "2024-05-01T10:00:00Z" },
+            { "id": "2", "role": "assistant", "content": answer, "timestamp": "2024-05-01T10:00:05Z", "persona_id": "jordan" },
+        ]);
+        create_conversation(
+            &pool,
+            CreateConversation {
+                id: "c1".to_string(),
+                title: Some("Active employee query".to_string()),
+                messages_json: Some(messages.to_string()),
+            },
+        )
+        .await
+        .unwrap();
+        sqlx::query("UPDATE conversations SET created_at = '2024-05-01 10:00:00' WHERE id = 'c1'")
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        let markdown = export_conversation_markdown(&pool, "c1").await.unwrap();
+        let expected = format!(
+            "# Active employee query\n\n_Created 2024-05-01_\n\n**You:**\n\nHow do I list active employees?\n\n**Jordan:**\n\n{}\n",
+            answer
+        );
+        assert_eq!(markdown, expected);
+
+        assert!(matches!(
+            export_conversation_markdown(&pool, "missing").await,
+            Err(ConversationError::NotFound(_))
+        ));
+    }
+
+    #[test]
+    fn test_export_markdown_tolerates_bad_messages() {
+        let mut conversation = Conversation {
+            id: "c1".to_string(),
+            title: None,
+            summary: None,
+            messages_json: "{not json".to_string(),
+            created_at: "2024-05-01 10:00:00".to_string(),
+            updated_at: "2024-05-01 10:00:00".to_string(),
+            tags: "[]".to_string(),
+            archived: false,
+            pinned: false,
+            exclude_from_memory: false,
+            model: None,
+        };
+        let markdown = render_markdown(&conversation, None);
+        assert!(markdown.starts_with("# Untitled conversation\n"));
+        assert!(markdown.ends_with(&format!("{}\n", UNREADABLE_MESSAGE)));
+
+        // One bad message and one with an unclosed fence; the default persona labels the reply
+        conversation.messages_json = r#"[{"role": "user"}, {"role": "assistant", "content": "```\nopen fence"}]"#.to_string();
+        let markdown = render_markdown(&conversation, Some("sam"));
+        assert!(markdown.contains(&format!("**You:**\n\n{}\n", UNREADABLE_MESSAGE)));
+        assert!(markdown.ends_with("**Sam:**\n\n```\nopen fence\n```\n"));
+    }
 }
@@ -0,0 +1,215 @@
+// HR Command Center - CSV Export Module
+// Shared CSV writer so every export honors the same delimiter, BOM, and
+// line-ending options (Excel in European locales expects semicolons + BOM).
+
+use serde::{Deserialize, Serialize};
+
+/// Byte-order mark that makes Excel detect UTF-8
+const UTF8_BOM: &str = "\u{FEFF}";
+
+/// Line terminator for CSV rows
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CsvLineEnding {
+    #[default]
+    Lf,
+    Crlf,
+}
+
+impl CsvLineEnding {
+    fn as_str(self) -> &'static str {
+        match self {
+            CsvLineEnding::Lf => "\n",
+            CsvLineEnding::Crlf => "\r\n",
+        }
+    }
+}
+
+/// Formatting options for CSV exports
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CsvExportOptions {
+    /// Field separator: ',' (default), ';', or '\t' are typical
+    pub delimiter: char,
+    /// Prefix the output with a UTF-8 byte-order mark
+    pub include_bom: bool,
+    pub line_ending: CsvLineEnding,
+}
+
+impl Default for CsvExportOptions {
+    fn default() -> Self {
+        Self {
+            delimiter: ',',
+            include_bom: false,
+            line_ending: CsvLineEnding::Lf,
+        }
+    }
+}
+
+impl CsvExportOptions {
+    /// Reject delimiters that would make the output ambiguous
+    pub fn validate(&self) -> Result<(), String> {
+        match self.delimiter {
+            '"' | '\n' | '\r' => Err(format!("Unsupported CSV delimiter {:?}", self.delimiter)),
+            c if c.is_alphanumeric() => Err(format!("Unsupported CSV delimiter {:?}", c)),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Builds CSV text row by row using the given options
+pub struct CsvWriter {
+    options: CsvExportOptions,
+    out: String,
+}
+
+impl CsvWriter {
+    pub fn new(options: &CsvExportOptions) -> Self {
+        let out = if options.include_bom {
+            UTF8_BOM.to_string()
+        } else {
+            String::new()
+        };
+        Self {
+            options: options.clone(),
+            out,
+        }
+    }
+
+    /// Append one row, quoting fields as needed for the delimiter
+    pub fn write_row<S: AsRef<str>>(&mut self, fields: &[S]) {
+        let delimiter = self.options.delimiter.to_string();
+        let row = fields
+            .iter()
+            .map(|f| escape_field(f.as_ref(), self.options.delimiter))
+            .collect::<Vec<_>>()
+            .join(&delimiter);
+        self.out.push_str(&row);
+        self.out.push_str(self.options.line_ending.as_str());
+    }
+
+    pub fn finish(self) -> String {
+        self.out
+    }
+}
+
+/// Escape a field for CSV output
+///
+/// Wraps in quotes if it contains the delimiter, a quote, or a newline.
+/// Doubles any internal quotes.
+pub fn escape_field(s: &str, delimiter: char) -> String {
+    let needs_quoting = s.contains(delimiter) || s.contains('"') || s.contains('\n') || s.contains('\r');
+
+    if needs_quoting {
+        format!("\"{}\"", s.replace('"', "\"\""))
+    } else {
+        s.to_string()
+    }
+}
+
+/// Characters that make a spreadsheet read a cell as a formula
+const FORMULA_PREFIXES: &[char] = &['=', '+', '-', '@', '\t', '\r'];
+
+/// Prefix a cell that a spreadsheet would evaluate as a formula with `'`, so
+/// a value like `=HYPERLINK(...)` opens as text. For CSV files meant to be
+/// opened in Excel or similar; other text passes through unchanged.
+pub fn neutralize_formula(s: &str) -> String {
+    if s.starts_with(FORMULA_PREFIXES) {
+        format!("'{}", s)
+    } else {
+        s.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_neutralize_formula() {
+        assert_eq!(neutralize_formula("=SUM(A1:A2)"), "'=SUM(A1:A2)");
+        assert_eq!(neutralize_formula("+1 555"), "'+1 555");
+        assert_eq!(neutralize_formula("-cmd"), "'-cmd");
+        assert_eq!(neutralize_formula("@import"), "'@import");
+        assert_eq!(neutralize_formula("Sales = Ops"), "Sales = Ops");
+        assert_eq!(neutralize_formula(""), "");
+    }
+
+    #[test]
+    fn test_escape_csv_simple() {
+        assert_eq!(escape_field("hello", ','), "hello");
+    }
+
+    #[test]
+    fn test_escape_csv_with_comma() {
+        assert_eq!(escape_field("hello, world", ','), "\"hello, world\"");
+    }
+
+    #[test]
+    fn test_escape_csv_with_quotes() {
+        assert_eq!(escape_field("say \"hello\"", ','), "\"say \"\"hello\"\"\"");
+    }
+
+    #[test]
+    fn test_escape_csv_with_newline() {
+        assert_eq!(escape_field("line1\nline2", ','), "\"line1\nline2\"");
+    }
+
+    #[test]
+    fn test_semicolon_delimiter_leaves_commas_unquoted() {
+        let options = CsvExportOptions {
+            delimiter: ';',
+            ..Default::default()
+        };
+        let mut writer = CsvWriter::new(&options);
+        writer.write_row(&["Chen, Sarah", "a;b", "plain"]);
+        assert_eq!(writer.finish(), "Chen, Sarah;\"a;b\";plain\n");
+    }
+
+    #[test]
+    fn test_line_endings() {
+        let mut lf = CsvWriter::new(&CsvExportOptions::default());
+        lf.write_row(&["a", "b"]);
+        lf.write_row(&["c", "d"]);
+        assert_eq!(lf.finish(), "a,b\nc,d\n");
+
+        let mut crlf = CsvWriter::new(&CsvExportOptions {
+            line_ending: CsvLineEnding::Crlf,
+            ..Default::default()
+        });
+        crlf.write_row(&["a", "b"]);
+        crlf.write_row(&["c", "d"]);
+        assert_eq!(crlf.finish(), "a,b\r\nc,d\r\n");
+    }
+
+    #[test]
+    fn test_bom_and_tab_delimiter() {
+        let mut writer = CsvWriter::new(&CsvExportOptions {
+            delimiter: '\t',
+            include_bom: true,
+            ..Default::default()
+        });
+        writer.write_row(&["a b", "c\td"]);
+        assert_eq!(writer.finish(), "\u{FEFF}a b\t\"c\td\"\n");
+    }
+
+    #[test]
+    fn test_validate_rejects_ambiguous_delimiters() {
+        for bad in ['"', '\n', 'x', '1'] {
+            let options = CsvExportOptions {
+                delimiter: bad,
+                ..Default::default()
+            };
+            assert!(options.validate().is_err(), "{:?}", bad);
+        }
+        assert!(CsvExportOptions::default().validate().is_ok());
+    }
+
+    #[test]
+    fn test_options_deserialize_with_defaults() {
+        let options: CsvExportOptions = serde_json::from_str(r#"{"delimiter": ";"}"#).unwrap();
+        assert_eq!(options.delimiter, ';');
+        assert!(!options.include_bom);
+        assert_eq!(options.line_ending, CsvLineEnding::Lf);
+    }
+}
@@ -1,9 +1,11 @@
 // HR Command Center - Database Module
 // SQLite connection management and migrations
 
-use sqlx::{sqlite::SqlitePoolOptions, Pool, Sqlite};
+use serde::Serialize;
+use sqlx::{sqlite::SqlitePoolOptions, Pool, Row, Sqlite};
 use std::fs;
 use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
 use tauri::{AppHandle, Manager};
 use thiserror::Error;
 
@@ -57,6 +59,22 @@ async fn run_migrations(pool: &DbPool) -> DbResult<()> {
         include_str!("../migrations/002_performance_enps.sql"),
         include_str!("../migrations/003_review_highlights.sql"),
         include_str!("../migrations/004_insight_canvas.sql"),
+        include_str!("../migrations/005_extraction_jobs.sql"),
+        include_str!("../migrations/006_legal_hold.sql"),
+        include_str!("../migrations/007_original_full_name.sql"),
+        include_str!("../migrations/008_conversation_tags.sql"),
+        include_str!("../migrations/009_conversation_memory_exclusion.sql"),
+        include_str!("../migrations/010_review_cycle_events.sql"),
+        include_str!("../migrations/011_department_changes.sql"),
+        include_str!("../migrations/012_audit_verification.sql"),
+        include_str!("../migrations/013_conversation_model.sql"),
+        include_str!("../migrations/014_audit_token_usage.sql"),
+        include_str!("../migrations/015_summary_embeddings.sql"),
+        include_str!("../migrations/016_multi_company.sql"),
+        include_str!("../migrations/017_conversation_pins.sql"),
+        include_str!("../migrations/018_review_templates.sql"),
+        include_str!("../migrations/019_proxy_password_storage.sql"),
+        include_str!("../migrations/020_audit_request_status.sql"),
     ];
 
     for migration_sql in migrations {
@@ -134,15 +152,262 @@ async fn run_migration_sql(pool: &DbPool, migration_sql: &str) -> DbResult<()> {
     Ok(())
 }
 
+// ============================================================================
+// Schema Drift Detection
+// ============================================================================
+
+/// Columns the code reads from each table, as created by the migrations above.
+/// Keep in sync when a migration adds a column.
+const EXPECTED_SCHEMA: &[(&str, &[&str])] = &[
+    ("employees", &[
+        "id", "email", "full_name", "department", "job_title", "manager_id",
+        "hire_date", "work_state", "status", "extra_fields", "created_at", "updated_at",
+        "date_of_birth", "gender", "ethnicity", "termination_date", "termination_reason",
+        "legal_hold", "original_full_name", "company_id",
+    ]),
+    ("conversations", &[
+        "id", "title", "summary", "messages_json", "created_at", "updated_at", "tags", "archived",
+        "exclude_from_memory", "model", "summary_embedding", "pinned",
+    ]),
+    ("company", &["id", "name", "state", "industry", "created_at"]),
+    ("settings", &["key", "value", "updated_at"]),
+    ("audit_log", &[
+        "id", "conversation_id", "request_redacted", "response_text", "context_used", "created_at",
+        "context_snapshot", "verification_status", "verified_at", "input_tokens", "output_tokens",
+    ]),
+    ("review_cycles", &[
+        "id", "name", "cycle_type", "start_date", "end_date", "status", "created_at", "template_id",
+    ]),
+    ("review_templates", &["id", "name", "description", "sections", "created_at"]),
+    ("review_cycle_events", &["id", "cycle_id", "old_status", "new_status", "created_at"]),
+    ("department_changes", &["id", "employee_id", "from_department", "to_department", "changed_at"]),
+    ("performance_ratings", &[
+        "id", "employee_id", "review_cycle_id", "overall_rating", "goals_rating",
+        "competencies_rating", "reviewer_id", "rating_date", "created_at", "updated_at",
+    ]),
+    ("performance_reviews", &[
+        "id", "employee_id", "review_cycle_id", "strengths", "areas_for_improvement",
+        "accomplishments", "goals_next_period", "manager_comments", "self_assessment",
+        "reviewer_id", "review_date", "created_at", "updated_at",
+    ]),
+    ("enps_responses", &[
+        "id", "employee_id", "score", "survey_date", "survey_name", "feedback_text", "created_at",
+    ]),
+    ("review_highlights", &[
+        "id", "review_id", "employee_id", "review_cycle_id", "strengths", "opportunities",
+        "themes", "quotes", "overall_sentiment", "extraction_model", "extraction_version",
+        "token_count", "created_at", "updated_at",
+    ]),
+    ("employee_summaries", &[
+        "id", "employee_id", "career_narrative", "key_strengths", "development_areas",
+        "notable_accomplishments", "reviews_analyzed", "last_review_date", "generation_model",
+        "created_at", "updated_at",
+    ]),
+    ("insight_boards", &["id", "name", "description", "layout", "created_at", "updated_at"]),
+    ("pinned_charts", &[
+        "id", "board_id", "chart_data", "analytics_request", "title", "position_x",
+        "position_y", "width", "height", "conversation_id", "message_id", "pinned_at", "updated_at",
+    ]),
+    ("chart_annotations", &[
+        "id", "chart_id", "content", "annotation_type", "position_x", "position_y",
+        "created_at", "updated_at",
+    ]),
+    ("extraction_jobs", &["id", "processed_review_ids", "total", "created_at", "updated_at"]),
+];
+
+/// A column the code expects but the database doesn't have
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct MissingColumn {
+    pub table: String,
+    pub column: String,
+    /// The whole table is absent (every expected column is reported)
+    pub table_missing: bool,
+}
+
+/// Compare each table's actual columns (via PRAGMA table_info) against the
+/// columns the code selects, so a partially-upgraded database is caught
+/// before a query fails at runtime. An empty result means no drift.
+pub async fn check_schema_columns(pool: &DbPool) -> DbResult<Vec<MissingColumn>> {
+    let mut missing = Vec::new();
+
+    for (table, expected) in EXPECTED_SCHEMA {
+        // Table names come from the constant above, never user input
+        let rows = sqlx::query(&format!("PRAGMA table_info({})", table))
+            .fetch_all(pool)
+            .await?;
+        let actual: Vec<String> = rows.iter().map(|row| row.get::<String, _>("name")).collect();
+
+        for column in *expected {
+            if !actual.iter().any(|c| c == column) {
+                missing.push(MissingColumn {
+                    table: table.to_string(),
+                    column: column.to_string(),
+                    table_missing: actual.is_empty(),
+                });
+            }
+        }
+    }
+
+    Ok(missing)
+}
+
+// ============================================================================
+// Schema Export
+// ============================================================================
+
+/// The live database schema, for tools built on the SQLite file
+#[derive(Debug, Clone, Serialize)]
+pub struct SchemaDoc {
+    /// Ordered by table name
+    pub tables: Vec<TableDoc>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct TableDoc {
+    pub name: String,
+    /// In declaration order
+    pub columns: Vec<ColumnDoc>,
+    pub foreign_keys: Vec<ForeignKeyDoc>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ColumnDoc {
+    pub name: String,
+    /// Declared type as written in the migration (may be empty)
+    pub data_type: String,
+    /// False only for NOT NULL columns
+    pub nullable: bool,
+    pub primary_key: bool,
+    pub default_value: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ForeignKeyDoc {
+    pub column: String,
+    pub references_table: String,
+    /// None when the key references the other table's primary key implicitly
+    pub references_column: Option<String>,
+    pub on_delete: String,
+}
+
+/// Describe every table via PRAGMA table_info and PRAGMA foreign_key_list.
+/// Full-text search tables and their internal shadow tables are left out.
+pub async fn export_schema(pool: &DbPool) -> DbResult<SchemaDoc> {
+    let rows: Vec<(String, Option<String>)> = sqlx::query_as(
+        "SELECT name, sql FROM sqlite_master WHERE type = 'table' AND name NOT LIKE 'sqlite_%' ORDER BY name",
+    )
+    .fetch_all(pool)
+    .await?;
+
+    let virtual_tables: Vec<&str> = rows
+        .iter()
+        .filter(|(_, sql)| sql.as_deref().is_some_and(|s| s.to_uppercase().starts_with("CREATE VIRTUAL")))
+        .map(|(name, _)| name.as_str())
+        .collect();
+
+    let mut tables = Vec::new();
+    for (name, _) in &rows {
+        let is_internal = virtual_tables
+            .iter()
+            .any(|vt| name == vt || name.starts_with(&format!("{}_", vt)));
+        if is_internal {
+            continue;
+        }
+
+        // Table names come from sqlite_master; quote them anyway
+        let quoted = name.replace('"', "\"\"");
+        let columns = sqlx::query(&format!("PRAGMA table_info(\"{}\")", quoted))
+            .fetch_all(pool)
+            .await?
+            .iter()
+            .map(|row| ColumnDoc {
+                name: row.get("name"),
+                data_type: row.get("type"),
+                nullable: row.get::<i64, _>("notnull") == 0,
+                primary_key: row.get::<i64, _>("pk") > 0,
+                default_value: row.get("dflt_value"),
+            })
+            .collect();
+        let foreign_keys = sqlx::query(&format!("PRAGMA foreign_key_list(\"{}\")", quoted))
+            .fetch_all(pool)
+            .await?
+            .iter()
+            .map(|row| ForeignKeyDoc {
+                column: row.get("from"),
+                references_table: row.get("table"),
+                references_column: row.get("to"),
+                on_delete: row.get("on_delete"),
+            })
+            .collect();
+
+        tables.push(TableDoc {
+            name: name.clone(),
+            columns,
+            foreign_keys,
+        });
+    }
+
+    Ok(SchemaDoc { tables })
+}
+
+/// Shown when a write command is refused in read-only mode
+pub const READ_ONLY_MESSAGE: &str = "Read-only mode is on. Turn it off to make changes.";
+
+/// A write was attempted while read-only mode is on. Each module's error
+/// type converts this into its own `ReadOnly` variant.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ReadOnly;
+
 /// Database state managed by Tauri
 pub struct Database {
     pub pool: DbPool,
+    /// Demo mode: write commands refuse to run while set. Not persisted, so
+    /// a restart always comes back writable.
+    read_only: AtomicBool,
 }
 
 impl Database {
     pub fn new(pool: DbPool) -> Self {
-        Self { pool }
+        Self {
+            pool,
+            read_only: AtomicBool::new(false),
+        }
     }
+
+    pub fn is_read_only(&self) -> bool {
+        self.read_only.load(Ordering::SeqCst)
+    }
+
+    pub fn set_read_only(&self, enabled: bool) {
+        self.read_only.store(enabled, Ordering::SeqCst);
+    }
+
+    /// Guard for write commands, checked before anything touches the pool;
+    /// `?` turns the error into the command's own `ReadOnly` variant
+    pub fn ensure_writable(&self) -> Result<(), ReadOnly> {
+        if self.is_read_only() {
+            Err(ReadOnly)
+        } else {
+            Ok(())
+        }
+    }
+}
+
+/// In-memory database with all migrations applied, for tests that need real queries
+#[cfg(test)]
+pub async fn test_pool() -> DbPool {
+    // A single connection that never expires, since each connection to
+    // sqlite::memory: gets its own private database
+    let pool = SqlitePoolOptions::new()
+        .max_connections(1)
+        .idle_timeout(None)
+        .max_lifetime(None)
+        .connect("sqlite::memory:")
+        .await
+        .expect("Failed to open in-memory database");
+
+    run_migrations(&pool).await.expect("Failed to run migrations");
+    pool
 }
 
 #[cfg(test)]
@@ -162,4 +427,133 @@ mod tests {
         assert!(sql.contains("audit_log"));
         assert!(sql.contains("conversations_fts"));
     }
+
+    #[tokio::test]
+    async fn test_multi_company_migration_assigns_default_company() {
+        let pool = test_pool().await;
+        // Roll back to the pre-016 schema with an employee already present
+        sqlx::query("DROP INDEX idx_employees_company").execute(&pool).await.unwrap();
+        sqlx::query("ALTER TABLE employees DROP COLUMN company_id").execute(&pool).await.unwrap();
+        sqlx::query("INSERT INTO employees (id, email, full_name) VALUES ('e1', 'e1@example.com', 'Existing Person')")
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        run_migrations(&pool).await.unwrap();
+        let company_id: String = sqlx::query_scalar("SELECT company_id FROM employees WHERE id = 'e1'")
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+        assert_eq!(company_id, "default");
+        assert!(check_schema_columns(&pool).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_check_schema_columns_clean_database() {
+        let pool = test_pool().await;
+        let missing = check_schema_columns(&pool).await.unwrap();
+        assert!(missing.is_empty(), "unexpected drift: {:?}", missing);
+    }
+
+    #[tokio::test]
+    async fn test_check_schema_columns_reports_missing_column() {
+        let pool = test_pool().await;
+        sqlx::query("ALTER TABLE employees DROP COLUMN termination_reason")
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        let missing = check_schema_columns(&pool).await.unwrap();
+        assert_eq!(
+            missing,
+            vec![MissingColumn {
+                table: "employees".to_string(),
+                column: "termination_reason".to_string(),
+                table_missing: false,
+            }]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_check_schema_columns_reports_missing_table() {
+        let pool = test_pool().await;
+        sqlx::query("DROP TABLE extraction_jobs").execute(&pool).await.unwrap();
+
+        let missing = check_schema_columns(&pool).await.unwrap();
+        assert_eq!(missing.len(), 5);
+        assert!(missing.iter().all(|m| m.table == "extraction_jobs" && m.table_missing));
+    }
+
+    #[tokio::test]
+    async fn test_read_only_blocks_writes_but_not_reads() {
+        use crate::employees::{self, CreateEmployee, EmployeeError};
+
+        // The same shape as a guarded write command wrapper
+        async fn guarded_create(db: &Database, email: &str) -> Result<employees::Employee, EmployeeError> {
+            db.ensure_writable()?;
+            employees::create_employee(
+                &db.pool,
+                CreateEmployee {
+                    email: email.to_string(),
+                    full_name: "Demo Person".to_string(),
+                    department: None,
+                    job_title: None,
+                    manager_id: None,
+                    hire_date: None,
+                    work_state: None,
+                    status: None,
+                    date_of_birth: None,
+                    gender: None,
+                    ethnicity: None,
+                    termination_date: None,
+                    termination_reason: None,
+                    extra_fields: None,
+                    company_id: None,
+                },
+            )
+            .await
+        }
+
+        let db = Database::new(test_pool().await);
+        assert!(guarded_create(&db, "before@example.com").await.is_ok());
+
+        db.set_read_only(true);
+        let blocked = guarded_create(&db, "during@example.com").await;
+        assert!(matches!(blocked, Err(EmployeeError::ReadOnly)));
+        assert_eq!(blocked.unwrap_err().to_string(), READ_ONLY_MESSAGE);
+        assert!(employees::get_employee_by_email(&db.pool, "during@example.com").await.unwrap().is_none());
+
+        // Reads go straight to the pool and are unaffected
+        assert!(employees::get_employee_by_email(&db.pool, "before@example.com").await.unwrap().is_some());
+
+        db.set_read_only(false);
+        assert!(guarded_create(&db, "after@example.com").await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_export_schema_describes_tables_and_foreign_keys() {
+        let pool = test_pool().await;
+        let schema = export_schema(&pool).await.unwrap();
+
+        let employees = schema.tables.iter().find(|t| t.name == "employees").unwrap();
+        let expected = EXPECTED_SCHEMA.iter().find(|(t, _)| *t == "employees").unwrap().1;
+        for column in expected {
+            assert!(employees.columns.iter().any(|c| c.name == *column), "missing {}", column);
+        }
+        let id = employees.columns.iter().find(|c| c.name == "id").unwrap();
+        assert!(id.primary_key);
+        let email = employees.columns.iter().find(|c| c.name == "email").unwrap();
+        assert!(!email.nullable);
+        assert_eq!(email.data_type, "TEXT");
+
+        let ratings = schema.tables.iter().find(|t| t.name == "performance_ratings").unwrap();
+        let fk = ratings.foreign_keys.iter().find(|f| f.column == "employee_id").unwrap();
+        assert_eq!(fk.references_table, "employees");
+        assert_eq!(fk.references_column.as_deref(), Some("id"));
+
+        // FTS internals are not part of the data model
+        assert!(!schema.tables.iter().any(|t| t.name.starts_with("conversations_fts")));
+        let json = serde_json::to_string(&schema).unwrap();
+        assert!(json.contains("\"foreign_keys\""));
+    }
 }
@@ -6,7 +6,12 @@ use sqlx::{FromRow, Row};
 use thiserror::Error;
 use uuid::Uuid;
 
+use crate::company;
 use crate::db::DbPool;
+use crate::settings;
+
+/// Settings key controlling how imported names are interpreted
+pub const NAME_FORMAT_SETTING: &str = "name_format";
 
 // ============================================================================
 // Error Types
@@ -22,6 +27,8 @@ pub enum EmployeeError {
     DuplicateEmail(String),
     #[error("Validation error: {0}")]
     Validation(String),
+    #[error("{}", crate::db::READ_ONLY_MESSAGE)]
+    ReadOnly,
 }
 
 impl From<sqlx::Error> for EmployeeError {
@@ -35,6 +42,12 @@ impl From<sqlx::Error> for EmployeeError {
     }
 }
 
+impl From<crate::db::ReadOnly> for EmployeeError {
+    fn from(_: crate::db::ReadOnly) -> Self {
+        EmployeeError::ReadOnly
+    }
+}
+
 // ============================================================================
 // Employee Struct
 // ============================================================================
@@ -44,6 +57,8 @@ pub struct Employee {
     pub id: String,
     pub email: String,
     pub full_name: String,
+    /// Name exactly as it appeared in the import file, before canonicalizing
+    pub original_full_name: Option<String>,
     pub department: Option<String>,
     pub job_title: Option<String>,
     pub manager_id: Option<String>,
@@ -63,6 +78,12 @@ pub struct Employee {
     // Flexibility
     pub extra_fields: Option<String>, // JSON string
 
+    // Litigation / investigation hold: warn in AI context, skip from bulk exports
+    pub legal_hold: bool,
+
+    // Legal entity (company row) the employee belongs to
+    pub company_id: Option<String>,
+
     // Timestamps
     pub created_at: String,
     pub updated_at: String,
@@ -93,9 +114,13 @@ pub struct CreateEmployee {
     pub termination_reason: Option<String>,
 
     pub extra_fields: Option<String>,
+
+    /// Defaults to the primary company
+    #[serde(default)]
+    pub company_id: Option<String>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct UpdateEmployee {
     pub email: Option<String>,
     pub full_name: Option<String>,
@@ -116,6 +141,9 @@ pub struct UpdateEmployee {
     pub termination_reason: Option<String>,
 
     pub extra_fields: Option<String>,
+
+    #[serde(default)]
+    pub company_id: Option<String>,
 }
 
 // ============================================================================
@@ -128,6 +156,35 @@ pub struct EmployeeFilter {
     pub department: Option<String>,
     pub work_state: Option<String>,
     pub search: Option<String>, // Search by name or email
+    /// Only employees of this company; all companies when omitted
+    #[serde(default)]
+    pub company_id: Option<String>,
+}
+
+/// File format for roster exports
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ExportFormat {
+    Csv,
+    Xlsx,
+}
+
+/// Options controlling what goes into a roster export
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct EmployeeExportOptions {
+    /// Include employees under legal hold. Off by default so held records
+    /// only leave the app when someone deliberately asks for them.
+    #[serde(default)]
+    pub include_legal_hold: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EmployeeExport {
+    pub data: Vec<u8>,
+    pub row_count: usize,
+    /// Employees matching the filter but left out because of a legal hold
+    pub legal_hold_excluded: usize,
+    pub filename: String,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -136,6 +193,57 @@ pub struct EmployeeListResult {
     pub total: i64,
 }
 
+// ============================================================================
+// Field Validation
+// ============================================================================
+
+/// Basic shape check: one `@`, a non-empty local part, and a dotted domain
+/// with no empty labels. Non-ASCII characters are allowed.
+pub fn validate_email(email: &str) -> Result<(), EmployeeError> {
+    let invalid = || EmployeeError::Validation(format!("Invalid email '{}'", email));
+    let email = email.trim();
+    if email.chars().any(char::is_whitespace) {
+        return Err(invalid());
+    }
+    let (local, domain) = email.split_once('@').ok_or_else(invalid)?;
+    if local.is_empty() || local.starts_with('.') || local.ends_with('.') || local.contains("..") {
+        return Err(invalid());
+    }
+    let labels: Vec<&str> = domain.split('.').collect();
+    if labels.len() < 2 || labels.iter().any(|l| l.is_empty() || l.starts_with('-') || l.ends_with('-')) {
+        return Err(invalid());
+    }
+    if domain.contains('@') {
+        return Err(invalid());
+    }
+    Ok(())
+}
+
+/// Require a real calendar date written as YYYY-MM-DD
+pub fn validate_iso_date(field: &str, value: &str) -> Result<(), EmployeeError> {
+    let value = value.trim();
+    let parsed = chrono::NaiveDate::parse_from_str(value, "%Y-%m-%d");
+    if value.len() != 10 || parsed.is_err() {
+        return Err(EmployeeError::Validation(format!(
+            "Invalid {} '{}'. Expected YYYY-MM-DD",
+            field, value
+        )));
+    }
+    Ok(())
+}
+
+/// Validate the email and hire date of an incoming employee row.
+/// A missing or blank hire date is allowed.
+pub(crate) fn validate_employee_fields(email: Option<&str>, hire_date: Option<&str>) -> Result<(), EmployeeError> {
+    if let Some(email) = email {
+        validate_email(email)?;
+    }
+    if let Some(hire_date) = hire_date.filter(|d| !d.trim().is_empty()) {
+        validate_iso_date("hire_date", hire_date)?;
+    }
+    Ok(())
+}
+
 // ============================================================================
 // CRUD Operations
 // ============================================================================
@@ -152,6 +260,8 @@ pub async fn create_employee(
     if input.full_name.trim().is_empty() {
         return Err(EmployeeError::Validation("Full name is required".to_string()));
     }
+    validate_employee_fields(Some(&input.email), input.hire_date.as_deref())?;
+    let company_id = resolve_company_id(pool, input.company_id.as_deref()).await?;
 
     let id = Uuid::new_v4().to_string();
     let status = input.status.unwrap_or_else(|| "active".to_string());
@@ -169,8 +279,8 @@ pub async fn create_employee(
         INSERT INTO employees (
             id, email, full_name, department, job_title, manager_id,
             hire_date, work_state, status, date_of_birth, gender, ethnicity,
-            termination_date, termination_reason, extra_fields
-        ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+            termination_date, termination_reason, extra_fields, company_id
+        ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
         "#,
     )
     .bind(&id)
@@ -188,6 +298,7 @@ pub async fn create_employee(
     .bind(&input.termination_date)
     .bind(&input.termination_reason)
     .bind(&input.extra_fields)
+    .bind(&company_id)
     .execute(pool)
     .await?;
 
@@ -229,10 +340,13 @@ pub async fn update_employee(
     // First check if employee exists
     let existing = get_employee(pool, id).await?;
 
+    // Only fields being changed are checked, so older bad data doesn't block edits
+    validate_employee_fields(input.email.as_deref(), input.hire_date.as_deref())?;
+
     // Build dynamic update - only update fields that are provided
     let email = input.email.unwrap_or(existing.email);
     let full_name = input.full_name.unwrap_or(existing.full_name);
-    let department = input.department.or(existing.department);
+    let department = input.department.or(existing.department.clone());
     let job_title = input.job_title.or(existing.job_title);
     let manager_id = input.manager_id.or(existing.manager_id);
     let hire_date = input.hire_date.or(existing.hire_date);
@@ -244,6 +358,10 @@ pub async fn update_employee(
     let termination_date = input.termination_date.or(existing.termination_date);
     let termination_reason = input.termination_reason.or(existing.termination_reason);
     let extra_fields = input.extra_fields.or(existing.extra_fields);
+    let company_id = match input.company_id {
+        Some(company_id) => resolve_company_id(pool, Some(&company_id)).await?,
+        None => existing.company_id.unwrap_or_else(|| company::DEFAULT_COMPANY_ID.to_string()),
+    };
 
     // Validate status
     if !["active", "terminated", "leave"].contains(&status.as_str()) {
@@ -260,7 +378,7 @@ pub async fn update_employee(
             manager_id = ?, hire_date = ?, work_state = ?, status = ?,
             date_of_birth = ?, gender = ?, ethnicity = ?,
             termination_date = ?, termination_reason = ?, extra_fields = ?,
-            updated_at = datetime('now')
+            company_id = ?, updated_at = datetime('now')
         WHERE id = ?
         "#,
     )
@@ -278,14 +396,61 @@ pub async fn update_employee(
     .bind(&termination_date)
     .bind(&termination_reason)
     .bind(&extra_fields)
+    .bind(&company_id)
     .bind(id)
     .execute(pool)
     .await?;
 
+    if let Some(to) = &department {
+        if department_changed(existing.department.as_deref(), to) {
+            sqlx::query(
+                "INSERT INTO department_changes (id, employee_id, from_department, to_department) VALUES (?, ?, ?, ?)",
+            )
+            .bind(Uuid::new_v4().to_string())
+            .bind(id)
+            .bind(&existing.department)
+            .bind(to)
+            .execute(pool)
+            .await?;
+        }
+    }
+
     // Return updated employee
     get_employee(pool, id).await
 }
 
+/// The company an employee is assigned to: the primary company when none is
+/// given, otherwise an existing company profile. The primary company needs
+/// no profile row, since employees can be imported before setup.
+async fn resolve_company_id(pool: &DbPool, company_id: Option<&str>) -> Result<String, EmployeeError> {
+    let company_id = match company_id.map(str::trim) {
+        None | Some("") => return Ok(company::DEFAULT_COMPANY_ID.to_string()),
+        Some(id) => id,
+    };
+    if company_id == company::DEFAULT_COMPANY_ID {
+        return Ok(company_id.to_string());
+    }
+    let exists: Option<(String,)> = sqlx::query_as("SELECT id FROM company WHERE id = ?")
+        .bind(company_id)
+        .fetch_optional(pool)
+        .await?;
+    exists
+        .map(|(id,)| id)
+        .ok_or_else(|| EmployeeError::Validation(format!("Unknown company '{}'", company_id)))
+}
+
+/// Whether a department update is a real move (ignores case and whitespace edits)
+fn department_changed(from: Option<&str>, to: &str) -> bool {
+    let to = to.trim();
+    if to.is_empty() {
+        return false;
+    }
+    match from.map(str::trim).filter(|f| !f.is_empty()) {
+        Some(from) => !from.eq_ignore_ascii_case(to),
+        None => true,
+    }
+}
+
 /// Delete an employee
 pub async fn delete_employee(pool: &DbPool, id: &str) -> Result<(), EmployeeError> {
     let result = sqlx::query("DELETE FROM employees WHERE id = ?")
@@ -300,6 +465,32 @@ pub async fn delete_employee(pool: &DbPool, id: &str) -> Result<(), EmployeeErro
     Ok(())
 }
 
+/// Place an employee under legal hold
+pub async fn set_legal_hold(pool: &DbPool, id: &str) -> Result<Employee, EmployeeError> {
+    update_legal_hold(pool, id, true).await
+}
+
+/// Release an employee from legal hold
+pub async fn clear_legal_hold(pool: &DbPool, id: &str) -> Result<Employee, EmployeeError> {
+    update_legal_hold(pool, id, false).await
+}
+
+async fn update_legal_hold(pool: &DbPool, id: &str, hold: bool) -> Result<Employee, EmployeeError> {
+    let result = sqlx::query(
+        "UPDATE employees SET legal_hold = ?, updated_at = datetime('now') WHERE id = ?"
+    )
+    .bind(hold)
+    .bind(id)
+    .execute(pool)
+    .await?;
+
+    if result.rows_affected() == 0 {
+        return Err(EmployeeError::NotFound(id.to_string()));
+    }
+
+    get_employee(pool, id).await
+}
+
 /// List employees with optional filtering
 pub async fn list_employees(
     pool: &DbPool,
@@ -310,7 +501,32 @@ pub async fn list_employees(
     let limit = limit.unwrap_or(100);
     let offset = offset.unwrap_or(0);
 
-    // Build WHERE clause dynamically
+    let where_clause = filter_where_clause(&filter);
+
+    // Get total count
+    let count_query = format!("SELECT COUNT(*) as count FROM employees {}", where_clause);
+    let total: i64 = sqlx::query(&count_query)
+        .fetch_one(pool)
+        .await?
+        .get("count");
+
+    // Get paginated results
+    let query = format!(
+        "SELECT * FROM employees {} ORDER BY full_name ASC LIMIT ? OFFSET ?",
+        where_clause
+    );
+
+    let employees = sqlx::query_as::<_, Employee>(&query)
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(pool)
+        .await?;
+
+    Ok(EmployeeListResult { employees, total })
+}
+
+/// WHERE clause (or empty string) for an employee filter
+fn filter_where_clause(filter: &EmployeeFilter) -> String {
     let mut conditions: Vec<String> = Vec::new();
 
     if let Some(ref status) = filter.status {
@@ -322,6 +538,9 @@ pub async fn list_employees(
     if let Some(ref work_state) = filter.work_state {
         conditions.push(format!("work_state = '{}'", work_state.replace('\'', "''")));
     }
+    if let Some(ref company_id) = filter.company_id {
+        conditions.push(format!("company_id = '{}'", company_id.replace('\'', "''")));
+    }
     if let Some(ref search) = filter.search {
         let escaped = search.replace('\'', "''");
         conditions.push(format!(
@@ -330,32 +549,11 @@ pub async fn list_employees(
         ));
     }
 
-    let where_clause = if conditions.is_empty() {
+    if conditions.is_empty() {
         String::new()
     } else {
         format!("WHERE {}", conditions.join(" AND "))
-    };
-
-    // Get total count
-    let count_query = format!("SELECT COUNT(*) as count FROM employees {}", where_clause);
-    let total: i64 = sqlx::query(&count_query)
-        .fetch_one(pool)
-        .await?
-        .get("count");
-
-    // Get paginated results
-    let query = format!(
-        "SELECT * FROM employees {} ORDER BY full_name ASC LIMIT ? OFFSET ?",
-        where_clause
-    );
-
-    let employees = sqlx::query_as::<_, Employee>(&query)
-        .bind(limit)
-        .bind(offset)
-        .fetch_all(pool)
-        .await?;
-
-    Ok(EmployeeListResult { employees, total })
+    }
 }
 
 /// Get all unique departments
@@ -393,6 +591,9 @@ pub async fn get_employee_counts(pool: &DbPool) -> Result<Vec<(String, i64)>, Em
 }
 
 /// Bulk import employees (upsert by email)
+///
+/// Names are canonicalized to "First Last" according to the `name_format`
+/// setting; the raw value is kept in `original_full_name`.
 pub async fn import_employees(
     pool: &DbPool,
     employees: Vec<CreateEmployee>,
@@ -401,7 +602,12 @@ pub async fn import_employees(
     let mut updated = 0;
     let mut errors: Vec<String> = Vec::new();
 
-    for (index, input) in employees.into_iter().enumerate() {
+    let name_format = load_name_format(pool).await;
+
+    for (index, mut input) in employees.into_iter().enumerate() {
+        let raw_name = input.full_name.clone();
+        input.full_name = normalize_display_name(&raw_name, name_format);
+
         // Check if employee with this email exists
         match get_employee_by_email(pool, &input.email).await? {
             Some(existing) => {
@@ -421,16 +627,27 @@ pub async fn import_employees(
                     termination_date: input.termination_date,
                     termination_reason: input.termination_reason,
                     extra_fields: input.extra_fields,
+                    company_id: input.company_id,
                 };
                 match update_employee(pool, &existing.id, update).await {
-                    Ok(_) => updated += 1,
+                    Ok(emp) => {
+                        if let Err(e) = store_original_name(pool, &emp.id, &raw_name).await {
+                            errors.push(format!("Row {}: original name not saved: {}", index + 1, e));
+                        }
+                        updated += 1;
+                    }
                     Err(e) => errors.push(format!("Row {}: {}", index + 1, e)),
                 }
             }
             None => {
                 // Create new employee
                 match create_employee(pool, input).await {
-                    Ok(_) => created += 1,
+                    Ok(emp) => {
+                        if let Err(e) = store_original_name(pool, &emp.id, &raw_name).await {
+                            errors.push(format!("Row {}: original name not saved: {}", index + 1, e));
+                        }
+                        created += 1;
+                    }
                     Err(e) => errors.push(format!("Row {}: {}", index + 1, e)),
                 }
             }
@@ -444,9 +661,1278 @@ pub async fn import_employees(
     })
 }
 
+async fn store_original_name(pool: &DbPool, id: &str, raw_name: &str) -> Result<(), EmployeeError> {
+    sqlx::query("UPDATE employees SET original_full_name = ? WHERE id = ?")
+        .bind(raw_name.trim())
+        .bind(id)
+        .execute(pool)
+        .await?;
+    Ok(())
+}
+
+// ============================================================================
+// Roster Export
+// ============================================================================
+
+/// Export the employees matching `filter` as CSV or XLSX for offline
+/// editing. Columns follow `EMPLOYEE_COLUMN_MAPPINGS`, so the file maps
+/// cleanly when imported again; full_name is split at the first space into
+/// first_name/last_name (no separate full_name column), and the manager is
+/// given by email. Missing values
+/// are empty cells. Employees under legal hold are left out unless
+/// `options.include_legal_hold` is set (backups always keep them). Date of birth, gender
+/// and ethnicity are only exported when diversity reporting is enabled. CSV
+/// cells that a spreadsheet would run as a formula are prefixed with `'`.
+pub async fn export_employees(
+    pool: &DbPool,
+    filter: EmployeeFilter,
+    format: ExportFormat,
+    options: Option<EmployeeExportOptions>,
+) -> Result<EmployeeExport, EmployeeError> {
+    let options = options.unwrap_or_default();
+    let include_demographics = crate::settings::load_typed_setting(
+        pool,
+        crate::settings::SettingKey::DiversityReportingEnabled,
+    )
+    .await
+    .unwrap_or(false);
+    let fields: Vec<&str> = export_fields()
+        .filter(|field| include_demographics || !DEMOGRAPHIC_FIELDS.contains(field))
+        .collect();
+
+    let query = format!(
+        r#"
+        SELECT employees.*,
+               (SELECT m.email FROM employees m WHERE m.id = employees.manager_id) as manager_email
+        FROM employees {}
+        ORDER BY full_name ASC, email ASC
+        "#,
+        filter_where_clause(&filter)
+    );
+    let rows = sqlx::query(&query).fetch_all(pool).await?;
+
+    let header: Vec<String> = fields.iter().map(|f| f.to_string()).collect();
+    let mut table = vec![header];
+    let mut legal_hold_excluded = 0;
+    for row in &rows {
+        let employee = Employee::from_row(row)?;
+        if employee.legal_hold && !options.include_legal_hold {
+            legal_hold_excluded += 1;
+            continue;
+        }
+        let manager_email: Option<String> = row.get("manager_email");
+        table.push(export_row(&employee, manager_email, &fields));
+    }
+    let row_count = table.len() - 1;
+
+    let today = crate::tenure::local_today().format("%Y-%m-%d");
+    let (data, filename) = match format {
+        ExportFormat::Csv => {
+            let mut csv = crate::csv_export::CsvWriter::new(&Default::default());
+            // XLSX cells are written as inline strings and never evaluated;
+            // CSV has no types, so formula-like text is neutralized
+            for row in &table {
+                let cells: Vec<String> = row.iter().map(|c| crate::csv_export::neutralize_formula(c)).collect();
+                csv.write_row(&cells);
+            }
+            (csv.finish().into_bytes(), format!("employees_{}.csv", today))
+        }
+        ExportFormat::Xlsx => (
+            crate::xlsx_export::build_workbook("Employees", &table),
+            format!("employees_{}.xlsx", today),
+        ),
+    };
+
+    Ok(EmployeeExport {
+        data,
+        row_count,
+        legal_hold_excluded,
+        filename,
+    })
+}
+
+/// Exported only when diversity reporting is enabled
+const DEMOGRAPHIC_FIELDS: &[&str] = &["date_of_birth", "gender", "ethnicity"];
+
+fn export_fields() -> impl Iterator<Item = &'static str> {
+    crate::file_parser::EMPLOYEE_COLUMN_MAPPINGS
+        .iter()
+        .map(|(field, _)| *field)
+        .filter(|field| *field != "full_name")
+}
+
+/// One roster row with a cell per field in `fields`
+fn export_row(employee: &Employee, manager_email: Option<String>, fields: &[&str]) -> Vec<String> {
+    let (first_name, last_name) = match employee.full_name.trim().split_once(' ') {
+        Some((first, last)) => (first.to_string(), last.trim().to_string()),
+        None => (employee.full_name.trim().to_string(), String::new()),
+    };
+    let text = |value: &Option<String>| value.clone().unwrap_or_default();
+
+    fields
+        .iter()
+        .map(|field| match *field {
+            "email" => employee.email.clone(),
+            "first_name" => first_name.clone(),
+            "last_name" => last_name.clone(),
+            "department" => text(&employee.department),
+            "title" => text(&employee.job_title),
+            "hire_date" => text(&employee.hire_date),
+            "work_state" => text(&employee.work_state),
+            "manager_email" => manager_email.clone().unwrap_or_default(),
+            "status" => employee.status.clone(),
+            "date_of_birth" => text(&employee.date_of_birth),
+            "gender" => text(&employee.gender),
+            "ethnicity" => text(&employee.ethnicity),
+            _ => String::new(),
+        })
+        .collect()
+}
+
+// ============================================================================
+// Name Normalization
+// ============================================================================
+
+/// How names without a comma are ordered in the source data
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NameFormat {
+    /// "Sarah Chen"
+    #[default]
+    FirstLast,
+    /// "Chen Sarah"
+    LastFirst,
+}
+
+impl NameFormat {
+    /// Parse the stored setting value, falling back to FirstLast
+    pub fn from_setting(value: Option<&str>) -> Self {
+        match value {
+            Some("last_first") => NameFormat::LastFirst,
+            _ => NameFormat::FirstLast,
+        }
+    }
+}
+
+/// The configured name_format, or FirstLast when unset
+pub async fn load_name_format(pool: &DbPool) -> NameFormat {
+    NameFormat::from_setting(
+        settings::get_setting(pool, NAME_FORMAT_SETTING)
+            .await
+            .ok()
+            .flatten()
+            .as_deref(),
+    )
+}
+
+/// Generational suffixes that may follow a comma without signalling "Last, First"
+const NAME_SUFFIXES: &[&str] = &["jr", "jr.", "sr", "sr.", "ii", "iii", "iv", "phd", "md"];
+
+fn is_name_suffix(word: &str) -> bool {
+    NAME_SUFFIXES.contains(&word.to_lowercase().as_str())
+}
+
+/// Canonicalize a name to "First Last".
+///
+/// A comma always means "Last, First" ("Chen, Sarah" → "Sarah Chen"), except
+/// when only a suffix follows ("Martin Luther King, Jr."). Suffixes after the
+/// comma move to the end ("Smith, John Jr." and "Smith, Jr., John" → "John
+/// Smith Jr."). Without a comma, `format` decides whether the first word is
+/// the surname. Whitespace is collapsed either way.
+pub fn normalize_display_name(raw: &str, format: NameFormat) -> String {
+    let collapse = |s: &str| s.split_whitespace().collect::<Vec<_>>().join(" ");
+
+    if let Some((last, rest)) = raw.split_once(',') {
+        let last = collapse(last);
+        let (suffixes, given): (Vec<&str>, Vec<&str>) = rest
+            .split(|c: char| c == ',' || c.is_whitespace())
+            .filter(|w| !w.is_empty())
+            .partition(|w| is_name_suffix(w));
+        if last.is_empty() || (given.is_empty() && suffixes.is_empty()) {
+            return collapse(&raw.replace(',', " "));
+        }
+        if given.is_empty() {
+            return format!("{}, {}", last, suffixes.join(" "));
+        }
+        let mut name = format!("{} {}", given.join(" "), last);
+        for suffix in suffixes {
+            name.push(' ');
+            name.push_str(suffix);
+        }
+        return name;
+    }
+
+    let mut words: Vec<&str> = raw.split_whitespace().collect();
+    let suffix = match words.last() {
+        Some(word) if words.len() > 1 && is_name_suffix(word) => words.pop(),
+        _ => None,
+    };
+    let name = match format {
+        NameFormat::FirstLast => words.join(" "),
+        NameFormat::LastFirst => match words.split_first() {
+            Some((last, given)) if !given.is_empty() => format!("{} {}", given.join(" "), last),
+            _ => words.join(" "),
+        },
+    };
+    match suffix {
+        Some(suffix) => format!("{} {}", name, suffix),
+        None => name,
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ImportResult {
     pub created: i64,
     pub updated: i64,
     pub errors: Vec<String>,
 }
+
+// ============================================================================
+// Org Structure
+// ============================================================================
+
+/// Default direct-report count above which a manager's span is flagged as too wide
+pub const DEFAULT_WIDE_SPAN_THRESHOLD: i64 = 10;
+
+/// Recursive walk of the reporting hierarchy over active employees.
+/// Yields one `(ancestor_id, employee_id)` row for every manager/report pair at
+/// any depth. `UNION` (not `UNION ALL`) keeps a bad manager_id cycle from
+/// recursing forever.
+pub(crate) const ORG_CHART_CTE: &str = r#"
+    WITH RECURSIVE org_chart(ancestor_id, employee_id) AS (
+        SELECT manager_id, id FROM employees
+        WHERE manager_id IS NOT NULL AND status = 'active'
+        UNION
+        SELECT oc.ancestor_id, e.id
+        FROM org_chart oc
+        JOIN employees e ON e.manager_id = oc.employee_id
+        WHERE e.status = 'active'
+    )
+"#;
+
+/// A manager's span of control
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ManagerSpan {
+    pub manager_id: String,
+    pub full_name: String,
+    pub department: Option<String>,
+    pub direct_reports: i64,
+    /// Everyone below this manager, at any depth
+    pub total_reports: i64,
+    pub is_too_wide: bool,
+    pub is_too_narrow: bool,
+}
+
+/// Span-of-control report across the organization
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SpanOfControl {
+    pub managers: Vec<ManagerSpan>,
+    /// Active employees with no direct reports
+    pub individual_contributors: i64,
+    pub wide_threshold: i64,
+}
+
+/// Compute direct and total report counts for every active manager.
+/// Managers with more than `wide_threshold` (default 10) direct reports are
+/// flagged too wide; those with exactly one are flagged too narrow.
+pub async fn compute_span_of_control(
+    pool: &DbPool,
+    wide_threshold: Option<i64>,
+) -> Result<SpanOfControl, EmployeeError> {
+    let wide_threshold = wide_threshold.unwrap_or(DEFAULT_WIDE_SPAN_THRESHOLD);
+    if wide_threshold < 1 {
+        return Err(EmployeeError::Validation(
+            "Wide span threshold must be at least 1".to_string(),
+        ));
+    }
+
+    let query = format!(
+        r#"{}
+        SELECT m.id, m.full_name, m.department,
+            (SELECT COUNT(*) FROM employees d
+             WHERE d.manager_id = m.id AND d.status = 'active') as direct_reports,
+            COUNT(DISTINCT oc.employee_id) as total_reports
+        FROM org_chart oc
+        JOIN employees m ON m.id = oc.ancestor_id
+        WHERE m.status = 'active' AND oc.employee_id != m.id
+        GROUP BY m.id
+        HAVING direct_reports > 0
+        ORDER BY direct_reports DESC, m.full_name
+        "#,
+        ORG_CHART_CTE
+    );
+
+    let rows = sqlx::query(&query).fetch_all(pool).await?;
+
+    let managers = rows
+        .iter()
+        .map(|row| {
+            let direct_reports: i64 = row.get("direct_reports");
+            let (is_too_wide, is_too_narrow) = classify_span(direct_reports, wide_threshold);
+            ManagerSpan {
+                manager_id: row.get("id"),
+                full_name: row.get("full_name"),
+                department: row.get("department"),
+                direct_reports,
+                total_reports: row.get("total_reports"),
+                is_too_wide,
+                is_too_narrow,
+            }
+        })
+        .collect();
+
+    let individual_contributors: i64 = sqlx::query_scalar(
+        r#"
+        SELECT COUNT(*) FROM employees e
+        WHERE e.status = 'active'
+          AND NOT EXISTS (
+              SELECT 1 FROM employees r
+              WHERE r.manager_id = e.id AND r.status = 'active'
+          )
+        "#,
+    )
+    .fetch_one(pool)
+    .await?;
+
+    Ok(SpanOfControl {
+        managers,
+        individual_contributors,
+        wide_threshold,
+    })
+}
+
+/// Returns (too_wide, too_narrow) for a direct-report count
+fn classify_span(direct_reports: i64, wide_threshold: i64) -> (bool, bool) {
+    (direct_reports > wide_threshold, direct_reports == 1)
+}
+
+/// One active employee and everyone reporting to them
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct OrgNode {
+    pub id: String,
+    pub full_name: String,
+    pub job_title: Option<String>,
+    pub department: Option<String>,
+    pub reports: Vec<OrgNode>,
+}
+
+/// The reporting hierarchy of active employees
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OrgChart {
+    /// Employees with no active manager, plus one member of each cycle
+    pub roots: Vec<OrgNode>,
+    /// Reporting cycles found and broken, e.g. "Reporting cycle: A → B → A"
+    pub warnings: Vec<String>,
+}
+
+/// Build the reporting tree of active employees from manager_id.
+/// Anyone whose manager is missing or inactive becomes a root. A manager_id
+/// cycle is broken by promoting its first member (by name) to a root, and
+/// reported in `warnings`.
+pub async fn get_org_chart(pool: &DbPool) -> Result<OrgChart, EmployeeError> {
+    let employees = sqlx::query_as::<_, Employee>(
+        "SELECT * FROM employees WHERE status = 'active' ORDER BY full_name, id",
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(build_org_chart(&employees))
+}
+
+fn build_org_chart(employees: &[Employee]) -> OrgChart {
+    use std::collections::{HashMap, HashSet};
+
+    let by_id: HashMap<&str, &Employee> = employees.iter().map(|e| (e.id.as_str(), e)).collect();
+    // Only managers who are themselves active employees count
+    let managers: HashMap<&str, &str> = employees
+        .iter()
+        .filter_map(|e| {
+            let manager_id = e.manager_id.as_deref().filter(|m| by_id.contains_key(m))?;
+            Some((e.id.as_str(), manager_id))
+        })
+        .collect();
+    let manager_of = |e: &Employee| managers.get(e.id.as_str()).copied();
+
+    // Employees arrive sorted by name, so children and roots stay sorted
+    let mut children: HashMap<&str, Vec<&Employee>> = HashMap::new();
+    let mut root_ids: Vec<&str> = Vec::new();
+    for employee in employees {
+        match manager_of(employee) {
+            Some(manager_id) => children.entry(manager_id).or_default().push(employee),
+            None => root_ids.push(&employee.id),
+        }
+    }
+
+    let mut reached: HashSet<&str> = HashSet::new();
+    let mut pending: Vec<&str> = root_ids.clone();
+    while let Some(id) = pending.pop() {
+        if reached.insert(id) {
+            pending.extend(children.get(id).into_iter().flatten().map(|e| e.id.as_str()));
+        }
+    }
+
+    // Whatever is unreached sits in or below a cycle. Walk up from the first
+    // unreached employee until an id repeats; that loop is the cycle.
+    let mut warnings = Vec::new();
+    let mut cycle_roots: HashSet<&str> = HashSet::new();
+    while let Some(start) = employees.iter().find(|e| !reached.contains(e.id.as_str())) {
+        let mut path: Vec<&Employee> = Vec::new();
+        let mut current = start;
+        while !path.iter().any(|e| e.id == current.id) {
+            path.push(current);
+            match manager_of(current).and_then(|m| by_id.get(m)) {
+                Some(manager) => current = manager,
+                None => break,
+            }
+        }
+        let loop_start = path.iter().position(|e| e.id == current.id).unwrap_or(0);
+        let cycle = &path[loop_start..];
+        let Some(&top) = cycle.iter().min_by(|a, b| (&a.full_name, &a.id).cmp(&(&b.full_name, &b.id))) else {
+            break;
+        };
+
+        // Order the cycle downward from the promoted member: top → its report → …
+        let mut names = vec![top.full_name.clone()];
+        let mut below = top;
+        for _ in 1..cycle.len() {
+            let Some(next) = cycle.iter().find(|e| manager_of(e) == Some(below.id.as_str())) else {
+                break;
+            };
+            names.push(next.full_name.clone());
+            below = next;
+        }
+        names.push(top.full_name.clone());
+        warnings.push(format!("Reporting cycle: {}", names.join(" → ")));
+        eprintln!("[Employees] {}; showing {} at the top level", warnings.last().unwrap(), top.full_name);
+
+        cycle_roots.insert(&top.id);
+        let mut pending = vec![top.id.as_str()];
+        while let Some(id) = pending.pop() {
+            if reached.insert(id) {
+                pending.extend(children.get(id).into_iter().flatten().map(|e| e.id.as_str()));
+            }
+        }
+    }
+
+    fn build<'a>(
+        employee: &'a Employee,
+        children: &HashMap<&str, Vec<&'a Employee>>,
+        cycle_roots: &HashSet<&str>,
+    ) -> OrgNode {
+        let reports = children
+            .get(employee.id.as_str())
+            .into_iter()
+            .flatten()
+            // A promoted cycle member is shown only at the top level
+            .filter(|e| !cycle_roots.contains(e.id.as_str()))
+            .map(|e| build(e, children, cycle_roots))
+            .collect();
+        OrgNode {
+            id: employee.id.clone(),
+            full_name: employee.full_name.clone(),
+            job_title: employee.job_title.clone(),
+            department: employee.department.clone(),
+            reports,
+        }
+    }
+
+    let roots = employees
+        .iter()
+        .filter(|e| manager_of(e).is_none() || cycle_roots.contains(e.id.as_str()))
+        .map(|e| build(e, &children, &cycle_roots))
+        .collect();
+
+    OrgChart { roots, warnings }
+}
+
+/// Active employees reporting to `manager_id`: direct reports only, or with
+/// `recursive` everyone below them at any depth. Sorted by name.
+pub async fn get_reports(
+    pool: &DbPool,
+    manager_id: &str,
+    recursive: bool,
+) -> Result<Vec<Employee>, EmployeeError> {
+    get_employee(pool, manager_id).await?;
+
+    let reports = if recursive {
+        let query = format!(
+            r#"{}
+            SELECT e.* FROM employees e
+            WHERE e.id IN (SELECT employee_id FROM org_chart WHERE ancestor_id = ?1)
+              AND e.id != ?1
+            ORDER BY e.full_name, e.id
+            "#,
+            ORG_CHART_CTE
+        );
+        sqlx::query_as::<_, Employee>(&query).bind(manager_id).fetch_all(pool).await?
+    } else {
+        sqlx::query_as::<_, Employee>(
+            "SELECT * FROM employees WHERE manager_id = ? AND status = 'active' ORDER BY full_name, id",
+        )
+        .bind(manager_id)
+        .fetch_all(pool)
+        .await?
+    };
+
+    Ok(reports)
+}
+
+// ============================================================================
+// Internal Mobility
+// ============================================================================
+
+/// Transfers between one pair of departments
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TransferPair {
+    pub from_department: String,
+    pub to_department: String,
+    pub count: i64,
+}
+
+/// Department-to-department transfers over a window
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MobilityReport {
+    /// Start of the window (YYYY-MM-DD); None means all recorded history
+    pub since: Option<String>,
+    pub total_transfers: i64,
+    /// Distinct employees with at least one transfer
+    pub employees_moved: i64,
+    /// Most frequent paths first
+    pub pairs: Vec<TransferPair>,
+}
+
+/// Summarize department transfers recorded on or after `since`. A first
+/// department assignment (from no department) isn't a transfer and is left out.
+pub async fn get_internal_mobility(
+    pool: &DbPool,
+    since: Option<String>,
+) -> Result<MobilityReport, EmployeeError> {
+    if let Some(ref date) = since {
+        chrono::NaiveDate::parse_from_str(date.trim(), "%Y-%m-%d").map_err(|_| {
+            EmployeeError::Validation(format!("Invalid since date '{}'. Expected YYYY-MM-DD", date))
+        })?;
+    }
+    let since = since.map(|d| d.trim().to_string());
+
+    let pairs: Vec<(String, String, i64)> = sqlx::query_as(
+        r#"
+        SELECT from_department, to_department, COUNT(*) as count
+        FROM department_changes
+        WHERE from_department IS NOT NULL AND from_department != ''
+          AND (? IS NULL OR date(changed_at) >= date(?))
+        GROUP BY from_department, to_department
+        ORDER BY count DESC, from_department, to_department
+        "#,
+    )
+    .bind(&since)
+    .bind(&since)
+    .fetch_all(pool)
+    .await?;
+
+    let employees_moved: i64 = sqlx::query_scalar(
+        r#"
+        SELECT COUNT(DISTINCT employee_id)
+        FROM department_changes
+        WHERE from_department IS NOT NULL AND from_department != ''
+          AND (? IS NULL OR date(changed_at) >= date(?))
+        "#,
+    )
+    .bind(&since)
+    .bind(&since)
+    .fetch_one(pool)
+    .await?;
+
+    Ok(MobilityReport {
+        since,
+        total_transfers: pairs.iter().map(|(_, _, count)| count).sum(),
+        employees_moved,
+        pairs: pairs
+            .into_iter()
+            .map(|(from_department, to_department, count)| TransferPair {
+                from_department,
+                to_department,
+                count,
+            })
+            .collect(),
+    })
+}
+
+// ============================================================================
+// Timeline
+// ============================================================================
+
+/// Kind of event on an employee's timeline, in same-day display order
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum TimelineEventType {
+    Hired,
+    Rated,
+    Reviewed,
+    EnpsResponse,
+    Terminated,
+}
+
+/// One dated event in an employee's history
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TimelineEvent {
+    pub event_type: TimelineEventType,
+    /// ISO date (YYYY-MM-DD)
+    pub date: String,
+    pub description: String,
+    /// Row id of the rating, review, or eNPS response (None for hire/termination)
+    pub source_id: Option<String>,
+}
+
+/// Assemble hire, ratings, reviews, eNPS responses, and termination into one
+/// chronological list. Ratings and reviews without their own date fall back
+/// to the cycle's end (then start) date; events with no usable date are left
+/// out. Feedback and review text are not included.
+pub async fn get_employee_timeline(
+    pool: &DbPool,
+    employee_id: &str,
+) -> Result<Vec<TimelineEvent>, EmployeeError> {
+    let employee = get_employee(pool, employee_id).await?;
+    let mut events = Vec::new();
+
+    if let Some(date) = employee.hire_date.as_deref().and_then(timeline_date) {
+        let role = match (&employee.job_title, &employee.department) {
+            (Some(title), Some(dept)) => format!(" as {} in {}", title, dept),
+            (Some(title), None) => format!(" as {}", title),
+            (None, Some(dept)) => format!(" in {}", dept),
+            (None, None) => String::new(),
+        };
+        events.push(TimelineEvent {
+            event_type: TimelineEventType::Hired,
+            date,
+            description: format!("Hired{}", role),
+            source_id: None,
+        });
+    }
+
+    let ratings: Vec<(String, f64, String, Option<String>)> = sqlx::query_as(
+        r#"
+        SELECT pr.id, pr.overall_rating, rc.name,
+               COALESCE(NULLIF(pr.rating_date, ''), NULLIF(rc.end_date, ''), rc.start_date)
+        FROM performance_ratings pr
+        JOIN review_cycles rc ON pr.review_cycle_id = rc.id
+        WHERE pr.employee_id = ?
+        "#,
+    )
+    .bind(employee_id)
+    .fetch_all(pool)
+    .await?;
+    for (id, rating, cycle, date) in ratings {
+        if let Some(date) = date.as_deref().and_then(timeline_date) {
+            events.push(TimelineEvent {
+                event_type: TimelineEventType::Rated,
+                date,
+                description: format!("Rated {:.1} in {}", rating, cycle),
+                source_id: Some(id),
+            });
+        }
+    }
+
+    let reviews: Vec<(String, String, Option<String>)> = sqlx::query_as(
+        r#"
+        SELECT r.id, rc.name,
+               COALESCE(NULLIF(r.review_date, ''), NULLIF(rc.end_date, ''), rc.start_date)
+        FROM performance_reviews r
+        JOIN review_cycles rc ON r.review_cycle_id = rc.id
+        WHERE r.employee_id = ?
+        "#,
+    )
+    .bind(employee_id)
+    .fetch_all(pool)
+    .await?;
+    for (id, cycle, date) in reviews {
+        if let Some(date) = date.as_deref().and_then(timeline_date) {
+            events.push(TimelineEvent {
+                event_type: TimelineEventType::Reviewed,
+                date,
+                description: format!("Performance review for {}", cycle),
+                source_id: Some(id),
+            });
+        }
+    }
+
+    let responses: Vec<(String, i32, String, Option<String>)> = sqlx::query_as(
+        "SELECT id, score, survey_date, survey_name FROM enps_responses WHERE employee_id = ?",
+    )
+    .bind(employee_id)
+    .fetch_all(pool)
+    .await?;
+    for (id, score, date, survey) in responses {
+        if let Some(date) = timeline_date(&date) {
+            let survey = survey.map(|s| format!(" ({})", s)).unwrap_or_default();
+            events.push(TimelineEvent {
+                event_type: TimelineEventType::EnpsResponse,
+                date,
+                description: format!("eNPS score {}{}", score, survey),
+                source_id: Some(id),
+            });
+        }
+    }
+
+    if let Some(date) = employee.termination_date.as_deref().and_then(timeline_date) {
+        let reason = employee
+            .termination_reason
+            .map(|r| format!(" ({})", r))
+            .unwrap_or_default();
+        events.push(TimelineEvent {
+            event_type: TimelineEventType::Terminated,
+            date,
+            description: format!("Terminated{}", reason),
+            source_id: None,
+        });
+    }
+
+    events.sort_by(|a, b| a.date.cmp(&b.date).then(a.event_type.cmp(&b.event_type)));
+    Ok(events)
+}
+
+/// Normalize a stored date or datetime to YYYY-MM-DD, rejecting anything else
+fn timeline_date(raw: &str) -> Option<String> {
+    let date = raw.trim().get(..10)?;
+    chrono::NaiveDate::parse_from_str(date, "%Y-%m-%d")
+        .ok()
+        .map(|_| date.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn seed(pool: &DbPool, id: &str, manager_id: Option<&str>, status: &str) {
+        sqlx::query(
+            "INSERT INTO employees (id, email, full_name, manager_id, status) VALUES (?, ?, ?, ?, ?)",
+        )
+        .bind(id)
+        .bind(format!("{}@example.com", id))
+        .bind(format!("Employee {}", id))
+        .bind(manager_id)
+        .bind(status)
+        .execute(pool)
+        .await
+        .unwrap();
+    }
+
+    fn span<'a>(report: &'a SpanOfControl, id: &str) -> &'a ManagerSpan {
+        report.managers.iter().find(|m| m.manager_id == id).unwrap()
+    }
+
+    #[test]
+    fn test_classify_span() {
+        assert_eq!(classify_span(1, 10), (false, true));
+        assert_eq!(classify_span(5, 10), (false, false));
+        assert_eq!(classify_span(10, 10), (false, false));
+        assert_eq!(classify_span(11, 10), (true, false));
+    }
+
+    #[tokio::test]
+    async fn test_compute_span_of_control() {
+        let pool = crate::db::test_pool().await;
+        // ceo → vp-a → (lead → ic-1), ic-2, ic-3
+        //     → vp-b → ic-4
+        seed(&pool, "ceo", None, "active").await;
+        seed(&pool, "vp-a", Some("ceo"), "active").await;
+        seed(&pool, "vp-b", Some("ceo"), "active").await;
+        seed(&pool, "lead", Some("vp-a"), "active").await;
+        seed(&pool, "ic-1", Some("lead"), "active").await;
+        seed(&pool, "ic-2", Some("vp-a"), "active").await;
+        seed(&pool, "ic-3", Some("vp-a"), "active").await;
+        seed(&pool, "ic-4", Some("vp-b"), "active").await;
+        // Terminated reports don't count toward anyone's span
+        seed(&pool, "gone", Some("vp-b"), "terminated").await;
+
+        let report = compute_span_of_control(&pool, Some(2)).await.unwrap();
+
+        assert_eq!(report.managers.len(), 4);
+        assert_eq!(report.individual_contributors, 4);
+        assert_eq!(report.wide_threshold, 2);
+
+        let ceo = span(&report, "ceo");
+        assert_eq!((ceo.direct_reports, ceo.total_reports), (2, 7));
+        assert!(!ceo.is_too_wide && !ceo.is_too_narrow);
+
+        let vp_a = span(&report, "vp-a");
+        assert_eq!((vp_a.direct_reports, vp_a.total_reports), (3, 4));
+        assert!(vp_a.is_too_wide);
+
+        let vp_b = span(&report, "vp-b");
+        assert_eq!((vp_b.direct_reports, vp_b.total_reports), (1, 1));
+        assert!(vp_b.is_too_narrow);
+
+        let lead = span(&report, "lead");
+        assert_eq!((lead.direct_reports, lead.total_reports), (1, 1));
+        assert!(lead.is_too_narrow);
+    }
+
+    #[tokio::test]
+    async fn test_compute_span_of_control_survives_cycle() {
+        let pool = crate::db::test_pool().await;
+        seed(&pool, "a", None, "active").await;
+        seed(&pool, "b", Some("a"), "active").await;
+        sqlx::query("UPDATE employees SET manager_id = 'b' WHERE id = 'a'")
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        let report = compute_span_of_control(&pool, None).await.unwrap();
+        assert_eq!(report.managers.len(), 2);
+        assert!(report.managers.iter().all(|m| m.total_reports == 1));
+    }
+
+    fn node_ids(nodes: &[OrgNode]) -> Vec<&str> {
+        nodes.iter().map(|n| n.id.as_str()).collect()
+    }
+
+    fn report_ids(reports: &[Employee]) -> Vec<&str> {
+        reports.iter().map(|e| e.id.as_str()).collect()
+    }
+
+    #[tokio::test]
+    async fn test_org_chart_and_reports_three_levels() {
+        let pool = crate::db::test_pool().await;
+        // a-ceo → b-vp → (c-lead → d-ic), e-ic
+        seed(&pool, "a-ceo", None, "active").await;
+        seed(&pool, "b-vp", Some("a-ceo"), "active").await;
+        seed(&pool, "c-lead", Some("b-vp"), "active").await;
+        seed(&pool, "d-ic", Some("c-lead"), "active").await;
+        seed(&pool, "e-ic", Some("b-vp"), "active").await;
+        seed(&pool, "gone", Some("b-vp"), "terminated").await;
+        // Manager left: shown at the top level
+        seed(&pool, "f-orphan", Some("gone"), "active").await;
+
+        let chart = get_org_chart(&pool).await.unwrap();
+        assert!(chart.warnings.is_empty());
+        assert_eq!(node_ids(&chart.roots), vec!["a-ceo", "f-orphan"]);
+        let vp = &chart.roots[0].reports[0];
+        assert_eq!(vp.id, "b-vp");
+        assert_eq!(node_ids(&vp.reports), vec!["c-lead", "e-ic"]);
+        assert_eq!(node_ids(&vp.reports[0].reports), vec!["d-ic"]);
+
+        let direct = get_reports(&pool, "b-vp", false).await.unwrap();
+        assert_eq!(report_ids(&direct), vec!["c-lead", "e-ic"]);
+        let all = get_reports(&pool, "a-ceo", true).await.unwrap();
+        assert_eq!(report_ids(&all), vec!["b-vp", "c-lead", "d-ic", "e-ic"]);
+        assert!(get_reports(&pool, "d-ic", true).await.unwrap().is_empty());
+        assert!(matches!(
+            get_reports(&pool, "missing", false).await,
+            Err(EmployeeError::NotFound(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_org_chart_breaks_cycle_with_warning() {
+        let pool = crate::db::test_pool().await;
+        seed(&pool, "ceo", None, "active").await;
+        // b → a → b, with c reporting into the loop
+        seed(&pool, "a", None, "active").await;
+        seed(&pool, "b", Some("a"), "active").await;
+        seed(&pool, "c", Some("b"), "active").await;
+        sqlx::query("UPDATE employees SET manager_id = 'b' WHERE id = 'a'")
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        let chart = get_org_chart(&pool).await.unwrap();
+        assert_eq!(chart.warnings, vec!["Reporting cycle: Employee a → Employee b → Employee a"]);
+        assert_eq!(node_ids(&chart.roots), vec!["a", "ceo"]);
+        let a = &chart.roots[0];
+        assert_eq!(node_ids(&a.reports), vec!["b"]);
+        assert_eq!(node_ids(&a.reports[0].reports), vec!["c"]);
+
+        let below_a = get_reports(&pool, "a", true).await.unwrap();
+        assert_eq!(report_ids(&below_a), vec!["b", "c"]);
+    }
+
+    #[tokio::test]
+    async fn test_compute_span_of_control_rejects_bad_threshold() {
+        let pool = crate::db::test_pool().await;
+        let result = compute_span_of_control(&pool, Some(0)).await;
+        assert!(matches!(result, Err(EmployeeError::Validation(_))));
+    }
+
+    fn import_row(email: &str, full_name: &str) -> CreateEmployee {
+        CreateEmployee {
+            email: email.to_string(),
+            full_name: full_name.to_string(),
+            department: None,
+            job_title: None,
+            manager_id: None,
+            hire_date: None,
+            work_state: None,
+            status: None,
+            date_of_birth: None,
+            gender: None,
+            ethnicity: None,
+            termination_date: None,
+            termination_reason: None,
+            extra_fields: None,
+            company_id: None,
+        }
+    }
+
+    #[test]
+    fn test_normalize_display_name_last_comma_first() {
+        for format in [NameFormat::FirstLast, NameFormat::LastFirst] {
+            assert_eq!(normalize_display_name("Chen, Sarah", format), "Sarah Chen");
+            assert_eq!(normalize_display_name("  Ortiz ,  Ana   Maria ", format), "Ana Maria Ortiz");
+        }
+    }
+
+    #[test]
+    fn test_normalize_display_name_first_last() {
+        assert_eq!(normalize_display_name("Sarah Chen", NameFormat::FirstLast), "Sarah Chen");
+        assert_eq!(normalize_display_name("  Sarah   Chen ", NameFormat::FirstLast), "Sarah Chen");
+        assert_eq!(normalize_display_name("Cher", NameFormat::FirstLast), "Cher");
+    }
+
+    #[test]
+    fn test_normalize_display_name_last_first_without_comma() {
+        assert_eq!(normalize_display_name("Chen Sarah", NameFormat::LastFirst), "Sarah Chen");
+        assert_eq!(normalize_display_name("Ortiz Ana Maria", NameFormat::LastFirst), "Ana Maria Ortiz");
+        assert_eq!(normalize_display_name("Cher", NameFormat::LastFirst), "Cher");
+    }
+
+    #[test]
+    fn test_normalize_display_name_keeps_suffix() {
+        assert_eq!(
+            normalize_display_name("Martin Luther King, Jr.", NameFormat::FirstLast),
+            "Martin Luther King, Jr."
+        );
+        assert_eq!(normalize_display_name("Chen,", NameFormat::FirstLast), "Chen");
+        assert_eq!(normalize_display_name("Smith, Jr.", NameFormat::FirstLast), "Smith, Jr.");
+        for format in [NameFormat::FirstLast, NameFormat::LastFirst] {
+            assert_eq!(normalize_display_name("Smith, John Jr.", format), "John Smith Jr.");
+            assert_eq!(normalize_display_name("Smith, Jr., John", format), "John Smith Jr.");
+        }
+        assert_eq!(normalize_display_name("Smith John Jr.", NameFormat::LastFirst), "John Smith Jr.");
+        assert_eq!(normalize_display_name("John Smith Jr.", NameFormat::FirstLast), "John Smith Jr.");
+    }
+
+    #[test]
+    fn test_name_format_from_setting() {
+        assert_eq!(NameFormat::from_setting(None), NameFormat::FirstLast);
+        assert_eq!(NameFormat::from_setting(Some("last_first")), NameFormat::LastFirst);
+        assert_eq!(NameFormat::from_setting(Some("unknown")), NameFormat::FirstLast);
+    }
+
+    #[test]
+    fn test_validate_email() {
+        for ok in ["sarah@example.com", "a.b+hr@sub.example.co.uk", "josé.müller@bücher.de", "小林@例え.jp"] {
+            assert!(validate_email(ok).is_ok(), "{} should be valid", ok);
+        }
+        for bad in ["not-an-email", "@example.com", "sarah@", "sarah@example", "sarah@@example.com",
+            "sa rah@example.com", "sarah@example..com", ".sarah@example.com", "sarah@-example.com"]
+        {
+            assert!(matches!(validate_email(bad), Err(EmployeeError::Validation(_))), "{} should be invalid", bad);
+        }
+    }
+
+    #[test]
+    fn test_validate_iso_date_boundaries() {
+        for ok in ["2020-01-01", "2020-12-31", "2024-02-29", "1999-02-28"] {
+            assert!(validate_iso_date("hire_date", ok).is_ok(), "{} should be valid", ok);
+        }
+        for bad in ["2020-13-45", "2023-02-29", "2021-04-31", "2020-00-10", "2020-1-5", "01/15/2020", "2020-01-15T09:00:00"] {
+            assert!(validate_iso_date("hire_date", bad).is_err(), "{} should be invalid", bad);
+        }
+        let err = validate_iso_date("hire_date", "2020-13-45").unwrap_err();
+        assert_eq!(err.to_string(), "Validation error: Invalid hire_date '2020-13-45'. Expected YYYY-MM-DD");
+    }
+
+    #[tokio::test]
+    async fn test_create_and_update_reject_bad_fields() {
+        let pool = crate::db::test_pool().await;
+        let mut row = import_row("not-an-email", "Sarah Chen");
+        assert!(matches!(create_employee(&pool, row.clone()).await, Err(EmployeeError::Validation(_))));
+
+        row.email = "sarah@example.com".to_string();
+        row.hire_date = Some("2020-13-45".to_string());
+        assert!(matches!(create_employee(&pool, row.clone()).await, Err(EmployeeError::Validation(_))));
+
+        row.hire_date = Some("2020-02-29".to_string());
+        let sarah = create_employee(&pool, row).await.unwrap();
+        let bad_update = UpdateEmployee {
+            hire_date: Some("2020-02-30".to_string()),
+            ..Default::default()
+        };
+        assert!(matches!(update_employee(&pool, &sarah.id, bad_update).await, Err(EmployeeError::Validation(_))));
+        assert_eq!(get_employee(&pool, &sarah.id).await.unwrap().hire_date.as_deref(), Some("2020-02-29"));
+    }
+
+    #[tokio::test]
+    async fn test_import_employees_reports_bad_rows() {
+        let pool = crate::db::test_pool().await;
+        let mut bad_date = import_row("ben@example.com", "Ben Ortiz");
+        bad_date.hire_date = Some("2020-13-45".to_string());
+        let rows = vec![
+            import_row("sarah@example.com", "Sarah Chen"),
+            import_row("not-an-email", "Ana Ruiz"),
+            bad_date,
+        ];
+
+        let result = import_employees(&pool, rows).await.unwrap();
+        assert_eq!(result.created, 1);
+        assert_eq!(result.errors.len(), 2);
+        assert!(result.errors[0].starts_with("Row 2: ") && result.errors[0].contains("not-an-email"));
+        assert!(result.errors[1].starts_with("Row 3: ") && result.errors[1].contains("2020-13-45"));
+    }
+
+    #[tokio::test]
+    async fn test_import_employees_canonicalizes_names() {
+        let pool = crate::db::test_pool().await;
+        settings::set_setting(&pool, NAME_FORMAT_SETTING, "last_first").await.unwrap();
+
+        let rows = vec![
+            import_row("sarah@example.com", "Chen, Sarah"),
+            import_row("ben@example.com", "Ortiz Ben"),
+        ];
+        let result = import_employees(&pool, rows).await.unwrap();
+        assert_eq!(result.created, 2);
+
+        let sarah = get_employee_by_email(&pool, "sarah@example.com").await.unwrap().unwrap();
+        assert_eq!(sarah.full_name, "Sarah Chen");
+        assert_eq!(sarah.original_full_name.as_deref(), Some("Chen, Sarah"));
+
+        let ben = get_employee_by_email(&pool, "ben@example.com").await.unwrap().unwrap();
+        assert_eq!(ben.full_name, "Ben Ortiz");
+        assert_eq!(ben.original_full_name.as_deref(), Some("Ortiz Ben"));
+    }
+
+    #[tokio::test]
+    async fn test_employee_timeline_orders_all_event_types() {
+        let pool = crate::db::test_pool().await;
+        sqlx::query(
+            r#"INSERT INTO employees (id, email, full_name, department, job_title, hire_date, status, termination_date, termination_reason)
+               VALUES ('emp', 'emp@example.com', 'Dana Reyes', 'Sales', 'AE', '2021-03-01', 'terminated', '2024-09-30', 'voluntary')"#,
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+        sqlx::query(
+            r#"INSERT INTO review_cycles (id, name, cycle_type, start_date, end_date) VALUES
+                ('c-22', '2022 Annual', 'annual', '2022-01-01', '2022-12-31'),
+                ('c-23', '2023 Annual', 'annual', '2023-01-01', '2023-12-31')"#,
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+        sqlx::query(
+            r#"INSERT INTO performance_ratings (id, employee_id, review_cycle_id, overall_rating, rating_date) VALUES
+                ('r-23', 'emp', 'c-23', 4.0, '2024-01-15'),
+                ('r-22', 'emp', 'c-22', 3.5, NULL)"#,
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+        sqlx::query(
+            "INSERT INTO performance_reviews (id, employee_id, review_cycle_id, review_date) VALUES ('rev-23', 'emp', 'c-23', '2024-01-15')",
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+        sqlx::query(
+            r#"INSERT INTO enps_responses (id, employee_id, score, survey_date, survey_name, feedback_text) VALUES
+                ('n-1', 'emp', 9, '2023-06-01', 'Mid-year pulse', 'private feedback')"#,
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        let timeline = get_employee_timeline(&pool, "emp").await.unwrap();
+        let kinds: Vec<(TimelineEventType, &str)> =
+            timeline.iter().map(|e| (e.event_type, e.date.as_str())).collect();
+        assert_eq!(
+            kinds,
+            vec![
+                (TimelineEventType::Hired, "2021-03-01"),
+                // No rating_date: falls back to the cycle end
+                (TimelineEventType::Rated, "2022-12-31"),
+                (TimelineEventType::EnpsResponse, "2023-06-01"),
+                // Same day: rating sorts before review
+                (TimelineEventType::Rated, "2024-01-15"),
+                (TimelineEventType::Reviewed, "2024-01-15"),
+                (TimelineEventType::Terminated, "2024-09-30"),
+            ]
+        );
+        assert_eq!(timeline[0].description, "Hired as AE in Sales");
+        assert_eq!(timeline[3].description, "Rated 4.0 in 2023 Annual");
+        assert_eq!(timeline[5].description, "Terminated (voluntary)");
+        assert!(timeline.iter().all(|e| !e.description.contains("private feedback")));
+    }
+
+    #[tokio::test]
+    async fn test_employee_timeline_unknown_employee() {
+        let pool = crate::db::test_pool().await;
+        assert!(matches!(
+            get_employee_timeline(&pool, "missing").await,
+            Err(EmployeeError::NotFound(_))
+        ));
+    }
+
+    async fn move_to(pool: &DbPool, id: &str, department: &str) {
+        let update = UpdateEmployee { department: Some(department.to_string()), ..Default::default() };
+        update_employee(pool, id, update).await.unwrap();
+    }
+
+    #[test]
+    fn test_department_changed() {
+        assert!(department_changed(Some("Sales"), "Marketing"));
+        assert!(department_changed(None, "Sales"));
+        assert!(!department_changed(Some("Sales"), " sales "));
+        assert!(!department_changed(Some("Sales"), ""));
+    }
+
+    #[tokio::test]
+    async fn test_department_update_records_transfer() {
+        let pool = crate::db::test_pool().await;
+        for id in ["a", "b", "c"] {
+            seed(&pool, id, None, "active").await;
+        }
+
+        // First assignment is recorded but isn't a transfer
+        move_to(&pool, "a", "Sales").await;
+        move_to(&pool, "b", "Sales").await;
+        move_to(&pool, "c", "Engineering").await;
+        assert_eq!(get_internal_mobility(&pool, None).await.unwrap().total_transfers, 0);
+
+        move_to(&pool, "a", "Marketing").await;
+        move_to(&pool, "b", "Marketing").await;
+        move_to(&pool, "c", "Product").await;
+        // Re-saving the same department is not a move
+        move_to(&pool, "c", "product").await;
+
+        let recorded: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM department_changes")
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+        assert_eq!(recorded, 6);
+
+        let report = get_internal_mobility(&pool, Some("2000-01-01".to_string())).await.unwrap();
+        assert_eq!(report.total_transfers, 3);
+        assert_eq!(report.employees_moved, 3);
+        assert_eq!(
+            report.pairs[0],
+            TransferPair {
+                from_department: "Sales".to_string(),
+                to_department: "Marketing".to_string(),
+                count: 2,
+            }
+        );
+        assert_eq!(report.pairs[1].from_department, "Engineering");
+        assert_eq!(report.pairs[1].to_department, "Product");
+
+        // A window starting in the future is empty
+        let future = get_internal_mobility(&pool, Some("2999-01-01".to_string())).await.unwrap();
+        assert!(future.pairs.is_empty());
+        assert!(get_internal_mobility(&pool, Some("last month".to_string())).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_export_employees_round_trips_through_import_mapping() {
+        let pool = crate::db::test_pool().await;
+        sqlx::query(
+            r#"INSERT INTO employees (id, email, full_name, department, job_title, manager_id, hire_date, work_state, status, gender, legal_hold) VALUES
+                ('boss', 'boss@acme.com', 'Rita Moreno', 'Exec', 'CEO', NULL, '2015-02-01', 'CA', 'active', NULL, 0),
+                ('dev', 'dev@acme.com', 'Sam de la Cruz', 'Engineering, Platform', NULL, 'boss', '2021-07-15', NULL, 'leave', 'F', 0),
+                ('solo', 'cher@acme.com', 'Cher', NULL, NULL, NULL, NULL, NULL, 'terminated', NULL, 0),
+                ('held', 'held@acme.com', 'Hal Held', 'Legal', NULL, NULL, NULL, NULL, 'active', NULL, 1)"#,
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+        crate::settings::set_setting(&pool, crate::settings::SettingKey::DiversityReportingEnabled.as_str(), "true")
+            .await
+            .unwrap();
+
+        // (email, full_name, department, title, hire_date, work_state, manager_email, status, gender)
+        type RosterRow = (String, String, String, String, String, String, String, String, String);
+        let expected: std::collections::BTreeSet<RosterRow> = [
+            ("boss@acme.com", "Rita Moreno", "Exec", "CEO", "2015-02-01", "CA", "", "active", ""),
+            ("dev@acme.com", "Sam de la Cruz", "Engineering, Platform", "", "2021-07-15", "", "boss@acme.com", "leave", "F"),
+            ("cher@acme.com", "Cher", "", "", "", "", "", "terminated", ""),
+        ]
+        .into_iter()
+        .map(|r| (r.0.into(), r.1.into(), r.2.into(), r.3.into(), r.4.into(), r.5.into(), r.6.into(), r.7.into(), r.8.into()))
+        .collect();
+
+        for format in [ExportFormat::Csv, ExportFormat::Xlsx] {
+            let export = export_employees(&pool, EmployeeFilter::default(), format, None).await.unwrap();
+            assert_eq!(export.row_count, 3);
+            assert_eq!(export.legal_hold_excluded, 1);
+            if format == ExportFormat::Csv {
+                assert!(!String::from_utf8_lossy(&export.data).contains("None"));
+            }
+
+            let parsed = crate::file_parser::parse_file(&export.data, &export.filename, &Default::default()).unwrap();
+            let mapping = crate::file_parser::map_employee_columns(&parsed.headers);
+            assert_eq!(mapping.len(), parsed.headers.len());
+
+            // Read the rows back the way the importer does
+            let imported: std::collections::BTreeSet<RosterRow> = parsed
+                .rows
+                .iter()
+                .map(|row| {
+                    let get = |field: &str| row.get(&mapping[field]).map(|v| v.trim().to_string()).unwrap_or_default();
+                    let full_name = [get("first_name"), get("last_name")]
+                        .into_iter()
+                        .filter(|s| !s.is_empty())
+                        .collect::<Vec<_>>()
+                        .join(" ");
+                    (
+                        get("email"),
+                        full_name,
+                        get("department"),
+                        get("title"),
+                        get("hire_date"),
+                        get("work_state"),
+                        get("manager_email"),
+                        get("status"),
+                        get("gender"),
+                    )
+                })
+                .collect();
+            assert_eq!(imported, expected, "{:?}", format);
+        }
+
+        let filtered = export_employees(
+            &pool,
+            EmployeeFilter { status: Some("active".to_string()), ..Default::default() },
+            ExportFormat::Csv,
+            None,
+        )
+        .await
+        .unwrap();
+        assert_eq!((filtered.row_count, filtered.legal_hold_excluded), (1, 1));
+
+        let with_held = export_employees(
+            &pool,
+            EmployeeFilter { status: Some("active".to_string()), ..Default::default() },
+            ExportFormat::Csv,
+            Some(EmployeeExportOptions { include_legal_hold: true }),
+        )
+        .await
+        .unwrap();
+        assert_eq!((with_held.row_count, with_held.legal_hold_excluded), (2, 0));
+    }
+
+    #[tokio::test]
+    async fn test_export_employees_gates_demographics_and_neutralizes_formulas() {
+        let pool = crate::db::test_pool().await;
+        sqlx::query(
+            "INSERT INTO employees (id, email, full_name, department, gender, ethnicity) VALUES ('e1', 'e1@acme.com', 'Eve Ng', '=HYPERLINK(\"http://x\")', 'F', 'Asian')",
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        // Diversity reporting is off by default
+        let export = export_employees(&pool, EmployeeFilter::default(), ExportFormat::Csv, None).await.unwrap();
+        let csv = String::from_utf8(export.data).unwrap();
+        let header = csv.lines().next().unwrap();
+        assert!(!header.contains("gender") && !header.contains("ethnicity") && !header.contains("date_of_birth"), "{}", header);
+        assert!(!csv.contains("Asian"));
+        assert!(csv.contains("\"'=HYPERLINK(\"\"http://x\"\")\""), "{}", csv);
+    }
+}
@@ -16,6 +16,8 @@ pub enum EnpsError {
     NotFound(String),
     #[error("Validation error: {0}")]
     Validation(String),
+    #[error("{}", crate::db::READ_ONLY_MESSAGE)]
+    ReadOnly,
 }
 
 impl From<sqlx::Error> for EnpsError {
@@ -24,6 +26,12 @@ impl From<sqlx::Error> for EnpsError {
     }
 }
 
+impl From<crate::db::ReadOnly> for EnpsError {
+    fn from(_: crate::db::ReadOnly) -> Self {
+        EnpsError::ReadOnly
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
 pub struct EnpsResponse {
     pub id: String,
@@ -44,6 +52,36 @@ pub struct CreateEnps {
     pub feedback_text: Option<String>,
 }
 
+/// How far past today a survey_date may be before it counts as invalid
+/// (absorbs timezone differences). Queries that pick the latest response
+/// sort invalid dates last using the same one-day allowance.
+pub const SURVEY_DATE_FUTURE_TOLERANCE_DAYS: i64 = 1;
+
+/// ORDER BY terms that put the latest trustworthy response first: rows whose
+/// `column` isn't a date or is past the tolerance sort last, then newest first
+pub fn latest_survey_order(column: &str) -> String {
+    format!(
+        "CASE WHEN date({column}) IS NULL OR date({column}) > date('now', '+{SURVEY_DATE_FUTURE_TOLERANCE_DAYS} day') THEN 1 ELSE 0 END, {column} DESC"
+    )
+}
+
+/// Parse a survey_date ("YYYY-MM-DD", optionally followed by a time)
+fn parse_survey_date(raw: &str) -> Option<chrono::NaiveDate> {
+    let date_part = raw.trim().get(..10)?;
+    chrono::NaiveDate::parse_from_str(date_part, "%Y-%m-%d").ok()
+}
+
+/// Why a survey_date can't be trusted for latest-response ordering, if it can't
+fn survey_date_problem(raw: &str, today: chrono::NaiveDate) -> Option<String> {
+    match parse_survey_date(raw) {
+        None => Some(format!("survey_date '{}' is not a YYYY-MM-DD date", raw)),
+        Some(date) if (date - today).num_days() > SURVEY_DATE_FUTURE_TOLERANCE_DAYS => {
+            Some(format!("survey_date {} is in the future", date))
+        }
+        Some(_) => None,
+    }
+}
+
 pub async fn create_enps(pool: &DbPool, input: CreateEnps) -> Result<EnpsResponse, EnpsError> {
     if input.employee_id.trim().is_empty() {
         return Err(EnpsError::Validation("employee_id is required".to_string()));
@@ -51,6 +89,9 @@ pub async fn create_enps(pool: &DbPool, input: CreateEnps) -> Result<EnpsRespons
     if input.score < 0 || input.score > 10 {
         return Err(EnpsError::Validation("score must be between 0 and 10".to_string()));
     }
+    if let Some(problem) = survey_date_problem(&input.survey_date, chrono::Utc::now().date_naive()) {
+        return Err(EnpsError::Validation(problem));
+    }
 
     let id = Uuid::new_v4().to_string();
 
@@ -78,8 +119,10 @@ pub async fn get_enps(pool: &DbPool, id: &str) -> Result<EnpsResponse, EnpsError
 }
 
 pub async fn get_enps_for_employee(pool: &DbPool, employee_id: &str) -> Result<Vec<EnpsResponse>, EnpsError> {
+    let latest_order = latest_survey_order("survey_date");
     Ok(sqlx::query_as::<_, EnpsResponse>(
-        "SELECT * FROM enps_responses WHERE employee_id = ? ORDER BY survey_date DESC"
+        &format!("SELECT * FROM enps_responses WHERE employee_id = ?
+         ORDER BY {latest_order}")
     )
     .bind(employee_id)
     .fetch_all(pool)
@@ -135,21 +178,878 @@ pub async fn calculate_enps(pool: &DbPool, survey_name: &str) -> Result<EnpsScor
     let detractors: i64 = row.get("detractors");
     let total: i64 = row.get("total");
 
-    let score = if total > 0 {
-        ((promoters as f64 / total as f64) - (detractors as f64 / total as f64)) * 100.0
-    } else {
-        0.0
-    };
+    let score = nps(promoters, detractors, total);
 
     Ok(EnpsScore { promoters, passives, detractors, total, score })
 }
 
 /// Get latest eNPS score for an employee
 pub async fn get_latest_enps(pool: &DbPool, employee_id: &str) -> Result<Option<EnpsResponse>, EnpsError> {
+    let latest_order = latest_survey_order("survey_date");
     Ok(sqlx::query_as::<_, EnpsResponse>(
-        "SELECT * FROM enps_responses WHERE employee_id = ? ORDER BY survey_date DESC LIMIT 1"
+        &format!("SELECT * FROM enps_responses WHERE employee_id = ?
+         ORDER BY {latest_order} LIMIT 1")
     )
     .bind(employee_id)
     .fetch_optional(pool)
     .await?)
 }
+
+// ============================================================================
+// eNPS Trend
+// ============================================================================
+
+/// One survey's eNPS in a trend across surveys
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct EnpsTrendPoint {
+    pub survey_name: String,
+    /// Earliest survey_date among the survey's responses
+    pub survey_date: String,
+    pub responses: i64,
+    pub promoters: i64,
+    pub passives: i64,
+    pub detractors: i64,
+    /// None when the survey has no responses
+    pub score: Option<f64>,
+    /// Change from the previous survey with a score; None for the first
+    pub delta: Option<f64>,
+}
+
+/// eNPS for every named survey, oldest first.
+///
+/// Each survey is scored on its own responses, the same way as
+/// `calculate_enps`: an employee who answered several surveys counts once in
+/// each. Responses without a survey_name belong to no survey and are skipped.
+pub async fn get_enps_trend(pool: &DbPool) -> Result<Vec<EnpsTrendPoint>, EnpsError> {
+    let rows = sqlx::query(
+        r#"SELECT survey_name,
+            MIN(survey_date) as survey_date,
+            COUNT(CASE WHEN score >= 9 THEN 1 END) as promoters,
+            COUNT(CASE WHEN score >= 7 AND score < 9 THEN 1 END) as passives,
+            COUNT(CASE WHEN score < 7 THEN 1 END) as detractors,
+            COUNT(*) as total
+           FROM enps_responses
+           WHERE survey_name IS NOT NULL AND TRIM(survey_name) != ''
+           GROUP BY survey_name
+           ORDER BY MIN(survey_date), survey_name"#,
+    )
+    .fetch_all(pool)
+    .await?;
+
+    let surveys = rows
+        .iter()
+        .map(|row| {
+            let score = EnpsScore {
+                promoters: row.get("promoters"),
+                passives: row.get("passives"),
+                detractors: row.get("detractors"),
+                total: row.get("total"),
+                score: 0.0,
+            };
+            (row.get("survey_name"), row.get("survey_date"), score)
+        })
+        .collect();
+
+    Ok(build_enps_trend(surveys))
+}
+
+/// Score each survey (already in date order) and diff it against the last scored one
+fn build_enps_trend(surveys: Vec<(String, String, EnpsScore)>) -> Vec<EnpsTrendPoint> {
+    let mut previous: Option<f64> = None;
+    surveys
+        .into_iter()
+        .map(|(survey_name, survey_date, counts)| {
+            let score = (counts.total > 0).then(|| nps(counts.promoters, counts.detractors, counts.total));
+            let delta = score.zip(previous).map(|(current, prior)| current - prior);
+            if score.is_some() {
+                previous = score;
+            }
+            EnpsTrendPoint {
+                survey_name,
+                survey_date,
+                responses: counts.total,
+                promoters: counts.promoters,
+                passives: counts.passives,
+                detractors: counts.detractors,
+                score,
+                delta,
+            }
+        })
+        .collect()
+}
+
+// ============================================================================
+// Segmented eNPS
+// ============================================================================
+
+/// Segments with fewer responses than this are suppressed by default
+pub const DEFAULT_MIN_SEGMENT_RESPONSES: i64 = 5;
+
+/// Tenure segment for employees without a usable hire_date
+const UNKNOWN_TENURE: &str = "Unknown";
+
+/// How respondents are grouped for a segmented eNPS
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SegmentDimension {
+    Department,
+    /// Bands match the tenure stats: < 1, 1-3, 3-5 and 5+ years
+    TenureBand,
+}
+
+/// eNPS within one segment. Suppressed segments carry no numbers.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EnpsSegment {
+    pub segment: String,
+    pub suppressed: bool,
+    pub enps: Option<crate::context::EnpsAggregate>,
+    /// "insufficient data" when suppressed
+    pub note: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SegmentedEnps {
+    pub dimension: SegmentDimension,
+    pub min_responses: i64,
+    pub segments: Vec<EnpsSegment>,
+}
+
+/// Company eNPS broken down by department or tenure band.
+///
+/// Uses each employee's latest response, like the company-wide aggregate.
+/// Segments with fewer than `min_responses` (default 5) responses are
+/// suppressed so small groups can't be identified. Response rates are
+/// against the segment's active headcount.
+pub async fn calculate_enps_segmented(
+    pool: &DbPool,
+    dimension: SegmentDimension,
+    min_responses: Option<i64>,
+) -> Result<SegmentedEnps, EnpsError> {
+    let min_responses = min_responses.unwrap_or(DEFAULT_MIN_SEGMENT_RESPONSES);
+    if min_responses < 1 {
+        return Err(EnpsError::Validation("min_responses must be at least 1".to_string()));
+    }
+
+    // (department, hire_date, score, is_active); score is NULL for active
+    // employees who never responded
+    let latest_order = latest_survey_order("survey_date");
+    let rows = sqlx::query_as::<_, (Option<String>, Option<String>, Option<i32>, bool)>(
+        &format!(r#"
+        WITH latest_responses AS (
+            SELECT employee_id, score,
+                   ROW_NUMBER() OVER (
+                       PARTITION BY employee_id
+                       ORDER BY {latest_order}
+                   ) as rn
+            FROM enps_responses
+        )
+        SELECT e.department, e.hire_date, r.score, e.status = 'active' as is_active
+        FROM employees e
+        LEFT JOIN latest_responses r ON r.employee_id = e.id AND r.rn = 1
+        WHERE e.status = 'active' OR r.score IS NOT NULL
+        "#),
+    )
+    .fetch_all(pool)
+    .await?;
+
+    let today = crate::tenure::local_today();
+    let labelled = rows
+        .into_iter()
+        .map(|(department, hire_date, score, is_active)| {
+            let segment = match dimension {
+                SegmentDimension::Department => match department.as_deref().map(str::trim) {
+                    Some(d) if !d.is_empty() => d.to_string(),
+                    _ => "Unassigned".to_string(),
+                },
+                SegmentDimension::TenureBand => hire_date
+                    .as_deref()
+                    .and_then(crate::tenure::parse_hire_date)
+                    .map(|hire| crate::tenure::band_label(crate::tenure::tenure_years(hire, today)).to_string())
+                    .unwrap_or_else(|| UNKNOWN_TENURE.to_string()),
+            };
+            (segment, score, is_active)
+        })
+        .collect();
+
+    Ok(SegmentedEnps {
+        dimension,
+        min_responses,
+        segments: build_segments(dimension, labelled, min_responses),
+    })
+}
+
+fn build_segments(
+    dimension: SegmentDimension,
+    rows: Vec<(String, Option<i32>, bool)>,
+    min_responses: i64,
+) -> Vec<EnpsSegment> {
+    use std::collections::BTreeMap;
+
+    #[derive(Default)]
+    struct Counts {
+        promoters: i64,
+        passives: i64,
+        detractors: i64,
+        responses: i64,
+        active: i64,
+    }
+
+    let mut by_segment: BTreeMap<String, Counts> = BTreeMap::new();
+    if dimension == SegmentDimension::TenureBand {
+        for (label, _) in crate::tenure::TENURE_BANDS {
+            by_segment.entry(label.to_string()).or_default();
+        }
+    }
+    for (segment, score, is_active) in rows {
+        let counts = by_segment.entry(segment).or_default();
+        if let Some(score) = score {
+            match score {
+                9.. => counts.promoters += 1,
+                7..=8 => counts.passives += 1,
+                _ => counts.detractors += 1,
+            }
+            counts.responses += 1;
+        }
+        if is_active {
+            counts.active += 1;
+        }
+    }
+
+    let mut segments: Vec<EnpsSegment> = by_segment
+        .into_iter()
+        .map(|(segment, counts)| {
+            let Counts { promoters, passives, detractors, responses: total, active } = counts;
+            if total < min_responses {
+                return EnpsSegment {
+                    segment,
+                    suppressed: true,
+                    enps: None,
+                    note: Some("insufficient data".to_string()),
+                };
+            }
+            let response_rate = if active > 0 { total as f64 / active as f64 * 100.0 } else { 0.0 };
+            EnpsSegment {
+                segment,
+                suppressed: false,
+                enps: Some(crate::context::EnpsAggregate {
+                    score: ((promoters - detractors) * 100 / total) as i32,
+                    promoters,
+                    passives,
+                    detractors,
+                    total_responses: total,
+                    response_rate,
+                }),
+                note: None,
+            }
+        })
+        .collect();
+
+    // Tenure bands read youngest to oldest, with Unknown last
+    if dimension == SegmentDimension::TenureBand {
+        let rank = |segment: &str| {
+            crate::tenure::TENURE_BANDS
+                .iter()
+                .position(|(label, _)| *label == segment)
+                .unwrap_or(crate::tenure::TENURE_BANDS.len())
+        };
+        segments.sort_by_key(|s| rank(&s.segment));
+    }
+    segments
+}
+
+// ============================================================================
+// Weighted eNPS
+// ============================================================================
+
+/// How department scores are combined into a weighted eNPS
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WeightBy {
+    /// Each department counts in proportion to its active headcount
+    DepartmentHeadcount,
+}
+
+/// One department's share of a weighted eNPS
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DepartmentEnps {
+    /// Respondent's current department ("Unassigned" when empty)
+    pub department: String,
+    pub respondents: i64,
+    pub active_headcount: i64,
+    pub score: f64,
+}
+
+/// eNPS for a survey, both per respondent and weighted by department.
+///
+/// `unweighted_score` is the usual eNPS: every respondent counts once, so
+/// departments with more respondents pull harder. `weighted_score` first
+/// scores each department, then averages those scores weighted by active
+/// headcount, so a large unhappy department isn't masked by small happy ones.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WeightedEnps {
+    pub survey_name: String,
+    pub weight_by: WeightBy,
+    pub total_respondents: i64,
+    /// Same as calculate_enps: %promoters - %detractors over all respondents
+    pub unweighted_score: f64,
+    /// None when no department with respondents has active employees
+    pub weighted_score: Option<f64>,
+    pub departments: Vec<DepartmentEnps>,
+}
+
+fn nps(promoters: i64, detractors: i64, total: i64) -> f64 {
+    if total > 0 {
+        ((promoters as f64 / total as f64) - (detractors as f64 / total as f64)) * 100.0
+    } else {
+        0.0
+    }
+}
+
+/// Headcount-weighted mean of department scores; departments with no
+/// active employees carry no weight
+fn headcount_weighted_score(departments: &[DepartmentEnps]) -> Option<f64> {
+    let total_weight: i64 = departments.iter().map(|d| d.active_headcount).sum();
+    if total_weight == 0 {
+        return None;
+    }
+    let weighted: f64 = departments
+        .iter()
+        .map(|d| d.score * d.active_headcount as f64)
+        .sum();
+    Some(weighted / total_weight as f64)
+}
+
+/// Calculate a survey's eNPS per department and combine the department
+/// scores by `weight_by`, alongside the unweighted score for comparison
+pub async fn calculate_weighted_enps(
+    pool: &DbPool,
+    survey_name: &str,
+    weight_by: WeightBy,
+) -> Result<WeightedEnps, EnpsError> {
+    let rows = sqlx::query(
+        r#"
+        WITH dept_responses AS (
+            SELECT COALESCE(NULLIF(TRIM(e.department), ''), 'Unassigned') as department,
+                   COUNT(CASE WHEN r.score >= 9 THEN 1 END) as promoters,
+                   COUNT(CASE WHEN r.score < 7 THEN 1 END) as detractors,
+                   COUNT(*) as respondents
+            FROM enps_responses r
+            JOIN employees e ON r.employee_id = e.id
+            WHERE r.survey_name = ?
+            GROUP BY 1
+        ),
+        dept_headcount AS (
+            SELECT COALESCE(NULLIF(TRIM(department), ''), 'Unassigned') as department,
+                   COUNT(*) as active_headcount
+            FROM employees
+            WHERE status = 'active'
+            GROUP BY 1
+        )
+        SELECT d.department, d.promoters, d.detractors, d.respondents,
+               COALESCE(h.active_headcount, 0) as active_headcount
+        FROM dept_responses d
+        LEFT JOIN dept_headcount h ON h.department = d.department
+        ORDER BY d.department
+        "#,
+    )
+    .bind(survey_name)
+    .fetch_all(pool)
+    .await?;
+
+    let mut promoters = 0;
+    let mut detractors = 0;
+    let mut total_respondents = 0;
+    let departments: Vec<DepartmentEnps> = rows
+        .iter()
+        .map(|row| {
+            let dept_promoters: i64 = row.get("promoters");
+            let dept_detractors: i64 = row.get("detractors");
+            let respondents: i64 = row.get("respondents");
+            promoters += dept_promoters;
+            detractors += dept_detractors;
+            total_respondents += respondents;
+            DepartmentEnps {
+                department: row.get("department"),
+                respondents,
+                active_headcount: row.get("active_headcount"),
+                score: nps(dept_promoters, dept_detractors, respondents),
+            }
+        })
+        .collect();
+
+    let weighted_score = match weight_by {
+        WeightBy::DepartmentHeadcount => headcount_weighted_score(&departments),
+    };
+
+    Ok(WeightedEnps {
+        survey_name: survey_name.to_string(),
+        weight_by,
+        total_respondents,
+        unweighted_score: nps(promoters, detractors, total_respondents),
+        weighted_score,
+        departments,
+    })
+}
+
+// ============================================================================
+// Survey Date Diagnostics
+// ============================================================================
+
+/// A stored response whose survey_date is malformed or in the future
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InvalidEnpsDate {
+    pub id: String,
+    pub employee_id: String,
+    pub survey_date: String,
+    pub survey_name: Option<String>,
+    pub problem: String,
+}
+
+/// List responses whose survey_date would mislead latest-response selection
+pub async fn find_invalid_enps_dates(pool: &DbPool) -> Result<Vec<InvalidEnpsDate>, EnpsError> {
+    let rows: Vec<(String, String, String, Option<String>)> = sqlx::query_as(
+        "SELECT id, employee_id, survey_date, survey_name FROM enps_responses ORDER BY employee_id, id",
+    )
+    .fetch_all(pool)
+    .await?;
+
+    let today = chrono::Utc::now().date_naive();
+    Ok(rows
+        .into_iter()
+        .filter_map(|(id, employee_id, survey_date, survey_name)| {
+            let problem = survey_date_problem(&survey_date, today)?;
+            Some(InvalidEnpsDate { id, employee_id, survey_date, survey_name, problem })
+        })
+        .collect())
+}
+
+// ============================================================================
+// Rating ↔ eNPS Correlation
+// ============================================================================
+
+/// Below this many paired employees, r is reported but flagged as unreliable
+pub const MIN_CORRELATION_SAMPLE: usize = 10;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CorrelationResult {
+    /// Pearson r in [-1, 1]; None when undefined (fewer than 2 pairs or zero variance)
+    pub coefficient: Option<f64>,
+    /// Employees with both a latest rating and a latest eNPS response
+    pub sample_size: usize,
+    pub is_significant_sample: bool,
+    pub note: Option<String>,
+}
+
+/// Correlate each employee's latest overall rating with their latest eNPS score.
+/// Employees missing either metric are excluded.
+pub async fn compute_rating_enps_correlation(pool: &DbPool) -> Result<CorrelationResult, EnpsError> {
+    let latest_order = latest_survey_order("survey_date");
+    let rows = sqlx::query(
+        &format!(r#"
+        WITH latest_ratings AS (
+            SELECT pr.employee_id, pr.overall_rating,
+                   ROW_NUMBER() OVER (PARTITION BY pr.employee_id ORDER BY rc.start_date DESC) as rn
+            FROM performance_ratings pr
+            JOIN review_cycles rc ON pr.review_cycle_id = rc.id
+        ),
+        latest_enps AS (
+            SELECT employee_id, score,
+                   ROW_NUMBER() OVER (
+                       PARTITION BY employee_id
+                       ORDER BY {latest_order}
+                   ) as rn
+            FROM enps_responses
+        )
+        SELECT lr.overall_rating, le.score
+        FROM latest_ratings lr
+        JOIN latest_enps le ON lr.employee_id = le.employee_id
+        WHERE lr.rn = 1 AND le.rn = 1
+        "#),
+    )
+    .fetch_all(pool)
+    .await?;
+
+    let pairs: Vec<(f64, f64)> = rows
+        .iter()
+        .map(|row| {
+            let rating: f64 = row.get("overall_rating");
+            let score: i32 = row.get("score");
+            (rating, score as f64)
+        })
+        .collect();
+
+    Ok(build_correlation_result(&pairs))
+}
+
+fn build_correlation_result(pairs: &[(f64, f64)]) -> CorrelationResult {
+    let sample_size = pairs.len();
+    let coefficient = pearson_correlation(pairs);
+    let is_significant_sample = sample_size >= MIN_CORRELATION_SAMPLE;
+
+    let note = if coefficient.is_none() && sample_size >= 2 {
+        Some("Correlation is undefined because one metric has no variation".to_string())
+    } else if !is_significant_sample {
+        Some(format!(
+            "Sample of {} employees is too small to be meaningful (need at least {})",
+            sample_size, MIN_CORRELATION_SAMPLE
+        ))
+    } else {
+        None
+    };
+
+    CorrelationResult { coefficient, sample_size, is_significant_sample, note }
+}
+
+/// Pearson correlation coefficient for (x, y) pairs
+fn pearson_correlation(pairs: &[(f64, f64)]) -> Option<f64> {
+    if pairs.len() < 2 {
+        return None;
+    }
+
+    let n = pairs.len() as f64;
+    let mean_x = pairs.iter().map(|(x, _)| x).sum::<f64>() / n;
+    let mean_y = pairs.iter().map(|(_, y)| y).sum::<f64>() / n;
+
+    let mut covariance = 0.0;
+    let mut var_x = 0.0;
+    let mut var_y = 0.0;
+    for (x, y) in pairs {
+        let dx = x - mean_x;
+        let dy = y - mean_y;
+        covariance += dx * dy;
+        var_x += dx * dx;
+        var_y += dy * dy;
+    }
+
+    if var_x == 0.0 || var_y == 0.0 {
+        return None;
+    }
+
+    Some((covariance / (var_x.sqrt() * var_y.sqrt())).clamp(-1.0, 1.0))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pearson_perfect_positive() {
+        let pairs: Vec<(f64, f64)> = (1..=5).map(|r| (r as f64, (r * 2) as f64)).collect();
+        let r = pearson_correlation(&pairs).unwrap();
+        assert!((r - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_pearson_perfect_negative() {
+        let pairs: Vec<(f64, f64)> = (1..=5).map(|r| (r as f64, (10 - r * 2) as f64)).collect();
+        let r = pearson_correlation(&pairs).unwrap();
+        assert!((r + 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_pearson_uncorrelated() {
+        // Symmetric around the x mean, so covariance is zero
+        let pairs = vec![(1.0, 5.0), (2.0, 7.0), (3.0, 9.0), (4.0, 7.0), (5.0, 5.0)];
+        let r = pearson_correlation(&pairs).unwrap();
+        assert!(r.abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_pearson_undefined_cases() {
+        assert!(pearson_correlation(&[]).is_none());
+        assert!(pearson_correlation(&[(3.0, 8.0)]).is_none());
+        // No variance in ratings
+        assert!(pearson_correlation(&[(3.0, 1.0), (3.0, 9.0)]).is_none());
+    }
+
+    #[test]
+    fn test_correlation_result_flags_small_sample() {
+        let pairs: Vec<(f64, f64)> = (1..=5).map(|r| (r as f64, r as f64)).collect();
+        let result = build_correlation_result(&pairs);
+        assert_eq!(result.sample_size, 5);
+        assert!(!result.is_significant_sample);
+        assert!(result.note.unwrap().contains("too small"));
+
+        let pairs: Vec<(f64, f64)> = (0..12).map(|i| ((i % 5) as f64 + 1.0, i as f64 % 11.0)).collect();
+        let result = build_correlation_result(&pairs);
+        assert!(result.is_significant_sample);
+        assert!(result.note.is_none());
+    }
+
+    fn date(s: &str) -> chrono::NaiveDate {
+        chrono::NaiveDate::parse_from_str(s, "%Y-%m-%d").unwrap()
+    }
+
+    #[test]
+    fn test_survey_date_problem() {
+        let today = date("2025-03-10");
+        assert!(survey_date_problem("2025-03-10", today).is_none());
+        assert!(survey_date_problem("2025-03-01 09:30:00", today).is_none());
+        // One day of slack for timezones
+        assert!(survey_date_problem("2025-03-11", today).is_none());
+        assert!(survey_date_problem("2025-03-12", today).unwrap().contains("future"));
+        assert!(survey_date_problem("03/01/2025", today).unwrap().contains("not a YYYY-MM-DD"));
+        assert!(survey_date_problem("2025-02-30", today).is_some());
+    }
+
+    #[test]
+    fn test_latest_survey_order_uses_tolerance() {
+        let order = latest_survey_order("er.survey_date");
+        assert!(order.contains(&format!("date('now', '+{} day')", SURVEY_DATE_FUTURE_TOLERANCE_DAYS)));
+        assert!(order.ends_with("er.survey_date DESC"));
+    }
+
+    #[tokio::test]
+    async fn test_invalid_dates_rejected_flagged_and_never_latest() {
+        let pool = crate::db::test_pool().await;
+        sqlx::query("INSERT INTO employees (id, email, full_name) VALUES ('emp-1', 'a@example.com', 'Ana')")
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        let input = |survey_date: &str| CreateEnps {
+            employee_id: "emp-1".to_string(),
+            score: 9,
+            survey_date: survey_date.to_string(),
+            survey_name: None,
+            feedback_text: None,
+        };
+        assert!(matches!(create_enps(&pool, input("2999-01-01")).await, Err(EnpsError::Validation(_))));
+        assert!(matches!(create_enps(&pool, input("soon")).await, Err(EnpsError::Validation(_))));
+        let valid = create_enps(&pool, input("2024-06-01")).await.unwrap();
+
+        // Rows that bypassed validation (older data, direct imports)
+        sqlx::query(
+            "INSERT INTO enps_responses (id, employee_id, score, survey_date) VALUES
+                ('future', 'emp-1', 2, '2999-01-01'), ('garbled', 'emp-1', 3, 'zzz')",
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        // Lexically both bad dates sort after '2024-06-01'; neither may win
+        let latest = get_latest_enps(&pool, "emp-1").await.unwrap().unwrap();
+        assert_eq!(latest.id, valid.id);
+        let all = get_enps_for_employee(&pool, "emp-1").await.unwrap();
+        assert_eq!(all[0].id, valid.id);
+
+        let aggregate = crate::context::calculate_aggregate_enps(&pool, None).await.unwrap();
+        assert_eq!(aggregate.promoters, 1);
+
+        let invalid = find_invalid_enps_dates(&pool).await.unwrap();
+        let ids: Vec<&str> = invalid.iter().map(|r| r.id.as_str()).collect();
+        assert_eq!(ids, vec!["future", "garbled"]);
+    }
+
+    #[tokio::test]
+    async fn test_weighted_enps_diverges_from_unweighted() {
+        let pool = crate::db::test_pool().await;
+        // Sales: 50 active, 2 respondents, both detractors.
+        // Design and Support: 5 active each, everyone a promoter.
+        let departments = [("Sales", 50, 2, 3), ("Design", 5, 5, 10), ("Support", 5, 5, 9)];
+        for (dept, headcount, respondents, score) in departments {
+            for i in 0..headcount {
+                let id = format!("{}-{}", dept, i);
+                sqlx::query("INSERT INTO employees (id, email, full_name, department) VALUES (?, ?, ?, ?)")
+                    .bind(&id)
+                    .bind(format!("{}@example.com", id))
+                    .bind(&id)
+                    .bind(dept)
+                    .execute(&pool)
+                    .await
+                    .unwrap();
+                if i < respondents {
+                    sqlx::query(
+                        "INSERT INTO enps_responses (id, employee_id, score, survey_date, survey_name) VALUES (?, ?, ?, '2025-01-15', 'Q1')",
+                    )
+                    .bind(format!("r-{}", id))
+                    .bind(&id)
+                    .bind(score)
+                    .execute(&pool)
+                    .await
+                    .unwrap();
+                }
+            }
+        }
+
+        let result = calculate_weighted_enps(&pool, "Q1", WeightBy::DepartmentHeadcount).await.unwrap();
+        assert_eq!(result.total_respondents, 12);
+        // 10 promoters, 2 detractors out of 12
+        assert!((result.unweighted_score - 66.666).abs() < 0.01);
+        assert_eq!(
+            result.unweighted_score,
+            calculate_enps(&pool, "Q1").await.unwrap().score
+        );
+        // (-100 * 50 + 100 * 5 + 100 * 5) / 60
+        assert!((result.weighted_score.unwrap() + 66.666).abs() < 0.01);
+
+        let sales = result.departments.iter().find(|d| d.department == "Sales").unwrap();
+        assert_eq!(sales.respondents, 2);
+        assert_eq!(sales.active_headcount, 50);
+        assert_eq!(sales.score, -100.0);
+    }
+
+    #[tokio::test]
+    async fn test_weighted_enps_empty_survey() {
+        let pool = crate::db::test_pool().await;
+        let result = calculate_weighted_enps(&pool, "none", WeightBy::DepartmentHeadcount).await.unwrap();
+        assert_eq!(result.total_respondents, 0);
+        assert_eq!(result.unweighted_score, 0.0);
+        assert!(result.weighted_score.is_none());
+        assert!(result.departments.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_enps_trend_rises_then_falls() {
+        let pool = crate::db::test_pool().await;
+        for id in ["e1", "e2", "e3", "e4"] {
+            sqlx::query("INSERT INTO employees (id, email, full_name) VALUES (?, ?, ?)")
+                .bind(id)
+                .bind(format!("{}@example.com", id))
+                .bind(id)
+                .execute(&pool)
+                .await
+                .unwrap();
+        }
+        // Same four employees answer every survey; inserted out of date order
+        let surveys = [
+            ("Q3 Pulse", "2024-07-01", [9, 3, 5, 8]),   // 1 promoter, 2 detractors: -25
+            ("Q1 Pulse", "2024-01-10", [9, 6, 4, 8]),   // 1 promoter, 2 detractors: -25
+            ("Q2 Pulse", "2024-04-02", [10, 9, 9, 6]),  // 3 promoters, 1 detractor: +50
+        ];
+        for (name, date, scores) in surveys {
+            for (employee_id, score) in ["e1", "e2", "e3", "e4"].iter().zip(scores) {
+                create_enps(
+                    &pool,
+                    CreateEnps {
+                        employee_id: employee_id.to_string(),
+                        score,
+                        survey_date: date.to_string(),
+                        survey_name: Some(name.to_string()),
+                        feedback_text: None,
+                    },
+                )
+                .await
+                .unwrap();
+            }
+        }
+
+        let trend = get_enps_trend(&pool).await.unwrap();
+        let names: Vec<&str> = trend.iter().map(|p| p.survey_name.as_str()).collect();
+        assert_eq!(names, vec!["Q1 Pulse", "Q2 Pulse", "Q3 Pulse"]);
+        assert!(trend.iter().all(|p| p.responses == 4));
+        assert_eq!(trend.iter().map(|p| p.score).collect::<Vec<_>>(), vec![Some(-25.0), Some(50.0), Some(-25.0)]);
+        assert_eq!(trend.iter().map(|p| p.delta).collect::<Vec<_>>(), vec![None, Some(75.0), Some(-75.0)]);
+
+        // Each point matches scoring that survey alone
+        let q2 = calculate_enps(&pool, "Q2 Pulse").await.unwrap();
+        assert_eq!(trend[1].score, Some(q2.score));
+    }
+
+    #[test]
+    fn test_enps_trend_skips_empty_survey_in_deltas() {
+        let counts = |promoters, passives, detractors| EnpsScore {
+            promoters,
+            passives,
+            detractors,
+            total: promoters + passives + detractors,
+            score: 0.0,
+        };
+        let trend = build_enps_trend(vec![
+            ("Jan".to_string(), "2024-01-01".to_string(), counts(2, 1, 1)),
+            ("Feb".to_string(), "2024-02-01".to_string(), counts(0, 0, 0)),
+            ("Mar".to_string(), "2024-03-01".to_string(), counts(1, 1, 2)),
+        ]);
+
+        assert_eq!(trend[0].score, Some(25.0));
+        assert_eq!(trend[1].responses, 0);
+        assert_eq!(trend[1].score, None);
+        assert_eq!(trend[1].delta, None);
+        // Compared with January, not the empty February survey
+        assert_eq!(trend[2].delta, Some(-50.0));
+    }
+
+    async fn seed_respondent(pool: &DbPool, id: &str, department: &str, hire_date: Option<&str>, score: Option<i32>) {
+        sqlx::query("INSERT INTO employees (id, email, full_name, department, hire_date) VALUES (?, ?, ?, ?, ?)")
+            .bind(id)
+            .bind(format!("{}@example.com", id))
+            .bind(id)
+            .bind(department)
+            .bind(hire_date)
+            .execute(pool)
+            .await
+            .unwrap();
+        if let Some(score) = score {
+            sqlx::query("INSERT INTO enps_responses (id, employee_id, score, survey_date, survey_name) VALUES (?, ?, ?, '2024-06-01', 'Pulse')")
+                .bind(format!("r-{}", id))
+                .bind(id)
+                .bind(score)
+                .execute(pool)
+                .await
+                .unwrap();
+        }
+    }
+
+    #[tokio::test]
+    async fn test_segmented_enps_by_department_suppresses_small_segments() {
+        let pool = crate::db::test_pool().await;
+        // Engineering: 6 respondents (4 promoters, 1 passive, 1 detractor) plus one non-respondent
+        for (i, score) in [10, 9, 9, 9, 8, 3].into_iter().enumerate() {
+            seed_respondent(&pool, &format!("eng-{}", i), "Engineering", None, Some(score)).await;
+        }
+        seed_respondent(&pool, "eng-quiet", "Engineering", None, None).await;
+        // Legal: only 2 respondents
+        seed_respondent(&pool, "legal-0", "Legal", None, Some(2)).await;
+        seed_respondent(&pool, "legal-1", "Legal", None, Some(10)).await;
+
+        let result = calculate_enps_segmented(&pool, SegmentDimension::Department, None).await.unwrap();
+        assert_eq!(result.min_responses, DEFAULT_MIN_SEGMENT_RESPONSES);
+        assert_eq!(result.segments.len(), 2);
+
+        let eng = &result.segments[0];
+        assert_eq!(eng.segment, "Engineering");
+        assert!(!eng.suppressed);
+        let enps = eng.enps.as_ref().unwrap();
+        assert_eq!((enps.promoters, enps.passives, enps.detractors, enps.total_responses), (4, 1, 1, 6));
+        assert_eq!(enps.score, 50);
+        assert!((enps.response_rate - 600.0 / 7.0).abs() < 1e-9);
+
+        let legal = &result.segments[1];
+        assert_eq!(legal.segment, "Legal");
+        assert!(legal.suppressed);
+        assert!(legal.enps.is_none());
+        assert_eq!(legal.note.as_deref(), Some("insufficient data"));
+
+        // A lower threshold reveals Legal
+        let relaxed = calculate_enps_segmented(&pool, SegmentDimension::Department, Some(2)).await.unwrap();
+        assert!(relaxed.segments.iter().all(|s| !s.suppressed));
+        assert!(matches!(
+            calculate_enps_segmented(&pool, SegmentDimension::Department, Some(0)).await,
+            Err(EnpsError::Validation(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_segmented_enps_by_tenure_puts_missing_hire_date_in_unknown() {
+        let pool = crate::db::test_pool().await;
+        let today = crate::tenure::local_today();
+        let years_ago = |years: i64| (today - chrono::Duration::days(years * 366)).format("%Y-%m-%d").to_string();
+        for i in 0..5 {
+            seed_respondent(&pool, &format!("vet-{}", i), "Ops", Some(&years_ago(6)), Some(9)).await;
+            seed_respondent(&pool, &format!("unk-{}", i), "Ops", None, Some(4)).await;
+        }
+        seed_respondent(&pool, "bad-date", "Ops", Some("2020-13-45"), Some(4)).await;
+        seed_respondent(&pool, "new-0", "Ops", Some(&today.format("%Y-%m-%d").to_string()), Some(10)).await;
+
+        let result = calculate_enps_segmented(&pool, SegmentDimension::TenureBand, None).await.unwrap();
+        let labels: Vec<&str> = result.segments.iter().map(|s| s.segment.as_str()).collect();
+        assert_eq!(labels, vec!["< 1 year", "1-3 years", "3-5 years", "5+ years", "Unknown"]);
+
+        let suppressed: Vec<bool> = result.segments.iter().map(|s| s.suppressed).collect();
+        assert_eq!(suppressed, vec![true, true, true, false, false]);
+        assert_eq!(result.segments[3].enps.as_ref().unwrap().score, 100);
+        let unknown = result.segments[4].enps.as_ref().unwrap();
+        assert_eq!(unknown.total_responses, 6);
+        assert_eq!(unknown.score, -100);
+    }
+}
@@ -2,13 +2,17 @@
 // Supports CSV, TSV, XLSX, and XLS file formats
 // Returns a consistent structure regardless of input format
 
-use calamine::{open_workbook_auto_from_rs, Data, Reader};
+use calamine::{open_workbook_auto_from_rs, Data, Reader, Xlsx};
 use csv::ReaderBuilder;
 use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
 use std::collections::HashMap;
 use std::io::Cursor;
 use thiserror::Error;
 
+use crate::db::DbPool;
+use crate::settings::{self, SettingKey};
+
 // ============================================================================
 // Types
 // ============================================================================
@@ -29,6 +33,72 @@ pub enum ParseError {
 
     #[error("No headers found in first row")]
     NoHeaders,
+
+    #[error("File is too large ({size} bytes); the import limit is {limit} bytes")]
+    FileTooLarge { size: u64, limit: u64 },
+
+    #[error("File has more than {limit} data rows, the import limit")]
+    TooManyRows { limit: usize },
+}
+
+/// Default cap on raw import size (50 MB)
+pub const DEFAULT_MAX_IMPORT_BYTES: u64 = 50 * 1024 * 1024;
+
+/// Default cap on data rows per import
+pub const DEFAULT_MAX_IMPORT_ROWS: usize = 100_000;
+
+/// Batch size used when a preview streams through the file
+const PREVIEW_CHUNK_ROWS: usize = 1_000;
+
+/// XLSX is zipped XML; allow its unpacked size to be this many times the
+/// byte limit before refusing to open it
+const XLSX_EXPANSION_ALLOWANCE: u64 = 5;
+
+/// Size and row caps applied before a file is fully parsed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ImportLimits {
+    pub max_bytes: u64,
+    pub max_rows: usize,
+}
+
+impl Default for ImportLimits {
+    fn default() -> Self {
+        Self {
+            max_bytes: DEFAULT_MAX_IMPORT_BYTES,
+            max_rows: DEFAULT_MAX_IMPORT_ROWS,
+        }
+    }
+}
+
+impl ImportLimits {
+    /// Build limits from the `max_import_bytes` / `max_import_rows` settings.
+    /// Missing, unparseable, or zero values fall back to the defaults.
+    pub fn from_settings(max_bytes: Option<&str>, max_rows: Option<&str>) -> Self {
+        let defaults = Self::default();
+        Self {
+            max_bytes: max_bytes
+                .and_then(|v| v.trim().parse().ok())
+                .filter(|v| *v > 0)
+                .unwrap_or(defaults.max_bytes),
+            max_rows: max_rows
+                .and_then(|v| v.trim().parse().ok())
+                .filter(|v| *v > 0)
+                .unwrap_or(defaults.max_rows),
+        }
+    }
+
+    /// Read the configured limits; a settings read failure means defaults
+    pub async fn load(pool: &DbPool) -> Self {
+        let max_bytes = settings::get_setting(pool, SettingKey::MaxImportBytes.as_str())
+            .await
+            .ok()
+            .flatten();
+        let max_rows = settings::get_setting(pool, SettingKey::MaxImportRows.as_str())
+            .await
+            .ok()
+            .flatten();
+        Self::from_settings(max_bytes.as_deref(), max_rows.as_deref())
+    }
 }
 
 /// A single parsed row as column_name -> value
@@ -45,10 +115,34 @@ pub struct ParseResult {
     pub total_rows: usize,
     /// Detected file format (CSV, TSV, XLSX, XLS)
     pub file_format: String,
+    /// Text encoding the file was read as (CSV/TSV only), e.g. "UTF-16LE"
+    pub encoding: Option<String>,
+    /// Field delimiter that was used (CSV/TSV only)
+    pub delimiter: Option<String>,
     /// Warnings during parsing (e.g., skipped rows)
     pub warnings: Vec<String>,
 }
 
+/// One batch of rows from `parse_file_chunked`
+#[derive(Debug)]
+pub struct ParsedChunk {
+    /// 0-based position of the chunk's first row among all data rows
+    pub start_row: usize,
+    pub rows: Vec<ParsedRow>,
+}
+
+/// Everything `parse_file_chunked` learned about the file, minus the rows
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ChunkedParseSummary {
+    pub headers: Vec<String>,
+    pub total_rows: usize,
+    pub chunks: usize,
+    pub file_format: String,
+    pub encoding: Option<String>,
+    pub delimiter: Option<String>,
+    pub warnings: Vec<String>,
+}
+
 /// Preview result (limited rows for UI display)
 #[derive(Debug, Serialize, Deserialize)]
 pub struct ParsePreview {
@@ -60,6 +154,9 @@ pub struct ParsePreview {
     pub total_rows: usize,
     /// Detected file format
     pub file_format: String,
+    /// Text encoding and delimiter, for CSV/TSV
+    pub encoding: Option<String>,
+    pub delimiter: Option<String>,
 }
 
 /// Supported file formats
@@ -110,13 +207,28 @@ fn detect_format(file_name: &str) -> Result<FileFormat, ParseError> {
 // CSV/TSV Parsing
 // ============================================================================
 
-/// Parse delimited text (CSV or TSV)
-fn parse_delimited(data: &[u8], delimiter: u8, format: FileFormat) -> Result<ParseResult, ParseError> {
+/// Stream delimited rows to `on_chunk` in batches of `chunk_size`. The
+/// encoding and delimiter are detected first; the extension only sets the
+/// delimiter to prefer when the sample is ambiguous.
+fn read_delimited<F>(
+    data: &[u8],
+    format: FileFormat,
+    max_rows: usize,
+    chunk_size: usize,
+    mut on_chunk: F,
+) -> Result<ChunkedParseSummary, ParseError>
+where
+    F: FnMut(ParsedChunk) -> Result<(), ParseError>,
+{
+    let (text, encoding) = decode_text(data);
+    let default_delimiter = if format == FileFormat::Tsv { b'\t' } else { b',' };
+    let delimiter = detect_delimiter(&text, default_delimiter);
+
     let mut reader = ReaderBuilder::new()
         .delimiter(delimiter)
         .flexible(true) // Allow rows with varying column counts
         .trim(csv::Trim::All)
-        .from_reader(data);
+        .from_reader(text.as_ref());
 
     // Extract headers
     let headers: Vec<String> = reader
@@ -131,7 +243,7 @@ fn parse_delimited(data: &[u8], delimiter: u8, format: FileFormat) -> Result<Par
     }
 
     // Parse data rows
-    let mut rows = Vec::new();
+    let mut chunker = Chunker::new(chunk_size);
     let mut warnings = Vec::new();
 
     for (idx, result) in reader.records().enumerate() {
@@ -149,7 +261,10 @@ fn parse_delimited(data: &[u8], delimiter: u8, format: FileFormat) -> Result<Par
                 }
                 // Only include rows that have at least one value
                 if !row.is_empty() {
-                    rows.push(row);
+                    if chunker.total_rows == max_rows {
+                        return Err(ParseError::TooManyRows { limit: max_rows });
+                    }
+                    chunker.push(row, &mut on_chunk)?;
                 }
             }
             Err(e) => {
@@ -158,25 +273,223 @@ fn parse_delimited(data: &[u8], delimiter: u8, format: FileFormat) -> Result<Par
         }
     }
 
-    if rows.is_empty() {
-        return Err(ParseError::NoData);
+    let mut summary = chunker.finish(headers, format, warnings, &mut on_chunk)?;
+    summary.encoding = Some(encoding.to_string());
+    summary.delimiter = Some((delimiter as char).to_string());
+    Ok(summary)
+}
+
+// ============================================================================
+// Encoding & Delimiter Detection
+// ============================================================================
+
+/// Bytes examined when sniffing BOM-less UTF-16 or guessing the delimiter
+const SNIFF_BYTES: usize = 64 * 1024;
+
+/// Records compared when guessing the delimiter
+const DELIMITER_SAMPLE_RECORDS: usize = 20;
+
+/// Delimiters tried after the extension's default, in tie-break order
+const CANDIDATE_DELIMITERS: [u8; 4] = [b',', b';', b'\t', b'|'];
+
+/// Windows-1252 characters for bytes 0x80-0x9F (unassigned bytes become U+FFFD).
+/// The rest of the high half matches Latin-1.
+const WINDOWS_1252_HIGH: [char; 32] = [
+    '\u{20AC}', '\u{FFFD}', '\u{201A}', '\u{0192}', '\u{201E}', '\u{2026}', '\u{2020}', '\u{2021}',
+    '\u{02C6}', '\u{2030}', '\u{0160}', '\u{2039}', '\u{0152}', '\u{FFFD}', '\u{017D}', '\u{FFFD}',
+    '\u{FFFD}', '\u{2018}', '\u{2019}', '\u{201C}', '\u{201D}', '\u{2022}', '\u{2013}', '\u{2014}',
+    '\u{02DC}', '\u{2122}', '\u{0161}', '\u{203A}', '\u{0153}', '\u{FFFD}', '\u{017E}', '\u{0178}',
+];
+
+/// Convert file bytes to UTF-8 and name the source encoding. A BOM decides
+/// when present. Otherwise NULs in alternating bytes mean UTF-16 without a
+/// BOM, valid UTF-8 is used as-is, and anything else is read as
+/// Windows-1252, which covers Latin-1 exports.
+fn decode_text(data: &[u8]) -> (Cow<'_, [u8]>, &'static str) {
+    if let Some(rest) = data.strip_prefix(b"\xEF\xBB\xBF") {
+        return (Cow::Borrowed(rest), "UTF-8");
+    }
+    if let Some(rest) = data.strip_prefix(b"\xFF\xFE") {
+        return (Cow::Owned(decode_utf16(rest, u16::from_le_bytes).into_bytes()), "UTF-16LE");
+    }
+    if let Some(rest) = data.strip_prefix(b"\xFE\xFF") {
+        return (Cow::Owned(decode_utf16(rest, u16::from_be_bytes).into_bytes()), "UTF-16BE");
     }
 
-    Ok(ParseResult {
-        headers,
-        total_rows: rows.len(),
-        rows,
-        file_format: format.as_str().to_string(),
-        warnings,
-    })
+    // ASCII text in UTF-16 has a NUL in every other byte
+    let sample = &data[..data.len().min(SNIFF_BYTES)];
+    let pairs = sample.len() / 2;
+    if pairs > 0 {
+        let even_nuls = sample.iter().step_by(2).filter(|b| **b == 0).count();
+        let odd_nuls = sample.iter().skip(1).step_by(2).filter(|b| **b == 0).count();
+        if odd_nuls * 2 > pairs && even_nuls * 10 < pairs {
+            return (Cow::Owned(decode_utf16(data, u16::from_le_bytes).into_bytes()), "UTF-16LE");
+        }
+        if even_nuls * 2 > pairs && odd_nuls * 10 < pairs {
+            return (Cow::Owned(decode_utf16(data, u16::from_be_bytes).into_bytes()), "UTF-16BE");
+        }
+    }
+
+    if std::str::from_utf8(data).is_ok() {
+        return (Cow::Borrowed(data), "UTF-8");
+    }
+    let text: String = data
+        .iter()
+        .map(|&b| match b {
+            0x80..=0x9F => WINDOWS_1252_HIGH[(b - 0x80) as usize],
+            _ => b as char,
+        })
+        .collect();
+    (Cow::Owned(text.into_bytes()), "Windows-1252")
+}
+
+/// Decode UTF-16 code units; a trailing odd byte is dropped and unpaired
+/// surrogates become U+FFFD
+fn decode_utf16(data: &[u8], unit: fn([u8; 2]) -> u16) -> String {
+    let units = data.chunks_exact(2).map(|pair| unit([pair[0], pair[1]]));
+    char::decode_utf16(units)
+        .map(|c| c.unwrap_or(char::REPLACEMENT_CHARACTER))
+        .collect()
+}
+
+/// Pick the delimiter whose field count is most consistent across the first
+/// records, preferring more columns and then `default`. Files where no
+/// candidate splits the header get `default`.
+fn detect_delimiter(text: &[u8], default: u8) -> u8 {
+    let complete = text.len() <= SNIFF_BYTES;
+    let sample = &text[..text.len().min(SNIFF_BYTES)];
+
+    let candidates = std::iter::once(default).chain(CANDIDATE_DELIMITERS.into_iter().filter(|d| *d != default));
+    let mut best: Option<((usize, usize), u8)> = None;
+    for delimiter in candidates {
+        let counts = field_counts(sample, delimiter, complete);
+        let Some(&columns) = counts.first() else { continue };
+        if columns < 2 {
+            continue;
+        }
+        let consistent = counts.iter().filter(|c| **c == columns).count();
+        if best.is_none_or(|(score, _)| (consistent, columns) > score) {
+            best = Some(((consistent, columns), delimiter));
+        }
+    }
+    best.map(|(_, delimiter)| delimiter).unwrap_or(default)
+}
+
+/// Fields per record in the sample, ignoring delimiters inside quotes and
+/// blank lines. A record cut off by the end of a partial sample is dropped.
+fn field_counts(sample: &[u8], delimiter: u8, complete: bool) -> Vec<usize> {
+    let mut counts = Vec::new();
+    let mut in_quotes = false;
+    let mut fields = 1;
+    let mut has_content = false;
+    for &b in sample {
+        match b {
+            b'"' => {
+                in_quotes = !in_quotes;
+                has_content = true;
+            }
+            b'\n' if !in_quotes => {
+                if has_content {
+                    counts.push(fields);
+                    if counts.len() == DELIMITER_SAMPLE_RECORDS {
+                        return counts;
+                    }
+                }
+                fields = 1;
+                has_content = false;
+            }
+            _ if b == delimiter && !in_quotes => {
+                fields += 1;
+                has_content = true;
+            }
+            b'\r' | b' ' => {}
+            _ => has_content = true,
+        }
+    }
+    if complete && has_content {
+        counts.push(fields);
+    }
+    counts
+}
+
+/// Batches rows for a chunk callback and keeps the running totals
+struct Chunker {
+    chunk_size: usize,
+    pending: Vec<ParsedRow>,
+    total_rows: usize,
+    chunks: usize,
+}
+
+impl Chunker {
+    fn new(chunk_size: usize) -> Self {
+        Self {
+            chunk_size: chunk_size.max(1),
+            pending: Vec::new(),
+            total_rows: 0,
+            chunks: 0,
+        }
+    }
+
+    fn push<F>(&mut self, row: ParsedRow, on_chunk: &mut F) -> Result<(), ParseError>
+    where
+        F: FnMut(ParsedChunk) -> Result<(), ParseError>,
+    {
+        self.pending.push(row);
+        self.total_rows += 1;
+        if self.pending.len() == self.chunk_size {
+            self.flush(on_chunk)?;
+        }
+        Ok(())
+    }
+
+    fn flush<F>(&mut self, on_chunk: &mut F) -> Result<(), ParseError>
+    where
+        F: FnMut(ParsedChunk) -> Result<(), ParseError>,
+    {
+        if self.pending.is_empty() {
+            return Ok(());
+        }
+        let rows = std::mem::take(&mut self.pending);
+        self.chunks += 1;
+        on_chunk(ParsedChunk {
+            start_row: self.total_rows - rows.len(),
+            rows,
+        })
+    }
+
+    fn finish<F>(
+        mut self,
+        headers: Vec<String>,
+        format: FileFormat,
+        warnings: Vec<String>,
+        on_chunk: &mut F,
+    ) -> Result<ChunkedParseSummary, ParseError>
+    where
+        F: FnMut(ParsedChunk) -> Result<(), ParseError>,
+    {
+        if self.total_rows == 0 {
+            return Err(ParseError::NoData);
+        }
+        self.flush(on_chunk)?;
+        Ok(ChunkedParseSummary {
+            headers,
+            total_rows: self.total_rows,
+            chunks: self.chunks,
+            file_format: format.as_str().to_string(),
+            encoding: None,
+            delimiter: None,
+            warnings,
+        })
+    }
 }
 
 // ============================================================================
 // Excel Parsing
 // ============================================================================
 
-/// Parse Excel file (XLSX or XLS)
-fn parse_excel(data: &[u8], format: FileFormat) -> Result<ParseResult, ParseError> {
+/// Parse an XLS file. XLSX goes through `read_xlsx_chunked` instead; both
+/// convert cells with `cell_to_string`.
+fn parse_xls(data: &[u8], limits: &ImportLimits) -> Result<ParseResult, ParseError> {
     // Create cursor for reading from bytes
     let cursor = Cursor::new(data);
 
@@ -199,23 +512,21 @@ fn parse_excel(data: &[u8], format: FileFormat) -> Result<ParseResult, ParseErro
     if row_count == 0 || col_count == 0 {
         return Err(ParseError::NoData);
     }
+    // Sheet dimensions include the header row; blank rows are still counted
+    // here, so this is only a cheap upper-bound check
+    if row_count.saturating_sub(1) > limits.max_rows {
+        let non_empty = (1..row_count)
+            .filter(|r| (0..col_count).any(|c| !cell_to_string(range.get((*r, c))).is_empty()))
+            .count();
+        if non_empty > limits.max_rows {
+            return Err(ParseError::TooManyRows { limit: limits.max_rows });
+        }
+    }
 
     // Extract headers from first row
     let mut headers: Vec<String> = Vec::new();
     for col in 0..col_count {
-        let cell = range.get((0, col));
-        let header = match cell {
-            Some(Data::String(s)) => normalize_header(s),
-            Some(Data::Int(n)) => normalize_header(&n.to_string()),
-            Some(Data::Float(n)) => normalize_header(&n.to_string()),
-            Some(Data::Bool(b)) => normalize_header(&b.to_string()),
-            Some(Data::DateTime(dt)) => normalize_header(&dt.to_string()),
-            Some(Data::Error(e)) => normalize_header(&format!("{:?}", e)),
-            Some(Data::Empty) | Some(Data::DateTimeIso(_)) | Some(Data::DurationIso(_)) | None => {
-                format!("column_{}", col + 1)
-            }
-        };
-        headers.push(header);
+        headers.push(header_from_cell(range.get((0, col)), col));
     }
 
     if headers.is_empty() {
@@ -263,12 +574,195 @@ fn parse_excel(data: &[u8], format: FileFormat) -> Result<ParseResult, ParseErro
         headers,
         total_rows: rows.len(),
         rows,
-        file_format: format.as_str().to_string(),
+        file_format: FileFormat::Xls.as_str().to_string(),
+        encoding: None,
+        delimiter: None,
         warnings,
     })
 }
 
-/// Convert Excel cell to string
+/// Stream the first sheet of an XLSX file row by row, so the whole sheet
+/// is never held in memory
+fn read_xlsx_chunked<F>(
+    data: &[u8],
+    limits: &ImportLimits,
+    chunk_size: usize,
+    mut on_chunk: F,
+) -> Result<ChunkedParseSummary, ParseError>
+where
+    F: FnMut(ParsedChunk) -> Result<(), ParseError>,
+{
+    check_xlsx_expansion(data, limits)?;
+
+    let mut workbook: Xlsx<_> = Xlsx::new(Cursor::new(data))
+        .map_err(|e| ParseError::ReadError(format!("Failed to open Excel file: {}", e)))?;
+    let sheet_names = workbook.sheet_names().to_vec();
+    if sheet_names.is_empty() {
+        return Err(ParseError::NoData);
+    }
+    let mut cells = workbook
+        .worksheet_cells_reader(&sheet_names[0])
+        .map_err(|e| ParseError::ReadError(format!("Failed to read worksheet: {}", e)))?;
+
+    // Columns are numbered from the sheet's declared first column
+    let dimensions = cells.dimensions();
+    let first_col = dimensions.start.1;
+    let declared_width = (dimensions.end.1 + 1).saturating_sub(first_col) as usize;
+
+    let mut headers: Vec<String> = Vec::new();
+    let mut header_row: Option<u32> = None;
+    let mut current: Option<(u32, Vec<(usize, String)>)> = None;
+    let mut chunker = Chunker::new(chunk_size);
+
+    loop {
+        let next = cells
+            .next_cell()
+            .map_err(|e| ParseError::ReadError(format!("Failed to read worksheet: {}", e)))?;
+        let next = next.map(|cell| {
+            let (row, col) = cell.get_position();
+            let value = Data::from(cell.get_value().clone());
+            if value == Data::Empty {
+                return None;
+            }
+            Some((row, col.saturating_sub(first_col) as usize, value))
+        });
+        let Some(next) = next else { break };
+        let Some((row, col, value)) = next else { continue };
+
+        // The first row with any value is the header row
+        if header_row.is_none_or(|h| h == row) {
+            header_row = Some(row);
+            if headers.len() <= col {
+                let width = headers.len();
+                headers.extend((width..=col).map(|c| format!("column_{}", c + 1)));
+            }
+            headers[col] = header_from_cell(Some(&value), col);
+            continue;
+        }
+
+        if current.as_ref().is_some_and(|(r, _)| *r != row) {
+            let (_, values) = current.take().unwrap_or_default();
+            push_xlsx_row(&mut headers, values, &mut chunker, limits, &mut on_chunk)?;
+        }
+        let (_, values) = current.get_or_insert_with(|| (row, Vec::new()));
+        let text = cell_to_string(Some(&value));
+        if !text.is_empty() {
+            values.push((col, text));
+        }
+    }
+    if let Some((_, values)) = current {
+        push_xlsx_row(&mut headers, values, &mut chunker, limits, &mut on_chunk)?;
+    }
+
+    if headers.is_empty() {
+        return Err(ParseError::NoData);
+    }
+    if headers.len() < declared_width {
+        let width = headers.len();
+        headers.extend((width..declared_width).map(|c| format!("column_{}", c + 1)));
+    }
+
+    let mut warnings = Vec::new();
+    if sheet_names.len() > 1 {
+        warnings.push(format!(
+            "File has {} sheets. Using first sheet: '{}'",
+            sheet_names.len(),
+            sheet_names[0]
+        ));
+    }
+    chunker.finish(headers, FileFormat::Xlsx, warnings, &mut on_chunk)
+}
+
+fn push_xlsx_row<F>(
+    headers: &mut Vec<String>,
+    values: Vec<(usize, String)>,
+    chunker: &mut Chunker,
+    limits: &ImportLimits,
+    on_chunk: &mut F,
+) -> Result<(), ParseError>
+where
+    F: FnMut(ParsedChunk) -> Result<(), ParseError>,
+{
+    if values.is_empty() {
+        return Ok(());
+    }
+    if chunker.total_rows == limits.max_rows {
+        return Err(ParseError::TooManyRows { limit: limits.max_rows });
+    }
+    let mut row = HashMap::new();
+    for (col, value) in values {
+        // Data wider than the header row gets generated column names
+        if headers.len() <= col {
+            let width = headers.len();
+            headers.extend((width..=col).map(|c| format!("column_{}", c + 1)));
+        }
+        row.insert(headers[col].clone(), value);
+    }
+    chunker.push(row, on_chunk)
+}
+
+/// Refuse an XLSX whose unpacked size is past the expansion allowance
+fn check_xlsx_expansion(data: &[u8], limits: &ImportLimits) -> Result<(), ParseError> {
+    if let Some(unpacked) = zip_uncompressed_size(data) {
+        let limit = limits.max_bytes.saturating_mul(XLSX_EXPANSION_ALLOWANCE);
+        if unpacked > limit {
+            return Err(ParseError::FileTooLarge { size: unpacked, limit });
+        }
+    }
+    Ok(())
+}
+
+/// Sum of the uncompressed entry sizes listed in a ZIP central directory.
+/// Returns None when the archive can't be read this way; calamine then
+/// reports the real error.
+fn zip_uncompressed_size(data: &[u8]) -> Option<u64> {
+    const EOCD_SIGNATURE: [u8; 4] = [0x50, 0x4b, 0x05, 0x06];
+    const CENTRAL_HEADER_SIGNATURE: [u8; 4] = [0x50, 0x4b, 0x01, 0x02];
+    const EOCD_LEN: usize = 22;
+    const CENTRAL_HEADER_LEN: usize = 46;
+
+    let read_u16 = |at: usize| -> Option<u64> {
+        Some(u16::from_le_bytes(data.get(at..at + 2)?.try_into().ok()?) as u64)
+    };
+    let read_u32 = |at: usize| -> Option<u64> {
+        Some(u32::from_le_bytes(data.get(at..at + 4)?.try_into().ok()?) as u64)
+    };
+
+    // End-of-central-directory record sits at the end, before an optional comment
+    let last = data.len().checked_sub(EOCD_LEN)?;
+    let first = last.saturating_sub(u16::MAX as usize);
+    let eocd = (first..=last).rev().find(|&i| data[i..i + 4] == EOCD_SIGNATURE)?;
+
+    let entries = read_u16(eocd + 10)?;
+    let mut pos = read_u32(eocd + 16)? as usize;
+    let mut total: u64 = 0;
+    for _ in 0..entries {
+        if data.get(pos..pos + 4)? != CENTRAL_HEADER_SIGNATURE {
+            return None;
+        }
+        total = total.saturating_add(read_u32(pos + 24)?);
+        let name_len = read_u16(pos + 28)? as usize;
+        let extra_len = read_u16(pos + 30)? as usize;
+        let comment_len = read_u16(pos + 32)? as usize;
+        pos += CENTRAL_HEADER_LEN + name_len + extra_len + comment_len;
+    }
+
+    Some(total)
+}
+
+/// Header name for a header-row cell, read as `cell_to_string` reads data
+/// cells; blank cells get "column_<n>"
+fn header_from_cell(cell: Option<&Data>, col: usize) -> String {
+    let text = cell_to_string(cell);
+    if text.is_empty() {
+        format!("column_{}", col + 1)
+    } else {
+        normalize_header(&text)
+    }
+}
+
+/// Convert Excel cell to string. Shared by the XLS and streaming XLSX
+/// readers, so the preview and the full import see the same values.
 fn cell_to_string(cell: Option<&Data>) -> String {
     match cell {
         Some(Data::String(s)) => s.trim().to_string(),
@@ -326,6 +820,112 @@ fn normalize_header(header: &str) -> String {
         .collect()
 }
 
+// ============================================================================
+// Date Normalization
+// ============================================================================
+
+/// How to read an all-numeric date like 1/2/2020 when either part could be
+/// the month
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DateLocale {
+    /// Month first: 1/2/2020 is January 2
+    #[default]
+    Us,
+    /// Day first: 1/2/2020 is February 1
+    Eu,
+}
+
+/// Year-first and month-name formats, tried in order before the numeric
+/// day/month ones
+const UNAMBIGUOUS_DATE_FORMATS: &[&str] = &[
+    "%Y-%m-%d", "%Y/%m/%d", "%Y.%m.%d", "%Y%m%d",
+    "%d-%b-%Y", "%d %b %Y", "%d-%b-%y", "%d %b %y", "%b %d, %Y", "%b %d %Y", "%b-%d-%Y",
+    "%d %B %Y", "%B %d, %Y", "%B %d %Y",
+];
+
+/// Month-first numeric formats; four-digit years before two-digit ones
+const MONTH_FIRST_DATE_FORMATS: &[&str] = &["%m/%d/%Y", "%m-%d-%Y", "%m.%d.%Y", "%m/%d/%y", "%m-%d-%y", "%m.%d.%y"];
+const DAY_FIRST_DATE_FORMATS: &[&str] = &["%d/%m/%Y", "%d-%m-%Y", "%d.%m.%Y", "%d/%m/%y", "%d-%m-%y", "%d.%m.%y"];
+
+/// Parses outside this range are a format misread (e.g. "3/15/20" as year
+/// 20), not a real hire or birth date
+const MIN_DATE_YEAR: i32 = 1900;
+const MAX_DATE_YEAR: i32 = 2100;
+
+/// Convert a date in any of the common export formats to ISO `%Y-%m-%d`.
+/// Numeric dates are read in `locale` order first and fall back to the other
+/// order, so 3/15/2020 parses either way while 1/2/2020 follows the hint.
+/// A trailing time ("2020-03-15T09:00:00", "3/15/2020 9:00 AM") is ignored.
+/// Two-digit years pivot on the current year: one that would land after this
+/// year is read as last century, so "3/15/65" is 1965.
+pub fn normalize_date(raw: &str, locale: DateLocale) -> Option<String> {
+    use chrono::{Datelike, NaiveDate};
+
+    let trimmed = raw.trim();
+    if trimmed.is_empty() {
+        return None;
+    }
+
+    let (preferred, fallback) = match locale {
+        DateLocale::Us => (MONTH_FIRST_DATE_FORMATS, DAY_FIRST_DATE_FORMATS),
+        DateLocale::Eu => (DAY_FIRST_DATE_FORMATS, MONTH_FIRST_DATE_FORMATS),
+    };
+    // Month-name dates contain spaces themselves, so try the whole value
+    // before dropping a time suffix
+    let date_part = trimmed.split(['T', ' ']).next().unwrap_or(trimmed);
+    let candidates = if date_part.len() < trimmed.len() { vec![trimmed, date_part] } else { vec![trimmed] };
+    let current_year = crate::tenure::local_today().year();
+
+    for value in candidates {
+        let formats = UNAMBIGUOUS_DATE_FORMATS.iter().chain(preferred).chain(fallback);
+        for format in formats {
+            if let Ok(mut date) = NaiveDate::parse_from_str(value, format) {
+                if format.contains("%y") && date.year() > current_year {
+                    match date.with_year(date.year() - 100) {
+                        Some(previous_century) => date = previous_century,
+                        None => continue,
+                    }
+                }
+                if (MIN_DATE_YEAR..=MAX_DATE_YEAR).contains(&date.year()) {
+                    return Some(date.format("%Y-%m-%d").to_string());
+                }
+            }
+        }
+    }
+    None
+}
+
+/// One non-empty date cell checked for import
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DateCheck {
+    /// Spreadsheet row number (the header is row 1)
+    pub row: usize,
+    pub raw: String,
+    /// ISO date, or None when the value couldn't be parsed
+    pub normalized: Option<String>,
+}
+
+/// Normalize one column across parsed rows so the preview can show the
+/// dates that will be imported and flag the ones that won't. Blank cells are
+/// left out.
+pub fn check_date_column(rows: &[ParsedRow], column: &str, locale: DateLocale) -> Vec<DateCheck> {
+    rows.iter()
+        .enumerate()
+        .filter_map(|(idx, row)| {
+            let raw = row.get(column)?.trim();
+            if raw.is_empty() {
+                return None;
+            }
+            Some(DateCheck {
+                row: idx + 2,
+                raw: raw.to_string(),
+                normalized: normalize_date(raw, locale),
+            })
+        })
+        .collect()
+}
+
 // ============================================================================
 // Public API
 // ============================================================================
@@ -335,16 +935,73 @@ fn normalize_header(header: &str) -> String {
 /// # Arguments
 /// * `data` - Raw file bytes
 /// * `file_name` - Original filename (used for format detection)
+/// * `limits` - Byte and row caps
 ///
 /// # Returns
 /// * `ParseResult` with headers, rows, and metadata
-pub fn parse_file(data: &[u8], file_name: &str) -> Result<ParseResult, ParseError> {
+///
+/// Rejects the file with `FileTooLarge` / `TooManyRows` as soon as a limit
+/// is exceeded rather than after reading everything into memory. Rows are
+/// read by `parse_file_chunked`, the same reader the preview uses.
+pub fn parse_file(
+    data: &[u8],
+    file_name: &str,
+    limits: &ImportLimits,
+) -> Result<ParseResult, ParseError> {
+    let mut rows = Vec::new();
+    let summary = parse_file_chunked(data, file_name, usize::MAX, limits, |chunk| {
+        rows.extend(chunk.rows);
+        Ok(())
+    })?;
+
+    Ok(ParseResult {
+        headers: summary.headers,
+        total_rows: summary.total_rows,
+        rows,
+        file_format: summary.file_format,
+        encoding: summary.encoding,
+        delimiter: summary.delimiter,
+        warnings: summary.warnings,
+    })
+}
+
+/// Parse a file in batches of `chunk_size` rows, handing each batch to
+/// `on_chunk` as soon as it is read instead of building one `ParseResult`.
+///
+/// CSV, TSV, and XLSX are streamed, so memory stays flat however many rows
+/// the file has; XLS has no streaming reader and is read whole, then
+/// batched. Limits apply as in `parse_file`, but chunks before the failing
+/// row have already been delivered. Returning an error from `on_chunk`
+/// stops parsing and is passed through. `start_row` on each chunk lets an
+/// importer record progress and skip already-stored chunks on a retry.
+pub fn parse_file_chunked<F>(
+    data: &[u8],
+    file_name: &str,
+    chunk_size: usize,
+    limits: &ImportLimits,
+    mut on_chunk: F,
+) -> Result<ChunkedParseSummary, ParseError>
+where
+    F: FnMut(ParsedChunk) -> Result<(), ParseError>,
+{
     let format = detect_format(file_name)?;
 
+    let size = data.len() as u64;
+    if size > limits.max_bytes {
+        return Err(ParseError::FileTooLarge { size, limit: limits.max_bytes });
+    }
+
     match format {
-        FileFormat::Csv => parse_delimited(data, b',', format),
-        FileFormat::Tsv => parse_delimited(data, b'\t', format),
-        FileFormat::Xlsx | FileFormat::Xls => parse_excel(data, format),
+        FileFormat::Csv | FileFormat::Tsv => read_delimited(data, format, limits.max_rows, chunk_size, on_chunk),
+        FileFormat::Xlsx => read_xlsx_chunked(data, limits, chunk_size, on_chunk),
+        FileFormat::Xls => {
+            let result = parse_xls(data, limits)?;
+            let mut chunker = Chunker::new(chunk_size);
+            for row in result.rows {
+                chunker.push(row, &mut on_chunk)?;
+            }
+            chunker.finish(result.headers, format, result.warnings, &mut on_chunk)
+        }
     }
 }
 
@@ -355,19 +1012,31 @@ pub fn parse_file(data: &[u8], file_name: &str) -> Result<ParseResult, ParseErro
 /// * `data` - Raw file bytes
 /// * `file_name` - Original filename
 /// * `preview_rows` - Number of rows to include in preview (default: 5)
+/// * `limits` - Byte and row caps
 pub fn parse_file_preview(
     data: &[u8],
     file_name: &str,
     preview_rows: Option<usize>,
+    limits: &ImportLimits,
 ) -> Result<ParsePreview, ParseError> {
     let limit = preview_rows.unwrap_or(5);
-    let result = parse_file(data, file_name)?;
+
+    // Only the preview rows are kept; the rest are counted and dropped
+    let mut rows = Vec::new();
+    let summary = parse_file_chunked(data, file_name, PREVIEW_CHUNK_ROWS, limits, |chunk| {
+        if chunk.start_row < limit {
+            rows.extend(chunk.rows.into_iter().take(limit - chunk.start_row));
+        }
+        Ok(())
+    })?;
 
     Ok(ParsePreview {
-        headers: result.headers,
-        preview_rows: result.rows.into_iter().take(limit).collect(),
-        total_rows: result.total_rows,
-        file_format: result.file_format,
+        headers: summary.headers,
+        preview_rows: rows,
+        total_rows: summary.total_rows,
+        file_format: summary.file_format,
+        encoding: summary.encoding,
+        delimiter: summary.delimiter,
     })
 }
 
@@ -385,38 +1054,155 @@ pub fn is_supported_file(file_name: &str) -> bool {
 // Column Mapping Helpers
 // ============================================================================
 
-/// Standard column names we look for when importing employees
+/// Standard column names we look for when importing employees. Aliases
+/// include the headers Workday and BambooHR exports use; they're compared
+/// by `match_key`, so case, spacing, and punctuation don't matter.
 pub const EMPLOYEE_COLUMN_MAPPINGS: &[(&str, &[&str])] = &[
-    ("email", &["email", "email_address", "e_mail", "emailaddress", "work_email"]),
-    ("first_name", &["first_name", "firstname", "first", "given_name", "givenname"]),
-    ("last_name", &["last_name", "lastname", "last", "surname", "family_name", "familyname"]),
-    ("department", &["department", "dept", "team", "division", "group"]),
-    ("title", &["title", "job_title", "jobtitle", "position", "role"]),
-    ("hire_date", &["hire_date", "hiredate", "start_date", "startdate", "date_hired", "joined"]),
-    ("work_state", &["work_state", "workstate", "state", "location_state", "work_location"]),
-    ("manager_email", &["manager_email", "manageremail", "manager", "reports_to", "reportsto"]),
-    ("status", &["status", "employment_status", "employmentstatus", "active"]),
+    ("email", &["email", "email_address", "e_mail", "emailaddress", "work_email", "primary_work_email", "email_work", "business_email", "work_email_address"]),
+    ("first_name", &["first_name", "firstname", "first", "given_name", "givenname", "legal_first_name", "legal_name_first_name", "preferred_first_name"]),
+    ("last_name", &["last_name", "lastname", "last", "surname", "family_name", "familyname", "legal_last_name", "legal_name_last_name"]),
+    ("full_name", &["full_name", "name", "employee_name", "worker", "legal_name", "preferred_name", "display_name"]),
+    ("department", &["department", "dept", "team", "division", "group", "supervisory_organization", "cost_center", "organization"]),
+    ("title", &["title", "job_title", "jobtitle", "position", "role", "business_title", "job_profile", "position_title"]),
+    ("hire_date", &["hire_date", "hiredate", "start_date", "startdate", "date_hired", "joined", "original_hire_date", "most_recent_hire_date", "date_of_hire"]),
+    ("work_state", &["work_state", "workstate", "state", "location_state", "work_location", "work_address_state_province", "work_address_state", "location"]),
+    ("manager_email", &["manager_email", "manageremail", "reports_to", "reportsto", "supervisor_email", "reports_to_email", "manager_work_email"]),
+    ("status", &["status", "employment_status", "employmentstatus", "active", "worker_status", "employee_status", "active_status"]),
     ("date_of_birth", &["date_of_birth", "dateofbirth", "dob", "birth_date", "birthdate"]),
     ("gender", &["gender", "sex"]),
-    ("ethnicity", &["ethnicity", "race", "race_ethnicity"]),
+    ("ethnicity", &["ethnicity", "race", "race_ethnicity", "ethnicity_race", "ethnic_group"]),
 ];
 
-/// Try to map parsed headers to standard employee fields
-/// Returns a map of standard_field -> parsed_header
-pub fn map_employee_columns(headers: &[String]) -> HashMap<String, String> {
-    let mut mapping = HashMap::new();
-
-    for (standard_field, alternatives) in EMPLOYEE_COLUMN_MAPPINGS {
-        for header in headers {
-            let normalized = normalize_header(header);
-            if alternatives.contains(&normalized.as_str()) {
-                mapping.insert(standard_field.to_string(), header.clone());
-                break;
+/// Employee aliases that often hold something else: "Reports To" is often a
+/// name, BambooHR's "Employment Status" is Full-Time/Part-Time, "Location"
+/// is usually an office, and cost centers rarely line up with departments.
+/// These match with low confidence. Workday's "Manager" and BambooHR's
+/// "Reporting to" always hold names, so they aren't manager_email aliases.
+const WEAK_EMPLOYEE_ALIASES: &[&str] = &[
+    "team",
+    "division",
+    "group",
+    "supervisory_organization",
+    "cost_center",
+    "organization",
+    "role",
+    "job_profile",
+    "joined",
+    "state",
+    "location",
+    "reports_to",
+    "employment_status",
+    "active",
+    "preferred_name",
+    "sex",
+    "race",
+];
+
+const EXACT_CONFIDENCE: f64 = 1.0;
+const ALIAS_CONFIDENCE: f64 = 0.9;
+const WEAK_ALIAS_CONFIDENCE: f64 = 0.6;
+/// The header contains an alias, e.g. "Primary Email (Work)"
+const PARTIAL_CONFIDENCE: f64 = 0.4;
+/// Shorter aliases are too generic to match inside a longer header
+const MIN_PARTIAL_KEY_LEN: usize = 5;
+
+/// One header mapped to a standard field
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ColumnMatch {
+    pub field: String,
+    pub header: String,
+    /// 1.0 for the field's own name, 0.9 for a specific alias, 0.6 for a
+    /// weak alias, 0.4 for a partial match. Below 0.7 is worth confirming.
+    pub map_confidence: f64,
+}
+
+/// Comparison key for headers and aliases: lowercase letters and digits only,
+/// so "Work_State", "work state", and "Work-State" all compare equal
+pub fn match_key(header: &str) -> String {
+    header
+        .chars()
+        .filter(|c| c.is_alphanumeric())
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+/// How well `header` fits a field, or None if it doesn't
+fn score_header(field: &str, aliases: &[&str], weak: &[&str], partial: bool, header_key: &str) -> Option<f64> {
+    if header_key.is_empty() {
+        return None;
+    }
+    if header_key == match_key(field) {
+        return Some(EXACT_CONFIDENCE);
+    }
+    let mut best: Option<f64> = None;
+    for alias in aliases {
+        let alias_key = match_key(alias);
+        let score = if header_key == alias_key {
+            if weak.contains(alias) {
+                WEAK_ALIAS_CONFIDENCE
+            } else {
+                ALIAS_CONFIDENCE
             }
+        } else if partial && alias_key.len() >= MIN_PARTIAL_KEY_LEN && header_key.contains(&alias_key) {
+            PARTIAL_CONFIDENCE
+        } else {
+            continue;
+        };
+        best = Some(best.map_or(score, |b: f64| b.max(score)));
+    }
+    best
+}
+
+/// Map headers to fields, best matches first. Each field and each header is
+/// used at most once; ties keep field order, then header order. `partial`
+/// allows headers that merely contain an alias.
+fn score_columns(
+    mappings: &[(&str, &[&str])],
+    weak: &[&str],
+    partial: bool,
+    headers: &[String],
+) -> Vec<ColumnMatch> {
+    let keys: Vec<String> = headers.iter().map(|h| match_key(h)).collect();
+    let mut candidates: Vec<(f64, usize, usize)> = Vec::new();
+    for (field_index, (field, aliases)) in mappings.iter().enumerate() {
+        for (header_index, key) in keys.iter().enumerate() {
+            if let Some(score) = score_header(field, aliases, weak, partial, key) {
+                candidates.push((score, field_index, header_index));
+            }
+        }
+    }
+    candidates.sort_by(|a, b| b.0.total_cmp(&a.0).then(a.1.cmp(&b.1)).then(a.2.cmp(&b.2)));
+
+    let mut used_fields = vec![false; mappings.len()];
+    let mut used_headers = vec![false; headers.len()];
+    let mut matches = Vec::new();
+    for (score, field_index, header_index) in candidates {
+        if used_fields[field_index] || used_headers[header_index] {
+            continue;
         }
+        used_fields[field_index] = true;
+        used_headers[header_index] = true;
+        matches.push(ColumnMatch {
+            field: mappings[field_index].0.to_string(),
+            header: headers[header_index].clone(),
+            map_confidence: score,
+        });
     }
+    matches
+}
+
+/// Map employee headers to standard fields with a confidence per match
+pub fn map_employee_columns_scored(headers: &[String]) -> Vec<ColumnMatch> {
+    score_columns(EMPLOYEE_COLUMN_MAPPINGS, WEAK_EMPLOYEE_ALIASES, true, headers)
+}
 
-    mapping
+/// Try to map parsed headers to standard employee fields
+/// Returns a map of standard_field -> parsed_header
+pub fn map_employee_columns(headers: &[String]) -> HashMap<String, String> {
+    map_employee_columns_scored(headers)
+        .into_iter()
+        .map(|m| (m.field, m.header))
+        .collect()
 }
 
 /// Standard column names for performance ratings import
@@ -430,19 +1216,10 @@ pub const RATING_COLUMN_MAPPINGS: &[(&str, &[&str])] = &[
 
 /// Try to map parsed headers to rating fields
 pub fn map_rating_columns(headers: &[String]) -> HashMap<String, String> {
-    let mut mapping = HashMap::new();
-
-    for (standard_field, alternatives) in RATING_COLUMN_MAPPINGS {
-        for header in headers {
-            let normalized = normalize_header(header);
-            if alternatives.contains(&normalized.as_str()) {
-                mapping.insert(standard_field.to_string(), header.clone());
-                break;
-            }
-        }
-    }
-
-    mapping
+    score_columns(RATING_COLUMN_MAPPINGS, &[], false, headers)
+        .into_iter()
+        .map(|m| (m.field, m.header))
+        .collect()
 }
 
 /// Standard column names for eNPS import
@@ -456,19 +1233,10 @@ pub const ENPS_COLUMN_MAPPINGS: &[(&str, &[&str])] = &[
 
 /// Try to map parsed headers to eNPS fields
 pub fn map_enps_columns(headers: &[String]) -> HashMap<String, String> {
-    let mut mapping = HashMap::new();
-
-    for (standard_field, alternatives) in ENPS_COLUMN_MAPPINGS {
-        for header in headers {
-            let normalized = normalize_header(header);
-            if alternatives.contains(&normalized.as_str()) {
-                mapping.insert(standard_field.to_string(), header.clone());
-                break;
-            }
-        }
-    }
-
-    mapping
+    score_columns(ENPS_COLUMN_MAPPINGS, &[], false, headers)
+        .into_iter()
+        .map(|m| (m.field, m.header))
+        .collect()
 }
 
 #[cfg(test)]
@@ -494,7 +1262,7 @@ mod tests {
     #[test]
     fn test_parse_csv() {
         let csv_data = b"email,first_name,last_name\njohn@acme.com,John,Doe\njane@acme.com,Jane,Smith";
-        let result = parse_file(csv_data, "employees.csv").unwrap();
+        let result = parse_file(csv_data, "employees.csv", &ImportLimits::default()).unwrap();
 
         assert_eq!(result.headers.len(), 3);
         assert_eq!(result.rows.len(), 2);
@@ -502,6 +1270,105 @@ mod tests {
         assert_eq!(result.rows[0].get("email"), Some(&"john@acme.com".to_string()));
     }
 
+    #[test]
+    fn test_parse_csv_utf16le_bom() {
+        let text = "email,first_name,department\njos\u{e9}@acme.com,Jos\u{e9},R&D\n";
+        let mut data = vec![0xFF, 0xFE];
+        data.extend(text.encode_utf16().flat_map(u16::to_le_bytes));
+        let result = parse_file(&data, "employees.csv", &ImportLimits::default()).unwrap();
+
+        assert_eq!(result.encoding.as_deref(), Some("UTF-16LE"));
+        assert_eq!(result.delimiter.as_deref(), Some(","));
+        assert_eq!(result.headers, vec!["email", "first_name", "department"]);
+        assert_eq!(result.rows.len(), 1);
+        assert_eq!(result.rows[0]["first_name"], "Jos\u{e9}");
+    }
+
+    #[test]
+    fn test_parse_csv_detects_semicolon_delimiter() {
+        // European exports: semicolons, decimal commas, and a quoted comma
+        let data = b"email;first_name;salary\njohn@acme.com;John;\"55,000\"\njane@acme.com;Jane;61,5\n";
+        let result = parse_file(data, "employees.csv", &ImportLimits::default()).unwrap();
+
+        assert_eq!(result.delimiter.as_deref(), Some(";"));
+        assert_eq!(result.encoding.as_deref(), Some("UTF-8"));
+        assert_eq!(result.headers, vec!["email", "first_name", "salary"]);
+        assert_eq!(result.rows[0]["salary"], "55,000");
+        assert_eq!(result.rows[1]["salary"], "61,5");
+
+        // Latin-1 bytes fall back to Windows-1252
+        let result = parse_file(b"name,city\nRen\xe9e,Z\xfcrich\n", "people.csv", &ImportLimits::default()).unwrap();
+        assert_eq!(result.encoding.as_deref(), Some("Windows-1252"));
+        assert_eq!(result.rows[0]["city"], "Z\u{fc}rich");
+    }
+
+    #[test]
+    fn test_normalize_date_formats() {
+        let cases = [
+            ("2020-03-15", "2020-03-15"),
+            ("2020/03/15", "2020-03-15"),
+            ("2020.3.5", "2020-03-05"),
+            ("20200315", "2020-03-15"),
+            ("15-Mar-2020", "2020-03-15"),
+            ("15 March 2020", "2020-03-15"),
+            ("15-mar-20", "2020-03-15"),
+            ("Mar 15, 2020", "2020-03-15"),
+            ("March 15 2020", "2020-03-15"),
+            ("3/15/2020", "2020-03-15"),
+            ("3/15/20", "2020-03-15"),
+            ("15.03.2020", "2020-03-15"),
+            ("2020-03-15T09:30:00Z", "2020-03-15"),
+            ("3/15/2020 9:30 AM", "2020-03-15"),
+            ("  2020-03-15  ", "2020-03-15"),
+        ];
+        for (raw, expected) in cases {
+            assert_eq!(normalize_date(raw, DateLocale::Us).as_deref(), Some(expected), "{}", raw);
+            assert_eq!(normalize_date(raw, DateLocale::Eu).as_deref(), Some(expected), "{}", raw);
+        }
+
+        for raw in ["", "n/a", "13/13/2020", "2020-02-30", "15-Foo-2020", "0015-03-15"] {
+            assert_eq!(normalize_date(raw, DateLocale::Us), None, "{}", raw);
+        }
+    }
+
+    #[test]
+    fn test_normalize_date_ambiguous_uses_locale() {
+        assert_eq!(normalize_date("1/2/2020", DateLocale::Us).as_deref(), Some("2020-01-02"));
+        assert_eq!(normalize_date("1/2/2020", DateLocale::Eu).as_deref(), Some("2020-02-01"));
+        assert_eq!(normalize_date("01-02-20", DateLocale::Eu).as_deref(), Some("2020-02-01"));
+
+        let rows: Vec<ParsedRow> = [("1/2/2020", "a"), ("", "b"), ("soon", "c")]
+            .iter()
+            .map(|(date, name)| {
+                HashMap::from([("hire_date".to_string(), date.to_string()), ("name".to_string(), name.to_string())])
+            })
+            .collect();
+        let checks = check_date_column(&rows, "hire_date", DateLocale::Eu);
+        assert_eq!(
+            checks,
+            vec![
+                DateCheck { row: 2, raw: "1/2/2020".into(), normalized: Some("2020-02-01".into()) },
+                DateCheck { row: 4, raw: "soon".into(), normalized: None },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_normalize_date_two_digit_year_pivots_on_current_year() {
+        use chrono::Datelike;
+
+        assert_eq!(normalize_date("3/15/65", DateLocale::Us).as_deref(), Some("1965-03-15"));
+        assert_eq!(normalize_date("15-Mar-65", DateLocale::Us).as_deref(), Some("1965-03-15"));
+        assert_eq!(normalize_date("3/15/99", DateLocale::Us).as_deref(), Some("1999-03-15"));
+
+        // This year's two-digit form stays in this century
+        let year = crate::tenure::local_today().year();
+        let raw = format!("1/1/{:02}", year % 100);
+        assert_eq!(normalize_date(&raw, DateLocale::Us), Some(format!("{}-01-01", year)));
+        // Four-digit future years are taken as written
+        assert_eq!(normalize_date("3/15/2065", DateLocale::Us).as_deref(), Some("2065-03-15"));
+    }
+
     #[test]
     fn test_column_mapping() {
         let headers = vec![
@@ -517,4 +1384,326 @@ mod tests {
         assert_eq!(mapping.get("department"), Some(&"Dept".to_string()));
         assert_eq!(mapping.get("hire_date"), Some(&"Start Date".to_string()));
     }
+
+    fn scored(headers: &[&str]) -> HashMap<String, (String, f64)> {
+        let headers: Vec<String> = headers.iter().map(|h| h.to_string()).collect();
+        map_employee_columns_scored(&headers)
+            .into_iter()
+            .map(|m| (m.field, (m.header, m.map_confidence)))
+            .collect()
+    }
+
+    #[test]
+    fn test_column_mapping_ignores_case_spacing_and_punctuation() {
+        for header in ["Work_State", "work state", "WORK-STATE", "Work State:"] {
+            assert_eq!(scored(&[header])["work_state"], (header.to_string(), 1.0), "{}", header);
+        }
+    }
+
+    #[test]
+    fn test_column_mapping_workday_headers() {
+        let headers = [
+            "Employee ID",
+            "Worker",
+            "Legal Name - First Name",
+            "Legal Name - Last Name",
+            "Primary Work Email",
+            "Business Title",
+            "Supervisory Organization",
+            "Cost Center",
+            "Location",
+            "Work Address - State/Province",
+            "Hire Date",
+            "Original Hire Date",
+            "Manager",
+            "Active Status",
+            "Date of Birth",
+            "Gender",
+            "Race/Ethnicity",
+            "Time Type",
+        ];
+        let mapping = scored(&headers);
+        let expect = |field: &str, header: &str, confidence: f64| {
+            assert_eq!(mapping[field], (header.to_string(), confidence), "{}", field);
+        };
+        expect("email", "Primary Work Email", 0.9);
+        expect("first_name", "Legal Name - First Name", 0.9);
+        expect("last_name", "Legal Name - Last Name", 0.9);
+        expect("full_name", "Worker", 0.9);
+        expect("title", "Business Title", 0.9);
+        expect("department", "Supervisory Organization", 0.6);
+        expect("work_state", "Work Address - State/Province", 0.9);
+        expect("hire_date", "Hire Date", 1.0);
+        expect("status", "Active Status", 0.9);
+        expect("date_of_birth", "Date of Birth", 1.0);
+        expect("gender", "Gender", 1.0);
+        expect("ethnicity", "Race/Ethnicity", 0.9);
+        // Workday's "Manager" is a name, not an email
+        assert!(!mapping.contains_key("manager_email"));
+        assert_eq!(mapping.len(), EMPLOYEE_COLUMN_MAPPINGS.len() - 1);
+
+        // Headers as parse_file normalizes them map the same way
+        let normalized: Vec<String> = headers.iter().map(|h| normalize_header(h)).collect();
+        let by_normalized = map_employee_columns(&normalized);
+        for (field, (header, _)) in &mapping {
+            assert_eq!(by_normalized[field], normalize_header(header), "{}", field);
+        }
+    }
+
+    #[test]
+    fn test_column_mapping_bamboohr_headers() {
+        let headers = [
+            "Employee #",
+            "First Name",
+            "Last Name",
+            "Preferred Name",
+            "Work Email",
+            "Home Email",
+            "Job Title",
+            "Department",
+            "Division",
+            "Location",
+            "Hire Date",
+            "Status",
+            "Employment Status",
+            "Reporting to",
+            "Birth Date",
+            "Gender",
+            "Ethnicity",
+        ];
+        let mapping = scored(&headers);
+        let expect = |field: &str, header: &str, confidence: f64| {
+            assert_eq!(mapping[field], (header.to_string(), confidence), "{}", field);
+        };
+        expect("email", "Work Email", 0.9);
+        expect("first_name", "First Name", 1.0);
+        expect("last_name", "Last Name", 1.0);
+        expect("full_name", "Preferred Name", 0.6);
+        expect("title", "Job Title", 0.9);
+        expect("department", "Department", 1.0);
+        expect("work_state", "Location", 0.6);
+        expect("hire_date", "Hire Date", 1.0);
+        // "Status" is Active/Inactive; "Employment Status" is Full-Time etc.
+        expect("status", "Status", 1.0);
+        assert!(!mapping.contains_key("manager_email"));
+        expect("date_of_birth", "Birth Date", 0.9);
+        expect("gender", "Gender", 1.0);
+        expect("ethnicity", "Ethnicity", 1.0);
+
+        // Each header is used once
+        let mut used: Vec<&String> = mapping.values().map(|(h, _)| h).collect();
+        used.sort();
+        used.dedup();
+        assert_eq!(used.len(), mapping.len());
+    }
+
+    #[test]
+    fn test_column_mapping_partial_match_is_low_confidence() {
+        let mapping = scored(&["Primary Email (Work)", "Manager Email (Work)"]);
+        assert_eq!(mapping["email"].1, 0.4);
+        assert_eq!(mapping["manager_email"].1, 0.4);
+
+        // Rating and eNPS mappings stay exact-only
+        let headers = vec!["Employee ID".to_string(), "Score".to_string()];
+        assert!(!map_rating_columns(&headers).contains_key("employee_email"));
+        assert_eq!(map_enps_columns(&headers).get("score"), Some(&"Score".to_string()));
+    }
+
+    #[test]
+    fn test_byte_limit() {
+        let csv_data = b"email\na@acme.com\n";
+        let exact = ImportLimits { max_bytes: csv_data.len() as u64, ..Default::default() };
+        assert!(parse_file(csv_data, "e.csv", &exact).is_ok());
+
+        let under = ImportLimits { max_bytes: 16, ..Default::default() };
+        assert!(matches!(
+            parse_file(csv_data, "e.csv", &under),
+            Err(ParseError::FileTooLarge { size: 17, limit: 16 })
+        ));
+    }
+
+    #[test]
+    fn test_row_limit() {
+        let csv_data = b"email\na@acme.com\nb@acme.com\n\nc@acme.com";
+        let fits = ImportLimits { max_rows: 3, ..Default::default() };
+        assert_eq!(parse_file(csv_data, "e.csv", &fits).unwrap().total_rows, 3);
+
+        let too_few = ImportLimits { max_rows: 2, ..Default::default() };
+        assert!(matches!(
+            parse_file(csv_data, "e.csv", &too_few),
+            Err(ParseError::TooManyRows { limit: 2 })
+        ));
+    }
+
+    /// Minimal ZIP with one stored entry whose central directory claims
+    /// `uncompressed` bytes, enough to exercise the size estimate
+    fn fake_zip(uncompressed: u32) -> Vec<u8> {
+        let name = b"xl/worksheets/sheet1.xml";
+        let mut zip = Vec::new();
+        // Local file header (contents aren't inspected by the estimate)
+        zip.extend_from_slice(&[0x50, 0x4b, 0x03, 0x04]);
+        zip.extend_from_slice(&[0u8; 26]);
+        zip.extend_from_slice(name);
+        let central_offset = zip.len() as u32;
+        zip.extend_from_slice(&[0x50, 0x4b, 0x01, 0x02]);
+        zip.extend_from_slice(&[0u8; 16]);
+        zip.extend_from_slice(&0u32.to_le_bytes()); // compressed size
+        zip.extend_from_slice(&uncompressed.to_le_bytes());
+        zip.extend_from_slice(&(name.len() as u16).to_le_bytes());
+        zip.extend_from_slice(&[0u8; 14]);
+        zip.extend_from_slice(name);
+        let central_len = zip.len() as u32 - central_offset;
+        zip.extend_from_slice(&[0x50, 0x4b, 0x05, 0x06, 0, 0, 0, 0]);
+        zip.extend_from_slice(&1u16.to_le_bytes());
+        zip.extend_from_slice(&1u16.to_le_bytes());
+        zip.extend_from_slice(&central_len.to_le_bytes());
+        zip.extend_from_slice(&central_offset.to_le_bytes());
+        zip.extend_from_slice(&[0, 0]);
+        zip
+    }
+
+    #[test]
+    fn test_zip_uncompressed_size() {
+        assert_eq!(zip_uncompressed_size(&fake_zip(123_456)), Some(123_456));
+        assert_eq!(zip_uncompressed_size(b"not a zip"), None);
+    }
+
+    #[test]
+    fn test_xlsx_decompressed_size_limit() {
+        let limits = ImportLimits { max_bytes: 1_000, ..Default::default() };
+        assert!(matches!(
+            parse_file(&fake_zip(5_001), "big.xlsx", &limits),
+            Err(ParseError::FileTooLarge { size: 5_001, limit: 5_000 })
+        ));
+        // Just under the allowance gets past the size check (and then fails
+        // to open, since the fake archive holds no workbook)
+        assert!(matches!(
+            parse_file(&fake_zip(5_000), "small.xlsx", &limits),
+            Err(ParseError::ReadError(_))
+        ));
+    }
+
+    #[test]
+    fn test_import_limits_from_settings() {
+        assert_eq!(ImportLimits::from_settings(None, None), ImportLimits::default());
+        let limits = ImportLimits::from_settings(Some("2048"), Some(" 10 "));
+        assert_eq!(limits, ImportLimits { max_bytes: 2048, max_rows: 10 });
+        assert_eq!(ImportLimits::from_settings(Some("0"), Some("lots")), ImportLimits::default());
+    }
+
+    fn large_csv(rows: usize) -> Vec<u8> {
+        let mut csv = String::from("email,first_name,department\n");
+        for i in 0..rows {
+            csv.push_str(&format!("user{}@acme.com,User {},Dept {}\n", i, i, i % 7));
+            // Blank lines are skipped by both paths
+            if i % 1000 == 0 {
+                csv.push('\n');
+            }
+        }
+        csv.into_bytes()
+    }
+
+    #[test]
+    fn test_parse_file_chunked_matches_parse_file() {
+        let data = large_csv(25_003);
+        let limits = ImportLimits::default();
+        let whole = parse_file(&data, "big.csv", &limits).unwrap();
+
+        let mut chunk_sizes = Vec::new();
+        let mut starts = Vec::new();
+        let mut rows = Vec::new();
+        let summary = parse_file_chunked(&data, "big.csv", 1_000, &limits, |chunk| {
+            chunk_sizes.push(chunk.rows.len());
+            starts.push(chunk.start_row);
+            rows.extend(chunk.rows);
+            Ok(())
+        })
+        .unwrap();
+
+        assert_eq!(summary.total_rows, whole.total_rows);
+        assert_eq!(summary.total_rows, 25_003);
+        assert_eq!(summary.headers, whole.headers);
+        assert_eq!(summary.chunks, 26);
+        assert!(chunk_sizes[..25].iter().all(|n| *n == 1_000));
+        assert_eq!(chunk_sizes[25], 3);
+        assert_eq!(starts[1], 1_000);
+        assert_eq!(starts[25], 25_000);
+        assert_eq!(rows, whole.rows);
+
+        let preview = parse_file_preview(&data, "big.csv", Some(3), &limits).unwrap();
+        assert_eq!(preview.total_rows, 25_003);
+        assert_eq!(preview.preview_rows, whole.rows[..3].to_vec());
+    }
+
+    #[test]
+    fn test_parse_file_chunked_limits_and_callback_errors() {
+        let data = large_csv(50);
+        let limits = ImportLimits { max_rows: 40, ..Default::default() };
+        let mut delivered = 0;
+        let result = parse_file_chunked(&data, "e.csv", 16, &limits, |chunk| {
+            delivered += chunk.rows.len();
+            Ok(())
+        });
+        assert!(matches!(result, Err(ParseError::TooManyRows { limit: 40 })));
+        assert_eq!(delivered, 32);
+
+        let mut calls = 0;
+        let result = parse_file_chunked(&data, "e.csv", 10, &ImportLimits::default(), |_| {
+            calls += 1;
+            Err(ParseError::InvalidStructure("stop".to_string()))
+        });
+        assert!(matches!(result, Err(ParseError::InvalidStructure(_))));
+        assert_eq!(calls, 1);
+
+        assert!(matches!(
+            parse_file_chunked(b"email\n\n", "e.csv", 10, &ImportLimits::default(), |_| Ok(())),
+            Err(ParseError::NoData)
+        ));
+    }
+
+    #[test]
+    fn test_header_cells_convert_like_data_cells() {
+        use calamine::{ExcelDateTime, ExcelDateTimeType};
+
+        let date = Data::DateTime(ExcelDateTime::new(45292.0, ExcelDateTimeType::DateTime, false));
+        let text = cell_to_string(Some(&date));
+        assert!(text.starts_with("20") && text.len() == 10, "{}", text);
+        assert_eq!(header_from_cell(Some(&date), 0), normalize_header(&text));
+        assert_eq!(header_from_cell(Some(&Data::Int(2024)), 0), "2024");
+        assert_eq!(header_from_cell(Some(&Data::String("  ".into())), 2), "column_3");
+    }
+
+    #[test]
+    fn test_parse_file_chunked_streams_xlsx() {
+        let mut rows = vec![vec!["Email", "Full Name", ""]];
+        let emails: Vec<String> = (0..250).map(|i| format!("user{}@acme.com", i)).collect();
+        for (i, email) in emails.iter().enumerate() {
+            let extra = if i == 7 { "night shift" } else { "" };
+            rows.push(vec![email.as_str(), "Pat Doe", extra]);
+            if i == 100 {
+                rows.push(vec!["", "", ""]);
+            }
+        }
+        let data = crate::xlsx_export::build_workbook("Employees", &rows);
+        let limits = ImportLimits::default();
+        let whole = parse_file(&data, "staff.xlsx", &limits).unwrap();
+
+        let mut chunk_sizes = Vec::new();
+        let mut streamed = Vec::new();
+        let summary = parse_file_chunked(&data, "staff.xlsx", 100, &limits, |chunk| {
+            chunk_sizes.push(chunk.rows.len());
+            streamed.extend(chunk.rows);
+            Ok(())
+        })
+        .unwrap();
+
+        assert_eq!(whole.total_rows, 250);
+        assert_eq!(summary.total_rows, whole.total_rows);
+        assert_eq!(summary.headers, whole.headers);
+        assert_eq!(summary.headers, vec!["email", "full_name", "column_3"]);
+        assert_eq!(chunk_sizes, vec![100, 100, 50]);
+        assert_eq!(summary.file_format, "XLSX");
+        assert_eq!(streamed, whole.rows);
+        assert_eq!(streamed[7].get("column_3").map(String::as_str), Some("night shift"));
+    }
 }
@@ -3,6 +3,9 @@
 // Session 1: Types and CRUD operations
 // Session 2: Extraction pipeline with Claude API
 
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use futures::StreamExt;
 use serde::{Deserialize, Serialize};
 use sqlx::FromRow;
 use thiserror::Error;
@@ -11,6 +14,7 @@ use uuid::Uuid;
 use crate::chat::{ChatError, ChatMessage};
 use crate::db::DbPool;
 use crate::performance_reviews::PerformanceReview;
+use crate::settings::{self, SettingKey};
 
 // ============================================================================
 // Error Types
@@ -30,6 +34,8 @@ pub enum HighlightsError {
     JsonParse(String),
     #[error("Extraction error: {0}")]
     Extraction(String),
+    #[error("{}", crate::db::READ_ONLY_MESSAGE)]
+    ReadOnly,
 }
 
 impl From<sqlx::Error> for HighlightsError {
@@ -38,6 +44,12 @@ impl From<sqlx::Error> for HighlightsError {
     }
 }
 
+impl From<crate::db::ReadOnly> for HighlightsError {
+    fn from(_: crate::db::ReadOnly) -> Self {
+        HighlightsError::ReadOnly
+    }
+}
+
 impl From<serde_json::Error> for HighlightsError {
     fn from(err: serde_json::Error) -> Self {
         HighlightsError::JsonParse(err.to_string())
@@ -209,6 +221,18 @@ pub struct ExtractionProgress {
     pub completed: usize,
     pub failed: usize,
     pub is_running: bool,
+    /// Review that just finished (highlight extraction only)
+    #[serde(default)]
+    pub last_review_id: Option<String>,
+    /// Whether that review's extraction succeeded
+    #[serde(default)]
+    pub last_succeeded: Option<bool>,
+}
+
+/// Cancellation flag for the running highlight extraction, managed as app state
+#[derive(Debug, Default)]
+pub struct ExtractionControl {
+    pub cancel: AtomicBool,
 }
 
 // ============================================================================
@@ -240,7 +264,8 @@ fn to_json_string<T: Serialize>(value: &T) -> Result<String, HighlightsError> {
 // Valid Themes (whitelist)
 // ============================================================================
 
-/// Valid theme values that can be extracted
+/// Built-in theme vocabulary, used unless the `extraction_themes` setting
+/// supplies a company-specific list
 pub const VALID_THEMES: &[&str] = &[
     "leadership",
     "technical-growth",
@@ -254,11 +279,49 @@ pub const VALID_THEMES: &[&str] = &[
     "customer-focus",
 ];
 
-/// Filter themes to only include valid values
-pub fn filter_valid_themes(themes: Vec<String>) -> Vec<String> {
+/// The built-in vocabulary as owned strings
+pub fn default_themes() -> Vec<String> {
+    VALID_THEMES.iter().map(|t| t.to_string()).collect()
+}
+
+/// Parse the `extraction_themes` setting (a JSON array of strings).
+/// Blank entries and duplicates are dropped; returns None if nothing usable remains.
+pub fn parse_theme_vocabulary(raw: &str) -> Option<Vec<String>> {
+    let themes: Vec<String> = serde_json::from_str(raw).ok()?;
+    let mut vocabulary: Vec<String> = Vec::new();
+    for theme in themes {
+        let theme = theme.trim().to_lowercase();
+        if !theme.is_empty() && !vocabulary.contains(&theme) {
+            vocabulary.push(theme);
+        }
+    }
+    if vocabulary.is_empty() {
+        None
+    } else {
+        Some(vocabulary)
+    }
+}
+
+/// Active theme vocabulary: the configured list, or the built-in ten when
+/// the setting is unset or invalid
+pub async fn load_theme_vocabulary(pool: &DbPool) -> Vec<String> {
+    settings::get_setting(pool, SettingKey::ExtractionThemes.as_str())
+        .await
+        .ok()
+        .flatten()
+        .and_then(|raw| parse_theme_vocabulary(&raw))
+        .unwrap_or_else(default_themes)
+}
+
+/// Filter themes to only include values in the vocabulary (case-insensitive,
+/// returned in the vocabulary's spelling)
+pub fn filter_valid_themes(themes: Vec<String>, vocabulary: &[String]) -> Vec<String> {
     themes
         .into_iter()
-        .filter(|t| VALID_THEMES.contains(&t.as_str()))
+        .filter_map(|t| {
+            let t = t.trim().to_lowercase();
+            vocabulary.iter().find(|v| v.to_lowercase() == t).cloned()
+        })
         .collect()
 }
 
@@ -274,14 +337,30 @@ pub fn validate_sentiment(sentiment: &str) -> bool {
 /// Model to use for extraction (using faster model for batch processing)
 const EXTRACTION_MODEL: &str = "claude-sonnet-4-20250514";
 
-/// System prompt for extracting structured data from a performance review
-const EXTRACTION_SYSTEM_PROMPT: &str = r#"You are an HR data extraction system. Extract structured information from performance review text.
+/// Extraction calls in flight at once when `extraction_concurrency` is unset
+pub const DEFAULT_EXTRACTION_CONCURRENCY: usize = 4;
+
+/// Upper bound for the `extraction_concurrency` setting
+pub(crate) const MAX_EXTRACTION_CONCURRENCY: usize = 16;
+
+/// Sustained request rate for batch extraction, to stay under API rate limits
+const EXTRACTION_REQUESTS_PER_MINUTE: u32 = 40;
+
+/// Typical size of an extraction response (JSON with a handful of short lists)
+const EXPECTED_EXTRACTION_OUTPUT_TOKENS: usize = 400;
+
+/// Typical round-trip time for one extraction call
+const EXPECTED_EXTRACTION_CALL_SECS: f64 = 6.0;
+
+/// System prompt template for extracting structured data from a performance
+/// review; {THEMES} is replaced with the active theme vocabulary
+const EXTRACTION_SYSTEM_PROMPT_TEMPLATE: &str = r#"You are an HR data extraction system. Extract structured information from performance review text.
 
 Output ONLY valid JSON matching this schema:
 {
   "strengths": ["string array of 2-5 key strengths mentioned"],
   "opportunities": ["string array of 1-3 development areas mentioned"],
-  "themes": ["leadership", "technical-growth", "communication", "collaboration", "execution", "learning", "innovation", "mentoring", "problem-solving", "customer-focus"],
+  "themes": {THEMES},
   "quotes": [{"sentiment": "positive|negative|neutral", "text": "verbatim quote under 100 chars"}],
   "overall_sentiment": "positive|neutral|mixed|negative"
 }
@@ -293,6 +372,12 @@ Guidelines:
 - If a section has no content, use an empty array []
 - overall_sentiment should reflect the balance of positive vs negative feedback"#;
 
+/// Build the extraction system prompt for a theme vocabulary
+fn build_extraction_prompt(vocabulary: &[String]) -> String {
+    let themes = serde_json::to_string(vocabulary).unwrap_or_else(|_| "[]".to_string());
+    EXTRACTION_SYSTEM_PROMPT_TEMPLATE.replace("{THEMES}", &themes)
+}
+
 /// System prompt for generating employee career summaries
 const SUMMARY_SYSTEM_PROMPT: &str = r#"You are an HR analyst synthesizing performance review data into a career narrative.
 
@@ -349,7 +434,9 @@ pub async fn extract_highlights_for_review(
         content: user_prompt,
     }];
 
-    let response = chat::send_message(messages, Some(EXTRACTION_SYSTEM_PROMPT.to_string()))
+    let vocabulary = load_theme_vocabulary(pool).await;
+    let net = crate::network::NetworkConfig::load(pool).await;
+    let response = chat::send_message(messages, Some(build_extraction_prompt(&vocabulary)), None, &net, chat::DEFAULT_MAX_MESSAGE_TOKENS)
         .await
         .map_err(HighlightsError::from)?;
 
@@ -427,60 +514,440 @@ fn parse_extraction_response(content: &str) -> Result<ExtractionResponse, Highli
 
 /// Extract highlights for multiple reviews in batch
 /// Returns results for each review (success or error message)
-pub async fn extract_highlights_batch(
+///
+/// Progress is checkpointed under `job_id` (a new one is generated when None),
+/// so re-invoking with the same job id skips reviews already processed.
+/// `on_progress` is called after every review. Setting `cancel` stops the
+/// batch before the next review; the partial result can be resumed by job id.
+pub async fn extract_highlights_batch<P>(
     pool: &DbPool,
     review_ids: Vec<String>,
-) -> Result<BatchExtractionResult, HighlightsError> {
+    job_id: Option<String>,
+    cancel: &AtomicBool,
+    on_progress: P,
+) -> Result<BatchExtractionResult, HighlightsError>
+where
+    P: FnMut(&ExtractionProgress),
+{
     use crate::performance_reviews;
 
-    let mut result = BatchExtractionResult {
-        total: review_ids.len(),
-        succeeded: 0,
-        failed: 0,
-        errors: Vec::new(),
-    };
+    let job_id = job_id.unwrap_or_else(|| Uuid::new_v4().to_string());
+    let concurrency = load_extraction_concurrency(pool).await;
+    let limiter = RequestRateLimiter::new(concurrency, EXTRACTION_REQUESTS_PER_MINUTE);
+    let limiter = &limiter;
 
-    for review_id in review_ids {
-        // Get the review
-        let review = match performance_reviews::get_review(pool, &review_id).await {
-            Ok(r) => r,
-            Err(e) => {
-                result.failed += 1;
-                result.errors.push(format!("Review {}: {}", review_id, e));
-                continue;
-            }
-        };
+    run_extraction_job(pool, &job_id, review_ids, concurrency, cancel, on_progress, |review_id| async move {
+        let review = performance_reviews::get_review(pool, &review_id)
+            .await
+            .map_err(|e| HighlightsError::Database(e.to_string()))?;
 
         // Check if highlight already exists
         if let Ok(Some(_)) = get_highlight_for_review(pool, &review_id).await {
             // Already extracted, skip
-            result.succeeded += 1;
+            return Ok(());
+        }
+
+        limiter.acquire().await;
+        extract_highlights_for_review(pool, &review).await.map(|_| ())
+    })
+    .await
+}
+
+/// Calls to run at once: the `extraction_concurrency` setting, clamped to
+/// 1..=16, or the default when unset or invalid
+async fn load_extraction_concurrency(pool: &DbPool) -> usize {
+    settings::get_setting(pool, SettingKey::ExtractionConcurrency.as_str())
+        .await
+        .ok()
+        .flatten()
+        .and_then(|v| v.trim().parse::<usize>().ok())
+        .filter(|n| *n > 0)
+        .map(|n| n.min(MAX_EXTRACTION_CONCURRENCY))
+        .unwrap_or(DEFAULT_EXTRACTION_CONCURRENCY)
+}
+
+/// Token bucket shared by concurrent extraction calls: bursts of up to
+/// `capacity` requests, refilled at a steady per-minute rate
+struct RequestRateLimiter {
+    capacity: f64,
+    refill_per_sec: f64,
+    /// Tokens available and when they were last topped up
+    state: std::sync::Mutex<(f64, tokio::time::Instant)>,
+}
+
+impl RequestRateLimiter {
+    fn new(capacity: usize, per_minute: u32) -> Self {
+        let capacity = capacity.max(1) as f64;
+        Self {
+            capacity,
+            refill_per_sec: per_minute.max(1) as f64 / 60.0,
+            state: std::sync::Mutex::new((capacity, tokio::time::Instant::now())),
+        }
+    }
+
+    /// Wait until a request may be sent, then take its token
+    async fn acquire(&self) {
+        loop {
+            let wait = {
+                let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+                let now = tokio::time::Instant::now();
+                let elapsed = now.duration_since(state.1).as_secs_f64();
+                let tokens = (state.0 + elapsed * self.refill_per_sec).min(self.capacity);
+                if tokens >= 1.0 {
+                    *state = (tokens - 1.0, now);
+                    return;
+                }
+                *state = (tokens, now);
+                std::time::Duration::from_secs_f64((1.0 - tokens) / self.refill_per_sec)
+            };
+            tokio::time::sleep(wait).await;
+        }
+    }
+}
+
+/// Projected token usage, cost and duration of a batch extraction
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExtractionEstimate {
+    pub model: String,
+    /// Reviews that would be sent to the API
+    pub review_count: usize,
+    /// Reviews skipped because they already have highlights
+    pub already_extracted: usize,
+    /// Requested ids with no matching review
+    pub not_found: usize,
+    pub input_tokens: u64,
+    pub output_tokens: u64,
+    pub estimated_cost_usd: f64,
+    pub estimated_seconds: f64,
+}
+
+/// Estimate what `extract_highlights_batch` would cost for these reviews,
+/// without calling the API. Reviews that already have highlights are skipped
+/// by the batch, so they're excluded here too.
+pub async fn estimate_extraction_cost(
+    pool: &DbPool,
+    review_ids: Vec<String>,
+) -> Result<ExtractionEstimate, HighlightsError> {
+    use crate::performance_reviews;
+
+    let mut seen = std::collections::HashSet::new();
+    let mut pending = Vec::new();
+    let mut already_extracted = 0;
+    let mut not_found = 0;
+
+    for review_id in review_ids {
+        if !seen.insert(review_id.clone()) {
+            continue;
+        }
+        let Ok(review) = performance_reviews::get_review(pool, &review_id).await else {
+            not_found += 1;
+            continue;
+        };
+        if get_highlight_for_review(pool, &review_id).await?.is_some() {
+            already_extracted += 1;
             continue;
         }
+        pending.push(review);
+    }
 
-        // Extract highlights
-        match extract_highlights_for_review(pool, &review).await {
-            Ok(_) => result.succeeded += 1,
+    let vocabulary = load_theme_vocabulary(pool).await;
+    let system_prompt = build_extraction_prompt(&vocabulary);
+
+    let concurrency = load_extraction_concurrency(pool).await;
+    let mut estimate = estimate_for_reviews(&pending, &system_prompt, EXTRACTION_MODEL, concurrency);
+    estimate.already_extracted = already_extracted;
+    estimate.not_found = not_found;
+    Ok(estimate)
+}
+
+/// Token, cost and time projection for sending each review through extraction
+fn estimate_for_reviews(
+    reviews: &[PerformanceReview],
+    system_prompt: &str,
+    model: &str,
+    concurrency: usize,
+) -> ExtractionEstimate {
+    use crate::chat;
+    use crate::context::estimate_tokens;
+
+    let prompt_tokens = estimate_tokens(system_prompt);
+    let input_tokens: usize = reviews
+        .iter()
+        .map(|review| prompt_tokens + estimate_tokens(&format_review_for_extraction(review)))
+        .sum();
+    let output_tokens = reviews.len() * EXPECTED_EXTRACTION_OUTPUT_TOKENS;
+
+    let pricing = chat::model_pricing(model);
+    // Calls overlap up to the concurrency limit, but never faster than the rate limit
+    let per_call_secs = (EXPECTED_EXTRACTION_CALL_SECS / concurrency.max(1) as f64)
+        .max(60.0 / EXTRACTION_REQUESTS_PER_MINUTE as f64);
+
+    ExtractionEstimate {
+        model: model.to_string(),
+        review_count: reviews.len(),
+        already_extracted: 0,
+        not_found: 0,
+        input_tokens: input_tokens as u64,
+        output_tokens: output_tokens as u64,
+        estimated_cost_usd: pricing.cost_usd(input_tokens as u64, output_tokens as u64),
+        estimated_seconds: reviews.len() as f64 * per_call_secs,
+    }
+}
+
+/// Drive a checkpointed extraction job, calling `extract` for each review
+/// not yet recorded as processed, with up to `concurrency` calls in flight.
+/// Successes are persisted as they finish so an interruption loses at most
+/// the in-flight reviews; failures are retried on the next run. Errors are
+/// listed in request order whatever order the calls finish in.
+async fn run_extraction_job<F, Fut, P>(
+    pool: &DbPool,
+    job_id: &str,
+    review_ids: Vec<String>,
+    concurrency: usize,
+    cancel: &AtomicBool,
+    mut on_progress: P,
+    mut extract: F,
+) -> Result<BatchExtractionResult, HighlightsError>
+where
+    F: FnMut(String) -> Fut,
+    Fut: std::future::Future<Output = Result<(), HighlightsError>>,
+    P: FnMut(&ExtractionProgress),
+{
+    let mut processed = load_extraction_job(pool, job_id).await?;
+
+    // Cumulative total covers reviews from earlier runs plus any new ones
+    let mut seen = std::collections::HashSet::new();
+    let pending: Vec<String> = review_ids
+        .into_iter()
+        .filter(|id| !processed.contains(id) && seen.insert(id.clone()))
+        .collect();
+    let total = processed.len() + pending.len();
+    save_extraction_job(pool, job_id, &processed, total).await?;
+
+    let mut result = BatchExtractionResult {
+        job_id: job_id.to_string(),
+        total,
+        succeeded: processed.len(),
+        failed: 0,
+        errors: Vec::new(),
+        cancelled: false,
+    };
+    let mut progress = ExtractionProgress {
+        total,
+        completed: processed.len(),
+        is_running: true,
+        ..Default::default()
+    };
+
+    // Cancellation stops new calls from starting; in-flight ones finish
+    let pending_count = pending.len();
+    let mut attempted = 0;
+    let mut errors: Vec<(usize, String)> = Vec::new();
+    let mut outcomes = futures::stream::iter(pending.into_iter().enumerate())
+        .take_while(|_| futures::future::ready(!cancel.load(Ordering::SeqCst)))
+        .map(|(index, review_id)| {
+            let call = extract(review_id.clone());
+            async move { (index, review_id, call.await) }
+        })
+        .buffer_unordered(concurrency.max(1));
+
+    while let Some((index, review_id, outcome)) = outcomes.next().await {
+        attempted += 1;
+        let succeeded = match outcome {
+            Ok(()) => {
+                processed.push(review_id.clone());
+                save_extraction_job(pool, job_id, &processed, total).await?;
+                result.succeeded += 1;
+                true
+            }
             Err(e) => {
                 result.failed += 1;
-                result.errors.push(format!("Review {}: {}", review_id, e));
+                progress.failed += 1;
+                errors.push((index, format!("Review {}: {}", review_id, e)));
+                false
             }
-        }
+        };
 
-        // Small delay between API calls to avoid rate limiting
-        tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
+        progress.completed += 1;
+        progress.is_running = progress.completed < progress.total;
+        progress.last_review_id = Some(review_id);
+        progress.last_succeeded = Some(succeeded);
+        on_progress(&progress);
     }
 
+    errors.sort_by_key(|(index, _)| *index);
+    result.errors = errors.into_iter().map(|(_, error)| error).collect();
+    result.cancelled = attempted < pending_count;
     Ok(result)
 }
 
+/// Load processed review IDs for a job (empty if the job is new)
+async fn load_extraction_job(pool: &DbPool, job_id: &str) -> Result<Vec<String>, HighlightsError> {
+    let processed = sqlx::query_scalar::<_, String>(
+        "SELECT processed_review_ids FROM extraction_jobs WHERE id = ?"
+    )
+    .bind(job_id)
+    .fetch_optional(pool)
+    .await?;
+
+    match processed {
+        Some(json) => parse_json_array(&json),
+        None => Ok(Vec::new()),
+    }
+}
+
+/// Upsert the checkpoint for a job
+async fn save_extraction_job(
+    pool: &DbPool,
+    job_id: &str,
+    processed: &[String],
+    total: usize,
+) -> Result<(), HighlightsError> {
+    sqlx::query(
+        r#"INSERT INTO extraction_jobs (id, processed_review_ids, total)
+           VALUES (?, ?, ?)
+           ON CONFLICT(id) DO UPDATE SET
+               processed_review_ids = excluded.processed_review_ids,
+               total = excluded.total,
+               updated_at = datetime('now')"#,
+    )
+    .bind(job_id)
+    .bind(to_json_string(&processed)?)
+    .bind(total as i64)
+    .execute(pool)
+    .await?;
+    Ok(())
+}
+
 /// Result of batch extraction
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BatchExtractionResult {
+    /// Pass back to `extract_highlights_batch` to resume this job
+    pub job_id: String,
+    /// Counts are cumulative across every run of the job
     pub total: usize,
     pub succeeded: usize,
     pub failed: usize,
     pub errors: Vec<String>,
+    /// The run was stopped before every review was attempted
+    #[serde(default)]
+    pub cancelled: bool,
+}
+
+/// Attempts per employee before a batch summary is recorded as failed
+const SUMMARY_MAX_ATTEMPTS: u32 = 3;
+
+/// Wait before the first retry; doubles on each later attempt
+const SUMMARY_RETRY_BACKOFF_MS: u64 = 1000;
+
+/// Result of batch summary generation
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BatchSummaryResult {
+    pub total: usize,
+    pub succeeded: usize,
+    pub failed: usize,
+    /// Employees with no highlights to summarize
+    pub skipped_employee_ids: Vec<String>,
+    pub errors: Vec<String>,
+}
+
+/// Regenerate career summaries for several employees, one at a time.
+/// Employees without highlights are skipped and reported; API failures are
+/// retried with backoff, and each saved summary stays saved even if a later
+/// employee fails. `on_progress` is called after every employee.
+pub async fn generate_summaries_batch<P>(
+    pool: &DbPool,
+    employee_ids: Vec<String>,
+    on_progress: P,
+) -> Result<BatchSummaryResult, HighlightsError>
+where
+    P: FnMut(&ExtractionProgress),
+{
+    run_summary_batch(
+        pool,
+        employee_ids,
+        std::time::Duration::from_millis(SUMMARY_RETRY_BACKOFF_MS),
+        |employee_id| async move {
+            let result = generate_employee_summary(pool, &employee_id).await.map(|_| ());
+
+            // Small delay between API calls to avoid rate limiting
+            tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
+
+            result
+        },
+        on_progress,
+    )
+    .await
+}
+
+/// Drive a summary batch, calling `generate` for each employee that has
+/// highlights. Only extraction (API) errors are retried; database or
+/// validation errors fail the employee straight away.
+async fn run_summary_batch<F, Fut, P>(
+    pool: &DbPool,
+    employee_ids: Vec<String>,
+    backoff: std::time::Duration,
+    mut generate: F,
+    mut on_progress: P,
+) -> Result<BatchSummaryResult, HighlightsError>
+where
+    F: FnMut(String) -> Fut,
+    Fut: std::future::Future<Output = Result<(), HighlightsError>>,
+    P: FnMut(&ExtractionProgress),
+{
+    let mut seen = std::collections::HashSet::new();
+    let employee_ids: Vec<String> = employee_ids.into_iter().filter(|id| seen.insert(id.clone())).collect();
+
+    let mut result = BatchSummaryResult {
+        total: employee_ids.len(),
+        succeeded: 0,
+        failed: 0,
+        skipped_employee_ids: Vec::new(),
+        errors: Vec::new(),
+    };
+    let mut progress = ExtractionProgress {
+        total: employee_ids.len(),
+        is_running: true,
+        ..Default::default()
+    };
+
+    for employee_id in employee_ids {
+        let highlight_count: i64 =
+            sqlx::query_scalar("SELECT COUNT(*) FROM review_highlights WHERE employee_id = ?")
+                .bind(&employee_id)
+                .fetch_one(pool)
+                .await?;
+
+        if highlight_count == 0 {
+            result.skipped_employee_ids.push(employee_id);
+        } else {
+            let mut attempt = 1;
+            let outcome = loop {
+                match generate(employee_id.clone()).await {
+                    Err(HighlightsError::Extraction(_)) if attempt < SUMMARY_MAX_ATTEMPTS => {
+                        tokio::time::sleep(backoff * 2u32.pow(attempt - 1)).await;
+                        attempt += 1;
+                    }
+                    other => break other,
+                }
+            };
+
+            match outcome {
+                Ok(()) => result.succeeded += 1,
+                Err(e) => {
+                    result.failed += 1;
+                    progress.failed += 1;
+                    result.errors.push(format!("Employee {}: {}", employee_id, e));
+                }
+            }
+        }
+
+        progress.completed += 1;
+        progress.is_running = progress.completed < progress.total;
+        on_progress(&progress);
+    }
+
+    Ok(result)
 }
 
 /// Generate a career summary for an employee from their review highlights
@@ -514,7 +981,8 @@ pub async fn generate_employee_summary(
         content: user_prompt,
     }];
 
-    let response = chat::send_message(messages, Some(SUMMARY_SYSTEM_PROMPT.to_string()))
+    let net = crate::network::NetworkConfig::load(pool).await;
+    let response = chat::send_message(messages, Some(SUMMARY_SYSTEM_PROMPT.to_string()), None, &net, chat::DEFAULT_MAX_MESSAGE_TOKENS)
         .await
         .map_err(HighlightsError::from)?;
 
@@ -608,7 +1076,8 @@ pub async fn create_highlight(
     }
 
     let id = Uuid::new_v4().to_string();
-    let filtered_themes = filter_valid_themes(input.themes);
+    let vocabulary = load_theme_vocabulary(pool).await;
+    let filtered_themes = filter_valid_themes(input.themes, &vocabulary);
 
     sqlx::query(
         r#"
@@ -870,6 +1339,67 @@ pub async fn find_employees_pending_summary(
     Ok(rows)
 }
 
+// ============================================================================
+// Orphan Cleanup
+// ============================================================================
+// The foreign keys cascade, but rows written while enforcement was off (older
+// databases, manual edits) can outlive their review or employee.
+
+const ORPHANED_HIGHLIGHTS_WHERE: &str = r#"
+    review_id NOT IN (SELECT id FROM performance_reviews)
+    OR employee_id NOT IN (SELECT id FROM employees)
+"#;
+
+const ORPHANED_SUMMARIES_WHERE: &str = "employee_id NOT IN (SELECT id FROM employees)";
+
+/// Highlights whose review or employee no longer exists
+pub async fn find_orphaned_highlights(pool: &DbPool) -> Result<Vec<String>, HighlightsError> {
+    let ids = sqlx::query_scalar::<_, String>(&format!(
+        "SELECT id FROM review_highlights WHERE {} ORDER BY id",
+        ORPHANED_HIGHLIGHTS_WHERE
+    ))
+    .fetch_all(pool)
+    .await?;
+
+    Ok(ids)
+}
+
+/// Delete orphaned highlights, returning how many were removed
+pub async fn purge_orphaned_highlights(pool: &DbPool) -> Result<u64, HighlightsError> {
+    let result = sqlx::query(&format!(
+        "DELETE FROM review_highlights WHERE {}",
+        ORPHANED_HIGHLIGHTS_WHERE
+    ))
+    .execute(pool)
+    .await?;
+
+    Ok(result.rows_affected())
+}
+
+/// Summaries whose employee no longer exists
+pub async fn find_orphaned_summaries(pool: &DbPool) -> Result<Vec<String>, HighlightsError> {
+    let ids = sqlx::query_scalar::<_, String>(&format!(
+        "SELECT id FROM employee_summaries WHERE {} ORDER BY id",
+        ORPHANED_SUMMARIES_WHERE
+    ))
+    .fetch_all(pool)
+    .await?;
+
+    Ok(ids)
+}
+
+/// Delete orphaned summaries, returning how many were removed
+pub async fn purge_orphaned_summaries(pool: &DbPool) -> Result<u64, HighlightsError> {
+    let result = sqlx::query(&format!(
+        "DELETE FROM employee_summaries WHERE {}",
+        ORPHANED_SUMMARIES_WHERE
+    ))
+    .execute(pool)
+    .await?;
+
+    Ok(result.rows_affected())
+}
+
 // ============================================================================
 // Graceful Degradation Helpers
 // ============================================================================
@@ -974,30 +1504,59 @@ mod tests {
             "communication".to_string(),
             "not-a-theme".to_string(),
         ];
-        let filtered = filter_valid_themes(themes);
+        let filtered = filter_valid_themes(themes, &default_themes());
         assert_eq!(filtered, vec!["leadership", "communication"]);
     }
 
     #[test]
     fn test_filter_valid_themes_all_valid() {
         let themes = vec!["leadership".to_string(), "execution".to_string()];
-        let filtered = filter_valid_themes(themes);
+        let filtered = filter_valid_themes(themes, &default_themes());
         assert_eq!(filtered, vec!["leadership", "execution"]);
     }
 
     #[test]
     fn test_filter_valid_themes_all_invalid() {
         let themes = vec!["foo".to_string(), "bar".to_string()];
-        let filtered = filter_valid_themes(themes);
+        let filtered = filter_valid_themes(themes, &default_themes());
         assert!(filtered.is_empty());
     }
 
     #[test]
     fn test_filter_valid_themes_empty() {
-        let filtered = filter_valid_themes(vec![]);
+        let filtered = filter_valid_themes(vec![], &default_themes());
         assert!(filtered.is_empty());
     }
 
+    #[test]
+    fn test_filter_custom_theme_vocabulary() {
+        let vocabulary = parse_theme_vocabulary(r#"["Ownership", "customer-obsession", " ", "ownership"]"#).unwrap();
+        assert_eq!(vocabulary, vec!["ownership", "customer-obsession"]);
+
+        let themes = vec!["Ownership".to_string(), "leadership".to_string(), "customer-obsession".to_string()];
+        assert_eq!(filter_valid_themes(themes, &vocabulary), vec!["ownership", "customer-obsession"]);
+    }
+
+    #[test]
+    fn test_extraction_prompt_interpolates_vocabulary() {
+        let prompt = build_extraction_prompt(&["ownership".to_string(), "craft".to_string()]);
+        assert!(prompt.contains(r#""themes": ["ownership","craft"]"#));
+        assert!(!prompt.contains("{THEMES}"));
+        assert!(!prompt.contains("technical-growth"));
+    }
+
+    #[tokio::test]
+    async fn test_theme_vocabulary_defaults_when_unset_or_invalid() {
+        let pool = crate::db::test_pool().await;
+        assert_eq!(load_theme_vocabulary(&pool).await, default_themes());
+
+        settings::set_setting(&pool, "extraction_themes", "not json").await.unwrap();
+        assert_eq!(load_theme_vocabulary(&pool).await, default_themes());
+
+        settings::set_setting(&pool, "extraction_themes", r#"["ownership"]"#).await.unwrap();
+        assert_eq!(load_theme_vocabulary(&pool).await, vec!["ownership"]);
+    }
+
     // -------------------- JSON Serialization Tests --------------------
 
     #[test]
@@ -1216,4 +1775,391 @@ mod tests {
         assert!(formatted.contains("Development areas: communication"));
         assert!(formatted.contains("Quote (positive): \"Great work\""));
     }
+
+    // -------------------- Resumable Extraction Tests --------------------
+
+    #[tokio::test]
+    async fn test_extraction_job_resumes_remaining_reviews() {
+        let pool = crate::db::test_pool().await;
+        let review_ids: Vec<String> = (1..=6).map(|i| format!("review-{}", i)).collect();
+
+        // First run is "interrupted" after half: later reviews fail
+        let first = run_extraction_job(&pool, "job-1", review_ids.clone(), 1, &AtomicBool::new(false), |_| {}, |id| async move {
+            if id.as_str() <= "review-3" {
+                Ok(())
+            } else {
+                Err(HighlightsError::Extraction("connection lost".to_string()))
+            }
+        })
+        .await
+        .unwrap();
+        assert_eq!(first.total, 6);
+        assert_eq!(first.succeeded, 3);
+        assert_eq!(first.failed, 3);
+
+        // Re-run with the same job id only touches the remainder
+        let mut seen = Vec::new();
+        let second = run_extraction_job(&pool, "job-1", review_ids, 1, &AtomicBool::new(false), |_| {}, |id| {
+            seen.push(id);
+            async { Ok(()) }
+        })
+        .await
+        .unwrap();
+
+        assert_eq!(seen, vec!["review-4", "review-5", "review-6"]);
+        assert_eq!(second.job_id, "job-1");
+        assert_eq!(second.total, 6);
+        assert_eq!(second.succeeded, 6);
+        assert_eq!(second.failed, 0);
+    }
+
+    #[tokio::test]
+    async fn test_extraction_jobs_are_independent() {
+        let pool = crate::db::test_pool().await;
+        let ids = vec!["review-1".to_string()];
+
+        run_extraction_job(&pool, "job-a", ids.clone(), 1, &AtomicBool::new(false), |_| {}, |_| async { Ok(()) })
+            .await
+            .unwrap();
+
+        let mut calls = 0;
+        let result = run_extraction_job(&pool, "job-b", ids, 1, &AtomicBool::new(false), |_| {}, |_| {
+            calls += 1;
+            async { Ok(()) }
+        })
+        .await
+        .unwrap();
+
+        assert_eq!(calls, 1);
+        assert_eq!(result.total, 1);
+    }
+
+    #[tokio::test]
+    async fn test_extraction_job_reports_progress_and_stops_on_cancel() {
+        let pool = crate::db::test_pool().await;
+        let review_ids: Vec<String> = (1..=5).map(|i| format!("review-{}", i)).collect();
+        let cancel = AtomicBool::new(false);
+        let mut events = Vec::new();
+
+        let result = run_extraction_job(
+            &pool,
+            "job-cancel",
+            review_ids.clone(),
+            1,
+            &cancel,
+            |p| events.push(p.clone()),
+            |id| {
+                // User hits cancel while the second review is in flight
+                if id == "review-2" {
+                    cancel.store(true, Ordering::SeqCst);
+                }
+                async move {
+                    if id == "review-2" {
+                        Err(HighlightsError::Extraction("timeout".to_string()))
+                    } else {
+                        Ok(())
+                    }
+                }
+            },
+        )
+        .await
+        .unwrap();
+
+        assert!(result.cancelled);
+        assert_eq!(result.total, 5);
+        assert_eq!(result.succeeded, 1);
+        assert_eq!(result.failed, 1);
+
+        let reported: Vec<(Option<&str>, Option<bool>)> = events
+            .iter()
+            .map(|p| (p.last_review_id.as_deref(), p.last_succeeded))
+            .collect();
+        assert_eq!(reported, vec![(Some("review-1"), Some(true)), (Some("review-2"), Some(false))]);
+        assert_eq!(events[1].completed, 2);
+        assert_eq!(events[1].failed, 1);
+        assert!(events[1].is_running);
+
+        // Resuming picks up the rest, including the failed review
+        cancel.store(false, Ordering::SeqCst);
+        let resumed = run_extraction_job(&pool, "job-cancel", review_ids, 1, &cancel, |_| {}, |_| async { Ok(()) })
+            .await
+            .unwrap();
+        assert!(!resumed.cancelled);
+        assert_eq!(resumed.succeeded, 5);
+    }
+
+    // -------------------- Batch Summary Tests --------------------
+
+    async fn seed_highlight(pool: &DbPool, employee_id: &str) {
+        sqlx::query(
+            r#"
+            INSERT INTO employees (id, email, full_name) VALUES (?1, ?1 || '@example.com', ?1);
+            INSERT INTO review_cycles (id, name, cycle_type, start_date, end_date)
+                VALUES ('cycle-' || ?1, 'Cycle', 'annual', '2024-01-01', '2024-12-31');
+            INSERT INTO performance_reviews (id, employee_id, review_cycle_id)
+                VALUES ('review-' || ?1, ?1, 'cycle-' || ?1);
+            INSERT INTO review_highlights (id, review_id, employee_id, review_cycle_id, overall_sentiment)
+                VALUES ('hl-' || ?1, 'review-' || ?1, ?1, 'cycle-' || ?1, 'positive');
+            "#,
+        )
+        .bind(employee_id)
+        .execute(pool)
+        .await
+        .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_summary_batch_skips_employees_without_highlights() {
+        let pool = crate::db::test_pool().await;
+        seed_highlight(&pool, "emp-a").await;
+        seed_highlight(&pool, "emp-b").await;
+
+        let ids = ["emp-a", "emp-none", "emp-b", "emp-a"].map(String::from).to_vec();
+        let mut calls = Vec::new();
+        let mut updates = Vec::new();
+        let result = run_summary_batch(
+            &pool,
+            ids,
+            std::time::Duration::ZERO,
+            |id| {
+                calls.push(id);
+                async { Ok(()) }
+            },
+            |p| updates.push(p.clone()),
+        )
+        .await
+        .unwrap();
+
+        // Duplicates are collapsed, so each employee is processed once
+        assert_eq!(calls, vec!["emp-a", "emp-b"]);
+        assert_eq!(result.total, 3);
+        assert_eq!(result.succeeded, 2);
+        assert_eq!(result.skipped_employee_ids, vec!["emp-none"]);
+        assert_eq!(updates.len(), 3);
+        assert_eq!(updates.last().map(|p| (p.completed, p.is_running)), Some((3, false)));
+    }
+
+    #[tokio::test]
+    async fn test_summary_batch_retries_api_errors_then_records_failure() {
+        let pool = crate::db::test_pool().await;
+        seed_highlight(&pool, "emp-flaky").await;
+        seed_highlight(&pool, "emp-down").await;
+        seed_highlight(&pool, "emp-bad").await;
+
+        let attempts = std::cell::RefCell::new(std::collections::HashMap::<String, u32>::new());
+        let result = run_summary_batch(
+            &pool,
+            ["emp-flaky", "emp-down", "emp-bad"].map(String::from).to_vec(),
+            std::time::Duration::ZERO,
+            |id| {
+                let n = {
+                    let mut attempts = attempts.borrow_mut();
+                    let n = attempts.entry(id.clone()).or_default();
+                    *n += 1;
+                    *n
+                };
+                async move {
+                    match id.as_str() {
+                        "emp-flaky" if n == 1 => Err(HighlightsError::Extraction("overloaded".to_string())),
+                        "emp-down" => Err(HighlightsError::Extraction("overloaded".to_string())),
+                        "emp-bad" => Err(HighlightsError::JsonParse("garbled".to_string())),
+                        _ => Ok(()),
+                    }
+                }
+            },
+            |_| {},
+        )
+        .await
+        .unwrap();
+
+        let attempts = attempts.into_inner();
+        assert_eq!(attempts["emp-flaky"], 2);
+        assert_eq!(attempts["emp-down"], SUMMARY_MAX_ATTEMPTS);
+        // Non-API errors are not retried
+        assert_eq!(attempts["emp-bad"], 1);
+        assert_eq!(result.succeeded, 1);
+        assert_eq!(result.failed, 2);
+        assert!(result.errors[0].starts_with("Employee emp-down:"));
+    }
+
+    fn make_review(id: &str, strengths: &str) -> PerformanceReview {
+        PerformanceReview {
+            id: id.to_string(),
+            employee_id: "e1".to_string(),
+            review_cycle_id: "c1".to_string(),
+            strengths: Some(strengths.to_string()),
+            areas_for_improvement: None,
+            accomplishments: None,
+            goals_next_period: None,
+            manager_comments: None,
+            self_assessment: None,
+            reviewer_id: None,
+            review_date: None,
+            created_at: "2024-01-01".to_string(),
+            updated_at: "2024-01-01".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_estimate_scales_with_review_count() {
+        let prompt = build_extraction_prompt(&default_themes());
+        let one = estimate_for_reviews(&[make_review("r1", "Solid work")], &prompt, EXTRACTION_MODEL, DEFAULT_EXTRACTION_CONCURRENCY);
+        let three = estimate_for_reviews(
+            &[
+                make_review("r1", "Solid work"),
+                make_review("r2", "Solid work"),
+                make_review("r3", "Solid work"),
+            ],
+            &prompt,
+            EXTRACTION_MODEL,
+            DEFAULT_EXTRACTION_CONCURRENCY,
+        );
+
+        assert_eq!(three.review_count, 3);
+        assert_eq!(three.input_tokens, one.input_tokens * 3);
+        assert_eq!(three.output_tokens, one.output_tokens * 3);
+        assert!((three.estimated_cost_usd - one.estimated_cost_usd * 3.0).abs() < 1e-9);
+        assert!((three.estimated_seconds - one.estimated_seconds * 3.0).abs() < 1e-9);
+
+        let none = estimate_for_reviews(&[], &prompt, EXTRACTION_MODEL, DEFAULT_EXTRACTION_CONCURRENCY);
+        assert_eq!(none.input_tokens, 0);
+        assert_eq!(none.estimated_cost_usd, 0.0);
+    }
+
+    #[test]
+    fn test_estimate_scales_with_content_length() {
+        let prompt = build_extraction_prompt(&default_themes());
+        let short = estimate_for_reviews(&[make_review("r1", "Good")], &prompt, EXTRACTION_MODEL, DEFAULT_EXTRACTION_CONCURRENCY);
+        let long = estimate_for_reviews(
+            &[make_review("r1", &"Consistently strong delivery. ".repeat(200))],
+            &prompt,
+            EXTRACTION_MODEL,
+            DEFAULT_EXTRACTION_CONCURRENCY,
+        );
+
+        assert!(long.input_tokens > short.input_tokens + 1000);
+        assert_eq!(long.output_tokens, short.output_tokens);
+        assert!(long.estimated_cost_usd > short.estimated_cost_usd);
+
+        // Sonnet pricing: $3/M input, $15/M output
+        let pricing = crate::chat::model_pricing(EXTRACTION_MODEL);
+        let expected = (long.input_tokens as f64 * 3.0 + long.output_tokens as f64 * 15.0) / 1_000_000.0;
+        assert_eq!(pricing.input_per_million, 3.0);
+        assert!((long.estimated_cost_usd - expected).abs() < 1e-9);
+    }
+
+    #[tokio::test]
+    async fn test_find_and_purge_orphans() {
+        let pool = crate::db::test_pool().await;
+        sqlx::query("INSERT INTO employees (id, email, full_name) VALUES ('e1', 'e1@x.com', 'Kept Employee')")
+            .execute(&pool)
+            .await
+            .unwrap();
+        sqlx::query("INSERT INTO review_cycles (id, name, cycle_type, start_date, end_date) VALUES ('c1', '2024', 'annual', '2024-01-01', '2024-12-31')")
+            .execute(&pool)
+            .await
+            .unwrap();
+        sqlx::query("INSERT INTO performance_reviews (id, employee_id, review_cycle_id) VALUES ('r1', 'e1', 'c1')")
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        // Seed rows pointing at deleted parents, as an unenforced database would leave them
+        sqlx::query("PRAGMA foreign_keys = OFF").execute(&pool).await.unwrap();
+        sqlx::query(
+            r#"INSERT INTO review_highlights (id, review_id, employee_id, review_cycle_id) VALUES
+                ('h-ok', 'r1', 'e1', 'c1'),
+                ('h-no-review', 'r-gone', 'e1', 'c1'),
+                ('h-no-employee', 'r2', 'e-gone', 'c1')"#,
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+        sqlx::query(
+            "INSERT INTO employee_summaries (id, employee_id) VALUES ('s-ok', 'e1'), ('s-gone', 'e-gone')",
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+        sqlx::query("PRAGMA foreign_keys = ON").execute(&pool).await.unwrap();
+
+        assert_eq!(
+            find_orphaned_highlights(&pool).await.unwrap(),
+            vec!["h-no-employee".to_string(), "h-no-review".to_string()]
+        );
+        assert_eq!(find_orphaned_summaries(&pool).await.unwrap(), vec!["s-gone".to_string()]);
+
+        assert_eq!(purge_orphaned_highlights(&pool).await.unwrap(), 2);
+        assert_eq!(purge_orphaned_summaries(&pool).await.unwrap(), 1);
+        assert!(find_orphaned_highlights(&pool).await.unwrap().is_empty());
+        assert!(get_highlight_for_review(&pool, "r1").await.unwrap().is_some());
+        assert!(get_summary_for_employee(&pool, "e1").await.unwrap().is_some());
+    }
+
+    #[tokio::test]
+    async fn test_extraction_job_bounds_calls_in_flight() {
+        use std::sync::atomic::AtomicUsize;
+
+        let pool = crate::db::test_pool().await;
+        let review_ids: Vec<String> = (1..=10).map(|i| format!("review-{:02}", i)).collect();
+        let in_flight = AtomicUsize::new(0);
+        let max_in_flight = AtomicUsize::new(0);
+
+        let result = run_extraction_job(&pool, "job-parallel", review_ids, 3, &AtomicBool::new(false), |_| {}, |id| {
+            let (in_flight, max_in_flight) = (&in_flight, &max_in_flight);
+            async move {
+                let now = in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+                max_in_flight.fetch_max(now, Ordering::SeqCst);
+                // Later reviews finish first, so completion order differs from request order
+                let delay = if id == "review-02" { 60 } else { 10 };
+                tokio::time::sleep(std::time::Duration::from_millis(delay)).await;
+                in_flight.fetch_sub(1, Ordering::SeqCst);
+                if id == "review-02" || id == "review-07" {
+                    Err(HighlightsError::Extraction("rate limited".to_string()))
+                } else {
+                    Ok(())
+                }
+            }
+        })
+        .await
+        .unwrap();
+
+        assert_eq!(max_in_flight.load(Ordering::SeqCst), 3);
+        assert_eq!(result.total, 10);
+        assert_eq!(result.succeeded, 8);
+        assert_eq!(result.failed, 2);
+        assert!(!result.cancelled);
+        assert_eq!(
+            result.errors,
+            vec![
+                "Review review-02: Extraction error: rate limited".to_string(),
+                "Review review-07: Extraction error: rate limited".to_string(),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_rate_limiter_spaces_requests_after_burst() {
+        // Burst of 2, then one request every 50ms
+        let limiter = RequestRateLimiter::new(2, 1200);
+        let start = tokio::time::Instant::now();
+        limiter.acquire().await;
+        limiter.acquire().await;
+        assert!(start.elapsed() < std::time::Duration::from_millis(40));
+        limiter.acquire().await;
+        limiter.acquire().await;
+        assert!(start.elapsed() >= std::time::Duration::from_millis(95));
+    }
+
+    #[test]
+    fn test_estimate_accounts_for_concurrency() {
+        let prompt = build_extraction_prompt(&default_themes());
+        let reviews: Vec<PerformanceReview> = (1..=8).map(|i| make_review(&format!("r{}", i), "Solid work")).collect();
+        let serial = estimate_for_reviews(&reviews, &prompt, EXTRACTION_MODEL, 1);
+        let parallel = estimate_for_reviews(&reviews, &prompt, EXTRACTION_MODEL, 4);
+
+        assert!((serial.estimated_seconds - 8.0 * EXPECTED_EXTRACTION_CALL_SECS).abs() < 1e-9);
+        assert!((parallel.estimated_seconds - serial.estimated_seconds / 4.0).abs() < 1e-9);
+        assert_eq!(parallel.estimated_cost_usd, serial.estimated_cost_usd);
+    }
 }
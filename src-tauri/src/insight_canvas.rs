@@ -24,6 +24,8 @@ pub enum InsightCanvasError {
     AnnotationNotFound(String),
     #[error("Invalid input: {0}")]
     InvalidInput(String),
+    #[error("{}", crate::db::READ_ONLY_MESSAGE)]
+    ReadOnly,
 }
 
 impl From<sqlx::Error> for InsightCanvasError {
@@ -32,6 +34,12 @@ impl From<sqlx::Error> for InsightCanvasError {
     }
 }
 
+impl From<crate::db::ReadOnly> for InsightCanvasError {
+    fn from(_: crate::db::ReadOnly) -> Self {
+        InsightCanvasError::ReadOnly
+    }
+}
+
 impl serde::Serialize for InsightCanvasError {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
@@ -28,6 +28,20 @@ impl From<std::io::Error> for KeyringError {
 
 /// Get the path to the API key file
 fn get_key_path() -> Result<PathBuf, KeyringError> {
+    get_key_file(".api_key")
+}
+
+/// Get the path to the embeddings API key file
+fn get_embeddings_key_path() -> Result<PathBuf, KeyringError> {
+    get_key_file(".embeddings_api_key")
+}
+
+/// Get the path to the HTTP proxy password file
+fn get_proxy_password_path() -> Result<PathBuf, KeyringError> {
+    get_key_file(".proxy_password")
+}
+
+fn get_key_file(name: &str) -> Result<PathBuf, KeyringError> {
     let home = std::env::var("HOME")
         .map_err(|_| KeyringError::StorageAccess("Could not find home directory".into()))?;
     let app_dir = PathBuf::from(home)
@@ -38,7 +52,7 @@ fn get_key_path() -> Result<PathBuf, KeyringError> {
     // Ensure directory exists
     fs::create_dir_all(&app_dir)?;
 
-    Ok(app_dir.join(".api_key"))
+    Ok(app_dir.join(name))
 }
 
 // Make KeyringError serializable for Tauri commands
@@ -59,17 +73,23 @@ pub fn store_api_key(api_key: &str) -> Result<(), KeyringError> {
     }
 
     let path = get_key_path()?;
-    fs::write(&path, api_key)?;
+    write_key_file(&path, api_key)?;
+
+    println!("[keyring] API key stored to {:?}", path);
+    Ok(())
+}
+
+fn write_key_file(path: &PathBuf, key: &str) -> Result<(), KeyringError> {
+    fs::write(path, key)?;
 
     // Set restrictive permissions (owner read/write only)
     #[cfg(unix)]
     {
         use std::os::unix::fs::PermissionsExt;
         let perms = fs::Permissions::from_mode(0o600);
-        fs::set_permissions(&path, perms)?;
+        fs::set_permissions(path, perms)?;
     }
 
-    println!("[keyring] API key stored to {:?}", path);
     Ok(())
 }
 
@@ -97,6 +117,81 @@ pub fn has_api_key() -> bool {
     }
 }
 
+/// Store the Voyage AI API key used for memory embeddings
+pub fn store_embeddings_api_key(api_key: &str) -> Result<(), KeyringError> {
+    // Voyage keys start with "pa-"
+    let api_key = api_key.trim();
+    if !api_key.starts_with("pa-") {
+        return Err(KeyringError::InvalidFormat);
+    }
+
+    let path = get_embeddings_key_path()?;
+    write_key_file(&path, api_key)?;
+
+    println!("[keyring] Embeddings API key stored to {:?}", path);
+    Ok(())
+}
+
+/// Retrieve the embeddings API key
+pub fn get_embeddings_api_key() -> Result<String, KeyringError> {
+    let path = get_embeddings_key_path()?;
+    let key = fs::read_to_string(&path)?;
+    Ok(key.trim().to_string())
+}
+
+/// Delete the embeddings API key
+pub fn delete_embeddings_api_key() -> Result<(), KeyringError> {
+    let path = get_embeddings_key_path()?;
+    if path.exists() {
+        fs::remove_file(&path)?;
+    }
+    Ok(())
+}
+
+/// Check if an embeddings API key exists
+pub fn has_embeddings_api_key() -> bool {
+    match get_embeddings_key_path() {
+        Ok(path) => path.exists(),
+        Err(_) => false,
+    }
+}
+
+/// Store the password for the configured HTTP proxy
+pub fn store_proxy_password(password: &str) -> Result<(), KeyringError> {
+    if password.is_empty() {
+        return Err(KeyringError::InvalidFormat);
+    }
+
+    let path = get_proxy_password_path()?;
+    write_key_file(&path, password)?;
+
+    println!("[keyring] Proxy password stored to {:?}", path);
+    Ok(())
+}
+
+/// Retrieve the proxy password. Not trimmed: spaces can be part of a password.
+pub fn get_proxy_password() -> Result<String, KeyringError> {
+    let path = get_proxy_password_path()?;
+    Ok(fs::read_to_string(&path)?)
+}
+
+/// Delete the proxy password
+pub fn delete_proxy_password() -> Result<(), KeyringError> {
+    let path = get_proxy_password_path()?;
+    if path.exists() {
+        fs::remove_file(&path)?;
+    }
+    Ok(())
+}
+
+/// Check if a proxy password is stored
+pub fn has_proxy_password() -> bool {
+    match get_proxy_password_path() {
+        Ok(path) => path.exists(),
+        Err(_) => false,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -107,6 +202,12 @@ mod tests {
         assert!(matches!(result, Err(KeyringError::InvalidFormat)));
     }
 
+    #[test]
+    fn test_embeddings_key_invalid_format() {
+        let result = store_embeddings_api_key("sk-ant-test123");
+        assert!(matches!(result, Err(KeyringError::InvalidFormat)));
+    }
+
     #[test]
     fn test_valid_format_prefix() {
         let key = "sk-ant-test123";
@@ -1,7 +1,7 @@
 // HR Command Center - Rust Backend
 // This file contains the core library code for Tauri commands
 
-use tauri::Manager;
+use tauri::{Emitter, Manager};
 
 mod analytics;
 mod analytics_templates;
@@ -12,6 +12,7 @@ mod chat;
 mod company;
 mod context;
 mod conversations;
+mod csv_export;
 mod db;
 mod employees;
 mod enps;
@@ -19,13 +20,19 @@ mod file_parser;
 mod highlights;
 mod insight_canvas;
 mod keyring;
+mod maintenance;
 mod memory;
 mod network;
 mod performance_ratings;
 mod performance_reviews;
+mod pdf_export;
 mod pii;
+mod representation;
 mod review_cycles;
+mod review_templates;
 mod settings;
+mod tenure;
+mod xlsx_export;
 
 use db::Database;
 
@@ -43,6 +50,21 @@ fn check_db(state: tauri::State<'_, Database>) -> bool {
     true
 }
 
+/// Whether read-only (demo) mode is on
+#[tauri::command]
+fn get_read_only_mode(state: tauri::State<'_, Database>) -> bool {
+    state.is_read_only()
+}
+
+/// Turn read-only (demo) mode on or off. While on, commands that create,
+/// update, delete or import HR data fail with a ReadOnly error; chat history,
+/// audit entries and UI-only settings (`settings::writable_in_read_only`)
+/// still save so the app stays usable. Deleting conversations is refused.
+#[tauri::command]
+fn set_read_only_mode(state: tauri::State<'_, Database>, enabled: bool) {
+    state.set_read_only(enabled);
+}
+
 // ============================================================================
 // API Key Management Commands
 // ============================================================================
@@ -65,6 +87,42 @@ fn delete_api_key() -> Result<(), keyring::KeyringError> {
     keyring::delete_api_key()
 }
 
+/// Store the Voyage AI key used for semantic memory search
+#[tauri::command]
+fn store_embeddings_api_key(api_key: String) -> Result<(), keyring::KeyringError> {
+    keyring::store_embeddings_api_key(&api_key)
+}
+
+/// Check if an embeddings API key is configured
+#[tauri::command]
+fn has_embeddings_api_key() -> bool {
+    keyring::has_embeddings_api_key()
+}
+
+/// Delete the embeddings API key; memory search falls back to keywords
+#[tauri::command]
+fn delete_embeddings_api_key() -> Result<(), keyring::KeyringError> {
+    keyring::delete_embeddings_api_key()
+}
+
+/// Store the password for the configured HTTP proxy
+#[tauri::command]
+fn store_proxy_password(password: String) -> Result<(), keyring::KeyringError> {
+    keyring::store_proxy_password(&password)
+}
+
+/// Check if a proxy password is stored
+#[tauri::command]
+fn has_proxy_password() -> bool {
+    keyring::has_proxy_password()
+}
+
+/// Delete the proxy password
+#[tauri::command]
+fn delete_proxy_password() -> Result<(), keyring::KeyringError> {
+    keyring::delete_proxy_password()
+}
+
 /// Validate an API key format (does not store it)
 #[tauri::command]
 fn validate_api_key_format(api_key: String) -> bool {
@@ -78,10 +136,14 @@ fn validate_api_key_format(api_key: String) -> bool {
 /// Send a message to Claude and get a response (non-streaming)
 #[tauri::command]
 async fn send_chat_message(
+    state: tauri::State<'_, Database>,
     messages: Vec<chat::ChatMessage>,
     system_prompt: Option<String>,
+    model: Option<String>,
 ) -> Result<chat::ChatResponse, chat::ChatError> {
-    chat::send_message(messages, system_prompt).await
+    let max_message_tokens = chat::load_max_message_tokens(&state.pool).await;
+    let net = network::NetworkConfig::load(&state.pool).await;
+    chat::send_message(messages, system_prompt, model.as_deref(), &net, max_message_tokens).await
 }
 
 /// Send a message to Claude with streaming response
@@ -89,14 +151,42 @@ async fn send_chat_message(
 ///
 /// V2.1.4: Now accepts aggregates and query_type for answer verification
 #[tauri::command]
+#[allow(clippy::too_many_arguments)]
 async fn send_chat_message_streaming(
     app: tauri::AppHandle,
+    state: tauri::State<'_, Database>,
+    streams: tauri::State<'_, chat::ChatStreams>,
     messages: Vec<chat::ChatMessage>,
     system_prompt: Option<String>,
     aggregates: Option<context::OrgAggregates>,
     query_type: Option<context::QueryType>,
-) -> Result<(), chat::ChatError> {
-    chat::send_message_streaming(app, messages, system_prompt, aggregates, query_type).await
+    audit: chat::StreamAuditInput,
+    model: Option<String>,
+) -> Result<String, chat::ChatError> {
+    chat::send_message_streaming(
+        app,
+        &state.pool,
+        &streams,
+        audit,
+        messages,
+        system_prompt,
+        aggregates,
+        query_type,
+        model.as_deref(),
+    )
+    .await
+}
+
+/// Models the user can choose for a conversation
+#[tauri::command]
+fn list_available_models() -> Vec<chat::ModelInfo> {
+    chat::list_available_models()
+}
+
+/// Stop a streamed response; returns false if it had already finished
+#[tauri::command]
+fn cancel_chat_stream(streams: tauri::State<'_, chat::ChatStreams>, stream_id: String) -> bool {
+    streams.cancel(&stream_id)
 }
 
 // ============================================================================
@@ -105,14 +195,23 @@ async fn send_chat_message_streaming(
 
 /// Check if the network and Anthropic API are reachable
 #[tauri::command]
-async fn check_network_status() -> network::NetworkStatus {
-    network::check_network().await
+async fn check_network_status(state: tauri::State<'_, Database>) -> Result<network::NetworkStatus, String> {
+    let net = network::NetworkConfig::load(&state.pool).await;
+    Ok(network::check_network(&net).await)
 }
 
 /// Quick check if online (returns just a boolean)
 #[tauri::command]
-async fn is_online() -> bool {
-    network::is_online().await
+async fn is_online(state: tauri::State<'_, Database>) -> Result<bool, String> {
+    let net = network::NetworkConfig::load(&state.pool).await;
+    Ok(network::is_online(&net).await)
+}
+
+/// Check that the Anthropic API is reachable through the configured proxy
+#[tauri::command]
+async fn test_proxy_connection(state: tauri::State<'_, Database>) -> Result<network::ProxyCheck, String> {
+    let net = network::NetworkConfig::load(&state.pool).await;
+    network::test_proxy_connection(&net).await.map_err(|e| e.to_string())
 }
 
 // ============================================================================
@@ -122,8 +221,51 @@ async fn is_online() -> bool {
 /// Scan text for PII and return redaction result
 /// Used by frontend before sending messages to Claude API
 #[tauri::command]
-fn scan_pii(text: String) -> pii::RedactionResult {
-    pii::scan_and_redact(&text)
+async fn scan_pii(state: tauri::State<'_, Database>, text: String) -> Result<pii::RedactionResult, String> {
+    let custom = pii::load_custom_pii_patterns(&state.pool).await;
+    Ok(pii::scan_and_redact_with(&text, &custom))
+}
+
+/// Scan text for PII and swap each value for a numbered token, returning the
+/// token map so the frontend can restore the user's own view locally
+#[tauri::command]
+async fn scan_and_tokenize(state: tauri::State<'_, Database>, text: String) -> Result<pii::TokenizedResult, String> {
+    let custom = pii::load_custom_pii_patterns(&state.pool).await;
+    Ok(pii::scan_and_tokenize(&text, &custom))
+}
+
+/// Get the user-defined PII patterns applied on top of the built-ins
+#[tauri::command]
+async fn get_custom_pii_patterns(
+    state: tauri::State<'_, Database>,
+) -> Result<Vec<pii::CustomPiiPattern>, pii::PiiError> {
+    pii::get_custom_pii_patterns(&state.pool).await
+}
+
+/// Replace the user-defined PII patterns (each regex is validated first)
+#[tauri::command]
+async fn set_custom_pii_patterns(
+    state: tauri::State<'_, Database>,
+    patterns: Vec<pii::CustomPiiPattern>,
+) -> Result<(), pii::PiiError> {
+    state.ensure_writable()?;
+    pii::set_custom_pii_patterns(&state.pool, &patterns).await
+}
+
+/// Summarize PII across every user message in a conversation (read-only)
+#[tauri::command]
+async fn audit_pii_report(
+    state: tauri::State<'_, Database>,
+    messages_json: String,
+) -> Result<pii::PiiReport, pii::PiiError> {
+    let custom = pii::load_custom_pii_patterns(&state.pool).await;
+    pii::audit_pii_report(&messages_json, &custom)
+}
+
+/// Report PII stored in the company profile or settings (read-only)
+#[tauri::command]
+async fn scan_stored_pii(state: tauri::State<'_, Database>) -> Result<pii::StoredPiiReport, pii::PiiError> {
+    pii::scan_stored_pii(&state.pool).await
 }
 
 // ============================================================================
@@ -169,13 +311,75 @@ async fn count_audit_entries(
     audit::count_audit_entries(&state.pool, filter).await
 }
 
-/// Export audit log to CSV format
+/// Dry-run count of audit entries a retention purge would delete
+#[tauri::command]
+async fn count_audit_entries_before(
+    state: tauri::State<'_, Database>,
+    cutoff: chrono::DateTime<chrono::Utc>,
+) -> Result<audit::PurgePreview, audit::AuditError> {
+    audit::count_audit_entries_before(&state.pool, cutoff).await
+}
+
+/// Delete audit entries older than the cutoff; requires the preview's token
+#[tauri::command]
+async fn purge_audit_entries_before(
+    state: tauri::State<'_, Database>,
+    cutoff: chrono::DateTime<chrono::Utc>,
+    confirmation_token: String,
+) -> Result<audit::PurgeResult, audit::AuditError> {
+    state.ensure_writable()?;
+    audit::purge_audit_entries_before(&state.pool, cutoff, &confirmation_token).await
+}
+
+/// Export audit log to CSV format (PII-scrubbed unless options disable it)
 #[tauri::command]
 async fn export_audit_log(
     state: tauri::State<'_, Database>,
     filter: Option<audit::AuditFilter>,
+    options: Option<audit::AuditExportOptions>,
+    csv_options: Option<csv_export::CsvExportOptions>,
 ) -> Result<audit::ExportResult, audit::AuditError> {
-    audit::export_to_csv(&state.pool, filter).await
+    audit::export_to_csv(&state.pool, filter, options, csv_options).await
+}
+
+/// Export audit log as a paginated PDF (PII-scrubbed unless options disable it)
+#[tauri::command]
+async fn export_audit_log_pdf(
+    state: tauri::State<'_, Database>,
+    filter: Option<audit::AuditFilter>,
+    options: Option<audit::AuditExportOptions>,
+) -> Result<audit::PdfExportResult, audit::AuditError> {
+    audit::export_to_pdf(&state.pool, filter, options).await
+}
+
+/// Rank employees by how often they appeared in chat context
+#[tauri::command]
+async fn get_most_discussed_employees(
+    state: tauri::State<'_, Database>,
+    since: Option<String>,
+    limit: Option<usize>,
+) -> Result<Vec<audit::EmployeeMention>, audit::AuditError> {
+    audit::get_most_discussed_employees(&state.pool, since, limit).await
+}
+
+/// Re-run answer verification for one audit entry against its stored snapshot
+#[tauri::command]
+async fn reverify_audit_entry(
+    state: tauri::State<'_, Database>,
+    audit_id: String,
+) -> Result<context::VerificationResult, audit::AuditError> {
+    state.ensure_writable()?;
+    audit::reverify_audit_entry(&state.pool, &audit_id).await
+}
+
+/// Re-verify every audit entry matching the filter
+#[tauri::command]
+async fn reverify_audit_log(
+    state: tauri::State<'_, Database>,
+    filter: Option<audit::AuditFilter>,
+) -> Result<audit::ReverifyReport, audit::AuditError> {
+    state.ensure_writable()?;
+    audit::reverify_audit_log(&state.pool, filter).await
 }
 
 // ============================================================================
@@ -190,20 +394,33 @@ async fn has_company(
     company::has_company(&state.pool).await
 }
 
-/// Get the company profile
+/// Get a company profile; the primary one when no ID is given
 #[tauri::command]
 async fn get_company(
     state: tauri::State<'_, Database>,
+    company_id: Option<String>,
 ) -> Result<company::Company, company::CompanyError> {
-    company::get_company(&state.pool).await
+    match company_id {
+        Some(id) => company::get_company_by_id(&state.pool, &id).await,
+        None => company::get_company(&state.pool).await,
+    }
+}
+
+/// List all company profiles, primary first
+#[tauri::command]
+async fn list_companies(
+    state: tauri::State<'_, Database>,
+) -> Result<Vec<company::Company>, company::CompanyError> {
+    company::list_companies(&state.pool).await
 }
 
-/// Create or update the company profile
+/// Create or update a company profile
 #[tauri::command]
 async fn upsert_company(
     state: tauri::State<'_, Database>,
     input: company::UpsertCompany,
 ) -> Result<company::Company, company::CompanyError> {
+    state.ensure_writable()?;
     company::upsert_company(&state.pool, input).await
 }
 
@@ -225,6 +442,7 @@ async fn create_employee(
     state: tauri::State<'_, Database>,
     input: employees::CreateEmployee,
 ) -> Result<employees::Employee, employees::EmployeeError> {
+    state.ensure_writable()?;
     employees::create_employee(&state.pool, input).await
 }
 
@@ -237,6 +455,24 @@ async fn get_employee(
     employees::get_employee(&state.pool, &id).await
 }
 
+/// Get an employee's hire, rating, review, eNPS, and termination events in date order
+#[tauri::command]
+async fn get_employee_timeline(
+    state: tauri::State<'_, Database>,
+    employee_id: String,
+) -> Result<Vec<employees::TimelineEvent>, employees::EmployeeError> {
+    employees::get_employee_timeline(&state.pool, &employee_id).await
+}
+
+/// Department-to-department transfers since a date (all history when omitted)
+#[tauri::command]
+async fn get_internal_mobility(
+    state: tauri::State<'_, Database>,
+    since: Option<String>,
+) -> Result<employees::MobilityReport, employees::EmployeeError> {
+    employees::get_internal_mobility(&state.pool, since).await
+}
+
 /// Get an employee by email
 #[tauri::command]
 async fn get_employee_by_email(
@@ -253,6 +489,7 @@ async fn update_employee(
     id: String,
     input: employees::UpdateEmployee,
 ) -> Result<employees::Employee, employees::EmployeeError> {
+    state.ensure_writable()?;
     employees::update_employee(&state.pool, &id, input).await
 }
 
@@ -262,9 +499,30 @@ async fn delete_employee(
     state: tauri::State<'_, Database>,
     id: String,
 ) -> Result<(), employees::EmployeeError> {
+    state.ensure_writable()?;
     employees::delete_employee(&state.pool, &id).await
 }
 
+/// Place an employee under legal hold
+#[tauri::command]
+async fn set_legal_hold(
+    state: tauri::State<'_, Database>,
+    id: String,
+) -> Result<employees::Employee, employees::EmployeeError> {
+    state.ensure_writable()?;
+    employees::set_legal_hold(&state.pool, &id).await
+}
+
+/// Release an employee from legal hold
+#[tauri::command]
+async fn clear_legal_hold(
+    state: tauri::State<'_, Database>,
+    id: String,
+) -> Result<employees::Employee, employees::EmployeeError> {
+    state.ensure_writable()?;
+    employees::clear_legal_hold(&state.pool, &id).await
+}
+
 /// List employees with filtering
 #[tauri::command]
 async fn list_employees(
@@ -276,6 +534,17 @@ async fn list_employees(
     employees::list_employees(&state.pool, filter, limit, offset).await
 }
 
+/// Export the filtered roster as CSV or XLSX in the import column layout
+#[tauri::command]
+async fn export_employees(
+    state: tauri::State<'_, Database>,
+    filter: employees::EmployeeFilter,
+    format: employees::ExportFormat,
+    options: Option<employees::EmployeeExportOptions>,
+) -> Result<employees::EmployeeExport, employees::EmployeeError> {
+    employees::export_employees(&state.pool, filter, format, options).await
+}
+
 /// Get all unique departments
 #[tauri::command]
 async fn get_departments(
@@ -292,12 +561,49 @@ async fn get_employee_counts(
     employees::get_employee_counts(&state.pool).await
 }
 
+/// Compute each manager's direct and total report counts with wide/narrow flags
+#[tauri::command]
+async fn compute_span_of_control(
+    state: tauri::State<'_, Database>,
+    wide_threshold: Option<i64>,
+) -> Result<employees::SpanOfControl, employees::EmployeeError> {
+    employees::compute_span_of_control(&state.pool, wide_threshold).await
+}
+
+/// Reporting tree of active employees; manager_id cycles come back as warnings
+#[tauri::command]
+async fn get_org_chart(
+    state: tauri::State<'_, Database>,
+) -> Result<employees::OrgChart, employees::EmployeeError> {
+    employees::get_org_chart(&state.pool).await
+}
+
+/// Direct reports of a manager, or with `recursive` everyone below them
+#[tauri::command]
+async fn get_reports(
+    state: tauri::State<'_, Database>,
+    manager_id: String,
+    recursive: Option<bool>,
+) -> Result<Vec<employees::Employee>, employees::EmployeeError> {
+    employees::get_reports(&state.pool, &manager_id, recursive.unwrap_or(false)).await
+}
+
+/// Representation of active employees by gender or ethnicity (opt-in only)
+#[tauri::command]
+async fn compute_representation(
+    state: tauri::State<'_, Database>,
+    dimension: representation::Dimension,
+) -> Result<representation::RepresentationReport, representation::RepresentationError> {
+    representation::compute_representation(&state.pool, dimension).await
+}
+
 /// Bulk import employees (upsert by email)
 #[tauri::command]
 async fn import_employees(
     state: tauri::State<'_, Database>,
     employees: Vec<employees::CreateEmployee>,
 ) -> Result<employees::ImportResult, employees::EmployeeError> {
+    state.ensure_writable()?;
     employees::import_employees(&state.pool, employees).await
 }
 
@@ -311,6 +617,7 @@ async fn create_review_cycle(
     state: tauri::State<'_, Database>,
     input: review_cycles::CreateReviewCycle,
 ) -> Result<review_cycles::ReviewCycle, review_cycles::ReviewCycleError> {
+    state.ensure_writable()?;
     review_cycles::create_review_cycle(&state.pool, input).await
 }
 
@@ -330,6 +637,7 @@ async fn update_review_cycle(
     id: String,
     input: review_cycles::UpdateReviewCycle,
 ) -> Result<review_cycles::ReviewCycle, review_cycles::ReviewCycleError> {
+    state.ensure_writable()?;
     review_cycles::update_review_cycle(&state.pool, &id, input).await
 }
 
@@ -339,6 +647,7 @@ async fn delete_review_cycle(
     state: tauri::State<'_, Database>,
     id: String,
 ) -> Result<(), review_cycles::ReviewCycleError> {
+    state.ensure_writable()?;
     review_cycles::delete_review_cycle(&state.pool, &id).await
 }
 
@@ -365,9 +674,75 @@ async fn close_review_cycle(
     state: tauri::State<'_, Database>,
     id: String,
 ) -> Result<review_cycles::ReviewCycle, review_cycles::ReviewCycleError> {
+    state.ensure_writable()?;
     review_cycles::close_review_cycle(&state.pool, &id).await
 }
 
+/// Close active review cycles whose end date has passed
+#[tauri::command]
+async fn close_expired_cycles(
+    state: tauri::State<'_, Database>,
+) -> Result<Vec<String>, review_cycles::ReviewCycleError> {
+    state.ensure_writable()?;
+    review_cycles::close_expired_cycles(&state.pool, None).await
+}
+
+/// List review cycles whose dates are unparseable or out of order
+#[tauri::command]
+async fn find_invalid_cycle_dates(
+    state: tauri::State<'_, Database>,
+) -> Result<Vec<review_cycles::InvalidCycleDates>, review_cycles::ReviewCycleError> {
+    review_cycles::find_invalid_cycle_dates(&state.pool).await
+}
+
+/// Review cycles opening or closing this week, overdue, or misconfigured
+#[tauri::command]
+async fn get_cycle_reminders(
+    state: tauri::State<'_, Database>,
+) -> Result<Vec<review_cycles::CycleReminder>, review_cycles::ReviewCycleError> {
+    review_cycles::get_cycle_reminders(&state.pool, None).await
+}
+
+/// Status transitions for a review cycle, oldest first
+#[tauri::command]
+async fn get_cycle_history(
+    state: tauri::State<'_, Database>,
+    cycle_id: String,
+) -> Result<Vec<review_cycles::CycleEvent>, review_cycles::ReviewCycleError> {
+    review_cycles::get_cycle_history(&state.pool, &cycle_id).await
+}
+
+// ============================================================================
+// Review Template Commands
+// ============================================================================
+
+/// Create a review template
+#[tauri::command]
+async fn create_review_template(
+    state: tauri::State<'_, Database>,
+    input: review_templates::CreateReviewTemplate,
+) -> Result<review_templates::ReviewTemplate, review_templates::ReviewTemplateError> {
+    state.ensure_writable()?;
+    review_templates::create_review_template(&state.pool, input).await
+}
+
+/// List review templates by name
+#[tauri::command]
+async fn list_review_templates(
+    state: tauri::State<'_, Database>,
+) -> Result<Vec<review_templates::ReviewTemplate>, review_templates::ReviewTemplateError> {
+    review_templates::list_review_templates(&state.pool).await
+}
+
+/// Get the template attached to a review cycle, for pre-populating its reviews
+#[tauri::command]
+async fn get_template_for_cycle(
+    state: tauri::State<'_, Database>,
+    review_cycle_id: String,
+) -> Result<Option<review_templates::ReviewTemplate>, review_templates::ReviewTemplateError> {
+    review_templates::get_template_for_cycle(&state.pool, &review_cycle_id).await
+}
+
 // ============================================================================
 // Performance Rating Commands
 // ============================================================================
@@ -378,6 +753,7 @@ async fn create_performance_rating(
     state: tauri::State<'_, Database>,
     input: performance_ratings::CreateRating,
 ) -> Result<performance_ratings::PerformanceRating, performance_ratings::RatingError> {
+    state.ensure_writable()?;
     performance_ratings::create_rating(&state.pool, input).await
 }
 
@@ -399,6 +775,16 @@ async fn get_ratings_for_employee(
     performance_ratings::get_ratings_for_employee(&state.pool, &employee_id).await
 }
 
+/// Get an employee's ratings per cycle, oldest first, with the change since
+/// the previous rated cycle
+#[tauri::command]
+async fn get_rating_history(
+    state: tauri::State<'_, Database>,
+    employee_id: String,
+) -> Result<Vec<performance_ratings::RatingHistoryPoint>, performance_ratings::RatingError> {
+    performance_ratings::get_rating_history(&state.pool, &employee_id).await
+}
+
 /// Get all ratings for a review cycle
 #[tauri::command]
 async fn get_ratings_for_cycle(
@@ -424,6 +810,7 @@ async fn update_performance_rating(
     id: String,
     input: performance_ratings::UpdateRating,
 ) -> Result<performance_ratings::PerformanceRating, performance_ratings::RatingError> {
+    state.ensure_writable()?;
     performance_ratings::update_rating(&state.pool, &id, input).await
 }
 
@@ -433,6 +820,7 @@ async fn delete_performance_rating(
     state: tauri::State<'_, Database>,
     id: String,
 ) -> Result<(), performance_ratings::RatingError> {
+    state.ensure_writable()?;
     performance_ratings::delete_rating(&state.pool, &id).await
 }
 
@@ -454,6 +842,43 @@ async fn get_average_rating(
     performance_ratings::get_average_rating(&state.pool, &review_cycle_id).await
 }
 
+/// List active employees coming due for review within the next `days` days
+#[tauri::command]
+async fn get_upcoming_reviews(
+    state: tauri::State<'_, Database>,
+    days: i64,
+    cadence_months: u32,
+) -> Result<Vec<performance_ratings::EmployeeReviewDue>, performance_ratings::RatingError> {
+    performance_ratings::get_upcoming_reviews(&state.pool, days, cadence_months).await
+}
+
+/// Share of active employees rated within the cadence, overall and by department
+#[tauri::command]
+async fn get_review_coverage(
+    state: tauri::State<'_, Database>,
+    cadence_months: u32,
+) -> Result<performance_ratings::ReviewCoverage, performance_ratings::RatingError> {
+    performance_ratings::get_review_coverage(&state.pool, cadence_months).await
+}
+
+/// Days from hire to first rating for active employees
+#[tauri::command]
+async fn compute_first_rating_lead_time(
+    state: tauri::State<'_, Database>,
+    threshold_days: Option<i64>,
+) -> Result<performance_ratings::LeadTimeStats, performance_ratings::RatingError> {
+    performance_ratings::compute_first_rating_lead_time(&state.pool, threshold_days).await
+}
+
+/// Ratings in a cycle with z-scores relative to each reviewer's own scale
+#[tauri::command]
+async fn normalize_cycle_ratings(
+    state: tauri::State<'_, Database>,
+    review_cycle_id: String,
+) -> Result<Vec<performance_ratings::NormalizedRating>, performance_ratings::RatingError> {
+    performance_ratings::normalize_cycle_ratings(&state.pool, &review_cycle_id).await
+}
+
 // ============================================================================
 // Performance Review Commands
 // ============================================================================
@@ -463,6 +888,7 @@ async fn create_performance_review(
     state: tauri::State<'_, Database>,
     input: performance_reviews::CreateReview,
 ) -> Result<performance_reviews::PerformanceReview, performance_reviews::ReviewError> {
+    state.ensure_writable()?;
     performance_reviews::create_review(&state.pool, input).await
 }
 
@@ -496,6 +922,7 @@ async fn update_performance_review(
     id: String,
     input: performance_reviews::UpdateReview,
 ) -> Result<performance_reviews::PerformanceReview, performance_reviews::ReviewError> {
+    state.ensure_writable()?;
     performance_reviews::update_review(&state.pool, &id, input).await
 }
 
@@ -504,6 +931,7 @@ async fn delete_performance_review(
     state: tauri::State<'_, Database>,
     id: String,
 ) -> Result<(), performance_reviews::ReviewError> {
+    state.ensure_writable()?;
     performance_reviews::delete_review(&state.pool, &id).await
 }
 
@@ -515,6 +943,17 @@ async fn search_performance_reviews(
     performance_reviews::search_reviews(&state.pool, &query).await
 }
 
+/// Import reviews from a file, resolving review cycles by name
+#[tauri::command]
+async fn import_performance_reviews(
+    state: tauri::State<'_, Database>,
+    rows: Vec<performance_reviews::ImportReviewRow>,
+    create_missing_cycles: bool,
+) -> Result<performance_reviews::ReviewImportResult, performance_reviews::ReviewError> {
+    state.ensure_writable()?;
+    performance_reviews::import_reviews(&state.pool, rows, create_missing_cycles).await
+}
+
 // ============================================================================
 // Review Highlights Commands (V2.2.1)
 // ============================================================================
@@ -543,6 +982,7 @@ async fn extract_review_highlight(
     state: tauri::State<'_, Database>,
     review_id: String,
 ) -> Result<highlights::ReviewHighlight, highlights::HighlightsError> {
+    state.ensure_writable()?;
     let review = performance_reviews::get_review(&state.pool, &review_id)
         .await
         .map_err(|e| highlights::HighlightsError::Database(e.to_string()))?;
@@ -550,12 +990,37 @@ async fn extract_review_highlight(
 }
 
 /// Extract highlights for multiple reviews in batch
+/// Pass a previous result's job_id to resume an interrupted run
+/// Emits "highlights-extraction-progress" events after each review
 #[tauri::command]
 async fn extract_highlights_batch(
+    app: tauri::AppHandle,
     state: tauri::State<'_, Database>,
+    control: tauri::State<'_, highlights::ExtractionControl>,
     review_ids: Vec<String>,
+    job_id: Option<String>,
 ) -> Result<highlights::BatchExtractionResult, highlights::HighlightsError> {
-    highlights::extract_highlights_batch(&state.pool, review_ids).await
+    state.ensure_writable()?;
+    control.cancel.store(false, std::sync::atomic::Ordering::SeqCst);
+    highlights::extract_highlights_batch(&state.pool, review_ids, job_id, &control.cancel, |progress| {
+        let _ = app.emit("highlights-extraction-progress", progress);
+    })
+    .await
+}
+
+/// Stop the running highlight extraction once the reviews in flight finish
+#[tauri::command]
+fn cancel_highlights_extraction(control: tauri::State<'_, highlights::ExtractionControl>) {
+    control.cancel.store(true, std::sync::atomic::Ordering::SeqCst);
+}
+
+/// Estimate tokens, cost and duration of a batch extraction before running it
+#[tauri::command]
+async fn estimate_extraction_cost(
+    state: tauri::State<'_, Database>,
+    review_ids: Vec<String>,
+) -> Result<highlights::ExtractionEstimate, highlights::HighlightsError> {
+    highlights::estimate_extraction_cost(&state.pool, review_ids).await
 }
 
 /// Find reviews that need highlights extracted
@@ -581,9 +1046,25 @@ async fn generate_employee_summary(
     state: tauri::State<'_, Database>,
     employee_id: String,
 ) -> Result<highlights::EmployeeSummary, highlights::HighlightsError> {
+    state.ensure_writable()?;
     highlights::generate_employee_summary(&state.pool, &employee_id).await
 }
 
+/// Regenerate career summaries for many employees
+/// Emits "summary-batch-progress" events after each employee
+#[tauri::command]
+async fn generate_summaries_batch(
+    app: tauri::AppHandle,
+    state: tauri::State<'_, Database>,
+    employee_ids: Vec<String>,
+) -> Result<highlights::BatchSummaryResult, highlights::HighlightsError> {
+    state.ensure_writable()?;
+    highlights::generate_summaries_batch(&state.pool, employee_ids, |progress| {
+        let _ = app.emit("summary-batch-progress", progress);
+    })
+    .await
+}
+
 /// Invalidate highlight and summary when a review is updated
 #[tauri::command]
 async fn invalidate_review_highlight(
@@ -591,9 +1072,44 @@ async fn invalidate_review_highlight(
     review_id: String,
     employee_id: String,
 ) -> Result<(), highlights::HighlightsError> {
+    state.ensure_writable()?;
     highlights::invalidate_for_review(&state.pool, &review_id, &employee_id).await
 }
 
+/// Highlights whose review or employee no longer exists
+#[tauri::command]
+async fn find_orphaned_highlights(
+    state: tauri::State<'_, Database>,
+) -> Result<Vec<String>, highlights::HighlightsError> {
+    highlights::find_orphaned_highlights(&state.pool).await
+}
+
+/// Delete orphaned highlights, returning the number removed
+#[tauri::command]
+async fn purge_orphaned_highlights(
+    state: tauri::State<'_, Database>,
+) -> Result<u64, highlights::HighlightsError> {
+    state.ensure_writable()?;
+    highlights::purge_orphaned_highlights(&state.pool).await
+}
+
+/// Summaries whose employee no longer exists
+#[tauri::command]
+async fn find_orphaned_summaries(
+    state: tauri::State<'_, Database>,
+) -> Result<Vec<String>, highlights::HighlightsError> {
+    highlights::find_orphaned_summaries(&state.pool).await
+}
+
+/// Delete orphaned summaries, returning the number removed
+#[tauri::command]
+async fn purge_orphaned_summaries(
+    state: tauri::State<'_, Database>,
+) -> Result<u64, highlights::HighlightsError> {
+    state.ensure_writable()?;
+    highlights::purge_orphaned_summaries(&state.pool).await
+}
+
 // ============================================================================
 // eNPS Commands
 // ============================================================================
@@ -603,6 +1119,7 @@ async fn create_enps_response(
     state: tauri::State<'_, Database>,
     input: enps::CreateEnps,
 ) -> Result<enps::EnpsResponse, enps::EnpsError> {
+    state.ensure_writable()?;
     enps::create_enps(&state.pool, input).await
 }
 
@@ -635,6 +1152,7 @@ async fn delete_enps_response(
     state: tauri::State<'_, Database>,
     id: String,
 ) -> Result<(), enps::EnpsError> {
+    state.ensure_writable()?;
     enps::delete_enps(&state.pool, &id).await
 }
 
@@ -646,6 +1164,16 @@ async fn calculate_enps_score(
     enps::calculate_enps(&state.pool, &survey_name).await
 }
 
+/// eNPS for a survey weighted by department, with the unweighted score
+#[tauri::command]
+async fn calculate_weighted_enps(
+    state: tauri::State<'_, Database>,
+    survey_name: String,
+    weight_by: enps::WeightBy,
+) -> Result<enps::WeightedEnps, enps::EnpsError> {
+    enps::calculate_weighted_enps(&state.pool, &survey_name, weight_by).await
+}
+
 #[tauri::command]
 async fn get_latest_enps_for_employee(
     state: tauri::State<'_, Database>,
@@ -654,6 +1182,40 @@ async fn get_latest_enps_for_employee(
     enps::get_latest_enps(&state.pool, &employee_id).await
 }
 
+/// Correlate latest performance ratings with latest eNPS scores
+#[tauri::command]
+async fn compute_rating_enps_correlation(
+    state: tauri::State<'_, Database>,
+) -> Result<enps::CorrelationResult, enps::EnpsError> {
+    enps::compute_rating_enps_correlation(&state.pool).await
+}
+
+/// eNPS by department or tenure band; small segments are suppressed
+#[tauri::command]
+async fn calculate_enps_segmented(
+    state: tauri::State<'_, Database>,
+    dimension: enps::SegmentDimension,
+    min_responses: Option<i64>,
+) -> Result<enps::SegmentedEnps, enps::EnpsError> {
+    enps::calculate_enps_segmented(&state.pool, dimension, min_responses).await
+}
+
+/// eNPS for each survey in date order, with the change from the prior survey
+#[tauri::command]
+async fn get_enps_trend(
+    state: tauri::State<'_, Database>,
+) -> Result<Vec<enps::EnpsTrendPoint>, enps::EnpsError> {
+    enps::get_enps_trend(&state.pool).await
+}
+
+/// List eNPS responses with malformed or future survey dates
+#[tauri::command]
+async fn find_invalid_enps_dates(
+    state: tauri::State<'_, Database>,
+) -> Result<Vec<enps::InvalidEnpsDate>, enps::EnpsError> {
+    enps::find_invalid_enps_dates(&state.pool).await
+}
+
 // ============================================================================
 // Bulk Import Commands (Test Data)
 // ============================================================================
@@ -663,16 +1225,21 @@ async fn get_latest_enps_for_employee(
 async fn bulk_clear_data(
     state: tauri::State<'_, Database>,
 ) -> Result<(), bulk_import::ImportError> {
+    state.ensure_writable()?;
     bulk_import::clear_all_data(&state.pool).await
 }
 
-/// Bulk import review cycles with predefined IDs
+/// Bulk import review cycles with predefined IDs. Atomic unless
+/// `options.continue_on_error` is set; the same applies to the other
+/// bulk_import_* commands.
 #[tauri::command]
 async fn bulk_import_review_cycles(
     state: tauri::State<'_, Database>,
     cycles: Vec<bulk_import::ImportReviewCycle>,
+    options: Option<bulk_import::BulkImportOptions>,
 ) -> Result<bulk_import::BulkImportResult, bulk_import::ImportError> {
-    bulk_import::import_review_cycles(&state.pool, cycles).await
+    state.ensure_writable()?;
+    bulk_import::import_review_cycles(&state.pool, cycles, options.unwrap_or_default()).await
 }
 
 /// Bulk import employees with predefined IDs
@@ -680,8 +1247,10 @@ async fn bulk_import_review_cycles(
 async fn bulk_import_employees(
     state: tauri::State<'_, Database>,
     employees: Vec<bulk_import::ImportEmployee>,
+    options: Option<bulk_import::BulkImportOptions>,
 ) -> Result<bulk_import::BulkImportResult, bulk_import::ImportError> {
-    bulk_import::import_employees_bulk(&state.pool, employees).await
+    state.ensure_writable()?;
+    bulk_import::import_employees_bulk(&state.pool, employees, options.unwrap_or_default()).await
 }
 
 /// Bulk import performance ratings with predefined IDs
@@ -689,8 +1258,10 @@ async fn bulk_import_employees(
 async fn bulk_import_ratings(
     state: tauri::State<'_, Database>,
     ratings: Vec<bulk_import::ImportRating>,
+    options: Option<bulk_import::BulkImportOptions>,
 ) -> Result<bulk_import::BulkImportResult, bulk_import::ImportError> {
-    bulk_import::import_ratings_bulk(&state.pool, ratings).await
+    state.ensure_writable()?;
+    bulk_import::import_ratings_bulk(&state.pool, ratings, options.unwrap_or_default()).await
 }
 
 /// Bulk import performance reviews with predefined IDs
@@ -698,8 +1269,10 @@ async fn bulk_import_ratings(
 async fn bulk_import_reviews(
     state: tauri::State<'_, Database>,
     reviews: Vec<bulk_import::ImportReview>,
+    options: Option<bulk_import::BulkImportOptions>,
 ) -> Result<bulk_import::BulkImportResult, bulk_import::ImportError> {
-    bulk_import::import_reviews_bulk(&state.pool, reviews).await
+    state.ensure_writable()?;
+    bulk_import::import_reviews_bulk(&state.pool, reviews, options.unwrap_or_default()).await
 }
 
 /// Bulk import eNPS responses with predefined IDs
@@ -707,8 +1280,10 @@ async fn bulk_import_reviews(
 async fn bulk_import_enps(
     state: tauri::State<'_, Database>,
     responses: Vec<bulk_import::ImportEnps>,
+    options: Option<bulk_import::BulkImportOptions>,
 ) -> Result<bulk_import::BulkImportResult, bulk_import::ImportError> {
-    bulk_import::import_enps_bulk(&state.pool, responses).await
+    state.ensure_writable()?;
+    bulk_import::import_enps_bulk(&state.pool, responses, options.unwrap_or_default()).await
 }
 
 /// Verify data integrity after import
@@ -724,22 +1299,27 @@ async fn verify_data_integrity(
 // ============================================================================
 
 /// Parse a file (CSV, TSV, XLSX, XLS) and return all rows
+/// Rejects files over the configured max_import_bytes / max_import_rows
 #[tauri::command]
-fn parse_file(
+async fn parse_file(
+    state: tauri::State<'_, Database>,
     data: Vec<u8>,
     file_name: String,
 ) -> Result<file_parser::ParseResult, file_parser::ParseError> {
-    file_parser::parse_file(&data, &file_name)
+    let limits = file_parser::ImportLimits::load(&state.pool).await;
+    file_parser::parse_file(&data, &file_name, &limits)
 }
 
 /// Parse a file and return only a preview (first N rows)
 #[tauri::command]
-fn parse_file_preview(
+async fn parse_file_preview(
+    state: tauri::State<'_, Database>,
     data: Vec<u8>,
     file_name: String,
     preview_rows: Option<usize>,
 ) -> Result<file_parser::ParsePreview, file_parser::ParseError> {
-    file_parser::parse_file_preview(&data, &file_name, preview_rows)
+    let limits = file_parser::ImportLimits::load(&state.pool).await;
+    file_parser::parse_file_preview(&data, &file_name, preview_rows, &limits)
 }
 
 /// Get list of supported file extensions
@@ -762,6 +1342,22 @@ fn map_employee_columns(
     file_parser::map_employee_columns(&headers)
 }
 
+/// Map parsed headers to employee fields with a confidence for each match
+#[tauri::command]
+fn map_employee_columns_scored(headers: Vec<String>) -> Vec<file_parser::ColumnMatch> {
+    file_parser::map_employee_columns_scored(&headers)
+}
+
+/// Normalize a date column from a parsed import file, flagging unparseable values
+#[tauri::command]
+fn check_import_dates(
+    rows: Vec<file_parser::ParsedRow>,
+    column: String,
+    locale: Option<file_parser::DateLocale>,
+) -> Vec<file_parser::DateCheck> {
+    file_parser::check_date_column(&rows, &column, locale.unwrap_or_default())
+}
+
 /// Map parsed headers to rating fields
 #[tauri::command]
 fn map_rating_columns(
@@ -784,13 +1380,16 @@ fn map_enps_columns(
 
 /// Build chat context for a user message (extracts mentions, finds employees)
 /// If selected_employee_id is provided, that employee is always included first
+/// company_id scopes the company profile and aggregates to one entity
 #[tauri::command]
 async fn build_chat_context(
     state: tauri::State<'_, Database>,
     user_message: String,
     selected_employee_id: Option<String>,
+    company_id: Option<String>,
 ) -> Result<context::ChatContext, context::ContextError> {
-    context::build_chat_context(&state.pool, &user_message, selected_employee_id.as_deref()).await
+    context::build_chat_context(&state.pool, &user_message, selected_employee_id.as_deref(), company_id.as_deref())
+        .await
 }
 
 /// Get the system prompt for a chat message
@@ -802,8 +1401,15 @@ async fn get_system_prompt(
     state: tauri::State<'_, Database>,
     user_message: String,
     selected_employee_id: Option<String>,
+    company_id: Option<String>,
 ) -> Result<context::SystemPromptResult, context::ContextError> {
-    context::get_system_prompt_for_message(&state.pool, &user_message, selected_employee_id.as_deref()).await
+    context::get_system_prompt_for_message(
+        &state.pool,
+        &user_message,
+        selected_employee_id.as_deref(),
+        company_id.as_deref(),
+    )
+    .await
 }
 
 /// Get employee context by ID (for debugging/display)
@@ -815,20 +1421,32 @@ async fn get_employee_context(
     context::get_employee_context(&state.pool, &employee_id).await
 }
 
-/// Get company context
+/// Get company context, for one company or the primary profile
 #[tauri::command]
 async fn get_company_context(
     state: tauri::State<'_, Database>,
+    company_id: Option<String>,
 ) -> Result<Option<context::CompanyContext>, context::ContextError> {
-    context::get_company_context(&state.pool).await
+    context::get_company_context(&state.pool, company_id.as_deref()).await
 }
 
-/// Get aggregate eNPS score for the organization
+/// Get aggregate eNPS score for the organization or one company
 #[tauri::command]
 async fn get_aggregate_enps(
     state: tauri::State<'_, Database>,
+    company_id: Option<String>,
 ) -> Result<context::EnpsAggregate, context::ContextError> {
-    context::calculate_aggregate_enps(&state.pool).await
+    context::calculate_aggregate_enps(&state.pool, company_id.as_deref()).await
+}
+
+/// Rank employees side by side by rating or eNPS
+#[tauri::command]
+async fn compare_employees(
+    state: tauri::State<'_, Database>,
+    employee_ids: Vec<String>,
+    metric: context::CompareMetric,
+) -> Result<Vec<context::CompareRow>, context::ContextError> {
+    context::compare_employees(&state.pool, employee_ids, metric).await
 }
 
 // ============================================================================
@@ -856,6 +1474,7 @@ async fn create_insight_board(
     state: tauri::State<'_, Database>,
     input: insight_canvas::CreateBoardInput,
 ) -> Result<insight_canvas::InsightBoard, insight_canvas::InsightCanvasError> {
+    state.ensure_writable()?;
     insight_canvas::create_board(&state.pool, input).await
 }
 
@@ -875,6 +1494,7 @@ async fn update_insight_board(
     id: String,
     input: insight_canvas::UpdateBoardInput,
 ) -> Result<insight_canvas::InsightBoard, insight_canvas::InsightCanvasError> {
+    state.ensure_writable()?;
     insight_canvas::update_board(&state.pool, &id, input).await
 }
 
@@ -884,6 +1504,7 @@ async fn delete_insight_board(
     state: tauri::State<'_, Database>,
     id: String,
 ) -> Result<(), insight_canvas::InsightCanvasError> {
+    state.ensure_writable()?;
     insight_canvas::delete_board(&state.pool, &id).await
 }
 
@@ -901,6 +1522,7 @@ async fn pin_chart(
     state: tauri::State<'_, Database>,
     input: insight_canvas::PinChartInput,
 ) -> Result<insight_canvas::PinnedChart, insight_canvas::InsightCanvasError> {
+    state.ensure_writable()?;
     insight_canvas::pin_chart(&state.pool, input).await
 }
 
@@ -920,6 +1542,7 @@ async fn update_pinned_chart(
     id: String,
     input: insight_canvas::UpdatePinnedChartInput,
 ) -> Result<insight_canvas::PinnedChart, insight_canvas::InsightCanvasError> {
+    state.ensure_writable()?;
     insight_canvas::update_pinned_chart(&state.pool, &id, input).await
 }
 
@@ -929,6 +1552,7 @@ async fn unpin_chart(
     state: tauri::State<'_, Database>,
     id: String,
 ) -> Result<(), insight_canvas::InsightCanvasError> {
+    state.ensure_writable()?;
     insight_canvas::unpin_chart(&state.pool, &id).await
 }
 
@@ -938,6 +1562,7 @@ async fn create_chart_annotation(
     state: tauri::State<'_, Database>,
     input: insight_canvas::CreateAnnotationInput,
 ) -> Result<insight_canvas::ChartAnnotation, insight_canvas::InsightCanvasError> {
+    state.ensure_writable()?;
     insight_canvas::create_annotation(&state.pool, input).await
 }
 
@@ -957,6 +1582,7 @@ async fn update_chart_annotation(
     id: String,
     content: String,
 ) -> Result<insight_canvas::ChartAnnotation, insight_canvas::InsightCanvasError> {
+    state.ensure_writable()?;
     insight_canvas::update_annotation(&state.pool, &id, &content).await
 }
 
@@ -966,6 +1592,7 @@ async fn delete_chart_annotation(
     state: tauri::State<'_, Database>,
     id: String,
 ) -> Result<(), insight_canvas::InsightCanvasError> {
+    state.ensure_writable()?;
     insight_canvas::delete_annotation(&state.pool, &id).await
 }
 
@@ -995,44 +1622,35 @@ pub struct DigestData {
     pub new_hires: Vec<DigestEmployee>,
 }
 
-/// Get Monday Digest data (anniversaries and new hires)
+/// Get Monday Digest data (anniversaries and new hires).
+/// With `milestones_only`, only anniversaries in the milestone_years setting are listed;
+/// with `company_id`, only that company's employees.
 #[tauri::command]
 async fn get_digest_data(
     state: tauri::State<'_, Database>,
+    milestones_only: Option<bool>,
+    company_id: Option<String>,
 ) -> Result<DigestData, context::ContextError> {
-    use chrono::{NaiveDate, Utc, Datelike};
-
-    let today = Utc::now().date_naive();
+    let company_id = company_id.as_deref();
+    let today = tenure::local_today();
+    let milestones = if milestones_only.unwrap_or(false) {
+        Some(tenure::load_milestone_years(&state.pool).await)
+    } else {
+        None
+    };
 
     // Get anniversaries (within 7 days) - existing function returns 30-day window
-    let anniversary_contexts = context::find_upcoming_anniversaries(&state.pool, 50).await?;
+    let anniversary_contexts = context::find_upcoming_anniversaries(&state.pool, company_id, 50).await?;
 
     // Filter to 7 days and convert to DigestEmployee
     let anniversaries: Vec<DigestEmployee> = anniversary_contexts
         .into_iter()
         .filter_map(|emp| {
             let hire_date = emp.hire_date.as_ref()?;
-            let hire = NaiveDate::parse_from_str(hire_date, "%Y-%m-%d").ok()?;
-
-            // Calculate this year's anniversary date
-            let this_year_anniversary = NaiveDate::from_ymd_opt(today.year(), hire.month(), hire.day())?;
-
-            // Check if anniversary is within 7 days (handles year boundary)
-            let days_until = if this_year_anniversary >= today {
-                (this_year_anniversary - today).num_days()
-            } else {
-                // Anniversary already passed this year, check next year
-                let next_year_anniversary = NaiveDate::from_ymd_opt(today.year() + 1, hire.month(), hire.day())?;
-                (next_year_anniversary - today).num_days()
-            };
-
-            if days_until > 7 {
-                return None;
-            }
+            let hire = tenure::parse_hire_date(hire_date)?;
 
-            // Calculate years of tenure
-            let years = today.year() - hire.year();
-            let years_tenure = if this_year_anniversary > today { years } else { years + 1 };
+            // Years of tenure being celebrated at that anniversary
+            let years_tenure = tenure::upcoming_anniversary_years(hire, today, 7, milestones.as_deref())?;
 
             Some(DigestEmployee {
                 id: emp.id,
@@ -1046,14 +1664,14 @@ async fn get_digest_data(
         .collect();
 
     // Get new hires (last 90 days)
-    let new_hire_contexts = context::find_recent_hires(&state.pool, 90, 20).await?;
+    let new_hire_contexts = context::find_recent_hires(&state.pool, company_id, 90, 20).await?;
 
     let new_hires: Vec<DigestEmployee> = new_hire_contexts
         .into_iter()
         .filter_map(|emp| {
             let hire_date = emp.hire_date.as_ref()?;
-            let hire = NaiveDate::parse_from_str(hire_date, "%Y-%m-%d").ok()?;
-            let days = (today - hire).num_days() as i32;
+            let hire = tenure::parse_hire_date(hire_date)?;
+            let days = tenure::tenure_days(hire, today) as i32;
 
             Some(DigestEmployee {
                 id: emp.id,
@@ -1072,6 +1690,23 @@ async fn get_digest_data(
     })
 }
 
+/// Get tenure statistics for active employees, optionally in one company
+#[tauri::command]
+async fn get_tenure_stats(
+    state: tauri::State<'_, Database>,
+    company_id: Option<String>,
+) -> Result<tenure::TenureStats, tenure::TenureError> {
+    tenure::compute_tenure_stats(&state.pool, company_id.as_deref()).await
+}
+
+/// Explain why headcounts from different views disagree
+#[tauri::command]
+async fn reconcile_counts(
+    state: tauri::State<'_, Database>,
+) -> Result<context::ReconciliationReport, context::ContextError> {
+    context::reconcile_counts(&state.pool).await
+}
+
 // ============================================================================
 // Memory Commands (Cross-Conversation Memory)
 // ============================================================================
@@ -1079,9 +1714,11 @@ async fn get_digest_data(
 /// Generate a summary for a conversation using Claude
 #[tauri::command]
 async fn generate_conversation_summary(
+    state: tauri::State<'_, Database>,
     messages_json: String,
 ) -> Result<String, memory::MemoryError> {
-    memory::generate_summary(&messages_json).await
+    let net = network::NetworkConfig::load(&state.pool).await;
+    memory::generate_summary(&messages_json, &net).await
 }
 
 /// Save a summary to an existing conversation
@@ -1105,6 +1742,15 @@ async fn search_memories(
     memory::find_relevant_memories(&state.pool, &query, limit).await
 }
 
+/// Embed summaries saved before an embeddings key was configured
+#[tauri::command]
+async fn reindex_memory_embeddings(
+    state: tauri::State<'_, Database>,
+) -> Result<memory::ReindexResult, memory::MemoryError> {
+    state.ensure_writable()?;
+    memory::reindex_memory_embeddings(&state.pool).await
+}
+
 // ============================================================================
 // Conversation Management Commands
 // ============================================================================
@@ -1143,10 +1789,11 @@ async fn list_conversations(
     state: tauri::State<'_, Database>,
     limit: Option<i64>,
     offset: Option<i64>,
+    include_archived: Option<bool>,
 ) -> Result<Vec<conversations::ConversationListItem>, conversations::ConversationError> {
     let limit = limit.unwrap_or(50);
     let offset = offset.unwrap_or(0);
-    conversations::list_conversations(&state.pool, limit, offset).await
+    conversations::list_conversations(&state.pool, limit, offset, include_archived.unwrap_or(false)).await
 }
 
 /// Search conversations using FTS
@@ -1166,15 +1813,67 @@ async fn delete_conversation(
     state: tauri::State<'_, Database>,
     id: String,
 ) -> Result<(), conversations::ConversationError> {
+    state.ensure_writable()?;
     conversations::delete_conversation(&state.pool, &id).await
 }
 
+/// Render a conversation as Markdown for sharing
+#[tauri::command]
+async fn export_conversation_markdown(
+    state: tauri::State<'_, Database>,
+    id: String,
+) -> Result<String, conversations::ConversationError> {
+    conversations::export_conversation_markdown(&state.pool, &id).await
+}
+
+/// Pin a conversation to the top of the sidebar
+#[tauri::command]
+async fn pin_conversation(
+    state: tauri::State<'_, Database>,
+    id: String,
+) -> Result<(), conversations::ConversationError> {
+    conversations::set_conversation_pinned(&state.pool, &id, true).await
+}
+
+/// Unpin a conversation
+#[tauri::command]
+async fn unpin_conversation(
+    state: tauri::State<'_, Database>,
+    id: String,
+) -> Result<(), conversations::ConversationError> {
+    conversations::set_conversation_pinned(&state.pool, &id, false).await
+}
+
+/// Archive a conversation, hiding it from the default list
+#[tauri::command]
+async fn archive_conversation(
+    state: tauri::State<'_, Database>,
+    id: String,
+) -> Result<(), conversations::ConversationError> {
+    conversations::set_conversation_archived(&state.pool, &id, true).await
+}
+
+/// Tag, untag, archive, or delete many conversations in one transaction
+#[tauri::command]
+async fn bulk_conversation_op(
+    state: tauri::State<'_, Database>,
+    ids: Vec<String>,
+    op: conversations::ConversationOp,
+) -> Result<conversations::BatchResult, conversations::ConversationError> {
+    if matches!(op, conversations::ConversationOp::Delete) {
+        state.ensure_writable()?;
+    }
+    conversations::bulk_conversation_op(&state.pool, ids, op).await
+}
+
 /// Generate a title for a conversation
 #[tauri::command]
 async fn generate_conversation_title(
+    state: tauri::State<'_, Database>,
     first_message: String,
 ) -> Result<String, conversations::ConversationError> {
-    Ok(conversations::generate_title_with_fallback(&first_message).await)
+    let net = network::NetworkConfig::load(&state.pool).await;
+    Ok(conversations::generate_title_with_fallback(&first_message, &net).await)
 }
 
 // ============================================================================
@@ -1197,7 +1896,10 @@ async fn set_setting(
     key: String,
     value: String,
 ) -> Result<(), settings::SettingsError> {
-    settings::set_setting(&state.pool, &key, &value).await
+    if !settings::writable_in_read_only(&key) {
+        state.ensure_writable()?;
+    }
+    settings::set_setting_validated(&state.pool, &key, &value).await
 }
 
 /// Delete a setting by key
@@ -1206,6 +1908,9 @@ async fn delete_setting(
     state: tauri::State<'_, Database>,
     key: String,
 ) -> Result<(), settings::SettingsError> {
+    if !settings::writable_in_read_only(&key) {
+        state.ensure_writable()?;
+    }
     settings::delete_setting(&state.pool, &key).await
 }
 
@@ -1218,6 +1923,24 @@ async fn has_setting(
     settings::has_setting(&state.pool, &key).await
 }
 
+/// Export portable settings (persona, display name, preferences) as JSON
+#[tauri::command]
+async fn export_config(
+    state: tauri::State<'_, Database>,
+) -> Result<String, settings::SettingsError> {
+    settings::export_config(&state.pool).await
+}
+
+/// Apply settings exported from another device
+#[tauri::command]
+async fn import_config(
+    state: tauri::State<'_, Database>,
+    json: String,
+) -> Result<settings::ConfigImportResult, settings::SettingsError> {
+    state.ensure_writable()?;
+    settings::import_config(&state.pool, &json).await
+}
+
 // ============================================================================
 // Persona Commands (V2.1.3)
 // ============================================================================
@@ -1240,6 +1963,27 @@ fn get_data_path(app: tauri::AppHandle) -> Result<String, String> {
     Ok(path.to_string_lossy().to_string())
 }
 
+/// Report columns the code expects that are missing from the database
+/// (e.g. after a partial upgrade)
+#[tauri::command]
+async fn check_schema_columns(
+    state: tauri::State<'_, Database>,
+) -> Result<Vec<db::MissingColumn>, String> {
+    db::check_schema_columns(&state.pool)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Describe every table's columns and foreign keys (data dictionary)
+#[tauri::command]
+async fn export_schema(
+    state: tauri::State<'_, Database>,
+) -> Result<db::SchemaDoc, String> {
+    db::export_schema(&state.pool)
+        .await
+        .map_err(|e| e.to_string())
+}
+
 // ============================================================================
 // Backup & Restore Commands
 // ============================================================================
@@ -1249,8 +1993,35 @@ fn get_data_path(app: tauri::AppHandle) -> Result<String, String> {
 async fn export_backup(
     state: tauri::State<'_, Database>,
     password: String,
+    options: Option<backup::BackupExportOptions>,
 ) -> Result<backup::ExportResult, backup::BackupError> {
-    backup::export_backup(&state.pool, &password).await
+    backup::export_backup(&state.pool, &password, options).await
+}
+
+/// Export rows changed since a point in time to an encrypted incremental backup
+#[tauri::command]
+async fn export_incremental_backup(
+    state: tauri::State<'_, Database>,
+    password: String,
+    since: chrono::DateTime<chrono::Utc>,
+) -> Result<backup::ExportResult, backup::BackupError> {
+    backup::export_incremental_backup(&state.pool, &password, since).await
+}
+
+/// Estimate backup password strength (0-4) with improvement suggestions
+#[tauri::command]
+fn estimate_backup_password_strength(password: String) -> backup::PasswordStrength {
+    backup::estimate_password_strength(&password)
+}
+
+/// Re-encrypt a backup file under a new password
+#[tauri::command]
+fn rotate_backup_password(
+    encrypted_data: Vec<u8>,
+    old_password: String,
+    new_password: String,
+) -> Result<Vec<u8>, backup::BackupError> {
+    backup::rotate_backup_password(&encrypted_data, &old_password, &new_password)
 }
 
 /// Validate a backup file and return its metadata (without importing)
@@ -1262,45 +2033,119 @@ fn validate_backup(
     backup::validate_backup(&encrypted_data, &password)
 }
 
-/// Import data from an encrypted backup, replacing all existing data
+/// Import data from an encrypted backup, replacing existing data in the selected tables
 #[tauri::command]
 async fn import_backup(
     state: tauri::State<'_, Database>,
     encrypted_data: Vec<u8>,
     password: String,
+    options: Option<backup::ImportOptions>,
+) -> Result<backup::ImportResult, backup::BackupError> {
+    state.ensure_writable()?;
+    backup::import_backup(&state.pool, &encrypted_data, &password, options).await
+}
+
+/// Merge a backup into existing data, skipping rows that already exist
+#[tauri::command]
+async fn import_backup_merge(
+    state: tauri::State<'_, Database>,
+    encrypted_data: Vec<u8>,
+    password: String,
 ) -> Result<backup::ImportResult, backup::BackupError> {
-    backup::import_backup(&state.pool, &encrypted_data, &password).await
+    state.ensure_writable()?;
+    backup::import_backup_merge(&state.pool, &encrypted_data, &password).await
+}
+
+// ============================================================================
+// Maintenance Commands
+// ============================================================================
+
+/// Recompute derived columns and indexes after bulk operations
+#[tauri::command]
+async fn recalculate_derived(
+    state: tauri::State<'_, Database>,
+) -> Result<maintenance::RecalcReport, maintenance::MaintenanceError> {
+    state.ensure_writable()?;
+    maintenance::recalculate_derived(&state.pool).await
+}
+
+/// Rebuild the database file to reclaim space after large deletes
+#[tauri::command]
+async fn vacuum_database(
+    state: tauri::State<'_, Database>,
+) -> Result<maintenance::VacuumReport, maintenance::MaintenanceError> {
+    state.ensure_writable()?;
+    maintenance::vacuum_database(&state.pool).await
+}
+
+/// Run SQLite's integrity check and return any problems found
+#[tauri::command]
+async fn check_database_integrity(
+    state: tauri::State<'_, Database>,
+) -> Result<maintenance::IntegrityReport, maintenance::MaintenanceError> {
+    maintenance::check_database_integrity(&state.pool).await
+}
+
+/// Database file size and per-table row counts
+#[tauri::command]
+async fn get_database_stats(
+    state: tauri::State<'_, Database>,
+) -> Result<maintenance::DatabaseStats, maintenance::MaintenanceError> {
+    maintenance::get_database_stats(&state.pool).await
 }
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
     tauri::Builder::default()
         .plugin(tauri_plugin_shell::init())
+        .manage(highlights::ExtractionControl::default())
+        .manage(chat::ChatStreams::default())
         .invoke_handler(tauri::generate_handler![
             greet,
             check_db,
+            get_read_only_mode,
+            set_read_only_mode,
             store_api_key,
             has_api_key,
             delete_api_key,
+            store_embeddings_api_key,
+            has_embeddings_api_key,
+            delete_embeddings_api_key,
+            store_proxy_password,
+            has_proxy_password,
+            delete_proxy_password,
             validate_api_key_format,
             send_chat_message,
             send_chat_message_streaming,
+            cancel_chat_stream,
+            list_available_models,
             check_network_status,
             is_online,
+            test_proxy_connection,
             // Company profile
             has_company,
             get_company,
+            list_companies,
             upsert_company,
             get_employee_work_states,
             // Employee management
             create_employee,
             get_employee,
+            get_employee_timeline,
+            get_internal_mobility,
             get_employee_by_email,
             update_employee,
             delete_employee,
+            set_legal_hold,
+            clear_legal_hold,
             list_employees,
+            export_employees,
             get_departments,
             get_employee_counts,
+            compute_span_of_control,
+            get_org_chart,
+            get_reports,
+            compute_representation,
             import_employees,
             // Review cycles
             create_review_cycle,
@@ -1310,16 +2155,29 @@ pub fn run() {
             list_review_cycles,
             get_active_review_cycle,
             close_review_cycle,
+            close_expired_cycles,
+            find_invalid_cycle_dates,
+            get_cycle_history,
+            get_cycle_reminders,
+            // Review templates
+            create_review_template,
+            list_review_templates,
+            get_template_for_cycle,
             // Performance ratings
             create_performance_rating,
             get_performance_rating,
             get_ratings_for_employee,
+            get_rating_history,
             get_ratings_for_cycle,
             get_latest_rating,
             update_performance_rating,
             delete_performance_rating,
             get_rating_distribution,
             get_average_rating,
+            get_upcoming_reviews,
+            get_review_coverage,
+            compute_first_rating_lead_time,
+            normalize_cycle_ratings,
             // Performance reviews
             create_performance_review,
             get_performance_review,
@@ -1328,15 +2186,23 @@ pub fn run() {
             update_performance_review,
             delete_performance_review,
             search_performance_reviews,
+            import_performance_reviews,
             // Review highlights (V2.2.1)
             get_review_highlight,
             get_highlights_for_employee,
             extract_review_highlight,
             extract_highlights_batch,
+            cancel_highlights_extraction,
+            estimate_extraction_cost,
             find_reviews_pending_extraction,
             get_employee_summary,
             generate_employee_summary,
+            generate_summaries_batch,
             invalidate_review_highlight,
+            find_orphaned_highlights,
+            purge_orphaned_highlights,
+            find_orphaned_summaries,
+            purge_orphaned_summaries,
             // eNPS
             create_enps_response,
             get_enps_response,
@@ -1344,13 +2210,20 @@ pub fn run() {
             get_enps_for_survey,
             delete_enps_response,
             calculate_enps_score,
+            calculate_weighted_enps,
             get_latest_enps_for_employee,
+            compute_rating_enps_correlation,
+            find_invalid_enps_dates,
+            get_enps_trend,
+            calculate_enps_segmented,
             // File parser
             parse_file,
             parse_file_preview,
             get_supported_extensions,
             is_supported_file,
             map_employee_columns,
+            map_employee_columns_scored,
+            check_import_dates,
             map_rating_columns,
             map_enps_columns,
             // Bulk import (test data)
@@ -1367,6 +2240,7 @@ pub fn run() {
             get_employee_context,
             get_company_context,
             get_aggregate_enps,
+            compare_employees,
             // Analytics (V2.3.2)
             execute_analytics,
             // Insight Canvas (V2.3.2g-l)
@@ -1385,10 +2259,13 @@ pub fn run() {
             delete_chart_annotation,
             // Monday Digest
             get_digest_data,
+            get_tenure_stats,
+            reconcile_counts,
             // Memory (cross-conversation)
             generate_conversation_summary,
             save_conversation_summary,
             search_memories,
+            reindex_memory_embeddings,
             // Conversation management
             create_conversation,
             get_conversation,
@@ -1396,28 +2273,57 @@ pub fn run() {
             list_conversations,
             search_conversations,
             delete_conversation,
+            export_conversation_markdown,
+            pin_conversation,
+            unpin_conversation,
+            archive_conversation,
+            bulk_conversation_op,
             generate_conversation_title,
             // Settings
             get_setting,
             set_setting,
             delete_setting,
             has_setting,
+            export_config,
+            import_config,
             // Personas (V2.1.3)
             get_personas,
             // PII scanning
             scan_pii,
+            scan_and_tokenize,
+            get_custom_pii_patterns,
+            set_custom_pii_patterns,
+            audit_pii_report,
+            scan_stored_pii,
             // Audit logging
             create_audit_entry,
             get_audit_entry,
             list_audit_entries,
             count_audit_entries,
+            count_audit_entries_before,
+            purge_audit_entries_before,
             export_audit_log,
+            export_audit_log_pdf,
+            get_most_discussed_employees,
+            reverify_audit_entry,
+            reverify_audit_log,
             // Data path
             get_data_path,
+            check_schema_columns,
+            export_schema,
             // Backup & restore
             export_backup,
+            export_incremental_backup,
+            estimate_backup_password_strength,
             validate_backup,
-            import_backup
+            rotate_backup_password,
+            import_backup,
+            import_backup_merge,
+            // Maintenance
+            recalculate_derived,
+            vacuum_database,
+            check_database_integrity,
+            get_database_stats
         ])
         .setup(|app| {
             let handle = app.handle().clone();
@@ -1426,6 +2332,11 @@ pub fn run() {
             tauri::async_runtime::block_on(async move {
                 match db::init_db(&handle).await {
                     Ok(pool) => {
+                        // Carry preferences saved under renamed keys forward
+                        if let Err(e) = settings::migrate_settings(&pool).await {
+                            eprintln!("Failed to migrate settings: {}", e);
+                        }
+
                         // Store database pool in app state
                         handle.manage(Database::new(pool));
                         println!("Database initialized successfully");
@@ -0,0 +1,314 @@
+// HR Command Center - Maintenance Module
+// Repairs for stored data that is derived from other tables and can drift
+// after imports, restores, or manual edits, plus SQLite housekeeping
+// (VACUUM, integrity check, size and row counts) for the Maintenance panel.
+//
+// Derived data kept today:
+// - review_highlights.employee_id / review_cycle_id (copied from the review)
+// - conversations_fts (full-text index over conversations)
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::db::DbPool;
+
+// ============================================================================
+// Error Types
+// ============================================================================
+
+#[derive(Error, Debug, Serialize)]
+pub enum MaintenanceError {
+    #[error("Database error: {0}")]
+    Database(String),
+    #[error("{}", crate::db::READ_ONLY_MESSAGE)]
+    ReadOnly,
+}
+
+impl From<sqlx::Error> for MaintenanceError {
+    fn from(err: sqlx::Error) -> Self {
+        MaintenanceError::Database(err.to_string())
+    }
+}
+
+impl From<crate::db::ReadOnly> for MaintenanceError {
+    fn from(_: crate::db::ReadOnly) -> Self {
+        MaintenanceError::ReadOnly
+    }
+}
+
+// ============================================================================
+// Recalculation
+// ============================================================================
+
+/// Rows corrected per derived field by `recalculate_derived`
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct RecalcReport {
+    /// Highlights whose employee_id no longer matched their review
+    pub highlight_employee_ids: u64,
+    /// Highlights whose review_cycle_id no longer matched their review
+    pub highlight_cycle_ids: u64,
+    /// Conversations re-indexed for search (the index is rebuilt in full)
+    pub search_index_rows: u64,
+}
+
+/// Recompute every derived column from its source in one transaction
+pub async fn recalculate_derived(pool: &DbPool) -> Result<RecalcReport, MaintenanceError> {
+    let mut tx = pool.begin().await?;
+
+    let highlight_employee_ids = sqlx::query(
+        r#"
+        UPDATE review_highlights
+        SET employee_id = (SELECT pr.employee_id FROM performance_reviews pr WHERE pr.id = review_highlights.review_id),
+            updated_at = datetime('now')
+        WHERE EXISTS (
+            SELECT 1 FROM performance_reviews pr
+            WHERE pr.id = review_highlights.review_id AND pr.employee_id != review_highlights.employee_id
+        )
+        "#,
+    )
+    .execute(&mut *tx)
+    .await?
+    .rows_affected();
+
+    let highlight_cycle_ids = sqlx::query(
+        r#"
+        UPDATE review_highlights
+        SET review_cycle_id = (SELECT pr.review_cycle_id FROM performance_reviews pr WHERE pr.id = review_highlights.review_id),
+            updated_at = datetime('now')
+        WHERE EXISTS (
+            SELECT 1 FROM performance_reviews pr
+            WHERE pr.id = review_highlights.review_id AND pr.review_cycle_id != review_highlights.review_cycle_id
+        )
+        "#,
+    )
+    .execute(&mut *tx)
+    .await?
+    .rows_affected();
+
+    sqlx::query("INSERT INTO conversations_fts(conversations_fts) VALUES ('rebuild')")
+        .execute(&mut *tx)
+        .await?;
+    let search_index_rows: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM conversations")
+        .fetch_one(&mut *tx)
+        .await?;
+
+    tx.commit().await?;
+
+    Ok(RecalcReport {
+        highlight_employee_ids,
+        highlight_cycle_ids,
+        search_index_rows: search_index_rows as u64,
+    })
+}
+
+// ============================================================================
+// Database Housekeeping
+// ============================================================================
+
+/// Database size before and after `vacuum_database`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct VacuumReport {
+    pub size_before_bytes: u64,
+    pub size_after_bytes: u64,
+}
+
+/// Outcome of SQLite's `PRAGMA integrity_check`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct IntegrityReport {
+    pub ok: bool,
+    /// Problems as SQLite reports them; empty when ok
+    pub problems: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TableStats {
+    pub name: String,
+    pub rows: i64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DatabaseStats {
+    /// Pages in use times page size (the main file; WAL not included)
+    pub size_bytes: u64,
+    /// Space on the free list that `vacuum_database` would give back
+    pub reclaimable_bytes: u64,
+    /// Ordinary tables by name; FTS shadow tables are left out
+    pub tables: Vec<TableStats>,
+}
+
+/// Rebuild the database file to reclaim space left by deletes.
+/// SQLite refuses to VACUUM inside a transaction, so this runs on a plain
+/// pooled connection rather than `pool.begin()`.
+pub async fn vacuum_database(pool: &DbPool) -> Result<VacuumReport, MaintenanceError> {
+    let mut conn = pool.acquire().await?;
+    let (size_before_bytes, _) = page_usage(&mut conn).await?;
+    sqlx::query("VACUUM").execute(&mut *conn).await?;
+    let (size_after_bytes, _) = page_usage(&mut conn).await?;
+
+    eprintln!("[Maintenance] VACUUM: {} -> {} bytes", size_before_bytes, size_after_bytes);
+    Ok(VacuumReport {
+        size_before_bytes,
+        size_after_bytes,
+    })
+}
+
+/// Run `PRAGMA integrity_check`, which reports "ok" or one row per problem
+pub async fn check_database_integrity(pool: &DbPool) -> Result<IntegrityReport, MaintenanceError> {
+    let rows: Vec<String> = sqlx::query_scalar("PRAGMA integrity_check").fetch_all(pool).await?;
+    let ok = rows.len() == 1 && rows[0] == "ok";
+    Ok(IntegrityReport {
+        ok,
+        problems: if ok { Vec::new() } else { rows },
+    })
+}
+
+/// File size, reclaimable space, and per-table row counts
+pub async fn get_database_stats(pool: &DbPool) -> Result<DatabaseStats, MaintenanceError> {
+    let mut conn = pool.acquire().await?;
+    let (size_bytes, reclaimable_bytes) = page_usage(&mut conn).await?;
+
+    let names: Vec<String> = sqlx::query_scalar(
+        "SELECT name FROM pragma_table_list WHERE schema = 'main' AND type = 'table' AND name NOT LIKE 'sqlite_%' ORDER BY name",
+    )
+    .fetch_all(&mut *conn)
+    .await?;
+
+    let mut tables = Vec::with_capacity(names.len());
+    for name in names {
+        let rows: i64 = sqlx::query_scalar(&format!("SELECT COUNT(*) FROM \"{}\"", name.replace('"', "\"\"")))
+            .fetch_one(&mut *conn)
+            .await?;
+        tables.push(TableStats { name, rows });
+    }
+
+    Ok(DatabaseStats {
+        size_bytes,
+        reclaimable_bytes,
+        tables,
+    })
+}
+
+/// (total bytes, free-list bytes) of the main database
+async fn page_usage(conn: &mut sqlx::SqliteConnection) -> Result<(u64, u64), MaintenanceError> {
+    let page_size: i64 = sqlx::query_scalar("PRAGMA page_size").fetch_one(&mut *conn).await?;
+    let page_count: i64 = sqlx::query_scalar("PRAGMA page_count").fetch_one(&mut *conn).await?;
+    let free_pages: i64 = sqlx::query_scalar("PRAGMA freelist_count").fetch_one(&mut *conn).await?;
+    Ok(((page_count * page_size) as u64, (free_pages * page_size) as u64))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn seed_review_with_highlight(pool: &DbPool, highlight_employee: &str) {
+        sqlx::query(
+            r#"
+            INSERT INTO employees (id, email, full_name) VALUES
+                ('emp-a', 'a@example.com', 'Ana'),
+                ('emp-b', 'b@example.com', 'Ben');
+            INSERT INTO review_cycles (id, name, cycle_type, start_date, end_date)
+                VALUES ('cycle-1', '2024 Annual', 'annual', '2024-01-01', '2024-12-31');
+            INSERT INTO performance_reviews (id, employee_id, review_cycle_id)
+                VALUES ('rev-1', 'emp-a', 'cycle-1');
+            "#,
+        )
+        .execute(pool)
+        .await
+        .unwrap();
+        sqlx::query(
+            "INSERT INTO review_highlights (id, review_id, employee_id, review_cycle_id) VALUES ('hl-1', 'rev-1', ?, 'cycle-1')",
+        )
+        .bind(highlight_employee)
+        .execute(pool)
+        .await
+        .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_recalculate_fixes_stale_highlight_employee() {
+        let pool = crate::db::test_pool().await;
+        seed_review_with_highlight(&pool, "emp-b").await;
+
+        let report = recalculate_derived(&pool).await.unwrap();
+        assert_eq!(report.highlight_employee_ids, 1);
+        assert_eq!(report.highlight_cycle_ids, 0);
+
+        let employee_id: String =
+            sqlx::query_scalar("SELECT employee_id FROM review_highlights WHERE id = 'hl-1'")
+                .fetch_one(&pool)
+                .await
+                .unwrap();
+        assert_eq!(employee_id, "emp-a");
+
+        // Second pass finds nothing left to fix
+        let again = recalculate_derived(&pool).await.unwrap();
+        assert_eq!(again, RecalcReport::default());
+    }
+
+    #[tokio::test]
+    async fn test_recalculate_rebuilds_search_index() {
+        let pool = crate::db::test_pool().await;
+        sqlx::query("INSERT INTO conversations (id, title, messages_json) VALUES ('c1', 'Promotion planning', '[]')")
+            .execute(&pool)
+            .await
+            .unwrap();
+        // Simulate a drifted index
+        sqlx::query("INSERT INTO conversations_fts(conversations_fts) VALUES ('delete-all')")
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        let report = recalculate_derived(&pool).await.unwrap();
+        assert_eq!(report.search_index_rows, 1);
+
+        let hits: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM conversations_fts WHERE conversations_fts MATCH 'promotion'")
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+        assert_eq!(hits, 1);
+    }
+
+    #[tokio::test]
+    async fn test_integrity_check_ok_on_fresh_database() {
+        let pool = crate::db::test_pool().await;
+        let report = check_database_integrity(&pool).await.unwrap();
+        assert!(report.ok);
+        assert!(report.problems.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_vacuum_reclaims_deleted_space() {
+        let pool = crate::db::test_pool().await;
+        sqlx::query(
+            r#"
+            WITH RECURSIVE n(i) AS (SELECT 1 UNION ALL SELECT i + 1 FROM n WHERE i < 500)
+            INSERT INTO audit_log (id, request_redacted, response_text)
+            SELECT 'log-' || i, printf('%.2000c', 'x'), printf('%.2000c', 'y') FROM n
+            "#,
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+        sqlx::query("INSERT INTO employees (id, email, full_name) VALUES ('emp-a', 'a@example.com', 'Ana')")
+            .execute(&pool)
+            .await
+            .unwrap();
+        sqlx::query("DELETE FROM audit_log WHERE id != 'log-1'").execute(&pool).await.unwrap();
+
+        let before = get_database_stats(&pool).await.unwrap();
+        assert!(before.reclaimable_bytes > 0);
+        let rows = |stats: &DatabaseStats, table: &str| stats.tables.iter().find(|t| t.name == table).map(|t| t.rows);
+        assert_eq!(rows(&before, "employees"), Some(1));
+        assert_eq!(rows(&before, "audit_log"), Some(1));
+        assert!(!before.tables.iter().any(|t| t.name.starts_with("conversations_fts")));
+
+        let report = vacuum_database(&pool).await.unwrap();
+        assert_eq!(report.size_before_bytes, before.size_bytes);
+        assert!(report.size_after_bytes < report.size_before_bytes);
+
+        let after = get_database_stats(&pool).await.unwrap();
+        assert_eq!(after.reclaimable_bytes, 0);
+        assert_eq!(rows(&after, "audit_log"), Some(1));
+        assert!(check_database_integrity(&pool).await.unwrap().ok);
+    }
+}
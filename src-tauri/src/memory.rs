@@ -4,14 +4,16 @@
 // Key responsibilities:
 // 1. Generate Claude-powered conversation summaries
 // 2. Store summaries in the conversations table
-// 3. Search past summaries for context (hybrid: summary-only → full FTS fallback)
+// 3. Search past summaries for context (embeddings when configured,
+//    otherwise summary-only → full FTS fallback)
 
 use serde::{Deserialize, Serialize};
-use sqlx::FromRow;
+use sqlx::{FromRow, Row};
 use thiserror::Error;
 
 use crate::chat::{ChatMessage, ChatResponse};
 use crate::db::DbPool;
+use crate::keyring;
 
 // ============================================================================
 // Error Types
@@ -29,6 +31,16 @@ pub enum MemoryError {
     ParseError(String),
     #[error("Conversation not found: {0}")]
     NotFound(String),
+    #[error("Embeddings API key not configured")]
+    NoEmbeddingsKey,
+    #[error("{}", crate::db::READ_ONLY_MESSAGE)]
+    ReadOnly,
+}
+
+impl From<crate::db::ReadOnly> for MemoryError {
+    fn from(_: crate::db::ReadOnly) -> Self {
+        MemoryError::ReadOnly
+    }
 }
 
 impl From<sqlx::Error> for MemoryError {
@@ -37,6 +49,12 @@ impl From<sqlx::Error> for MemoryError {
     }
 }
 
+impl From<crate::network::NetworkError> for MemoryError {
+    fn from(err: crate::network::NetworkError) -> Self {
+        MemoryError::ApiError(err.to_string())
+    }
+}
+
 impl From<crate::chat::ChatError> for MemoryError {
     fn from(err: crate::chat::ChatError) -> Self {
         match err {
@@ -79,6 +97,9 @@ pub struct StoredMessage {
     pub role: String,
     pub content: String,
     pub timestamp: String,
+    /// Model, persona, and token usage; only set on assistant turns
+    #[serde(flatten)]
+    pub turn: crate::chat::TurnMetadata,
 }
 
 // ============================================================================
@@ -101,6 +122,27 @@ const SUMMARY_MAX_TOKENS: u32 = 200;
 /// Default number of memories to retrieve
 pub const DEFAULT_MEMORY_LIMIT: usize = 3;
 
+const VOYAGE_API_URL: &str = "https://api.voyageai.com/v1/embeddings";
+const EMBEDDING_MODEL: &str = "voyage-3-lite";
+
+/// Summaries sent per embeddings request when reindexing
+const EMBEDDING_BATCH_SIZE: usize = 64;
+
+/// Summaries less similar than this to the query are not recalled
+const MIN_MEMORY_SIMILARITY: f32 = 0.3;
+
+/// Embedding runs on every chat message; a stalled request falls back to
+/// keyword search instead of holding up the reply
+const EMBEDDING_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(8);
+
+/// Result of `reindex_memory_embeddings`
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ReindexResult {
+    pub embedded: usize,
+    /// Summaries still without an embedding
+    pub remaining: usize,
+}
+
 // ============================================================================
 // Core Functions
 // ============================================================================
@@ -109,7 +151,7 @@ pub const DEFAULT_MEMORY_LIMIT: usize = 3;
 ///
 /// Takes the messages_json from the conversations table and returns
 /// a 2-3 sentence summary focusing on topic, employees mentioned, and outcomes.
-pub async fn generate_summary(messages_json: &str) -> Result<String, MemoryError> {
+pub async fn generate_summary(messages_json: &str, net: &crate::network::NetworkConfig) -> Result<String, MemoryError> {
     // Parse the messages from JSON
     let messages: Vec<StoredMessage> = serde_json::from_str(messages_json)
         .map_err(|e| MemoryError::ParseError(e.to_string()))?;
@@ -131,7 +173,7 @@ pub async fn generate_summary(messages_json: &str) -> Result<String, MemoryError
     }];
 
     // Call Claude for summary (using existing chat module)
-    let response = generate_summary_internal(summary_request).await?;
+    let response = generate_summary_internal(summary_request, net).await?;
 
     Ok(response.content.trim().to_string())
 }
@@ -140,12 +182,13 @@ pub async fn generate_summary(messages_json: &str) -> Result<String, MemoryError
 /// Separated for testability
 async fn generate_summary_internal(
     messages: Vec<ChatMessage>,
+    net: &crate::network::NetworkConfig,
 ) -> Result<ChatResponse, MemoryError> {
     use crate::chat;
 
     // Use a simpler, direct API call for summaries
     // This avoids the conversation trimming logic meant for longer chats
-    chat::send_message(messages, Some(SUMMARY_SYSTEM_PROMPT.to_string()))
+    chat::send_message(messages, Some(SUMMARY_SYSTEM_PROMPT.to_string()), None, net, chat::DEFAULT_MAX_MESSAGE_TOKENS)
         .await
         .map_err(MemoryError::from)
 }
@@ -186,14 +229,38 @@ pub async fn save_summary(
         return Err(MemoryError::NotFound(conversation_id.to_string()));
     }
 
+    // Replace the cached embedding; a failure only costs semantic recall
+    // until the next reindex
+    let embedding = match keyring::get_embeddings_api_key() {
+        Ok(api_key) => match embed_texts(pool, &api_key, &[summary.to_string()], "document").await {
+            Ok(mut vectors) => vectors.pop().map(|v| encode_embedding(&v)),
+            Err(e) => {
+                eprintln!("[Memory] Failed to embed summary: {}", e);
+                None
+            }
+        },
+        Err(_) => None,
+    };
+    sqlx::query("UPDATE conversations SET summary_embedding = ? WHERE id = ?")
+        .bind(embedding)
+        .bind(conversation_id)
+        .execute(pool)
+        .await?;
+
     Ok(())
 }
 
 /// Find relevant memories for a query using hybrid search
 ///
 /// Strategy:
-/// 1. First try summary-only search (more focused results)
-/// 2. Fall back to full FTS if no summary matches found
+/// 1. With an embeddings key, rank embedded summaries by cosine similarity
+/// 2. Try summary-only keyword search
+/// 3. Fall back to full FTS if no summary matches found
+///
+/// Semantic and keyword results are interleaved, so summaries that have no
+/// embedding yet can still be recalled.
+///
+/// Conversations flagged `exclude_from_memory` are never returned.
 pub async fn find_relevant_memories(
     pool: &DbPool,
     query: &str,
@@ -204,15 +271,250 @@ pub async fn find_relevant_memories(
         return Ok(Vec::new());
     }
 
-    // Step 1: Try summary-only search (more focused)
-    let results = search_summaries_only(pool, query, limit).await?;
+    // Step 1: Semantic search, so paraphrases match
+    let mut semantic = Vec::new();
+    if let Ok(api_key) = keyring::get_embeddings_api_key() {
+        match embed_texts(pool, &api_key, &[query.to_string()], "query").await {
+            Ok(mut vectors) => {
+                if let Some(query_vector) = vectors.pop() {
+                    semantic = search_by_embedding(pool, &query_vector, limit).await?;
+                }
+            }
+            Err(e) => eprintln!("[Memory] Embedding query failed, using keyword search: {}", e),
+        }
+    }
+
+    // Step 2: Try summary-only search (more focused)
+    let mut keyword = search_summaries_only(pool, query, limit).await?;
+
+    // Step 3: Fall back to full FTS search
+    if keyword.is_empty() {
+        keyword = search_full_conversation_fts(pool, query, limit).await?;
+    }
+
+    Ok(merge_memories(semantic, keyword, limit))
+}
+
+/// Alternate two ranked result lists, dropping repeats, up to `limit`
+fn merge_memories(
+    first: Vec<ConversationSummary>,
+    second: Vec<ConversationSummary>,
+    limit: usize,
+) -> Vec<ConversationSummary> {
+    let mut merged: Vec<ConversationSummary> = Vec::with_capacity(limit);
+    let mut first = first.into_iter();
+    let mut second = second.into_iter();
+    loop {
+        let (a, b) = (first.next(), second.next());
+        if a.is_none() && b.is_none() {
+            break;
+        }
+        for memory in [a, b].into_iter().flatten() {
+            if merged.len() < limit && !merged.iter().any(|m| m.conversation_id == memory.conversation_id) {
+                merged.push(memory);
+            }
+        }
+    }
+    merged
+}
+
+/// Rank embedded summaries against a query embedding
+async fn search_by_embedding(
+    pool: &DbPool,
+    query_vector: &[f32],
+    limit: usize,
+) -> Result<Vec<ConversationSummary>, MemoryError> {
+    let rows = sqlx::query(
+        r#"
+        SELECT id, summary, created_at, summary_embedding
+        FROM conversations
+        WHERE summary IS NOT NULL
+          AND summary != ''
+          AND exclude_from_memory = 0
+          AND summary_embedding IS NOT NULL
+        "#,
+    )
+    .fetch_all(pool)
+    .await?;
+
+    let candidates = rows
+        .iter()
+        .filter_map(|row| {
+            let embedding = decode_embedding(&row.get::<Vec<u8>, _>("summary_embedding"))?;
+            let summary = ConversationSummary {
+                conversation_id: row.get("id"),
+                summary: row.get("summary"),
+                created_at: row.get("created_at"),
+            };
+            Some((summary, embedding))
+        })
+        .collect();
+
+    Ok(rank_by_similarity(query_vector, candidates, limit))
+}
+
+/// Most similar first, dropping those under `MIN_MEMORY_SIMILARITY`
+fn rank_by_similarity(
+    query_vector: &[f32],
+    candidates: Vec<(ConversationSummary, Vec<f32>)>,
+    limit: usize,
+) -> Vec<ConversationSummary> {
+    let mut scored: Vec<(f32, ConversationSummary)> = candidates
+        .into_iter()
+        .map(|(summary, embedding)| (cosine_similarity(query_vector, &embedding), summary))
+        .filter(|(score, _)| *score >= MIN_MEMORY_SIMILARITY)
+        .collect();
+    scored.sort_by(|a, b| b.0.total_cmp(&a.0));
+    scored.into_iter().take(limit).map(|(_, summary)| summary).collect()
+}
+
+/// Cosine similarity; 0.0 for mismatched lengths or zero vectors
+fn cosine_similarity(a: &[f32], b: &[f32]) -> f32 {
+    if a.len() != b.len() || a.is_empty() {
+        return 0.0;
+    }
+    let dot: f32 = a.iter().zip(b).map(|(x, y)| x * y).sum();
+    let norm_a = a.iter().map(|x| x * x).sum::<f32>().sqrt();
+    let norm_b = b.iter().map(|x| x * x).sum::<f32>().sqrt();
+    if norm_a == 0.0 || norm_b == 0.0 {
+        return 0.0;
+    }
+    dot / (norm_a * norm_b)
+}
+
+fn encode_embedding(vector: &[f32]) -> Vec<u8> {
+    vector.iter().flat_map(|x| x.to_le_bytes()).collect()
+}
+
+fn decode_embedding(bytes: &[u8]) -> Option<Vec<f32>> {
+    if bytes.is_empty() || !bytes.len().is_multiple_of(4) {
+        return None;
+    }
+    Some(
+        bytes
+            .chunks_exact(4)
+            .map(|b| f32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+            .collect(),
+    )
+}
+
+// ============================================================================
+// Embeddings
+// ============================================================================
+
+#[derive(Debug, Serialize)]
+struct EmbeddingRequest<'a> {
+    input: &'a [String],
+    model: &'a str,
+    /// "query" or "document"
+    input_type: &'a str,
+}
+
+#[derive(Debug, Deserialize)]
+struct EmbeddingResponse {
+    data: Vec<EmbeddingData>,
+}
+
+#[derive(Debug, Deserialize)]
+struct EmbeddingData {
+    embedding: Vec<f32>,
+    index: usize,
+}
+
+/// Embed texts with the Voyage AI API, returning vectors in input order.
+/// Texts are PII-redacted before they leave the machine.
+async fn embed_texts(
+    pool: &DbPool,
+    api_key: &str,
+    texts: &[String],
+    input_type: &str,
+) -> Result<Vec<Vec<f32>>, MemoryError> {
+    let custom = crate::pii::load_custom_pii_patterns(pool).await;
+    let redacted: Vec<String> = texts
+        .iter()
+        .map(|text| crate::pii::scan_and_redact_with(text, &custom).redacted_text)
+        .collect();
+
+    let client = crate::network::NetworkConfig::load(pool)
+        .await
+        .client_builder()?
+        .timeout(EMBEDDING_TIMEOUT)
+        .build()
+        .map_err(|e| MemoryError::ApiError(e.to_string()))?;
+    let response = client
+        .post(VOYAGE_API_URL)
+        .bearer_auth(api_key)
+        .json(&EmbeddingRequest {
+            input: &redacted,
+            model: EMBEDDING_MODEL,
+            input_type,
+        })
+        .send()
+        .await
+        .map_err(|e| MemoryError::ApiError(e.to_string()))?;
+
+    if !response.status().is_success() {
+        let status = response.status().as_u16();
+        let body = response.text().await.unwrap_or_default();
+        return Err(MemoryError::ApiError(format!("embeddings request failed (HTTP {}): {}", status, body)));
+    }
+
+    let mut parsed: EmbeddingResponse = response
+        .json()
+        .await
+        .map_err(|e| MemoryError::ApiError(e.to_string()))?;
+    if parsed.data.len() != texts.len() {
+        return Err(MemoryError::ApiError(format!(
+            "expected {} embeddings, got {}",
+            texts.len(),
+            parsed.data.len()
+        )));
+    }
+    parsed.data.sort_by_key(|d| d.index);
+    Ok(parsed.data.into_iter().map(|d| d.embedding).collect())
+}
+
+/// Embed every summary that has no cached embedding yet
+///
+/// Used to backfill summaries saved before an embeddings key was configured.
+/// Stops at the first failed batch; batches already embedded are kept.
+pub async fn reindex_memory_embeddings(pool: &DbPool) -> Result<ReindexResult, MemoryError> {
+    let api_key = keyring::get_embeddings_api_key().map_err(|_| MemoryError::NoEmbeddingsKey)?;
 
-    if !results.is_empty() {
-        return Ok(results);
+    let pending: Vec<(String, String)> = sqlx::query_as(
+        r#"
+        SELECT id, summary FROM conversations
+        WHERE summary IS NOT NULL AND summary != '' AND summary_embedding IS NULL
+        ORDER BY updated_at DESC
+        "#,
+    )
+    .fetch_all(pool)
+    .await?;
+
+    let mut result = ReindexResult {
+        embedded: 0,
+        remaining: pending.len(),
+    };
+    for batch in pending.chunks(EMBEDDING_BATCH_SIZE) {
+        let texts: Vec<String> = batch.iter().map(|(_, summary)| summary.clone()).collect();
+        let vectors = embed_texts(pool, &api_key, &texts, "document").await?;
+        for ((id, summary), vector) in batch.iter().zip(vectors) {
+            // Skip if the summary changed while the batch was in flight
+            let updated = sqlx::query("UPDATE conversations SET summary_embedding = ? WHERE id = ? AND summary = ?")
+                .bind(encode_embedding(&vector))
+                .bind(id)
+                .bind(summary)
+                .execute(pool)
+                .await?
+                .rows_affected();
+            if updated > 0 {
+                result.embedded += 1;
+                result.remaining -= 1;
+            }
+        }
     }
 
-    // Step 2: Fall back to full FTS search
-    search_full_conversation_fts(pool, query, limit).await
+    Ok(result)
 }
 
 /// Search only in summary field using LIKE (case-insensitive substring match)
@@ -238,6 +540,7 @@ async fn search_summaries_only(
         FROM conversations
         WHERE summary IS NOT NULL
           AND summary != ''
+          AND exclude_from_memory = 0
           AND summary LIKE ?
         ORDER BY updated_at DESC
         LIMIT ?
@@ -271,6 +574,7 @@ async fn search_full_conversation_fts(
         INNER JOIN conversations_fts fts ON c.rowid = fts.rowid
         WHERE c.summary IS NOT NULL
           AND c.summary != ''
+          AND c.exclude_from_memory = 0
           AND conversations_fts MATCH ?
         ORDER BY rank
         LIMIT ?
@@ -343,12 +647,14 @@ mod tests {
                 role: "user".to_string(),
                 content: "What should I do about Sarah's attendance?".to_string(),
                 timestamp: "2024-01-15T10:00:00Z".to_string(),
+                turn: Default::default(),
             },
             StoredMessage {
                 id: "2".to_string(),
                 role: "assistant".to_string(),
                 content: "I recommend documenting the incidents first.".to_string(),
                 timestamp: "2024-01-15T10:00:30Z".to_string(),
+                turn: Default::default(),
             },
         ];
 
@@ -426,5 +732,189 @@ mod tests {
         assert_eq!(messages.len(), 2);
         assert_eq!(messages[0].role, "user");
         assert_eq!(messages[1].role, "assistant");
+        // Older messages have no turn metadata
+        assert_eq!(messages[1].turn, crate::chat::TurnMetadata::default());
+    }
+
+    #[test]
+    fn test_stored_message_turn_metadata_round_trip() {
+        let json = r#"{"id": "2", "role": "assistant", "content": "Hi", "timestamp": "2024-01-01T00:00:01Z",
+            "model": "claude-sonnet-4-20250514", "persona_id": "alex", "input_tokens": 1500,
+            "output_tokens": 220, "verification_status": "Verified", "chartData": {"ignored": true}}"#;
+
+        let message: StoredMessage = serde_json::from_str(json).unwrap();
+        assert_eq!(message.turn.model.as_deref(), Some("claude-sonnet-4-20250514"));
+        assert_eq!(message.turn.persona_id.as_deref(), Some("alex"));
+        assert_eq!(message.turn.input_tokens, Some(1500));
+        assert_eq!(message.turn.output_tokens, Some(220));
+        assert_eq!(
+            message.turn.verification_status,
+            Some(crate::context::VerificationStatus::Verified)
+        );
+
+        let value = serde_json::to_value(&message).unwrap();
+        assert_eq!(value["output_tokens"], 220);
+        assert_eq!(value["verification_status"], "Verified");
+
+        // Unset fields are omitted rather than written as null
+        let user = StoredMessage {
+            id: "1".to_string(),
+            role: "user".to_string(),
+            content: "Hello".to_string(),
+            timestamp: "2024-01-01T00:00:00Z".to_string(),
+            turn: Default::default(),
+        };
+        let value = serde_json::to_value(&user).unwrap();
+        assert!(value.get("model").is_none());
+    }
+
+    async fn seed_summary(pool: &DbPool, id: &str, summary: &str) {
+        sqlx::query("INSERT INTO conversations (id, title, summary, messages_json) VALUES (?, ?, ?, '[]')")
+            .bind(id)
+            .bind(format!("Conversation {}", id))
+            .bind(summary)
+            .execute(pool)
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_excluded_conversation_never_recalled() {
+        let pool = crate::db::test_pool().await;
+        seed_summary(&pool, "kept", "Discussed relocation options for the Denver office").await;
+        seed_summary(&pool, "hidden", "Sensitive relocation request from a manager").await;
+        crate::conversations::update_conversation(
+            &pool,
+            "hidden",
+            crate::conversations::UpdateConversation {
+                title: None,
+                messages_json: None,
+                summary: None,
+                exclude_from_memory: Some(true),
+                model: None,
+            },
+        )
+        .await
+        .unwrap();
+
+        // Summary LIKE path
+        let ids: Vec<String> = find_relevant_memories(&pool, "relocation", 5)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|m| m.conversation_id)
+            .collect();
+        assert_eq!(ids, vec!["kept"]);
+
+        // FTS fallback path (no summary contains "Conversation")
+        let ids: Vec<String> = find_relevant_memories(&pool, "conversation hidden", 5)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|m| m.conversation_id)
+            .collect();
+        assert!(!ids.contains(&"hidden".to_string()));
+    }
+
+    fn summary(id: &str) -> ConversationSummary {
+        ConversationSummary {
+            conversation_id: id.to_string(),
+            summary: format!("Summary {}", id),
+            created_at: "2024-01-01 00:00:00".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_cosine_similarity() {
+        assert!((cosine_similarity(&[1.0, 0.0], &[2.0, 0.0]) - 1.0).abs() < 1e-6);
+        assert!(cosine_similarity(&[1.0, 0.0], &[0.0, 3.0]).abs() < 1e-6);
+        assert!((cosine_similarity(&[1.0, 1.0], &[1.0, 0.0]) - std::f32::consts::FRAC_1_SQRT_2).abs() < 1e-6);
+        assert_eq!(cosine_similarity(&[1.0, 0.0], &[1.0, 0.0, 0.0]), 0.0);
+        assert_eq!(cosine_similarity(&[0.0, 0.0], &[1.0, 0.0]), 0.0);
+    }
+
+    #[test]
+    fn test_rank_by_similarity_orders_and_filters() {
+        let query = [1.0, 0.0, 0.0];
+        let candidates = vec![
+            (summary("loose"), vec![0.6, 0.8, 0.0]),
+            (summary("unrelated"), vec![0.0, 0.0, 1.0]),
+            (summary("close"), vec![0.9, 0.1, 0.0]),
+            (summary("opposite"), vec![-1.0, 0.0, 0.0]),
+        ];
+
+        let ranked: Vec<String> = rank_by_similarity(&query, candidates.clone(), 5)
+            .into_iter()
+            .map(|s| s.conversation_id)
+            .collect();
+        assert_eq!(ranked, vec!["close", "loose"]);
+
+        let top: Vec<String> = rank_by_similarity(&query, candidates, 1)
+            .into_iter()
+            .map(|s| s.conversation_id)
+            .collect();
+        assert_eq!(top, vec!["close"]);
+    }
+
+    #[test]
+    fn test_embedding_blob_round_trip() {
+        let vector = vec![0.25, -1.5, 3.0e-7];
+        assert_eq!(decode_embedding(&encode_embedding(&vector)), Some(vector));
+        assert_eq!(decode_embedding(&[1, 2, 3]), None);
+        assert_eq!(decode_embedding(&[]), None);
+    }
+
+    #[tokio::test]
+    async fn test_search_by_embedding_uses_cached_vectors() {
+        let pool = crate::db::test_pool().await;
+        seed_summary(&pool, "termination", "Walked through termination procedures for a sales rep").await;
+        seed_summary(&pool, "benefits", "Compared dental plans for open enrollment").await;
+        seed_summary(&pool, "unembedded", "Reviewed separation paperwork").await;
+        for (id, vector) in [("termination", [0.9f32, 0.1, 0.0]), ("benefits", [0.0, 0.2, 0.9])] {
+            sqlx::query("UPDATE conversations SET summary_embedding = ? WHERE id = ?")
+                .bind(encode_embedding(&vector))
+                .bind(id)
+                .execute(&pool)
+                .await
+                .unwrap();
+        }
+
+        // "how do I let someone go" embeds close to the termination summary
+        let found = search_by_embedding(&pool, &[1.0, 0.0, 0.0], 5).await.unwrap();
+        let ids: Vec<&str> = found.iter().map(|s| s.conversation_id.as_str()).collect();
+        assert_eq!(ids, vec!["termination"]);
+
+        // Changing the summary drops its stale embedding
+        crate::conversations::update_conversation(
+            &pool,
+            "termination",
+            crate::conversations::UpdateConversation {
+                title: None,
+                messages_json: None,
+                summary: Some("Discussed a retention bonus".to_string()),
+                exclude_from_memory: None,
+                model: None,
+            },
+        )
+        .await
+        .unwrap();
+        assert!(search_by_embedding(&pool, &[1.0, 0.0, 0.0], 5).await.unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_merge_memories_interleaves_and_dedupes() {
+        let memory = |id: &str| ConversationSummary {
+            conversation_id: id.to_string(),
+            summary: format!("Summary {}", id),
+            created_at: "2024-01-01".to_string(),
+        };
+        let ids = |list: Vec<ConversationSummary>| list.into_iter().map(|m| m.conversation_id).collect::<Vec<_>>();
+
+        // "k1" has no embedding, so only keyword search finds it
+        let merged = merge_memories(vec![memory("s1"), memory("s2"), memory("s3")], vec![memory("k1"), memory("s1")], 3);
+        assert_eq!(ids(merged), vec!["s1", "k1", "s2"]);
+
+        assert_eq!(ids(merge_memories(vec![], vec![memory("k1")], 3)), vec!["k1"]);
+        assert_eq!(ids(merge_memories(vec![memory("s1")], vec![], 3)), vec!["s1"]);
     }
 }
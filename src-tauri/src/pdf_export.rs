@@ -0,0 +1,288 @@
+// HR Command Center - PDF Export Module
+// Minimal text-only PDF writer for exports. Uses the built-in Courier fonts,
+// so no font embedding is needed and every glyph has the same width, which
+// keeps line wrapping a simple character count.
+
+/// US Letter, in points
+const PAGE_WIDTH: f32 = 612.0;
+const PAGE_HEIGHT: f32 = 792.0;
+const MARGIN: f32 = 54.0;
+
+/// Courier glyphs are 0.6em wide
+const GLYPH_WIDTH_EM: f32 = 0.6;
+
+const BODY_SIZE: f32 = 9.0;
+const HEADING_SIZE: f32 = 12.0;
+const LINE_SPACING: f32 = 1.3;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Style {
+    Regular,
+    Bold,
+}
+
+#[derive(Debug, Clone)]
+struct Line {
+    text: String,
+    style: Style,
+    size: f32,
+    y: f32,
+}
+
+/// Builds a paginated text document top to bottom. Each page gets a
+/// "Page n of m" footer when the document is finished.
+pub struct PdfWriter {
+    pages: Vec<Vec<Line>>,
+    current: Vec<Line>,
+    y: f32,
+}
+
+impl Default for PdfWriter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PdfWriter {
+    pub fn new() -> Self {
+        Self {
+            pages: Vec::new(),
+            current: Vec::new(),
+            y: PAGE_HEIGHT - MARGIN,
+        }
+    }
+
+    /// Bold line in the larger heading size
+    pub fn heading(&mut self, text: &str) {
+        self.write(text, Style::Bold, HEADING_SIZE);
+    }
+
+    /// Bold body-size line, for labels and section titles
+    pub fn label(&mut self, text: &str) {
+        self.write(text, Style::Bold, BODY_SIZE);
+    }
+
+    /// Body text, wrapped to the page width. Embedded newlines are kept.
+    pub fn paragraph(&mut self, text: &str) {
+        self.write(text, Style::Regular, BODY_SIZE);
+    }
+
+    pub fn blank_line(&mut self) {
+        self.advance(BODY_SIZE);
+    }
+
+    /// Number of pages written so far, counting the one in progress
+    pub fn page_count(&self) -> usize {
+        self.pages.len() + usize::from(!self.current.is_empty())
+    }
+
+    fn write(&mut self, text: &str, style: Style, size: f32) {
+        for line in wrap(text, chars_per_line(size)) {
+            self.advance(size);
+            self.current.push(Line {
+                text: line,
+                style,
+                size,
+                y: self.y,
+            });
+        }
+    }
+
+    /// Move down one line, breaking the page when the footer area is reached
+    fn advance(&mut self, size: f32) {
+        let step = size * LINE_SPACING;
+        // Leave room for the footer below the bottom margin line
+        if self.y - step < MARGIN + BODY_SIZE * 2.0 {
+            self.pages.push(std::mem::take(&mut self.current));
+            self.y = PAGE_HEIGHT - MARGIN;
+        }
+        self.y -= step;
+    }
+
+    /// Serialize the document. An empty document still gets one blank page.
+    pub fn finish(mut self) -> Vec<u8> {
+        if !self.current.is_empty() || self.pages.is_empty() {
+            self.pages.push(std::mem::take(&mut self.current));
+        }
+        let page_count = self.pages.len();
+
+        // Objects 1-4 are fixed; each page then takes a page and a content object
+        let mut objects: Vec<Vec<u8>> = Vec::with_capacity(4 + page_count * 2);
+        let kids: Vec<String> = (0..page_count).map(|i| format!("{} 0 R", 5 + i * 2)).collect();
+        objects.push(b"<< /Type /Catalog /Pages 2 0 R >>".to_vec());
+        objects.push(format!("<< /Type /Pages /Kids [{}] /Count {} >>", kids.join(" "), page_count).into_bytes());
+        objects.push(b"<< /Type /Font /Subtype /Type1 /BaseFont /Courier /Encoding /WinAnsiEncoding >>".to_vec());
+        objects.push(b"<< /Type /Font /Subtype /Type1 /BaseFont /Courier-Bold /Encoding /WinAnsiEncoding >>".to_vec());
+
+        for (index, lines) in self.pages.iter().enumerate() {
+            let mut content = Vec::new();
+            for line in lines {
+                push_text(&mut content, &line.text, line.style, line.size, MARGIN, line.y);
+            }
+            let footer = format!("Page {} of {}", index + 1, page_count);
+            let footer_x = PAGE_WIDTH - MARGIN - footer.len() as f32 * BODY_SIZE * GLYPH_WIDTH_EM;
+            push_text(&mut content, &footer, Style::Regular, BODY_SIZE, footer_x, MARGIN);
+
+            objects.push(
+                format!(
+                    "<< /Type /Page /Parent 2 0 R /MediaBox [0 0 {} {}] /Resources << /Font << /F1 3 0 R /F2 4 0 R >> >> /Contents {} 0 R >>",
+                    PAGE_WIDTH,
+                    PAGE_HEIGHT,
+                    6 + index * 2
+                )
+                .into_bytes(),
+            );
+            let mut stream = format!("<< /Length {} >>\nstream\n", content.len()).into_bytes();
+            stream.extend_from_slice(&content);
+            stream.extend_from_slice(b"\nendstream");
+            objects.push(stream);
+        }
+
+        let mut out: Vec<u8> = b"%PDF-1.4\n%\xE2\xE3\xCF\xD3\n".to_vec();
+        let mut offsets = Vec::with_capacity(objects.len());
+        for (index, body) in objects.iter().enumerate() {
+            offsets.push(out.len());
+            out.extend_from_slice(format!("{} 0 obj\n", index + 1).as_bytes());
+            out.extend_from_slice(body);
+            out.extend_from_slice(b"\nendobj\n");
+        }
+
+        let xref_offset = out.len();
+        out.extend_from_slice(format!("xref\n0 {}\n0000000000 65535 f \n", objects.len() + 1).as_bytes());
+        for offset in offsets {
+            out.extend_from_slice(format!("{:010} 00000 n \n", offset).as_bytes());
+        }
+        out.extend_from_slice(
+            format!(
+                "trailer\n<< /Size {} /Root 1 0 R >>\nstartxref\n{}\n%%EOF\n",
+                objects.len() + 1,
+                xref_offset
+            )
+            .as_bytes(),
+        );
+        out
+    }
+}
+
+fn chars_per_line(size: f32) -> usize {
+    ((PAGE_WIDTH - 2.0 * MARGIN) / (size * GLYPH_WIDTH_EM)) as usize
+}
+
+fn push_text(content: &mut Vec<u8>, text: &str, style: Style, size: f32, x: f32, y: f32) {
+    let font = match style {
+        Style::Regular => "F1",
+        Style::Bold => "F2",
+    };
+    content.extend_from_slice(format!("BT /{} {} Tf {:.2} {:.2} Td (", font, size, x, y).as_bytes());
+    content.extend_from_slice(&encode_text(text));
+    content.extend_from_slice(b") Tj ET\n");
+}
+
+/// Wrap at whitespace to `width` characters, hard-breaking longer words.
+/// Blank input lines are kept so paragraphs stay separated.
+fn wrap(text: &str, width: usize) -> Vec<String> {
+    let mut lines = Vec::new();
+    for raw in text.split('\n') {
+        let mut line = String::new();
+        let mut line_len = 0;
+        for word in raw.split_whitespace() {
+            let mut word: Vec<char> = word.chars().collect();
+            while word.len() > width {
+                if line_len > 0 {
+                    lines.push(std::mem::take(&mut line));
+                    line_len = 0;
+                }
+                let rest = word.split_off(width);
+                lines.push(word.into_iter().collect());
+                word = rest;
+            }
+            let needed = if line_len == 0 { word.len() } else { line_len + 1 + word.len() };
+            if needed > width {
+                lines.push(std::mem::take(&mut line));
+                line_len = 0;
+            }
+            if line_len > 0 {
+                line.push(' ');
+                line_len += 1;
+            }
+            line.extend(word.iter());
+            line_len += word.len();
+        }
+        lines.push(line);
+    }
+    lines
+}
+
+/// Encode for a WinAnsi literal string: escape delimiters, map the common
+/// typographic characters, and replace anything else outside Latin-1 with '?'
+fn encode_text(text: &str) -> Vec<u8> {
+    let mut out = Vec::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '(' | ')' | '\\' => {
+                out.push(b'\\');
+                out.push(c as u8);
+            }
+            '\t' => out.push(b' '),
+            c if c.is_control() => {}
+            c if (c as u32) < 0x80 => out.push(c as u8),
+            '\u{20AC}' => out.push(0x80),
+            '\u{2026}' => out.push(0x85),
+            '\u{2018}' => out.push(0x91),
+            '\u{2019}' => out.push(0x92),
+            '\u{201C}' => out.push(0x93),
+            '\u{201D}' => out.push(0x94),
+            '\u{2022}' => out.push(0x95),
+            '\u{2013}' => out.push(0x96),
+            '\u{2014}' => out.push(0x97),
+            c if (0xA0..=0xFF).contains(&(c as u32)) => out.push(c as u32 as u8),
+            _ => out.push(b'?'),
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_wrap_breaks_at_whitespace_and_long_words() {
+        assert_eq!(wrap("the quick brown fox", 9), vec!["the quick", "brown fox"]);
+        assert_eq!(wrap("abcdefghij xy", 4), vec!["abcd", "efgh", "ij", "xy"]);
+        assert_eq!(wrap("one\n\ntwo", 10), vec!["one", "", "two"]);
+    }
+
+    #[test]
+    fn test_encode_text_escapes_and_maps_characters() {
+        assert_eq!(encode_text(r"a (b) \c"), br"a \(b\) \\c".to_vec());
+        assert_eq!(encode_text("caf\u{e9} \u{2014} \u{2019}"), vec![b'c', b'a', b'f', 0xE9, b' ', 0x97, b' ', 0x92]);
+        assert_eq!(encode_text("\u{4e2d}\t"), b"? ".to_vec());
+    }
+
+    #[test]
+    fn test_finish_paginates_with_valid_xref() {
+        let mut pdf = PdfWriter::new();
+        pdf.heading("Report");
+        for i in 0..200 {
+            pdf.paragraph(&format!("line {}", i));
+        }
+        assert!(pdf.page_count() > 1);
+        let pages = pdf.page_count();
+        let bytes = pdf.finish();
+        let text = String::from_utf8_lossy(&bytes);
+
+        assert!(bytes.starts_with(b"%PDF-1.4"));
+        assert!(text.trim_end().ends_with("%%EOF"));
+        assert!(text.contains(&format!("/Count {}", pages)));
+        assert!(text.contains(&format!("(Page {} of {})", pages, pages)));
+
+        // startxref points at the xref table, and the first object offset is
+        // right. Everything from the xref table on is ASCII.
+        let startxref: usize = text.rsplit("startxref\n").next().unwrap().lines().next().unwrap().parse().unwrap();
+        let xref = std::str::from_utf8(&bytes[startxref..]).unwrap();
+        assert!(xref.starts_with("xref"));
+        let first: usize = xref.lines().nth(3).unwrap()[..10].parse().unwrap();
+        assert!(bytes[first..].starts_with(b"1 0 obj"));
+    }
+}
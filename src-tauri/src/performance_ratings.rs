@@ -22,6 +22,8 @@ pub enum RatingError {
     Validation(String),
     #[error("Duplicate rating: employee already has a rating for this cycle")]
     DuplicateRating,
+    #[error("{}", crate::db::READ_ONLY_MESSAGE)]
+    ReadOnly,
 }
 
 impl From<sqlx::Error> for RatingError {
@@ -35,6 +37,12 @@ impl From<sqlx::Error> for RatingError {
     }
 }
 
+impl From<crate::db::ReadOnly> for RatingError {
+    fn from(_: crate::db::ReadOnly) -> Self {
+        RatingError::ReadOnly
+    }
+}
+
 // ============================================================================
 // Performance Rating Struct
 // ============================================================================
@@ -91,6 +99,13 @@ fn validate_rating(value: f64, field_name: &str) -> Result<(), RatingError> {
     Ok(())
 }
 
+/// Sort key for how recent a review cycle is: its end date, falling back to
+/// the start date and then creation time when the end date is blank or not
+/// ISO. `alias` is the review_cycles table alias in the calling query.
+pub fn cycle_recency(alias: &str) -> String {
+    format!("COALESCE(date({alias}.end_date), date({alias}.start_date), {alias}.created_at)")
+}
+
 // ============================================================================
 // CRUD Operations
 // ============================================================================
@@ -156,14 +171,15 @@ pub async fn get_ratings_for_employee(
     pool: &DbPool,
     employee_id: &str,
 ) -> Result<Vec<PerformanceRating>, RatingError> {
-    let ratings = sqlx::query_as::<_, PerformanceRating>(
+    let ratings = sqlx::query_as::<_, PerformanceRating>(&format!(
         r#"
         SELECT pr.* FROM performance_ratings pr
         JOIN review_cycles rc ON pr.review_cycle_id = rc.id
         WHERE pr.employee_id = ?
-        ORDER BY rc.start_date DESC
+        ORDER BY {} DESC
         "#,
-    )
+        cycle_recency("rc")
+    ))
     .bind(employee_id)
     .fetch_all(pool)
     .await?;
@@ -191,15 +207,16 @@ pub async fn get_latest_rating_for_employee(
     pool: &DbPool,
     employee_id: &str,
 ) -> Result<Option<PerformanceRating>, RatingError> {
-    let rating = sqlx::query_as::<_, PerformanceRating>(
+    let rating = sqlx::query_as::<_, PerformanceRating>(&format!(
         r#"
         SELECT pr.* FROM performance_ratings pr
         JOIN review_cycles rc ON pr.review_cycle_id = rc.id
         WHERE pr.employee_id = ?
-        ORDER BY rc.start_date DESC
+        ORDER BY {} DESC
         LIMIT 1
         "#,
-    )
+        cycle_recency("rc")
+    ))
     .bind(employee_id)
     .fetch_optional(pool)
     .await?;
@@ -320,3 +337,847 @@ pub async fn get_average_rating(
 
     Ok(row.get("avg"))
 }
+
+// ============================================================================
+// Upcoming Review Schedule
+// ============================================================================
+
+/// An active employee who comes due for review, based on last rating age
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EmployeeReviewDue {
+    pub employee_id: String,
+    pub full_name: String,
+    pub department: Option<String>,
+    pub last_rating_date: Option<String>,
+    /// last_rating_date + cadence (None when never rated)
+    pub next_due_date: Option<String>,
+    pub days_until_due: Option<i64>,
+    /// Never rated, so a review is due immediately
+    pub due_now: bool,
+}
+
+/// List active employees whose next review falls within the next `days` days,
+/// soonest first. Next-due is the last rating date plus `cadence_months`;
+/// employees never rated are included and flagged as due now. Already-overdue
+/// employees are excluded (this is a forward-looking schedule).
+pub async fn get_upcoming_reviews(
+    pool: &DbPool,
+    days: i64,
+    cadence_months: u32,
+) -> Result<Vec<EmployeeReviewDue>, RatingError> {
+    if days < 0 {
+        return Err(RatingError::Validation("days must be non-negative".to_string()));
+    }
+    if cadence_months == 0 {
+        return Err(RatingError::Validation("cadence_months must be at least 1".to_string()));
+    }
+
+    let today = crate::tenure::local_today();
+    let candidates = fetch_review_candidates(pool).await?;
+
+    Ok(select_upcoming_reviews(candidates, today, days, cadence_months))
+}
+
+/// Every active employee with their most recent rating date
+async fn fetch_review_candidates(pool: &DbPool) -> Result<Vec<EmployeeReviewDue>, RatingError> {
+    // Fall back to the cycle end date when rating_date wasn't recorded
+    let rows = sqlx::query(
+        r#"
+        SELECT e.id, e.full_name, e.department,
+               MAX(COALESCE(NULLIF(pr.rating_date, ''), date(rc.end_date), date(rc.start_date))) as last_rating_date
+        FROM employees e
+        LEFT JOIN performance_ratings pr ON pr.employee_id = e.id
+        LEFT JOIN review_cycles rc ON pr.review_cycle_id = rc.id
+        WHERE e.status = 'active'
+        GROUP BY e.id
+        "#,
+    )
+    .fetch_all(pool)
+    .await?;
+
+    let candidates = rows
+        .into_iter()
+        .map(|row| EmployeeReviewDue {
+            employee_id: row.get("id"),
+            full_name: row.get("full_name"),
+            department: row.get("department"),
+            last_rating_date: row.get("last_rating_date"),
+            next_due_date: None,
+            days_until_due: None,
+            due_now: false,
+        })
+        .collect();
+
+    Ok(candidates)
+}
+
+/// Compute next-due dates and keep those within [today, today + days]
+fn select_upcoming_reviews(
+    candidates: Vec<EmployeeReviewDue>,
+    today: chrono::NaiveDate,
+    days: i64,
+    cadence_months: u32,
+) -> Vec<EmployeeReviewDue> {
+    let mut upcoming: Vec<EmployeeReviewDue> = candidates
+        .into_iter()
+        .filter_map(|mut emp| {
+            let last = emp.last_rating_date.as_deref().and_then(parse_rating_date);
+            match last {
+                None => {
+                    emp.due_now = true;
+                    emp.days_until_due = Some(0);
+                    Some(emp)
+                }
+                Some(last) => {
+                    let due = next_review_due(last, cadence_months)?;
+                    let days_until = (due - today).num_days();
+                    if days_until < 0 || days_until > days {
+                        return None;
+                    }
+                    emp.next_due_date = Some(due.format("%Y-%m-%d").to_string());
+                    emp.days_until_due = Some(days_until);
+                    Some(emp)
+                }
+            }
+        })
+        .collect();
+
+    upcoming.sort_by(|a, b| {
+        a.days_until_due
+            .cmp(&b.days_until_due)
+            .then_with(|| a.full_name.cmp(&b.full_name))
+    });
+    upcoming
+}
+
+// ============================================================================
+// Review Coverage
+// ============================================================================
+
+/// Rating coverage for one population of active employees
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct CoverageCounts {
+    pub active_count: i64,
+    /// Rated within the cadence
+    pub current_count: i64,
+    /// Rated, but longer ago than the cadence
+    pub stale_count: i64,
+    pub never_rated_count: i64,
+    /// current_count / active_count as a percentage (0 when nobody is active)
+    pub coverage_pct: f64,
+}
+
+impl CoverageCounts {
+    fn add(&mut self, last: Option<chrono::NaiveDate>, today: chrono::NaiveDate, cadence_months: u32) {
+        self.active_count += 1;
+        match last {
+            None => self.never_rated_count += 1,
+            Some(last) => match next_review_due(last, cadence_months) {
+                Some(due) if due >= today => self.current_count += 1,
+                _ => self.stale_count += 1,
+            },
+        }
+        self.coverage_pct = if self.active_count > 0 {
+            (self.current_count as f64 / self.active_count as f64 * 1000.0).round() / 10.0
+        } else {
+            0.0
+        };
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DepartmentReviewCoverage {
+    pub department: String,
+    pub counts: CoverageCounts,
+}
+
+/// Org-level share of active employees rated within the expected cadence
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReviewCoverage {
+    pub cadence_months: u32,
+    pub overall: CoverageCounts,
+    /// Alphabetical by department
+    pub by_department: Vec<DepartmentReviewCoverage>,
+}
+
+/// Share of active employees whose latest rating is within `cadence_months`,
+/// with stale and never-rated counts, overall and per department
+pub async fn get_review_coverage(pool: &DbPool, cadence_months: u32) -> Result<ReviewCoverage, RatingError> {
+    if cadence_months == 0 {
+        return Err(RatingError::Validation("cadence_months must be at least 1".to_string()));
+    }
+
+    let candidates = fetch_review_candidates(pool).await?;
+    let today = crate::tenure::local_today();
+    Ok(summarize_coverage(&candidates, today, cadence_months))
+}
+
+fn summarize_coverage(
+    candidates: &[EmployeeReviewDue],
+    today: chrono::NaiveDate,
+    cadence_months: u32,
+) -> ReviewCoverage {
+    let mut overall = CoverageCounts::default();
+    let mut by_department: std::collections::BTreeMap<String, CoverageCounts> = Default::default();
+
+    for emp in candidates {
+        let last = emp.last_rating_date.as_deref().and_then(parse_rating_date);
+        let department = match emp.department.as_deref().map(str::trim) {
+            Some(d) if !d.is_empty() => d.to_string(),
+            _ => "Unassigned".to_string(),
+        };
+        overall.add(last, today, cadence_months);
+        by_department.entry(department).or_default().add(last, today, cadence_months);
+    }
+
+    ReviewCoverage {
+        cadence_months,
+        overall,
+        by_department: by_department
+            .into_iter()
+            .map(|(department, counts)| DepartmentReviewCoverage { department, counts })
+            .collect(),
+    }
+}
+
+// ============================================================================
+// First Rating Lead Time
+// ============================================================================
+
+/// Default days after hire by which a first rating is expected
+pub const DEFAULT_FIRST_RATING_THRESHOLD_DAYS: i64 = 180;
+
+/// How long active employees wait from hire to their first rating
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LeadTimeStats {
+    pub threshold_days: i64,
+    /// Employees with a first rating on or after their hire date
+    pub rated_count: i64,
+    pub avg_days_to_first_rating: Option<f64>,
+    pub median_days_to_first_rating: Option<f64>,
+    /// Hired more than `threshold_days` ago and still never rated
+    pub unrated_past_threshold: i64,
+    /// Unrated, but hired too recently to expect a rating yet
+    pub excluded_recent_hires: i64,
+}
+
+/// Days from hire_date to each active employee's earliest rating (rating
+/// date, else the cycle's end/start date). Employees without a usable
+/// hire_date, or whose first rating predates their hire, are left out.
+pub async fn compute_first_rating_lead_time(
+    pool: &DbPool,
+    threshold_days: Option<i64>,
+) -> Result<LeadTimeStats, RatingError> {
+    let threshold_days = threshold_days.unwrap_or(DEFAULT_FIRST_RATING_THRESHOLD_DAYS);
+    if threshold_days < 0 {
+        return Err(RatingError::Validation("threshold_days must be non-negative".to_string()));
+    }
+
+    let rows: Vec<(Option<String>, Option<String>)> = sqlx::query_as(
+        r#"
+        SELECT e.hire_date,
+               MIN(COALESCE(NULLIF(pr.rating_date, ''), date(rc.end_date), date(rc.start_date))) as first_rating_date
+        FROM employees e
+        LEFT JOIN performance_ratings pr ON pr.employee_id = e.id
+        LEFT JOIN review_cycles rc ON pr.review_cycle_id = rc.id
+        WHERE e.status = 'active'
+        GROUP BY e.id
+        "#,
+    )
+    .fetch_all(pool)
+    .await?;
+
+    let today = crate::tenure::local_today();
+    Ok(summarize_lead_times(&rows, today, threshold_days))
+}
+
+fn summarize_lead_times(
+    rows: &[(Option<String>, Option<String>)],
+    today: chrono::NaiveDate,
+    threshold_days: i64,
+) -> LeadTimeStats {
+    let mut lead_days: Vec<i64> = Vec::new();
+    let mut unrated_past_threshold = 0;
+    let mut excluded_recent_hires = 0;
+
+    for (hire_date, first_rating_date) in rows {
+        let Some(hire) = hire_date.as_deref().and_then(parse_rating_date) else {
+            continue;
+        };
+        match first_rating_date.as_deref().and_then(parse_rating_date) {
+            Some(first) => {
+                let days = (first - hire).num_days();
+                if days >= 0 {
+                    lead_days.push(days);
+                }
+            }
+            None if (today - hire).num_days() > threshold_days => unrated_past_threshold += 1,
+            None => excluded_recent_hires += 1,
+        }
+    }
+
+    lead_days.sort_unstable();
+    let avg_days_to_first_rating = if lead_days.is_empty() {
+        None
+    } else {
+        Some(lead_days.iter().sum::<i64>() as f64 / lead_days.len() as f64)
+    };
+    let median_days_to_first_rating = match lead_days.len() {
+        0 => None,
+        n if n.is_multiple_of(2) => Some((lead_days[n / 2 - 1] + lead_days[n / 2]) as f64 / 2.0),
+        n => Some(lead_days[n / 2] as f64),
+    };
+
+    LeadTimeStats {
+        threshold_days,
+        rated_count: lead_days.len() as i64,
+        avg_days_to_first_rating,
+        median_days_to_first_rating,
+        unrated_past_threshold,
+        excluded_recent_hires,
+    }
+}
+
+// ============================================================================
+// Rating Normalization
+// ============================================================================
+
+/// Reviewers with fewer ratings than this in a cycle are not normalized
+pub const MIN_RATINGS_FOR_NORMALIZATION: usize = 3;
+
+/// Why a rating was left un-normalized (or normalized trivially)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NormalizationFlag {
+    /// The rating has no reviewer to compare against
+    NoReviewer,
+    /// The reviewer gave fewer than `MIN_RATINGS_FOR_NORMALIZATION` ratings
+    TooFewRatings,
+    /// The reviewer gave everyone the same rating; z-score is 0.0
+    ZeroVariance,
+}
+
+/// One rating alongside its z-score within the reviewer's ratings for the cycle
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NormalizedRating {
+    pub rating_id: String,
+    pub employee_id: String,
+    pub reviewer_id: Option<String>,
+    pub raw_rating: f64,
+    /// (raw - reviewer mean) / reviewer standard deviation; None when flagged
+    /// as `NoReviewer` or `TooFewRatings`
+    pub z_score: Option<f64>,
+    pub reviewer_mean: Option<f64>,
+    /// Population standard deviation of the reviewer's ratings
+    pub reviewer_std_dev: Option<f64>,
+    pub flag: Option<NormalizationFlag>,
+}
+
+/// Z-score each overall rating in a cycle against its reviewer's mean and
+/// standard deviation, so a 4.0 from a tough grader and a 4.0 from an easy
+/// one can be told apart. Ordered like `get_ratings_for_cycle`.
+pub async fn normalize_cycle_ratings(
+    pool: &DbPool,
+    review_cycle_id: &str,
+) -> Result<Vec<NormalizedRating>, RatingError> {
+    let cycle_exists: Option<(i64,)> = sqlx::query_as("SELECT 1 FROM review_cycles WHERE id = ?")
+        .bind(review_cycle_id)
+        .fetch_optional(pool)
+        .await?;
+    if cycle_exists.is_none() {
+        return Err(RatingError::NotFound(format!("review cycle {}", review_cycle_id)));
+    }
+
+    let ratings = get_ratings_for_cycle(pool, review_cycle_id).await?;
+    Ok(normalize_ratings(&ratings))
+}
+
+fn normalize_ratings(ratings: &[PerformanceRating]) -> Vec<NormalizedRating> {
+    use std::collections::HashMap;
+
+    let mut by_reviewer: HashMap<&str, Vec<f64>> = HashMap::new();
+    for rating in ratings {
+        if let Some(reviewer) = rating.reviewer_id.as_deref() {
+            by_reviewer.entry(reviewer).or_default().push(rating.overall_rating);
+        }
+    }
+
+    // Mean and population standard deviation per reviewer with enough ratings
+    let stats: HashMap<&str, (f64, f64)> = by_reviewer
+        .iter()
+        .filter(|(_, values)| values.len() >= MIN_RATINGS_FOR_NORMALIZATION)
+        .map(|(reviewer, values)| {
+            let n = values.len() as f64;
+            let mean = values.iter().sum::<f64>() / n;
+            let variance = values.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / n;
+            (*reviewer, (mean, variance.sqrt()))
+        })
+        .collect();
+
+    ratings
+        .iter()
+        .map(|rating| {
+            let reviewer = rating.reviewer_id.as_deref();
+            let (z_score, reviewer_mean, reviewer_std_dev, flag) = match reviewer {
+                None => (None, None, None, Some(NormalizationFlag::NoReviewer)),
+                Some(id) => match stats.get(id) {
+                    None => (None, None, None, Some(NormalizationFlag::TooFewRatings)),
+                    // Identical ratings (or float noise around them) carry no
+                    // spread to normalize by; everyone sits at the mean
+                    Some(&(mean, sd)) if sd < 1e-9 => {
+                        (Some(0.0), Some(mean), Some(sd), Some(NormalizationFlag::ZeroVariance))
+                    }
+                    Some(&(mean, sd)) => (Some((rating.overall_rating - mean) / sd), Some(mean), Some(sd), None),
+                },
+            };
+            NormalizedRating {
+                rating_id: rating.id.clone(),
+                employee_id: rating.employee_id.clone(),
+                reviewer_id: rating.reviewer_id.clone(),
+                raw_rating: rating.overall_rating,
+                z_score,
+                reviewer_mean,
+                reviewer_std_dev,
+                flag,
+            }
+        })
+        .collect()
+}
+
+// ============================================================================
+// Rating History
+// ============================================================================
+
+/// One cycle in an employee's rating history
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, FromRow)]
+pub struct RatingHistoryPoint {
+    pub review_cycle_id: String,
+    pub cycle_name: String,
+    pub cycle_start_date: String,
+    pub rating_date: Option<String>,
+    pub overall_rating: f64,
+    pub goals_rating: Option<f64>,
+    pub competencies_rating: Option<f64>,
+    /// Change in overall rating since the employee's previous rated cycle;
+    /// None for the first point. Cycles without a rating are skipped.
+    #[sqlx(skip)]
+    pub delta: Option<f64>,
+}
+
+/// An employee's ratings in cycle order (oldest first), for charting. Uses
+/// the same cycle ordering as the latest-rating lookups, so the last point
+/// is always the rating shown as current.
+pub async fn get_rating_history(
+    pool: &DbPool,
+    employee_id: &str,
+) -> Result<Vec<RatingHistoryPoint>, RatingError> {
+    let mut points = sqlx::query_as::<_, RatingHistoryPoint>(&format!(
+        r#"
+        SELECT pr.review_cycle_id, rc.name AS cycle_name, rc.start_date AS cycle_start_date,
+               pr.rating_date, pr.overall_rating, pr.goals_rating, pr.competencies_rating
+        FROM performance_ratings pr
+        JOIN review_cycles rc ON pr.review_cycle_id = rc.id
+        WHERE pr.employee_id = ?
+        ORDER BY {} ASC, rc.id ASC
+        "#,
+        cycle_recency("rc")
+    ))
+    .bind(employee_id)
+    .fetch_all(pool)
+    .await?;
+
+    fill_deltas(&mut points);
+    Ok(points)
+}
+
+fn fill_deltas(points: &mut [RatingHistoryPoint]) {
+    let mut previous: Option<f64> = None;
+    for point in points {
+        // Rounded so 3.5 -> 3.2 reads as -0.3, not -0.2999...
+        point.delta = previous.map(|prev| ((point.overall_rating - prev) * 100.0).round() / 100.0);
+        previous = Some(point.overall_rating);
+    }
+}
+
+/// Next review date: last rating date plus the cadence, clamped to month end
+/// (e.g. Aug 31 + 6 months = Feb 28/29)
+fn next_review_due(last: chrono::NaiveDate, cadence_months: u32) -> Option<chrono::NaiveDate> {
+    last.checked_add_months(chrono::Months::new(cadence_months))
+}
+
+/// Parse the date portion of a stored rating date ("YYYY-MM-DD" or a datetime)
+fn parse_rating_date(value: &str) -> Option<chrono::NaiveDate> {
+    let date_part = value.get(..10).unwrap_or(value);
+    chrono::NaiveDate::parse_from_str(date_part, "%Y-%m-%d").ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+
+    fn date(s: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(s, "%Y-%m-%d").unwrap()
+    }
+
+    fn candidate(name: &str, last: Option<&str>) -> EmployeeReviewDue {
+        EmployeeReviewDue {
+            employee_id: format!("emp-{}", name),
+            full_name: name.to_string(),
+            department: None,
+            last_rating_date: last.map(|s| s.to_string()),
+            next_due_date: None,
+            days_until_due: None,
+            due_now: false,
+        }
+    }
+
+    #[test]
+    fn test_next_review_due_cadence_math() {
+        assert_eq!(next_review_due(date("2024-03-15"), 12), Some(date("2025-03-15")));
+        assert_eq!(next_review_due(date("2024-03-15"), 6), Some(date("2024-09-15")));
+        // Clamps to the last day of shorter months
+        assert_eq!(next_review_due(date("2024-08-31"), 6), Some(date("2025-02-28")));
+        assert_eq!(next_review_due(date("2024-02-29"), 12), Some(date("2025-02-28")));
+    }
+
+    #[test]
+    fn test_parse_rating_date_accepts_datetimes() {
+        assert_eq!(parse_rating_date("2024-06-01"), Some(date("2024-06-01")));
+        assert_eq!(parse_rating_date("2024-06-01 10:30:00"), Some(date("2024-06-01")));
+        assert_eq!(parse_rating_date("not a date"), None);
+    }
+
+    #[test]
+    fn test_upcoming_window_boundary() {
+        let today = date("2025-01-01");
+        let candidates = vec![
+            candidate("Edge", Some("2024-01-31")),    // due 2025-01-31, day 30
+            candidate("Outside", Some("2024-02-01")), // due 2025-02-01, day 31
+            candidate("Today", Some("2024-01-01")),   // due today, day 0
+            candidate("Overdue", Some("2023-12-31")), // due yesterday
+        ];
+
+        let upcoming = select_upcoming_reviews(candidates, today, 30, 12);
+        let names: Vec<&str> = upcoming.iter().map(|e| e.full_name.as_str()).collect();
+        assert_eq!(names, vec!["Today", "Edge"]);
+        assert_eq!(upcoming[1].days_until_due, Some(30));
+        assert_eq!(upcoming[1].next_due_date.as_deref(), Some("2025-01-31"));
+    }
+
+    #[test]
+    fn test_never_rated_is_due_now_and_first() {
+        let today = date("2025-01-01");
+        let candidates = vec![
+            candidate("Rated", Some("2024-01-10")),
+            candidate("Unrated", None),
+        ];
+
+        let upcoming = select_upcoming_reviews(candidates, today, 30, 12);
+        assert_eq!(upcoming.len(), 2);
+        assert_eq!(upcoming[0].full_name, "Unrated");
+        assert!(upcoming[0].due_now);
+        assert!(upcoming[0].next_due_date.is_none());
+        assert!(!upcoming[1].due_now);
+    }
+
+    #[test]
+    fn test_review_coverage_percentages() {
+        let today = date("2025-01-01");
+        let mut candidates = vec![
+            candidate("Recent", Some("2024-06-01")),
+            candidate("Boundary", Some("2024-01-01")), // due today, still current
+            candidate("Stale", Some("2023-12-31")),
+            candidate("Never", None),
+        ];
+        for emp in candidates.iter_mut().take(2) {
+            emp.department = Some("Engineering".to_string());
+        }
+        candidates[2].department = Some("Sales".to_string());
+
+        let coverage = summarize_coverage(&candidates, today, 12);
+        assert_eq!(coverage.overall.active_count, 4);
+        assert_eq!(coverage.overall.current_count, 2);
+        assert_eq!(coverage.overall.stale_count, 1);
+        assert_eq!(coverage.overall.never_rated_count, 1);
+        assert_eq!(coverage.overall.coverage_pct, 50.0);
+
+        let depts: Vec<(&str, f64)> = coverage
+            .by_department
+            .iter()
+            .map(|d| (d.department.as_str(), d.counts.coverage_pct))
+            .collect();
+        assert_eq!(depts, vec![("Engineering", 100.0), ("Sales", 0.0), ("Unassigned", 0.0)]);
+    }
+
+    #[test]
+    fn test_review_coverage_empty_org() {
+        let coverage = summarize_coverage(&[], date("2025-01-01"), 12);
+        assert_eq!(coverage.overall, CoverageCounts::default());
+        assert!(coverage.by_department.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_review_candidates_skip_blank_rating_dates() {
+        let pool = crate::db::test_pool().await;
+        sqlx::query(
+            r#"
+            INSERT INTO employees (id, email, full_name, hire_date, status)
+                VALUES ('a', 'a@example.com', 'Ana', '2020-01-01', 'active');
+            INSERT INTO review_cycles (id, name, cycle_type, start_date, end_date)
+                VALUES ('c1', '2024 Annual', 'annual', '2024-01-01', '2024-12-31');
+            INSERT INTO performance_ratings (id, employee_id, review_cycle_id, overall_rating, rating_date)
+                VALUES ('r1', 'a', 'c1', 3.5, '');
+            "#,
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        // A blank rating_date falls back to the cycle end date, not "never rated"
+        let candidates = fetch_review_candidates(&pool).await.unwrap();
+        assert_eq!(candidates[0].last_rating_date.as_deref(), Some("2024-12-31"));
+    }
+
+    #[tokio::test]
+    async fn test_get_review_coverage_rejects_zero_cadence() {
+        let pool = crate::db::test_pool().await;
+        assert!(matches!(
+            get_review_coverage(&pool, 0).await,
+            Err(RatingError::Validation(_))
+        ));
+        let coverage = get_review_coverage(&pool, 12).await.unwrap();
+        assert_eq!(coverage.overall.active_count, 0);
+    }
+
+    #[test]
+    fn test_first_rating_lead_time_stats() {
+        let today = date("2025-01-01");
+        let row = |hire: &str, first: Option<&str>| (Some(hire.to_string()), first.map(|f| f.to_string()));
+        let rows = vec![
+            row("2024-01-01", Some("2024-01-31")), // quick: 30 days
+            row("2023-01-01", Some("2023-12-27")), // slow: 360 days
+            row("2022-06-01", Some("2022-09-09")), // 100 days
+            row("2023-06-01", None),               // unrated, long past threshold
+            row("2024-11-01", None),               // unrated, too recent to expect one
+            row("2024-05-01", Some("2024-04-01")), // rated before hire: bad data, skipped
+            (None, Some("2024-01-01".to_string())), // no hire date
+        ];
+
+        let stats = summarize_lead_times(&rows, today, 180);
+        assert_eq!(stats.rated_count, 3);
+        assert_eq!(stats.median_days_to_first_rating, Some(100.0));
+        let avg = stats.avg_days_to_first_rating.unwrap();
+        assert!((avg - 490.0 / 3.0).abs() < 0.001, "avg was {}", avg);
+        assert_eq!(stats.unrated_past_threshold, 1);
+        assert_eq!(stats.excluded_recent_hires, 1);
+    }
+
+    #[tokio::test]
+    async fn test_first_rating_lead_time_uses_cycle_date_fallback() {
+        let pool = crate::db::test_pool().await;
+        sqlx::query(
+            r#"
+            INSERT INTO employees (id, email, full_name, hire_date, status) VALUES
+                ('a', 'a@example.com', 'Ana', '2024-01-01', 'active'),
+                ('b', 'b@example.com', 'Ben', '2020-01-01', 'active');
+            INSERT INTO review_cycles (id, name, cycle_type, start_date, end_date)
+                VALUES ('c1', 'H1 2024', 'semi-annual', '2024-01-01', '2024-06-30'),
+                       ('c2', 'H2 2024', 'semi-annual', '2024-07-01', '2024-12-31');
+            INSERT INTO performance_ratings (id, employee_id, review_cycle_id, overall_rating)
+                VALUES ('r1', 'a', 'c1', 3.5), ('r2', 'a', 'c2', 4.0);
+            "#,
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        let stats = compute_first_rating_lead_time(&pool, None).await.unwrap();
+        // First rating falls back to the earlier cycle's end date (181 days in)
+        assert_eq!(stats.rated_count, 1);
+        assert_eq!(stats.median_days_to_first_rating, Some(181.0));
+        assert_eq!(stats.unrated_past_threshold, 1);
+        assert_eq!(stats.threshold_days, DEFAULT_FIRST_RATING_THRESHOLD_DAYS);
+
+        // A blank rating_date is not an earlier "first rating"
+        sqlx::query("INSERT INTO performance_ratings (id, employee_id, review_cycle_id, overall_rating, rating_date) VALUES ('r3', 'b', 'c2', 3.0, '')")
+            .execute(&pool)
+            .await
+            .unwrap();
+        let stats = compute_first_rating_lead_time(&pool, None).await.unwrap();
+        assert_eq!(stats.rated_count, 2);
+        assert_eq!(stats.unrated_past_threshold, 0);
+    }
+
+    fn cycle_rating(id: &str, reviewer: Option<&str>, overall: f64) -> PerformanceRating {
+        PerformanceRating {
+            id: id.to_string(),
+            employee_id: format!("emp-{}", id),
+            review_cycle_id: "c1".to_string(),
+            overall_rating: overall,
+            goals_rating: None,
+            competencies_rating: None,
+            reviewer_id: reviewer.map(|r| r.to_string()),
+            rating_date: None,
+            created_at: String::new(),
+            updated_at: String::new(),
+        }
+    }
+
+    #[test]
+    fn test_normalize_ratings_normal_spread() {
+        // Reviewer "tough": 2.0, 3.0, 4.0 → mean 3.0, population sd sqrt(2/3)
+        let ratings = vec![
+            cycle_rating("a", Some("tough"), 2.0),
+            cycle_rating("b", Some("tough"), 3.0),
+            cycle_rating("c", Some("tough"), 4.0),
+        ];
+        let normalized = normalize_ratings(&ratings);
+        let sd = (2.0f64 / 3.0).sqrt();
+
+        assert_eq!(normalized[1].z_score, Some(0.0));
+        assert!((normalized[0].z_score.unwrap() + 1.0 / sd).abs() < 1e-9);
+        assert!((normalized[2].z_score.unwrap() - 1.0 / sd).abs() < 1e-9);
+        assert_eq!(normalized[2].raw_rating, 4.0);
+        assert_eq!(normalized[2].reviewer_mean, Some(3.0));
+        assert!(normalized.iter().all(|n| n.flag.is_none()));
+    }
+
+    #[test]
+    fn test_normalize_ratings_zero_variance_and_small_reviewers() {
+        let ratings = vec![
+            cycle_rating("a", Some("easy"), 5.0),
+            cycle_rating("b", Some("easy"), 5.0),
+            cycle_rating("c", Some("easy"), 5.0),
+            cycle_rating("d", Some("new-manager"), 4.0),
+            cycle_rating("e", Some("new-manager"), 2.0),
+            cycle_rating("f", None, 3.0),
+        ];
+        let normalized = normalize_ratings(&ratings);
+
+        for n in &normalized[..3] {
+            assert_eq!(n.z_score, Some(0.0));
+            assert_eq!(n.reviewer_std_dev, Some(0.0));
+            assert_eq!(n.flag, Some(NormalizationFlag::ZeroVariance));
+        }
+        for n in &normalized[3..5] {
+            assert_eq!(n.z_score, None);
+            assert_eq!(n.flag, Some(NormalizationFlag::TooFewRatings));
+        }
+        assert_eq!(normalized[5].flag, Some(NormalizationFlag::NoReviewer));
+        assert!(normalized.iter().all(|n| n.z_score.is_none_or(f64::is_finite)));
+    }
+
+    #[tokio::test]
+    async fn test_normalize_cycle_ratings_unknown_cycle() {
+        let pool = crate::db::test_pool().await;
+        assert!(matches!(
+            normalize_cycle_ratings(&pool, "missing").await,
+            Err(RatingError::NotFound(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_rating_history_orders_cycles_and_computes_deltas() {
+        let pool = crate::db::test_pool().await;
+        sqlx::query(
+            r#"
+            INSERT INTO employees (id, email, full_name, hire_date, status) VALUES
+                ('a', 'a@example.com', 'Ana', '2020-01-01', 'active'),
+                ('b', 'b@example.com', 'Ben', '2020-01-01', 'active'),
+                ('c', 'c@example.com', 'Cy', '2023-06-01', 'active');
+            INSERT INTO review_cycles (id, name, cycle_type, start_date, end_date)
+                VALUES ('c2024', '2024 Annual', 'annual', '2024-01-01', '2024-12-31'),
+                       ('c2022', '2022 Annual', 'annual', '2022-01-01', '2022-12-31'),
+                       ('c2023', '2023 Annual', 'annual', '2023-01-01', '2023-12-31');
+            INSERT INTO performance_ratings (id, employee_id, review_cycle_id, overall_rating, goals_rating, rating_date)
+                VALUES ('r3', 'a', 'c2024', 3.2, 3.0, '2024-12-15'),
+                       ('r1', 'a', 'c2022', 3.5, NULL, '2022-12-15'),
+                       ('r2', 'b', 'c2023', 4.0, NULL, '2023-12-15'),
+                       ('r4', 'b', 'c2024', 4.5, NULL, '2024-12-15'),
+                       ('r5', 'c', 'c2024', 3.8, NULL, NULL);
+            "#,
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        // Ana has no 2023 rating: the 2024 delta compares against 2022
+        let history = get_rating_history(&pool, "a").await.unwrap();
+        let cycles: Vec<&str> = history.iter().map(|p| p.cycle_name.as_str()).collect();
+        assert_eq!(cycles, vec!["2022 Annual", "2024 Annual"]);
+        assert_eq!(history[0].delta, None);
+        assert_eq!(history[1].delta, Some(-0.3));
+        assert_eq!(history[1].goals_rating, Some(3.0));
+        assert_eq!(history[1].cycle_start_date, "2024-01-01");
+
+        let history = get_rating_history(&pool, "b").await.unwrap();
+        assert_eq!(history.iter().map(|p| p.delta).collect::<Vec<_>>(), vec![None, Some(0.5)]);
+
+        let history = get_rating_history(&pool, "c").await.unwrap();
+        assert_eq!(history.len(), 1);
+        assert_eq!(history[0].delta, None);
+        assert_eq!(history[0].rating_date, None);
+
+        assert!(get_rating_history(&pool, "nobody").await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_rating_history_ends_on_latest_rating() {
+        let pool = crate::db::test_pool().await;
+        sqlx::query(
+            r#"
+            INSERT INTO employees (id, email, full_name, hire_date, status)
+                VALUES ('a', 'a@example.com', 'Ana', '2020-01-01', 'active');
+            INSERT INTO review_cycles (id, name, cycle_type, start_date, end_date)
+                VALUES ('annual', '2024 Annual', 'annual', '2024-01-01', '2024-12-31'),
+                       ('mid', '2024 Mid-Year', 'semi-annual', '2024-03-01', '2024-06-30');
+            INSERT INTO performance_ratings (id, employee_id, review_cycle_id, overall_rating)
+                VALUES ('r1', 'a', 'annual', 4.0),
+                       ('r2', 'a', 'mid', 3.0);
+            "#,
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        let history = get_rating_history(&pool, "a").await.unwrap();
+        let cycles: Vec<&str> = history.iter().map(|p| p.cycle_name.as_str()).collect();
+        assert_eq!(cycles, vec!["2024 Mid-Year", "2024 Annual"]);
+        let latest = get_latest_rating_for_employee(&pool, "a").await.unwrap().unwrap();
+        assert_eq!(latest.review_cycle_id, history[1].review_cycle_id);
+    }
+
+    #[tokio::test]
+    async fn test_latest_rating_uses_cycle_end_date() {
+        let pool = crate::db::test_pool().await;
+        sqlx::query(
+            r#"
+            INSERT INTO employees (id, email, full_name, hire_date, status)
+                VALUES ('a', 'a@example.com', 'Ana', '2020-01-01', 'active');
+            INSERT INTO review_cycles (id, name, cycle_type, start_date, end_date)
+                VALUES ('annual', '2024 Annual', 'annual', '2024-01-01', '2024-12-31'),
+                       ('mid', '2024 Mid-Year', 'semi-annual', '2024-03-01', '2024-06-30'),
+                       ('draft', '2025 Annual', 'annual', '2025-01-01', '');
+            INSERT INTO performance_ratings (id, employee_id, review_cycle_id, overall_rating)
+                VALUES ('r1', 'a', 'annual', 4.0),
+                       ('r2', 'a', 'mid', 3.0);
+            "#,
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        // The annual cycle started first but closed last
+        let latest = get_latest_rating_for_employee(&pool, "a").await.unwrap().unwrap();
+        assert_eq!(latest.id, "r1");
+
+        // A blank end date falls back to the start date
+        sqlx::query("INSERT INTO performance_ratings (id, employee_id, review_cycle_id, overall_rating) VALUES ('r3', 'a', 'draft', 3.5)")
+            .execute(&pool)
+            .await
+            .unwrap();
+        let latest = get_latest_rating_for_employee(&pool, "a").await.unwrap().unwrap();
+        assert_eq!(latest.id, "r3");
+    }
+}
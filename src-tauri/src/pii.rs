@@ -6,13 +6,20 @@
 // - Credit Card Numbers
 // - Bank Account Numbers (with context keywords)
 //
+// Stored-data scans (company profile, settings) also flag email addresses.
+// Users can add their own patterns (e.g. internal badge numbers), stored in
+// the custom_pii_patterns setting.
+//
 // Design: Auto-redact and notify (no blocking modals)
 
 use regex::Regex;
 use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
 use std::sync::LazyLock;
 use thiserror::Error;
 
+use crate::db::DbPool;
+
 // ============================================================================
 // Error Types
 // ============================================================================
@@ -24,6 +31,27 @@ pub enum PiiError {
 
     #[error("Scan failed: {0}")]
     ScanError(String),
+
+    #[error("Database error: {0}")]
+    Database(String),
+
+    #[error("Invalid custom pattern: {0}")]
+    InvalidPattern(String),
+
+    #[error("{}", crate::db::READ_ONLY_MESSAGE)]
+    ReadOnly,
+}
+
+impl From<sqlx::Error> for PiiError {
+    fn from(err: sqlx::Error) -> Self {
+        PiiError::Database(err.to_string())
+    }
+}
+
+impl From<crate::db::ReadOnly> for PiiError {
+    fn from(_: crate::db::ReadOnly) -> Self {
+        PiiError::ReadOnly
+    }
 }
 
 // ============================================================================
@@ -40,6 +68,10 @@ pub enum PiiType {
     CreditCard,
     /// Bank Account Number (requires context keywords)
     BankAccount,
+    /// Email address (stored-data scans only)
+    Email,
+    /// User-defined pattern; the match carries the pattern's label
+    Custom,
 }
 
 impl PiiType {
@@ -49,6 +81,19 @@ impl PiiType {
             PiiType::Ssn => "[SSN_REDACTED]",
             PiiType::CreditCard => "[CC_REDACTED]",
             PiiType::BankAccount => "[BANK_ACCT_REDACTED]",
+            PiiType::Email => "[EMAIL_REDACTED]",
+            PiiType::Custom => "[CUSTOM_REDACTED]",
+        }
+    }
+
+    /// Prefix for numbered tokens, e.g. "SSN" in `[SSN_1]`
+    pub fn token_prefix(&self) -> &'static str {
+        match self {
+            PiiType::Ssn => "SSN",
+            PiiType::CreditCard => "CC",
+            PiiType::BankAccount => "BANK_ACCT",
+            PiiType::Email => "EMAIL",
+            PiiType::Custom => "CUSTOM",
         }
     }
 
@@ -58,6 +103,8 @@ impl PiiType {
             PiiType::Ssn => "Social Security Number",
             PiiType::CreditCard => "Credit Card Number",
             PiiType::BankAccount => "Bank Account Number",
+            PiiType::Email => "Email Address",
+            PiiType::Custom => "Custom Pattern",
         }
     }
 }
@@ -78,6 +125,54 @@ pub struct PiiMatch {
     /// Note: This is included for audit purposes but should be handled carefully
     #[serde(skip_serializing)]
     pub matched_text: String,
+
+    /// Label of the custom pattern that matched (custom matches only)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub custom_label: Option<String>,
+}
+
+impl PiiMatch {
+    /// Redaction placeholder; custom matches use their label, e.g. `[EMPLOYEE_BADGE_REDACTED]`
+    pub fn placeholder(&self) -> String {
+        match &self.custom_label {
+            Some(label) => format!("[{}_REDACTED]", label_slug(label)),
+            None => self.pii_type.placeholder().to_string(),
+        }
+    }
+
+    /// Prefix for numbered tokens; custom matches use their label
+    pub fn token_prefix(&self) -> String {
+        match &self.custom_label {
+            Some(label) => label_slug(label),
+            None => self.pii_type.token_prefix().to_string(),
+        }
+    }
+}
+
+/// Uppercase a label for use in placeholders: "Employee badge" → "EMPLOYEE_BADGE"
+fn label_slug(label: &str) -> String {
+    let slug: String = label
+        .trim()
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c.to_ascii_uppercase() } else { '_' })
+        .collect();
+    slug.split('_').filter(|part| !part.is_empty()).collect::<Vec<_>>().join("_")
+}
+
+/// A user-defined PII pattern, as stored in the custom_pii_patterns setting
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CustomPiiPattern {
+    /// Shown in redaction summaries and placeholders, e.g. "Employee badge"
+    pub label: String,
+    /// Rust regex syntax, e.g. `EMP-\d{6}`
+    pub regex: String,
+}
+
+/// A custom pattern compiled for scanning
+#[derive(Debug, Clone)]
+pub struct CompiledPiiPattern {
+    pub label: String,
+    pub regex: Regex,
 }
 
 /// Result of scanning and redacting text
@@ -96,6 +191,26 @@ pub struct RedactionResult {
     pub summary: Option<String>,
 }
 
+/// Result of scanning text and swapping PII for numbered tokens
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TokenizedResult {
+    /// The text with each PII value replaced by a token like `[SSN_1]`
+    pub tokenized_text: String,
+
+    /// Token → original value, for re-hydrating the user's own view locally.
+    /// Never send this to Claude or write it to the audit log.
+    pub tokens: HashMap<String, String>,
+
+    /// List of PII matches found (without the actual matched text in serialization)
+    pub matches: Vec<PiiMatch>,
+
+    /// Whether any PII was found and tokenized
+    pub had_pii: bool,
+
+    /// Summary of what was redacted (for notification display)
+    pub summary: Option<String>,
+}
+
 // ============================================================================
 // Compiled Regex Patterns (Lazy Static)
 // ============================================================================
@@ -167,6 +282,12 @@ static ROUTING_NUMBER_PATTERN: LazyLock<Regex> = LazyLock::new(|| {
     Regex::new(r"\b[0-9]{9}\b").expect("Routing number regex should compile")
 });
 
+// Email pattern (local@domain.tld), only used when scanning stored data
+static EMAIL_PATTERN: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"\b[A-Za-z0-9._%+\-]+@[A-Za-z0-9.\-]+\.[A-Za-z]{2,}\b")
+        .expect("Email regex should compile")
+});
+
 // ============================================================================
 // Detection Functions
 // ============================================================================
@@ -181,6 +302,7 @@ pub fn detect_ssn(text: &str) -> Vec<PiiMatch> {
             start: m.start(),
             end: m.end(),
             matched_text: m.as_str().to_string(),
+            custom_label: None,
         })
         .collect()
 }
@@ -235,6 +357,7 @@ pub fn detect_credit_cards(text: &str) -> Vec<PiiMatch> {
             start: m.start(),
             end: m.end(),
             matched_text: m.as_str().to_string(),
+            custom_label: None,
         })
         .collect()
 }
@@ -309,6 +432,7 @@ pub fn detect_bank_accounts(text: &str) -> Vec<PiiMatch> {
                 start: m.start(),
                 end: m.end(),
                 matched_text: matched.to_string(),
+                custom_label: None,
             });
         }
     }
@@ -335,6 +459,7 @@ pub fn detect_bank_accounts(text: &str) -> Vec<PiiMatch> {
                     start: m.start(),
                     end: m.end(),
                     matched_text: matched.to_string(),
+                    custom_label: None,
                 });
             }
         }
@@ -343,6 +468,20 @@ pub fn detect_bank_accounts(text: &str) -> Vec<PiiMatch> {
     matches
 }
 
+/// Detect email addresses in text
+pub fn detect_emails(text: &str) -> Vec<PiiMatch> {
+    EMAIL_PATTERN
+        .find_iter(text)
+        .map(|m| PiiMatch {
+            pii_type: PiiType::Email,
+            start: m.start(),
+            end: m.end(),
+            matched_text: m.as_str().to_string(),
+            custom_label: None,
+        })
+        .collect()
+}
+
 // ============================================================================
 // Main Scanning and Redaction
 // ============================================================================
@@ -373,9 +512,54 @@ pub fn scan_for_pii(text: &str) -> Vec<PiiMatch> {
     filtered_matches
 }
 
-/// Scan text and redact any PII found
+/// Scan text for built-in PII plus custom patterns. Built-in matches always
+/// win; a custom match is dropped if it overlaps one. Among custom matches
+/// the earliest start wins, then the longest, then the first pattern listed.
+pub fn scan_for_pii_with(text: &str, custom: &[CompiledPiiPattern]) -> Vec<PiiMatch> {
+    let mut matches = scan_for_pii(text);
+    if custom.is_empty() {
+        return matches;
+    }
+
+    let mut custom_matches: Vec<PiiMatch> = custom
+        .iter()
+        .flat_map(|pattern| {
+            pattern
+                .regex
+                .find_iter(text)
+                .filter(|m| !m.is_empty())
+                .map(|m| PiiMatch {
+                    pii_type: PiiType::Custom,
+                    start: m.start(),
+                    end: m.end(),
+                    matched_text: m.as_str().to_string(),
+                    custom_label: Some(pattern.label.clone()),
+                })
+        })
+        .collect();
+    // Stable sort keeps pattern order for identical spans
+    custom_matches.sort_by_key(|m| (m.start, std::cmp::Reverse(m.end)));
+
+    for m in custom_matches {
+        let overlaps = matches.iter().any(|kept| m.start < kept.end && kept.start < m.end);
+        if !overlaps {
+            matches.push(m);
+        }
+    }
+
+    matches.sort_by_key(|m| m.start);
+    matches
+}
+
+/// Scan text and redact any PII found (built-in types only)
+#[cfg(test)]
 pub fn scan_and_redact(text: &str) -> RedactionResult {
-    let matches = scan_for_pii(text);
+    scan_and_redact_with(text, &[])
+}
+
+/// Scan text and redact built-in PII plus any custom patterns
+pub fn scan_and_redact_with(text: &str, custom: &[CompiledPiiPattern]) -> RedactionResult {
+    let matches = scan_for_pii_with(text, custom);
 
     if matches.is_empty() {
         return RedactionResult {
@@ -394,7 +578,7 @@ pub fn scan_and_redact(text: &str) -> RedactionResult {
         // Add text before this match
         redacted.push_str(&text[last_end..m.start]);
         // Add placeholder
-        redacted.push_str(m.pii_type.placeholder());
+        redacted.push_str(&m.placeholder());
         last_end = m.end;
     }
 
@@ -412,17 +596,92 @@ pub fn scan_and_redact(text: &str) -> RedactionResult {
     }
 }
 
+/// Scan text and replace each PII value with a stable token instead of a
+/// fixed placeholder. The same value (ignoring separators, so "123-45-6789"
+/// and "123456789" match) gets the same token everywhere in the text.
+pub fn scan_and_tokenize(text: &str, custom: &[CompiledPiiPattern]) -> TokenizedResult {
+    let matches = scan_for_pii_with(text, custom);
+
+    if matches.is_empty() {
+        return TokenizedResult {
+            tokenized_text: text.to_string(),
+            tokens: HashMap::new(),
+            matches: Vec::new(),
+            had_pii: false,
+            summary: None,
+        };
+    }
+
+    let mut tokens: HashMap<String, String> = HashMap::new();
+    let mut token_for_value: HashMap<(String, String), String> = HashMap::new();
+    let mut next_number: HashMap<String, usize> = HashMap::new();
+
+    let mut tokenized = String::with_capacity(text.len());
+    let mut last_end = 0;
+
+    for m in &matches {
+        tokenized.push_str(&text[last_end..m.start]);
+
+        let prefix = m.token_prefix();
+        let value_key = (prefix.clone(), normalize_pii_value(&m.pii_type, &m.matched_text));
+        let token = token_for_value
+            .entry(value_key)
+            .or_insert_with(|| {
+                let number = next_number.entry(prefix.clone()).or_insert(0);
+                *number += 1;
+                let token = format!("[{}_{}]", prefix, number);
+                tokens.insert(token.clone(), m.matched_text.clone());
+                token
+            })
+            .clone();
+        tokenized.push_str(&token);
+        last_end = m.end;
+    }
+
+    tokenized.push_str(&text[last_end..]);
+
+    let summary = build_redaction_summary(&matches);
+
+    TokenizedResult {
+        tokenized_text: tokenized,
+        tokens,
+        matches,
+        had_pii: true,
+        summary: Some(summary),
+    }
+}
+
+/// Comparison form of a matched value: digits only for numbers, lowercase for
+/// emails, exact text for custom patterns
+fn normalize_pii_value(pii_type: &PiiType, value: &str) -> String {
+    match pii_type {
+        PiiType::Email => value.to_lowercase(),
+        PiiType::Custom => value.to_string(),
+        _ => value.chars().filter(|c| c.is_ascii_digit()).collect(),
+    }
+}
+
 /// Build a human-readable summary of what was redacted
 fn build_redaction_summary(matches: &[PiiMatch]) -> String {
     let mut ssn_count = 0;
     let mut cc_count = 0;
     let mut bank_count = 0;
+    let mut email_count = 0;
+    let mut custom_counts: Vec<(&str, usize)> = Vec::new();
 
     for m in matches {
         match m.pii_type {
             PiiType::Ssn => ssn_count += 1,
             PiiType::CreditCard => cc_count += 1,
             PiiType::BankAccount => bank_count += 1,
+            PiiType::Email => email_count += 1,
+            PiiType::Custom => {
+                let label = m.custom_label.as_deref().unwrap_or("custom match");
+                match custom_counts.iter_mut().find(|(l, _)| *l == label) {
+                    Some((_, count)) => *count += 1,
+                    None => custom_counts.push((label, 1)),
+                }
+            }
         }
     }
 
@@ -449,10 +708,319 @@ fn build_redaction_summary(matches: &[PiiMatch]) -> String {
             if bank_count > 1 { "s" } else { "" }
         ));
     }
+    if email_count > 0 {
+        parts.push(format!(
+            "{} email{}",
+            email_count,
+            if email_count > 1 { "s" } else { "" }
+        ));
+    }
+    for (label, count) in custom_counts {
+        parts.push(format!("{} {}", count, label));
+    }
 
     format!("Redacted: {}", parts.join(", "))
 }
 
+// ============================================================================
+// Custom Patterns
+// ============================================================================
+
+/// Compile and sanity-check custom patterns. Fails on the first pattern with
+/// an empty label, an invalid regex, or a regex that matches empty text
+/// (which would redact nothing while matching everywhere).
+pub fn compile_custom_patterns(
+    patterns: &[CustomPiiPattern],
+) -> Result<Vec<CompiledPiiPattern>, PiiError> {
+    patterns
+        .iter()
+        .map(|pattern| {
+            let label = pattern.label.trim();
+            if label_slug(label).is_empty() {
+                return Err(PiiError::InvalidPattern(format!(
+                    "pattern '{}' needs a label with at least one letter or digit",
+                    pattern.regex
+                )));
+            }
+            let regex = Regex::new(&pattern.regex)
+                .map_err(|e| PiiError::RegexError(format!("'{}': {}", label, e)))?;
+            if regex.is_match("") {
+                return Err(PiiError::InvalidPattern(format!(
+                    "'{}' matches empty text; make the pattern require at least one character",
+                    label
+                )));
+            }
+            Ok(CompiledPiiPattern {
+                label: label.to_string(),
+                regex,
+            })
+        })
+        .collect()
+}
+
+/// Custom patterns saved in settings (empty when none are set)
+pub async fn get_custom_pii_patterns(pool: &DbPool) -> Result<Vec<CustomPiiPattern>, PiiError> {
+    let stored = crate::settings::get_setting(pool, crate::settings::SettingKey::CustomPiiPatterns.as_str())
+        .await
+        .map_err(|e| PiiError::Database(e.to_string()))?;
+    match stored {
+        Some(json) => serde_json::from_str(&json)
+            .map_err(|e| PiiError::InvalidPattern(format!("stored patterns are unreadable: {}", e))),
+        None => Ok(Vec::new()),
+    }
+}
+
+/// Validate and save custom patterns, replacing any saved before
+pub async fn set_custom_pii_patterns(
+    pool: &DbPool,
+    patterns: &[CustomPiiPattern],
+) -> Result<(), PiiError> {
+    compile_custom_patterns(patterns)?;
+    let json = serde_json::to_string(patterns).map_err(|e| PiiError::ScanError(e.to_string()))?;
+    crate::settings::set_setting(pool, crate::settings::SettingKey::CustomPiiPatterns.as_str(), &json)
+        .await
+        .map_err(|e| PiiError::Database(e.to_string()))
+}
+
+/// Compiled custom patterns for scanning. Patterns are validated when saved,
+/// so a failure here means the setting was edited by hand; a pattern that no
+/// longer compiles is skipped on its own and the rest still apply.
+pub async fn load_custom_pii_patterns(pool: &DbPool) -> Vec<CompiledPiiPattern> {
+    let patterns = match get_custom_pii_patterns(pool).await {
+        Ok(patterns) => patterns,
+        Err(e) => {
+            eprintln!("[PII] Ignoring custom patterns: {}", e);
+            return Vec::new();
+        }
+    };
+    compile_valid_patterns(&patterns)
+}
+
+/// Compile each pattern on its own, dropping (and logging) only the invalid ones
+fn compile_valid_patterns(patterns: &[CustomPiiPattern]) -> Vec<CompiledPiiPattern> {
+    patterns
+        .iter()
+        .filter_map(|pattern| match compile_custom_patterns(std::slice::from_ref(pattern)) {
+            Ok(mut compiled) => compiled.pop(),
+            Err(e) => {
+                eprintln!("[PII] Skipping custom pattern: {}", e);
+                None
+            }
+        })
+        .collect()
+}
+
+// ============================================================================
+// Conversation PII Report
+// ============================================================================
+
+/// Every PII type, in report order
+const ALL_PII_TYPES: [PiiType; 3] = [PiiType::Ssn, PiiType::CreditCard, PiiType::BankAccount];
+
+/// How many matches of one PII type were found
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PiiCategoryCount {
+    pub pii_type: PiiType,
+    pub label: String,
+    pub count: usize,
+}
+
+/// PII found in one user message of a conversation
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MessagePiiBreakdown {
+    /// Position of the message in the thread (0-based, all roles counted)
+    pub message_index: usize,
+    pub message_id: Option<String>,
+    /// Only the categories present in this message
+    pub counts: Vec<PiiCategoryCount>,
+}
+
+/// PII exposure across a whole conversation
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PiiReport {
+    pub user_messages_scanned: usize,
+    pub messages_with_pii: usize,
+    pub total_matches: usize,
+    /// Every category, including those with zero matches
+    pub by_category: Vec<PiiCategoryCount>,
+    /// Only messages that contained PII
+    pub messages: Vec<MessagePiiBreakdown>,
+}
+
+/// Just the fields the report needs from a stored message
+#[derive(Deserialize)]
+struct ThreadMessage {
+    #[serde(default)]
+    id: Option<String>,
+    role: String,
+    content: String,
+}
+
+fn count_by_type(types: &[PiiType], matches: &[PiiMatch], include_zero: bool) -> Vec<PiiCategoryCount> {
+    types
+        .iter()
+        .map(|pii_type| PiiCategoryCount {
+            pii_type: pii_type.clone(),
+            label: pii_type.label().to_string(),
+            count: matches.iter().filter(|m| m.pii_type == *pii_type).count(),
+        })
+        .filter(|c| include_zero || c.count > 0)
+        .collect()
+}
+
+/// Built-in counts followed by one count per custom label, in pattern order
+fn count_with_custom(
+    custom: &[CompiledPiiPattern],
+    matches: &[PiiMatch],
+    include_zero: bool,
+) -> Vec<PiiCategoryCount> {
+    let mut counts = count_by_type(&ALL_PII_TYPES, matches, include_zero);
+    let mut seen = HashSet::new();
+    for pattern in custom {
+        if !seen.insert(pattern.label.as_str()) {
+            continue;
+        }
+        let count = matches
+            .iter()
+            .filter(|m| m.custom_label.as_deref() == Some(pattern.label.as_str()))
+            .count();
+        if include_zero || count > 0 {
+            counts.push(PiiCategoryCount {
+                pii_type: PiiType::Custom,
+                label: pattern.label.clone(),
+                count,
+            });
+        }
+    }
+    counts
+}
+
+/// Scan every user message in a conversation's messages_json and summarize
+/// what PII it held, including matches of the given custom patterns.
+/// Read-only; nothing is redacted or stored.
+pub fn audit_pii_report(
+    messages_json: &str,
+    custom: &[CompiledPiiPattern],
+) -> Result<PiiReport, PiiError> {
+    let thread: Vec<ThreadMessage> = serde_json::from_str(messages_json)
+        .map_err(|e| PiiError::ScanError(format!("Invalid messages JSON: {}", e)))?;
+
+    let mut all_matches = Vec::new();
+    let mut messages = Vec::new();
+    let mut user_messages_scanned = 0;
+
+    for (message_index, message) in thread.into_iter().enumerate() {
+        if message.role != "user" {
+            continue;
+        }
+        user_messages_scanned += 1;
+
+        let matches = scan_for_pii_with(&message.content, custom);
+        if matches.is_empty() {
+            continue;
+        }
+        messages.push(MessagePiiBreakdown {
+            message_index,
+            message_id: message.id,
+            counts: count_with_custom(custom, &matches, false),
+        });
+        all_matches.extend(matches);
+    }
+
+    Ok(PiiReport {
+        user_messages_scanned,
+        messages_with_pii: messages.len(),
+        total_matches: all_matches.len(),
+        by_category: count_with_custom(custom, &all_matches, true),
+        messages,
+    })
+}
+
+// ============================================================================
+// Stored Data PII Report
+// ============================================================================
+
+/// PII types checked in data at rest, in report order
+const STORED_PII_TYPES: [PiiType; 4] = [
+    PiiType::Ssn,
+    PiiType::CreditCard,
+    PiiType::BankAccount,
+    PiiType::Email,
+];
+
+/// PII found in one stored field
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StoredPiiFinding {
+    /// Where the value lives, e.g. "company.industry" or "settings.<key>"
+    pub location: String,
+    /// Only the categories present in this field
+    pub counts: Vec<PiiCategoryCount>,
+}
+
+/// PII exposure across the company profile and settings
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StoredPiiReport {
+    pub locations_scanned: usize,
+    pub total_matches: usize,
+    /// Only locations that contained PII
+    pub findings: Vec<StoredPiiFinding>,
+}
+
+fn scan_stored_value(text: &str) -> Vec<PiiMatch> {
+    let mut matches = scan_for_pii(text);
+    matches.extend(detect_emails(text));
+    matches
+}
+
+/// Scan every company profile's fields and every settings value for PII.
+/// Read-only; matched text is never included in the report.
+pub async fn scan_stored_pii(pool: &DbPool) -> Result<StoredPiiReport, PiiError> {
+    let mut values: Vec<(String, String)> = Vec::new();
+
+    // Other companies' fields are located as "company:<id>.name" and so on
+    let companies: Vec<(String, String, String, Option<String>)> =
+        sqlx::query_as("SELECT id, name, state, industry FROM company ORDER BY id")
+            .fetch_all(pool)
+            .await?;
+    for (id, name, state, industry) in companies {
+        let prefix = if id == crate::company::DEFAULT_COMPANY_ID {
+            "company".to_string()
+        } else {
+            format!("company:{}", id)
+        };
+        values.push((format!("{}.name", prefix), name));
+        values.push((format!("{}.state", prefix), state));
+        if let Some(industry) = industry {
+            values.push((format!("{}.industry", prefix), industry));
+        }
+    }
+
+    let settings: Vec<(String, String)> = sqlx::query_as("SELECT key, value FROM settings ORDER BY key")
+        .fetch_all(pool)
+        .await?;
+    values.extend(settings.into_iter().map(|(key, value)| (format!("settings.{}", key), value)));
+
+    let mut total_matches = 0;
+    let mut findings = Vec::new();
+    for (location, value) in &values {
+        let matches = scan_stored_value(value);
+        if matches.is_empty() {
+            continue;
+        }
+        total_matches += matches.len();
+        findings.push(StoredPiiFinding {
+            location: location.clone(),
+            counts: count_by_type(&STORED_PII_TYPES, &matches, false),
+        });
+    }
+
+    Ok(StoredPiiReport {
+        locations_scanned: values.len(),
+        total_matches,
+        findings,
+    })
+}
+
 // ============================================================================
 // Unit Tests
 // ============================================================================
@@ -728,4 +1296,242 @@ mod tests {
         assert_eq!(PiiType::CreditCard.label(), "Credit Card Number");
         assert_eq!(PiiType::BankAccount.label(), "Bank Account Number");
     }
+
+    #[test]
+    fn test_audit_pii_report_counts_per_category_and_message() {
+        let messages = serde_json::json!([
+            {"id": "m1", "role": "user", "content": "Her SSN is 123-45-6789 and card 4111 1111 1111 1111"},
+            {"id": "m2", "role": "assistant", "content": "I can't use 987-65-4321"},
+            {"id": "m3", "role": "user", "content": "Nothing sensitive here"},
+            {"id": "m4", "role": "user", "content": "SSNs 111-22-3333 and 444-55-6666; bank account number is 12345678901234"}
+        ])
+        .to_string();
+
+        let report = audit_pii_report(&messages, &[]).unwrap();
+        assert_eq!(report.user_messages_scanned, 3);
+        assert_eq!(report.messages_with_pii, 2);
+        assert_eq!(report.total_matches, 5);
+
+        let totals: Vec<(PiiType, usize)> =
+            report.by_category.iter().map(|c| (c.pii_type.clone(), c.count)).collect();
+        // Assistant text is not counted
+        assert_eq!(totals, vec![(PiiType::Ssn, 3), (PiiType::CreditCard, 1), (PiiType::BankAccount, 1)]);
+
+        assert_eq!(report.messages[0].message_index, 0);
+        assert_eq!(report.messages[0].counts.len(), 2);
+        let last = &report.messages[1];
+        assert_eq!(last.message_id.as_deref(), Some("m4"));
+        assert_eq!(last.message_index, 3);
+        assert_eq!(last.counts[0].pii_type, PiiType::Ssn);
+        assert_eq!(last.counts[0].count, 2);
+        assert_eq!(last.counts[1].pii_type, PiiType::BankAccount);
+    }
+
+    #[test]
+    fn test_audit_pii_report_empty_and_invalid() {
+        let report = audit_pii_report("[]", &[]).unwrap();
+        assert_eq!(report.total_matches, 0);
+        assert!(report.by_category.iter().all(|c| c.count == 0));
+        assert!(audit_pii_report("not json", &[]).is_err());
+    }
+
+    #[test]
+    fn test_detect_emails() {
+        let matches = detect_emails("Reach me at jane.doe+hr@example.co.uk or not-an-email@");
+        assert_eq!(matches.len(), 1);
+        assert_eq!(matches[0].pii_type, PiiType::Email);
+        assert_eq!(matches[0].matched_text, "jane.doe+hr@example.co.uk");
+        // Chat redaction stays financial-only
+        assert!(scan_for_pii("jane@example.com").is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_scan_stored_pii_flags_setting_with_email() {
+        let pool = crate::db::test_pool().await;
+        sqlx::query("INSERT INTO company (id, name, state, industry) VALUES ('default', 'Acme', 'CA', 'Software')")
+            .execute(&pool)
+            .await
+            .unwrap();
+        sqlx::query("INSERT INTO settings (key, value) VALUES ('notes', 'Escalate to hr.lead@acme.com'), ('theme', 'dark')")
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        let report = scan_stored_pii(&pool).await.unwrap();
+        assert_eq!(report.locations_scanned, 5);
+        assert_eq!(report.total_matches, 1);
+        assert_eq!(report.findings.len(), 1);
+        assert_eq!(report.findings[0].location, "settings.notes");
+        assert_eq!(report.findings[0].counts[0].pii_type, PiiType::Email);
+        assert_eq!(report.findings[0].counts[0].count, 1);
+    }
+
+    #[tokio::test]
+    async fn test_scan_stored_pii_flags_company_industry() {
+        let pool = crate::db::test_pool().await;
+        sqlx::query("INSERT INTO company (id, name, state, industry) VALUES ('default', 'Acme', 'CA', 'SSN 123-45-6789')")
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        let report = scan_stored_pii(&pool).await.unwrap();
+        assert_eq!(report.findings.len(), 1);
+        assert_eq!(report.findings[0].location, "company.industry");
+        assert_eq!(report.findings[0].counts[0].pii_type, PiiType::Ssn);
+    }
+
+    #[test]
+    fn test_scan_and_tokenize_reuses_tokens_for_same_value() {
+        let text = "SSN 123-45-6789, card 4111111111111111, again SSN 123456789, spouse SSN 234-56-7890";
+        let result = scan_and_tokenize(text, &[]);
+
+        assert!(result.had_pii);
+        assert_eq!(
+            result.tokenized_text,
+            "SSN [SSN_1], card [CC_1], again SSN [SSN_1], spouse SSN [SSN_2]"
+        );
+        assert_eq!(result.tokens.len(), 3);
+        assert_eq!(result.tokens["[SSN_1]"], "123-45-6789");
+        assert_eq!(result.tokens["[SSN_2]"], "234-56-7890");
+        assert_eq!(result.tokens["[CC_1]"], "4111111111111111");
+        assert_eq!(result.summary.as_deref(), Some("Redacted: 3 SSNs, 1 credit card"));
+
+        // Originals never appear in the redacted form
+        assert!(!result.tokenized_text.contains("6789"));
+    }
+
+    #[test]
+    fn test_scan_and_tokenize_rehydrates_to_original() {
+        let text = "Routing 021000021 and account number 12345678901 for payroll";
+        let result = scan_and_tokenize(text, &[]);
+
+        let mut restored = result.tokenized_text.clone();
+        for (token, original) in &result.tokens {
+            restored = restored.replace(token, original);
+        }
+        assert_eq!(restored, text);
+    }
+
+    #[test]
+    fn test_scan_and_tokenize_without_pii() {
+        let result = scan_and_tokenize("How is Sarah doing this quarter?", &[]);
+        assert!(!result.had_pii);
+        assert!(result.tokens.is_empty());
+        assert_eq!(result.tokenized_text, "How is Sarah doing this quarter?");
+    }
+
+    fn badge_pattern() -> Vec<CompiledPiiPattern> {
+        compile_custom_patterns(&[CustomPiiPattern {
+            label: "Employee badge".to_string(),
+            regex: r"EMP-\d{6}".to_string(),
+        }])
+        .unwrap()
+    }
+
+    #[test]
+    fn test_custom_pattern_redacts_alongside_builtins() {
+        let text = "Badge EMP-123456, SSN 123-45-6789, also EMP-654321";
+        let result = scan_and_redact_with(text, &badge_pattern());
+
+        assert_eq!(
+            result.redacted_text,
+            "Badge [EMPLOYEE_BADGE_REDACTED], SSN [SSN_REDACTED], also [EMPLOYEE_BADGE_REDACTED]"
+        );
+        assert_eq!(result.summary.as_deref(), Some("Redacted: 1 SSN, 2 Employee badge"));
+        assert_eq!(result.matches[0].custom_label.as_deref(), Some("Employee badge"));
+
+        // Without custom patterns the badge passes through
+        assert!(scan_and_redact(text).redacted_text.contains("EMP-123456"));
+    }
+
+    #[test]
+    fn test_custom_pattern_overlap_precedence() {
+        let custom = compile_custom_patterns(&[
+            CustomPiiPattern { label: "Case id".to_string(), regex: r"CASE-\d+".to_string() },
+            CustomPiiPattern { label: "Long case id".to_string(), regex: r"CASE-\d+-[A-Z]+".to_string() },
+            CustomPiiPattern { label: "Digits".to_string(), regex: r"\d{3}-\d{2}-\d{4}".to_string() },
+        ])
+        .unwrap();
+
+        // Built-in SSN beats a custom pattern over the same span; the longer
+        // custom match beats a shorter one starting at the same place
+        let result = scan_and_redact_with("SSN 123-45-6789 on CASE-42-HR", &custom);
+        assert_eq!(result.redacted_text, "SSN [SSN_REDACTED] on [LONG_CASE_ID_REDACTED]");
+
+        // Same span from two patterns: the first listed wins
+        let custom = compile_custom_patterns(&[
+            CustomPiiPattern { label: "First".to_string(), regex: r"EMP-\d{6}".to_string() },
+            CustomPiiPattern { label: "Second".to_string(), regex: r"EMP-\d+".to_string() },
+        ])
+        .unwrap();
+        assert_eq!(scan_and_redact_with("EMP-123456", &custom).redacted_text, "[FIRST_REDACTED]");
+    }
+
+    #[test]
+    fn test_compile_custom_patterns_rejects_bad_input() {
+        let bad_regex = [CustomPiiPattern { label: "Badge".to_string(), regex: r"EMP-(\d{6}".to_string() }];
+        assert!(matches!(compile_custom_patterns(&bad_regex), Err(PiiError::RegexError(msg)) if msg.starts_with("'Badge'")));
+
+        let empty_match = [CustomPiiPattern { label: "Anything".to_string(), regex: r"\d*".to_string() }];
+        assert!(matches!(compile_custom_patterns(&empty_match), Err(PiiError::InvalidPattern(_))));
+
+        let no_label = [CustomPiiPattern { label: " - ".to_string(), regex: r"EMP-\d{6}".to_string() }];
+        assert!(matches!(compile_custom_patterns(&no_label), Err(PiiError::InvalidPattern(_))));
+    }
+
+    #[tokio::test]
+    async fn test_custom_patterns_saved_in_settings() {
+        let pool = crate::db::test_pool().await;
+        assert!(get_custom_pii_patterns(&pool).await.unwrap().is_empty());
+
+        let patterns = vec![CustomPiiPattern { label: "Employee badge".to_string(), regex: r"EMP-\d{6}".to_string() }];
+        set_custom_pii_patterns(&pool, &patterns).await.unwrap();
+        assert_eq!(get_custom_pii_patterns(&pool).await.unwrap(), patterns);
+
+        // An invalid set is rejected and the saved set is kept
+        let invalid = vec![CustomPiiPattern { label: "Broken".to_string(), regex: "[".to_string() }];
+        assert!(set_custom_pii_patterns(&pool, &invalid).await.is_err());
+        assert_eq!(get_custom_pii_patterns(&pool).await.unwrap(), patterns);
+
+        let loaded = load_custom_pii_patterns(&pool).await;
+        let result = scan_and_tokenize("Badge EMP-123456 and EMP-123456", &loaded);
+        assert_eq!(result.tokenized_text, "Badge [EMPLOYEE_BADGE_1] and [EMPLOYEE_BADGE_1]");
+    }
+
+    #[tokio::test]
+    async fn test_load_custom_patterns_skips_only_invalid_entries() {
+        let pool = crate::db::test_pool().await;
+        // Hand-edited setting: the broken pattern must not take the badge one with it
+        let json = serde_json::json!([
+            {"label": "Broken", "regex": "["},
+            {"label": "Employee badge", "regex": r"EMP-\d{6}"}
+        ])
+        .to_string();
+        crate::settings::set_setting(&pool, crate::settings::SettingKey::CustomPiiPatterns.as_str(), &json)
+            .await
+            .unwrap();
+
+        let loaded = load_custom_pii_patterns(&pool).await;
+        assert_eq!(loaded.len(), 1);
+        assert_eq!(loaded[0].label, "Employee badge");
+    }
+
+    #[test]
+    fn test_audit_pii_report_counts_custom_patterns() {
+        let messages = serde_json::json!([
+            {"id": "m1", "role": "user", "content": "Badge EMP-123456 and SSN 123-45-6789"},
+            {"id": "m2", "role": "user", "content": "Also EMP-654321"}
+        ])
+        .to_string();
+
+        let report = audit_pii_report(&messages, &badge_pattern()).unwrap();
+        assert_eq!(report.messages_with_pii, 2);
+        assert_eq!(report.total_matches, 3);
+        let badge = report.by_category.last().unwrap();
+        assert_eq!(badge.pii_type, PiiType::Custom);
+        assert_eq!(badge.label, "Employee badge");
+        assert_eq!(badge.count, 2);
+        assert_eq!(report.messages[1].counts.len(), 1);
+        assert_eq!(report.messages[1].counts[0].label, "Employee badge");
+    }
 }
@@ -0,0 +1,323 @@
+// HR Command Center - Representation Module
+// Gender/ethnicity representation of active employees, overall and by
+// department, with small-group suppression to prevent re-identification.
+//
+// Sensitive: reports are only produced when the user has explicitly enabled
+// them (settings key "representation_reports_enabled"), and nothing here is
+// ever fed into chat context.
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::db::DbPool;
+use crate::settings::{self, SettingKey};
+
+// ============================================================================
+// Error Types
+// ============================================================================
+
+#[derive(Error, Debug, Serialize)]
+pub enum RepresentationError {
+    #[error("Database error: {0}")]
+    Database(String),
+    #[error("Representation reports are disabled; enable them in Settings first")]
+    Disabled,
+}
+
+impl From<sqlx::Error> for RepresentationError {
+    fn from(err: sqlx::Error) -> Self {
+        RepresentationError::Database(err.to_string())
+    }
+}
+
+impl From<settings::SettingsError> for RepresentationError {
+    fn from(err: settings::SettingsError) -> Self {
+        RepresentationError::Database(err.to_string())
+    }
+}
+
+// ============================================================================
+// Data Types
+// ============================================================================
+
+/// Groups smaller than this are suppressed
+pub const MIN_GROUP_SIZE: i64 = 5;
+
+/// Category used for null or blank values
+pub const NOT_SPECIFIED: &str = "Not Specified";
+
+/// Department label for employees without one
+const UNASSIGNED_DEPARTMENT: &str = "Unassigned";
+
+/// Demographic dimension to report on
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Dimension {
+    Gender,
+    Ethnicity,
+}
+
+impl Dimension {
+    fn column(self) -> &'static str {
+        match self {
+            Dimension::Gender => "gender",
+            Dimension::Ethnicity => "ethnicity",
+        }
+    }
+}
+
+/// One category's share of a population. Suppressed groups carry no numbers.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GroupShare {
+    pub category: String,
+    pub count: Option<i64>,
+    pub percentage: Option<f64>,
+    pub suppressed: bool,
+}
+
+/// Representation within a single department
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DepartmentRepresentation {
+    pub department: String,
+    /// Hidden when the whole department is below the minimum group size
+    pub total: Option<i64>,
+    pub groups: Vec<GroupShare>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RepresentationReport {
+    pub dimension: Dimension,
+    pub min_group_size: i64,
+    pub total_active: i64,
+    pub overall: Vec<GroupShare>,
+    pub by_department: Vec<DepartmentRepresentation>,
+}
+
+// ============================================================================
+// Report
+// ============================================================================
+
+/// Whether the user has opted in to representation reports
+pub async fn is_enabled(pool: &DbPool) -> Result<bool, RepresentationError> {
+    let value = settings::get_setting(pool, SettingKey::RepresentationReportsEnabled.as_str()).await?;
+    Ok(value.as_deref() == Some("true"))
+}
+
+/// Compute representation of active employees by gender or ethnicity.
+/// Returns `Disabled` unless the opt-in setting is "true".
+pub async fn compute_representation(
+    pool: &DbPool,
+    dimension: Dimension,
+) -> Result<RepresentationReport, RepresentationError> {
+    if !is_enabled(pool).await? {
+        return Err(RepresentationError::Disabled);
+    }
+
+    // Column name comes from the Dimension enum, never from user input
+    let query = format!(
+        "SELECT department, {} FROM employees WHERE status = 'active'",
+        dimension.column()
+    );
+    let rows: Vec<(Option<String>, Option<String>)> = sqlx::query_as(&query).fetch_all(pool).await?;
+
+    Ok(build_report(dimension, &rows, MIN_GROUP_SIZE))
+}
+
+fn category_label(value: Option<&str>) -> String {
+    match value.map(str::trim) {
+        Some(v) if !v.is_empty() => v.to_string(),
+        _ => NOT_SPECIFIED.to_string(),
+    }
+}
+
+fn build_report(
+    dimension: Dimension,
+    rows: &[(Option<String>, Option<String>)],
+    min_group_size: i64,
+) -> RepresentationReport {
+    let mut overall: BTreeMap<String, i64> = BTreeMap::new();
+    let mut by_department: BTreeMap<String, BTreeMap<String, i64>> = BTreeMap::new();
+
+    for (department, value) in rows {
+        let category = category_label(value.as_deref());
+        let department = match department.as_deref().map(str::trim) {
+            Some(d) if !d.is_empty() => d.to_string(),
+            _ => UNASSIGNED_DEPARTMENT.to_string(),
+        };
+        *overall.entry(category.clone()).or_default() += 1;
+        *by_department.entry(department).or_default().entry(category).or_default() += 1;
+    }
+
+    let total_active = rows.len() as i64;
+    let by_department = by_department
+        .into_iter()
+        .map(|(department, counts)| {
+            let total: i64 = counts.values().sum();
+            if total < min_group_size {
+                // Too small to report any breakdown without identifying people
+                let groups = counts
+                    .into_keys()
+                    .map(|category| GroupShare {
+                        category,
+                        count: None,
+                        percentage: None,
+                        suppressed: true,
+                    })
+                    .collect();
+                return DepartmentRepresentation { department, total: None, groups };
+            }
+            DepartmentRepresentation {
+                department,
+                total: Some(total),
+                groups: suppress_groups(counts, min_group_size),
+            }
+        })
+        .collect();
+
+    RepresentationReport {
+        dimension,
+        min_group_size,
+        total_active,
+        overall: suppress_groups(overall, min_group_size),
+        by_department,
+    }
+}
+
+/// Turn category counts into shares, hiding groups below `min_group_size`.
+/// If only one group would be hidden, the next smallest is hidden too so the
+/// suppressed count can't be recovered by subtracting from the total.
+fn suppress_groups(counts: BTreeMap<String, i64>, min_group_size: i64) -> Vec<GroupShare> {
+    let total: i64 = counts.values().sum();
+    let mut groups: Vec<(String, i64, bool)> = counts
+        .into_iter()
+        .map(|(category, count)| (category, count, count < min_group_size))
+        .collect();
+
+    if groups.iter().filter(|g| g.2).count() == 1 {
+        if let Some(next) = groups.iter_mut().filter(|g| !g.2).min_by_key(|g| g.1) {
+            next.2 = true;
+        }
+    }
+
+    // Largest groups first; suppressed groups last so their order leaks nothing
+    groups.sort_by(|a, b| a.2.cmp(&b.2).then(b.1.cmp(&a.1)).then(a.0.cmp(&b.0)));
+
+    groups
+        .into_iter()
+        .map(|(category, count, suppressed)| {
+            if suppressed {
+                GroupShare { category, count: None, percentage: None, suppressed: true }
+            } else {
+                let percentage = if total > 0 {
+                    (count as f64 / total as f64 * 1000.0).round() / 10.0
+                } else {
+                    0.0
+                };
+                GroupShare {
+                    category,
+                    count: Some(count),
+                    percentage: Some(percentage),
+                    suppressed: false,
+                }
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rows(spec: &[(&str, Option<&str>, usize)]) -> Vec<(Option<String>, Option<String>)> {
+        spec.iter()
+            .flat_map(|(dept, value, n)| {
+                std::iter::repeat_n((Some(dept.to_string()), value.map(str::to_string)), *n)
+            })
+            .collect()
+    }
+
+    fn share<'a>(groups: &'a [GroupShare], category: &str) -> &'a GroupShare {
+        groups.iter().find(|g| g.category == category).unwrap()
+    }
+
+    #[test]
+    fn test_percentages_and_not_specified() {
+        let data = rows(&[
+            ("Engineering", Some("Female"), 6),
+            ("Engineering", Some("Male"), 8),
+            ("Engineering", None, 3),
+            ("Engineering", Some("  "), 3),
+        ]);
+        let report = build_report(Dimension::Gender, &data, MIN_GROUP_SIZE);
+
+        assert_eq!(report.total_active, 20);
+        assert_eq!(share(&report.overall, "Male").percentage, Some(40.0));
+        assert_eq!(share(&report.overall, "Female").percentage, Some(30.0));
+        // Null and blank fold into one category (6 people, reportable)
+        let unspecified = share(&report.overall, NOT_SPECIFIED);
+        assert_eq!(unspecified.count, Some(6));
+        assert_eq!(unspecified.percentage, Some(30.0));
+    }
+
+    #[test]
+    fn test_suppresses_tiny_groups_and_complement() {
+        let data = rows(&[
+            ("Sales", Some("Female"), 10),
+            ("Sales", Some("Male"), 7),
+            ("Sales", Some("Nonbinary"), 2),
+        ]);
+        let report = build_report(Dimension::Gender, &data, MIN_GROUP_SIZE);
+
+        let nonbinary = share(&report.overall, "Nonbinary");
+        assert!(nonbinary.suppressed);
+        assert_eq!(nonbinary.count, None);
+        // Male is hidden too, otherwise 19 - 10 - 7 reveals the suppressed 2
+        assert!(share(&report.overall, "Male").suppressed);
+        assert_eq!(share(&report.overall, "Female").count, Some(10));
+        assert_eq!(report.overall.last().map(|g| g.suppressed), Some(true));
+    }
+
+    #[test]
+    fn test_small_department_fully_suppressed() {
+        let data = rows(&[
+            ("Engineering", Some("Female"), 5),
+            ("Engineering", Some("Male"), 5),
+            ("Legal", Some("Female"), 2),
+            ("Legal", Some("Male"), 1),
+        ]);
+        let report = build_report(Dimension::Gender, &data, MIN_GROUP_SIZE);
+
+        let legal = report.by_department.iter().find(|d| d.department == "Legal").unwrap();
+        assert_eq!(legal.total, None);
+        assert!(legal.groups.iter().all(|g| g.suppressed && g.count.is_none()));
+
+        let eng = report.by_department.iter().find(|d| d.department == "Engineering").unwrap();
+        assert_eq!(eng.total, Some(10));
+        assert_eq!(share(&eng.groups, "Female").percentage, Some(50.0));
+    }
+
+    #[tokio::test]
+    async fn test_compute_representation_requires_opt_in() {
+        let pool = crate::db::test_pool().await;
+        assert!(matches!(
+            compute_representation(&pool, Dimension::Ethnicity).await,
+            Err(RepresentationError::Disabled)
+        ));
+
+        settings::set_setting(&pool, "representation_reports_enabled", "true").await.unwrap();
+        sqlx::query(
+            "INSERT INTO employees (id, email, full_name, ethnicity, status) VALUES ('a', 'a@example.com', 'A', NULL, 'active'), ('b', 'b@example.com', 'B', 'Asian', 'terminated')",
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        let report = compute_representation(&pool, Dimension::Ethnicity).await.unwrap();
+        assert_eq!(report.total_active, 1);
+        assert_eq!(report.overall.len(), 1);
+        assert_eq!(report.overall[0].category, NOT_SPECIFIED);
+        assert!(report.overall[0].suppressed);
+    }
+}
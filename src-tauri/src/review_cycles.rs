@@ -1,6 +1,7 @@
 // HR Command Center - Review Cycles Module
 // CRUD operations for performance review cycles
 
+use chrono::NaiveDate;
 use serde::{Deserialize, Serialize};
 use sqlx::{FromRow, Row};
 use thiserror::Error;
@@ -20,6 +21,10 @@ pub enum ReviewCycleError {
     NotFound(String),
     #[error("Validation error: {0}")]
     Validation(String),
+    #[error("Overlaps active review cycle {0}")]
+    Overlap(String),
+    #[error("{}", crate::db::READ_ONLY_MESSAGE)]
+    ReadOnly,
 }
 
 impl From<sqlx::Error> for ReviewCycleError {
@@ -28,6 +33,12 @@ impl From<sqlx::Error> for ReviewCycleError {
     }
 }
 
+impl From<crate::db::ReadOnly> for ReviewCycleError {
+    fn from(_: crate::db::ReadOnly) -> Self {
+        ReviewCycleError::ReadOnly
+    }
+}
+
 // ============================================================================
 // Review Cycle Struct
 // ============================================================================
@@ -41,6 +52,8 @@ pub struct ReviewCycle {
     pub end_date: String,
     pub status: String, // 'active' | 'closed'
     pub created_at: String,
+    /// Review template whose sections structure this cycle's reviews
+    pub template_id: Option<String>,
 }
 
 // ============================================================================
@@ -54,6 +67,11 @@ pub struct CreateReviewCycle {
     pub start_date: String,
     pub end_date: String,
     pub status: Option<String>,
+    /// Allow the cycle to overlap an active cycle of the same type
+    #[serde(default)]
+    pub force: bool,
+    #[serde(default)]
+    pub template_id: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -63,6 +81,134 @@ pub struct UpdateReviewCycle {
     pub start_date: Option<String>,
     pub end_date: Option<String>,
     pub status: Option<String>,
+    /// Allow the cycle to overlap an active cycle of the same type
+    #[serde(default)]
+    pub force: bool,
+    /// Attach a review template; None leaves the current one
+    #[serde(default)]
+    pub template_id: Option<String>,
+}
+
+/// One status transition in a cycle's history
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct CycleEvent {
+    pub id: String,
+    pub cycle_id: String,
+    pub old_status: Option<String>, // None when the cycle was created
+    pub new_status: String,
+    pub created_at: String,
+}
+
+/// A stored cycle whose dates can't be trusted for ordering
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InvalidCycleDates {
+    pub id: String,
+    pub name: String,
+    pub start_date: String,
+    pub end_date: String,
+    pub reason: String,
+}
+
+// ============================================================================
+// Date Validation
+// ============================================================================
+
+/// Check that both dates are ISO (YYYY-MM-DD) and the cycle doesn't end before it starts
+pub fn validate_cycle_dates(start_date: &str, end_date: &str) -> Result<(), String> {
+    let start = NaiveDate::parse_from_str(start_date.trim(), "%Y-%m-%d")
+        .map_err(|_| format!("Invalid start_date '{}'. Expected YYYY-MM-DD", start_date))?;
+    let end = NaiveDate::parse_from_str(end_date.trim(), "%Y-%m-%d")
+        .map_err(|_| format!("Invalid end_date '{}'. Expected YYYY-MM-DD", end_date))?;
+
+    if end < start {
+        return Err(format!(
+            "end_date {} is before start_date {}",
+            end_date, start_date
+        ));
+    }
+
+    Ok(())
+}
+
+/// Reject an active cycle whose [start_date, end_date] overlaps another active
+/// cycle of the same type. Ranges are inclusive, so back-to-back cycles
+/// (one ends 06-30, the next starts 07-01) are fine.
+async fn check_overlap(
+    pool: &DbPool,
+    exclude_id: Option<&str>,
+    cycle_type: &str,
+    start_date: &str,
+    end_date: &str,
+) -> Result<(), ReviewCycleError> {
+    let conflict: Option<(String, String, String)> = sqlx::query_as(
+        r#"
+        SELECT name, start_date, end_date FROM review_cycles
+        WHERE status = 'active' AND cycle_type = ? AND id != COALESCE(?, '')
+          AND date(start_date) <= date(?) AND date(end_date) >= date(?)
+        ORDER BY start_date
+        LIMIT 1
+        "#,
+    )
+    .bind(cycle_type)
+    .bind(exclude_id)
+    .bind(end_date.trim())
+    .bind(start_date.trim())
+    .fetch_optional(pool)
+    .await?;
+
+    match conflict {
+        Some((name, start, end)) => Err(ReviewCycleError::Overlap(format!(
+            "'{}' ({} to {})",
+            name, start, end
+        ))),
+        None => Ok(()),
+    }
+}
+
+async fn check_template_exists(pool: &DbPool, template_id: Option<&str>) -> Result<(), ReviewCycleError> {
+    let Some(template_id) = template_id else {
+        return Ok(());
+    };
+    let exists: Option<(i64,)> = sqlx::query_as("SELECT 1 FROM review_templates WHERE id = ?")
+        .bind(template_id)
+        .fetch_optional(pool)
+        .await?;
+    match exists {
+        Some(_) => Ok(()),
+        None => Err(ReviewCycleError::Validation(format!(
+            "Unknown review template '{}'",
+            template_id
+        ))),
+    }
+}
+
+/// List cycles with unparseable dates or an end before the start.
+/// Such cycles sort unpredictably when picking an employee's latest rating.
+pub async fn find_invalid_cycle_dates(
+    pool: &DbPool,
+) -> Result<Vec<InvalidCycleDates>, ReviewCycleError> {
+    let rows = sqlx::query(
+        "SELECT id, name, COALESCE(start_date, '') as start_date, COALESCE(end_date, '') as end_date FROM review_cycles ORDER BY name",
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(rows
+        .iter()
+        .filter_map(|row| {
+            let start_date: String = row.get("start_date");
+            let end_date: String = row.get("end_date");
+            validate_cycle_dates(&start_date, &end_date)
+                .err()
+                .map(|reason| InvalidCycleDates {
+                    id: row.get("id"),
+                    name: row.get("name"),
+                    start_date,
+                    end_date,
+                    reason,
+                })
+        })
+        .collect())
 }
 
 // ============================================================================
@@ -87,6 +233,8 @@ pub async fn create_review_cycle(
         )));
     }
 
+    validate_cycle_dates(&input.start_date, &input.end_date).map_err(ReviewCycleError::Validation)?;
+
     let id = Uuid::new_v4().to_string();
     let status = input.status.unwrap_or_else(|| "active".to_string());
 
@@ -98,10 +246,17 @@ pub async fn create_review_cycle(
         )));
     }
 
+    if status == "active" && !input.force {
+        check_overlap(pool, None, &input.cycle_type, &input.start_date, &input.end_date).await?;
+    }
+    check_template_exists(pool, input.template_id.as_deref()).await?;
+
+    // The cycle and its first history event land together or not at all
+    let mut tx = pool.begin().await?;
     sqlx::query(
         r#"
-        INSERT INTO review_cycles (id, name, cycle_type, start_date, end_date, status)
-        VALUES (?, ?, ?, ?, ?, ?)
+        INSERT INTO review_cycles (id, name, cycle_type, start_date, end_date, status, template_id)
+        VALUES (?, ?, ?, ?, ?, ?, ?)
         "#,
     )
     .bind(&id)
@@ -110,9 +265,13 @@ pub async fn create_review_cycle(
     .bind(&input.start_date)
     .bind(&input.end_date)
     .bind(&status)
-    .execute(pool)
+    .bind(&input.template_id)
+    .execute(&mut *tx)
     .await?;
 
+    record_cycle_event(&mut tx, &id, None, &status).await?;
+    tx.commit().await?;
+
     get_review_cycle(pool, &id).await
 }
 
@@ -136,10 +295,12 @@ pub async fn update_review_cycle(
     let existing = get_review_cycle(pool, id).await?;
 
     let name = input.name.unwrap_or(existing.name);
-    let cycle_type = input.cycle_type.unwrap_or(existing.cycle_type);
-    let start_date = input.start_date.unwrap_or(existing.start_date);
-    let end_date = input.end_date.unwrap_or(existing.end_date);
-    let status = input.status.unwrap_or(existing.status);
+    let cycle_type = input.cycle_type.unwrap_or_else(|| existing.cycle_type.clone());
+    let start_date = input.start_date.unwrap_or_else(|| existing.start_date.clone());
+    let end_date = input.end_date.unwrap_or_else(|| existing.end_date.clone());
+    let status = input.status.unwrap_or_else(|| existing.status.clone());
+    check_template_exists(pool, input.template_id.as_deref()).await?;
+    let template_id = input.template_id.or(existing.template_id);
 
     // Validate cycle_type
     if !["annual", "semi-annual", "quarterly"].contains(&cycle_type.as_str()) {
@@ -157,10 +318,23 @@ pub async fn update_review_cycle(
         )));
     }
 
+    validate_cycle_dates(&start_date, &end_date).map_err(ReviewCycleError::Validation)?;
+
+    // Only re-check when the change could create an overlap, so renaming a
+    // cycle that already overlaps (e.g. a forced one) still works
+    let range_changed = status != existing.status
+        || cycle_type != existing.cycle_type
+        || start_date != existing.start_date
+        || end_date != existing.end_date;
+    if status == "active" && range_changed && !input.force {
+        check_overlap(pool, Some(id), &cycle_type, &start_date, &end_date).await?;
+    }
+
+    let mut tx = pool.begin().await?;
     sqlx::query(
         r#"
         UPDATE review_cycles SET
-            name = ?, cycle_type = ?, start_date = ?, end_date = ?, status = ?
+            name = ?, cycle_type = ?, start_date = ?, end_date = ?, status = ?, template_id = ?
         WHERE id = ?
         "#,
     )
@@ -169,10 +343,16 @@ pub async fn update_review_cycle(
     .bind(&start_date)
     .bind(&end_date)
     .bind(&status)
+    .bind(&template_id)
     .bind(id)
-    .execute(pool)
+    .execute(&mut *tx)
     .await?;
 
+    if status != existing.status {
+        record_cycle_event(&mut tx, id, Some(&existing.status), &status).await?;
+    }
+    tx.commit().await?;
+
     get_review_cycle(pool, id).await
 }
 
@@ -235,7 +415,448 @@ pub async fn close_review_cycle(pool: &DbPool, id: &str) -> Result<ReviewCycle,
             start_date: None,
             end_date: None,
             status: Some("closed".to_string()),
+            force: false,
+            template_id: None,
         },
     )
     .await
 }
+
+/// Close every active cycle whose end_date is before `as_of` (default: today
+/// in local time). Returns the ids that were closed. Cycles with unparseable
+/// end dates are left alone; `find_invalid_cycle_dates` surfaces those.
+pub async fn close_expired_cycles(
+    pool: &DbPool,
+    as_of: Option<NaiveDate>,
+) -> Result<Vec<String>, ReviewCycleError> {
+    let as_of = as_of.unwrap_or_else(crate::tenure::local_today);
+
+    let rows = sqlx::query(
+        "SELECT id, COALESCE(end_date, '') as end_date FROM review_cycles WHERE status = 'active' ORDER BY end_date",
+    )
+    .fetch_all(pool)
+    .await?;
+
+    let mut closed = Vec::new();
+    for row in rows {
+        let end_date: String = row.get("end_date");
+        let expired = NaiveDate::parse_from_str(end_date.trim(), "%Y-%m-%d")
+            .map(|end| end < as_of)
+            .unwrap_or(false);
+        if !expired {
+            continue;
+        }
+
+        let id: String = row.get("id");
+        close_review_cycle(pool, &id).await?;
+        closed.push(id);
+    }
+
+    Ok(closed)
+}
+
+// ============================================================================
+// Reminders
+// ============================================================================
+
+/// Cycles opening or closing within this many days get a reminder
+pub const REMINDER_WINDOW_DAYS: i64 = 7;
+
+/// Why a cycle needs attention, most urgent first
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReminderKind {
+    /// Past end_date but still active
+    Overdue,
+    EndingSoon,
+    StartingSoon,
+    /// Dates are unparseable or the cycle ends before it starts
+    Misconfigured,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CycleReminder {
+    pub cycle_id: String,
+    pub name: String,
+    pub kind: ReminderKind,
+    pub start_date: String,
+    pub end_date: String,
+    /// Days from today to the start (StartingSoon) or end date; negative when
+    /// overdue. None for misconfigured cycles.
+    pub days_until: Option<i64>,
+    /// Active employees with no rating in this cycle yet
+    pub missing_ratings: i64,
+    /// Active employees with no review in this cycle yet
+    pub missing_reviews: i64,
+    /// Set for misconfigured cycles
+    pub reason: Option<String>,
+}
+
+/// Active cycles that start or end within `REMINDER_WINDOW_DAYS` of `as_of`
+/// (default: today in local time), are overdue, or have broken dates.
+/// A short cycle can be both starting and ending soon and gets both reminders.
+pub async fn get_cycle_reminders(
+    pool: &DbPool,
+    as_of: Option<NaiveDate>,
+) -> Result<Vec<CycleReminder>, ReviewCycleError> {
+    let as_of = as_of.unwrap_or_else(crate::tenure::local_today);
+
+    let rows = sqlx::query(
+        r#"
+        SELECT c.id, c.name,
+               COALESCE(c.start_date, '') as start_date,
+               COALESCE(c.end_date, '') as end_date,
+               (SELECT COUNT(*) FROM employees e
+                WHERE e.status = 'active'
+                  AND NOT EXISTS (SELECT 1 FROM performance_ratings r
+                                  WHERE r.employee_id = e.id AND r.review_cycle_id = c.id)) as missing_ratings,
+               (SELECT COUNT(*) FROM employees e
+                WHERE e.status = 'active'
+                  AND NOT EXISTS (SELECT 1 FROM performance_reviews r
+                                  WHERE r.employee_id = e.id AND r.review_cycle_id = c.id)) as missing_reviews
+        FROM review_cycles c
+        WHERE c.status = 'active'
+        ORDER BY c.end_date, c.name
+        "#,
+    )
+    .fetch_all(pool)
+    .await?;
+
+    let mut reminders = Vec::new();
+    for row in rows {
+        let start_date: String = row.get("start_date");
+        let end_date: String = row.get("end_date");
+        let reminder = |kind, days_until, reason| CycleReminder {
+            cycle_id: row.get("id"),
+            name: row.get("name"),
+            kind,
+            start_date: start_date.clone(),
+            end_date: end_date.clone(),
+            days_until,
+            missing_ratings: row.get("missing_ratings"),
+            missing_reviews: row.get("missing_reviews"),
+            reason,
+        };
+
+        if let Err(reason) = validate_cycle_dates(&start_date, &end_date) {
+            reminders.push(reminder(ReminderKind::Misconfigured, None, Some(reason)));
+            continue;
+        }
+
+        // Both parse after validate_cycle_dates
+        let start = NaiveDate::parse_from_str(start_date.trim(), "%Y-%m-%d").unwrap();
+        let end = NaiveDate::parse_from_str(end_date.trim(), "%Y-%m-%d").unwrap();
+        let until_start = (start - as_of).num_days();
+        let until_end = (end - as_of).num_days();
+
+        if until_end < 0 {
+            reminders.push(reminder(ReminderKind::Overdue, Some(until_end), None));
+            continue;
+        }
+        if until_end <= REMINDER_WINDOW_DAYS {
+            reminders.push(reminder(ReminderKind::EndingSoon, Some(until_end), None));
+        }
+        if (0..=REMINDER_WINDOW_DAYS).contains(&until_start) {
+            reminders.push(reminder(ReminderKind::StartingSoon, Some(until_start), None));
+        }
+    }
+
+    // Stable sort keeps end_date order within each kind
+    reminders.sort_by_key(|r| r.kind);
+    Ok(reminders)
+}
+
+// ============================================================================
+// Status History
+// ============================================================================
+
+async fn record_cycle_event(
+    conn: &mut sqlx::SqliteConnection,
+    cycle_id: &str,
+    old_status: Option<&str>,
+    new_status: &str,
+) -> Result<(), ReviewCycleError> {
+    sqlx::query(
+        "INSERT INTO review_cycle_events (id, cycle_id, old_status, new_status) VALUES (?, ?, ?, ?)",
+    )
+    .bind(Uuid::new_v4().to_string())
+    .bind(cycle_id)
+    .bind(old_status)
+    .bind(new_status)
+    .execute(conn)
+    .await?;
+
+    Ok(())
+}
+
+/// Status transitions for a cycle, oldest first
+pub async fn get_cycle_history(
+    pool: &DbPool,
+    cycle_id: &str,
+) -> Result<Vec<CycleEvent>, ReviewCycleError> {
+    // rowid breaks ties between events recorded in the same second
+    let events = sqlx::query_as::<_, CycleEvent>(
+        "SELECT id, cycle_id, old_status, new_status, created_at FROM review_cycle_events WHERE cycle_id = ? ORDER BY created_at, rowid",
+    )
+    .bind(cycle_id)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(events)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_cycle_dates() {
+        assert!(validate_cycle_dates("2024-01-01", "2024-12-31").is_ok());
+        assert!(validate_cycle_dates("2024-06-30", "2024-06-30").is_ok());
+
+        let err = validate_cycle_dates("2024-01-01", "").unwrap_err();
+        assert!(err.contains("end_date"));
+        assert!(validate_cycle_dates("01/01/2024", "2024-12-31").unwrap_err().contains("start_date"));
+        assert!(validate_cycle_dates("2024-12-31", "2024-01-01").unwrap_err().contains("before"));
+    }
+
+    #[tokio::test]
+    async fn test_create_review_cycle_rejects_bad_dates() {
+        let pool = crate::db::test_pool().await;
+        let input = CreateReviewCycle {
+            name: "2024 Annual".to_string(),
+            cycle_type: "annual".to_string(),
+            start_date: "2024-12-31".to_string(),
+            end_date: "2024-01-01".to_string(),
+            status: None,
+            force: false,
+            template_id: None,
+        };
+
+        let result = create_review_cycle(&pool, input).await;
+        assert!(matches!(result, Err(ReviewCycleError::Validation(_))));
+    }
+
+    #[tokio::test]
+    async fn test_find_invalid_cycle_dates() {
+        let pool = crate::db::test_pool().await;
+        sqlx::query(
+            r#"INSERT INTO review_cycles (id, name, cycle_type, start_date, end_date) VALUES
+                ('ok', '2023 Annual', 'annual', '2023-01-01', '2023-12-31'),
+                ('blank', '2024 Annual', 'annual', '2024-01-01', ''),
+                ('reversed', 'Q1 2024', 'quarterly', '2024-03-31', '2024-01-01')"#,
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        let invalid = find_invalid_cycle_dates(&pool).await.unwrap();
+        let ids: Vec<&str> = invalid.iter().map(|c| c.id.as_str()).collect();
+        assert_eq!(ids, vec!["blank", "reversed"]);
+        assert!(invalid[1].reason.contains("before"));
+    }
+
+    #[tokio::test]
+    async fn test_close_expired_cycles() {
+        let pool = crate::db::test_pool().await;
+        sqlx::query(
+            r#"INSERT INTO review_cycles (id, name, cycle_type, start_date, end_date, status) VALUES
+                ('expired', 'H1 2024', 'semi-annual', '2024-01-01', '2024-06-30', 'active'),
+                ('current', 'H2 2024', 'semi-annual', '2024-07-01', '2024-12-31', 'active'),
+                ('ends-today', 'Q3 2024', 'quarterly', '2024-07-01', '2024-09-30', 'active'),
+                ('already', '2023 Annual', 'annual', '2023-01-01', '2023-12-31', 'closed')"#,
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        let as_of = NaiveDate::from_ymd_opt(2024, 9, 30).unwrap();
+        let closed = close_expired_cycles(&pool, Some(as_of)).await.unwrap();
+        assert_eq!(closed, vec!["expired".to_string()]);
+
+        assert_eq!(get_review_cycle(&pool, "expired").await.unwrap().status, "closed");
+        assert_eq!(get_review_cycle(&pool, "current").await.unwrap().status, "active");
+        assert_eq!(get_review_cycle(&pool, "ends-today").await.unwrap().status, "active");
+
+        // Running again is a no-op
+        assert!(close_expired_cycles(&pool, Some(as_of)).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_cycle_history_records_transitions() {
+        let pool = crate::db::test_pool().await;
+        let cycle = create_review_cycle(
+            &pool,
+            CreateReviewCycle {
+                name: "2024 Annual".to_string(),
+                cycle_type: "annual".to_string(),
+                start_date: "2024-01-01".to_string(),
+                end_date: "2024-12-31".to_string(),
+                status: None,
+                force: false,
+                template_id: None,
+            },
+        )
+        .await
+        .unwrap();
+
+        // A rename is not a status transition
+        update_review_cycle(
+            &pool,
+            &cycle.id,
+            UpdateReviewCycle {
+                name: Some("2024 Annual Review".to_string()),
+                cycle_type: None,
+                start_date: None,
+                end_date: None,
+                status: None,
+                force: false,
+                template_id: None,
+            },
+        )
+        .await
+        .unwrap();
+        close_review_cycle(&pool, &cycle.id).await.unwrap();
+        // Closing again changes nothing
+        close_review_cycle(&pool, &cycle.id).await.unwrap();
+
+        let history = get_cycle_history(&pool, &cycle.id).await.unwrap();
+        let trail: Vec<(Option<&str>, &str)> = history
+            .iter()
+            .map(|e| (e.old_status.as_deref(), e.new_status.as_str()))
+            .collect();
+        assert_eq!(trail, vec![(None, "active"), (Some("active"), "closed")]);
+    }
+
+    #[tokio::test]
+    async fn test_get_cycle_reminders() {
+        let pool = crate::db::test_pool().await;
+        sqlx::query(
+            r#"INSERT INTO review_cycles (id, name, cycle_type, start_date, end_date, status) VALUES
+                ('overdue', 'H1 2024', 'semi-annual', '2024-01-01', '2024-06-30', 'active'),
+                ('ending', 'Q3 2024', 'quarterly', '2024-07-01', '2024-09-30', 'active'),
+                ('starting', 'Q4 2024', 'quarterly', '2024-10-01', '2024-12-31', 'active'),
+                ('later', '2025 Annual', 'annual', '2025-01-01', '2025-12-31', 'active'),
+                ('sprint', 'Calibration', 'quarterly', '2024-09-28', '2024-10-02', 'active'),
+                ('reversed', 'Q2 2024', 'quarterly', '2024-06-30', '2024-04-01', 'active'),
+                ('closed', 'H2 2023', 'semi-annual', '2023-07-01', '2023-12-31', 'closed')"#,
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+        sqlx::query(
+            r#"INSERT INTO employees (id, email, full_name, status) VALUES
+                ('e1', 'e1@example.com', 'Ann', 'active'),
+                ('e2', 'e2@example.com', 'Ben', 'active'),
+                ('e3', 'e3@example.com', 'Cy', 'terminated')"#,
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+        sqlx::query(
+            "INSERT INTO performance_ratings (id, employee_id, review_cycle_id, overall_rating) VALUES ('r1', 'e1', 'ending', 4.0), ('r3', 'e3', 'ending', 3.0)",
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        let today = NaiveDate::from_ymd_opt(2024, 9, 26).unwrap();
+        let reminders = get_cycle_reminders(&pool, Some(today)).await.unwrap();
+        let summary: Vec<(&str, ReminderKind, Option<i64>)> = reminders
+            .iter()
+            .map(|r| (r.cycle_id.as_str(), r.kind, r.days_until))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("overdue", ReminderKind::Overdue, Some(-88)),
+                ("ending", ReminderKind::EndingSoon, Some(4)),
+                ("sprint", ReminderKind::EndingSoon, Some(6)),
+                ("sprint", ReminderKind::StartingSoon, Some(2)),
+                ("starting", ReminderKind::StartingSoon, Some(5)),
+                ("reversed", ReminderKind::Misconfigured, None),
+            ]
+        );
+
+        // Only active employees count; e1 already has a rating in 'ending'
+        let ending = &reminders[1];
+        assert_eq!((ending.missing_ratings, ending.missing_reviews), (1, 2));
+        assert!(reminders[5].reason.as_deref().unwrap().contains("before"));
+    }
+
+    fn quarterly(name: &str, start_date: &str, end_date: &str, force: bool) -> CreateReviewCycle {
+        CreateReviewCycle {
+            name: name.to_string(),
+            cycle_type: "quarterly".to_string(),
+            start_date: start_date.to_string(),
+            end_date: end_date.to_string(),
+            status: None,
+            force,
+            template_id: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_overlapping_active_cycles() {
+        let pool = crate::db::test_pool().await;
+        create_review_cycle(&pool, quarterly("Q1 2024", "2024-01-01", "2024-03-31", false))
+            .await
+            .unwrap();
+
+        // Adjacent cycles share no days
+        let q2 = create_review_cycle(&pool, quarterly("Q2 2024", "2024-04-01", "2024-06-30", false))
+            .await
+            .unwrap();
+
+        // Overlap with Q2 is rejected and names it
+        let err = create_review_cycle(&pool, quarterly("Calibration", "2024-06-15", "2024-07-15", false))
+            .await
+            .unwrap_err();
+        match err {
+            ReviewCycleError::Overlap(msg) => assert!(msg.contains("Q2 2024"), "{}", msg),
+            other => panic!("expected Overlap, got {:?}", other),
+        }
+
+        // A different cycle_type or a closed cycle doesn't conflict
+        let mut annual = quarterly("2024 Annual", "2024-01-01", "2024-12-31", false);
+        annual.cycle_type = "annual".to_string();
+        create_review_cycle(&pool, annual).await.unwrap();
+        let mut closed = quarterly("Old calibration", "2024-06-15", "2024-07-15", false);
+        closed.status = Some("closed".to_string());
+        let closed = create_review_cycle(&pool, closed).await.unwrap();
+
+        // Re-activating the closed one overlaps Q2
+        let reactivate = UpdateReviewCycle {
+            name: None,
+            cycle_type: None,
+            start_date: None,
+            end_date: None,
+            status: Some("active".to_string()),
+            force: false,
+            template_id: None,
+        };
+        assert!(matches!(
+            update_review_cycle(&pool, &closed.id, reactivate.clone()).await,
+            Err(ReviewCycleError::Overlap(_))
+        ));
+
+        // force lets an admin through, and later renames aren't blocked
+        update_review_cycle(&pool, &closed.id, UpdateReviewCycle { force: true, ..reactivate })
+            .await
+            .unwrap();
+        let forced = create_review_cycle(&pool, quarterly("Mid-Q2 check-in", "2024-05-01", "2024-05-31", true))
+            .await
+            .unwrap();
+        assert_eq!(forced.status, "active");
+        let rename = UpdateReviewCycle {
+            name: Some("Q2 2024 (final)".to_string()),
+            cycle_type: None,
+            start_date: None,
+            end_date: None,
+            status: None,
+            force: false,
+            template_id: None,
+        };
+        update_review_cycle(&pool, &q2.id, rename).await.unwrap();
+    }
+}
@@ -0,0 +1,251 @@
+// HR Command Center - Review Templates Module
+// Named section layouts a review cycle can use to structure its reviews
+
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use thiserror::Error;
+use uuid::Uuid;
+
+use crate::db::DbPool;
+
+// ============================================================================
+// Error Types
+// ============================================================================
+
+#[derive(Error, Debug, Serialize)]
+pub enum ReviewTemplateError {
+    #[error("Database error: {0}")]
+    Database(String),
+    #[error("Review template not found: {0}")]
+    NotFound(String),
+    #[error("Validation error: {0}")]
+    Validation(String),
+    #[error("{}", crate::db::READ_ONLY_MESSAGE)]
+    ReadOnly,
+}
+
+impl From<sqlx::Error> for ReviewTemplateError {
+    fn from(err: sqlx::Error) -> Self {
+        let err_str = err.to_string();
+        if err_str.contains("UNIQUE constraint failed") {
+            ReviewTemplateError::Validation("A review template with this name already exists".to_string())
+        } else {
+            ReviewTemplateError::Database(err_str)
+        }
+    }
+}
+
+impl From<crate::db::ReadOnly> for ReviewTemplateError {
+    fn from(_: crate::db::ReadOnly) -> Self {
+        ReviewTemplateError::ReadOnly
+    }
+}
+
+// ============================================================================
+// Template Types
+// ============================================================================
+
+/// A text field of a performance review that a template can lay out
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReviewSection {
+    Strengths,
+    AreasForImprovement,
+    Accomplishments,
+    GoalsNextPeriod,
+    ManagerComments,
+    SelfAssessment,
+}
+
+impl ReviewSection {
+    pub fn label(&self) -> &'static str {
+        match self {
+            ReviewSection::Strengths => "Strengths",
+            ReviewSection::AreasForImprovement => "Areas for improvement",
+            ReviewSection::Accomplishments => "Accomplishments",
+            ReviewSection::GoalsNextPeriod => "Goals for next period",
+            ReviewSection::ManagerComments => "Manager comments",
+            ReviewSection::SelfAssessment => "Self assessment",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TemplateSection {
+    pub section: ReviewSection,
+    /// Guidance shown to the reviewer when the section is pre-populated
+    #[serde(default)]
+    pub prompt: Option<String>,
+    #[serde(default)]
+    pub required: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReviewTemplate {
+    pub id: String,
+    pub name: String,
+    pub description: Option<String>,
+    /// In display order
+    pub sections: Vec<TemplateSection>,
+    pub created_at: String,
+}
+
+impl ReviewTemplate {
+    pub fn required_sections(&self) -> impl Iterator<Item = ReviewSection> + '_ {
+        self.sections.iter().filter(|s| s.required).map(|s| s.section)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreateReviewTemplate {
+    pub name: String,
+    pub description: Option<String>,
+    pub sections: Vec<TemplateSection>,
+}
+
+#[derive(FromRow)]
+struct ReviewTemplateRow {
+    id: String,
+    name: String,
+    description: Option<String>,
+    sections: String,
+    created_at: String,
+}
+
+impl From<ReviewTemplateRow> for ReviewTemplate {
+    fn from(row: ReviewTemplateRow) -> Self {
+        let sections = serde_json::from_str(&row.sections).unwrap_or_else(|e| {
+            eprintln!("[ReviewTemplates] Unreadable sections for template {}: {}", row.id, e);
+            Vec::new()
+        });
+        ReviewTemplate {
+            id: row.id,
+            name: row.name,
+            description: row.description,
+            sections,
+            created_at: row.created_at,
+        }
+    }
+}
+
+// ============================================================================
+// CRUD Operations
+// ============================================================================
+
+pub async fn create_review_template(
+    pool: &DbPool,
+    input: CreateReviewTemplate,
+) -> Result<ReviewTemplate, ReviewTemplateError> {
+    let name = input.name.trim();
+    if name.is_empty() {
+        return Err(ReviewTemplateError::Validation("Name is required".to_string()));
+    }
+    if input.sections.is_empty() {
+        return Err(ReviewTemplateError::Validation("A template needs at least one section".to_string()));
+    }
+    for (i, section) in input.sections.iter().enumerate() {
+        if input.sections[..i].iter().any(|s| s.section == section.section) {
+            return Err(ReviewTemplateError::Validation(format!(
+                "Section '{}' appears more than once",
+                section.section.label()
+            )));
+        }
+    }
+
+    let id = Uuid::new_v4().to_string();
+    let sections = serde_json::to_string(&input.sections)
+        .map_err(|e| ReviewTemplateError::Validation(e.to_string()))?;
+
+    sqlx::query("INSERT INTO review_templates (id, name, description, sections) VALUES (?, ?, ?, ?)")
+        .bind(&id)
+        .bind(name)
+        .bind(&input.description)
+        .bind(&sections)
+        .execute(pool)
+        .await?;
+
+    get_review_template(pool, &id).await
+}
+
+pub async fn get_review_template(pool: &DbPool, id: &str) -> Result<ReviewTemplate, ReviewTemplateError> {
+    sqlx::query_as::<_, ReviewTemplateRow>("SELECT * FROM review_templates WHERE id = ?")
+        .bind(id)
+        .fetch_optional(pool)
+        .await?
+        .map(ReviewTemplate::from)
+        .ok_or_else(|| ReviewTemplateError::NotFound(id.to_string()))
+}
+
+pub async fn list_review_templates(pool: &DbPool) -> Result<Vec<ReviewTemplate>, ReviewTemplateError> {
+    Ok(sqlx::query_as::<_, ReviewTemplateRow>("SELECT * FROM review_templates ORDER BY name COLLATE NOCASE")
+        .fetch_all(pool)
+        .await?
+        .into_iter()
+        .map(ReviewTemplate::from)
+        .collect())
+}
+
+/// The template attached to a review cycle, if any
+pub async fn get_template_for_cycle(
+    pool: &DbPool,
+    review_cycle_id: &str,
+) -> Result<Option<ReviewTemplate>, ReviewTemplateError> {
+    Ok(sqlx::query_as::<_, ReviewTemplateRow>(
+        r#"SELECT rt.* FROM review_templates rt
+           JOIN review_cycles rc ON rc.template_id = rt.id
+           WHERE rc.id = ?"#,
+    )
+    .bind(review_cycle_id)
+    .fetch_optional(pool)
+    .await?
+    .map(ReviewTemplate::from))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn section(section: ReviewSection, required: bool) -> TemplateSection {
+        TemplateSection { section, prompt: None, required }
+    }
+
+    #[tokio::test]
+    async fn test_create_and_list_templates() {
+        let pool = crate::db::test_pool().await;
+
+        let input = CreateReviewTemplate {
+            name: "  Annual  ".to_string(),
+            description: None,
+            sections: vec![
+                TemplateSection {
+                    section: ReviewSection::Accomplishments,
+                    prompt: Some("What shipped this year?".to_string()),
+                    required: true,
+                },
+                section(ReviewSection::SelfAssessment, false),
+            ],
+        };
+        let created = create_review_template(&pool, input.clone()).await.unwrap();
+        assert_eq!(created.name, "Annual");
+        assert_eq!(created.sections, input.sections);
+        assert_eq!(created.required_sections().collect::<Vec<_>>(), vec![ReviewSection::Accomplishments]);
+
+        assert!(matches!(
+            create_review_template(&pool, input).await,
+            Err(ReviewTemplateError::Validation(_))
+        ));
+        let duplicate_section = CreateReviewTemplate {
+            name: "Quarterly".to_string(),
+            description: None,
+            sections: vec![section(ReviewSection::Strengths, true), section(ReviewSection::Strengths, false)],
+        };
+        assert!(matches!(
+            create_review_template(&pool, duplicate_section).await,
+            Err(ReviewTemplateError::Validation(_))
+        ));
+
+        let listed = list_review_templates(&pool).await.unwrap();
+        assert_eq!(listed.len(), 1);
+        assert_eq!(listed[0].id, created.id);
+    }
+}
@@ -8,6 +8,8 @@
 //       updated_at TEXT DEFAULT (datetime('now'))
 //   );
 
+use std::collections::BTreeMap;
+
 use serde::{Deserialize, Serialize};
 use sqlx::FromRow;
 use thiserror::Error;
@@ -22,6 +24,12 @@ use crate::db::DbPool;
 pub enum SettingsError {
     #[error("Database error: {0}")]
     Database(String),
+    #[error("Invalid config: {0}")]
+    InvalidConfig(String),
+    #[error("Invalid setting: {0}")]
+    Invalid(String),
+    #[error("{}", crate::db::READ_ONLY_MESSAGE)]
+    ReadOnly,
 }
 
 impl From<sqlx::Error> for SettingsError {
@@ -30,6 +38,12 @@ impl From<sqlx::Error> for SettingsError {
     }
 }
 
+impl From<crate::db::ReadOnly> for SettingsError {
+    fn from(_: crate::db::ReadOnly) -> Self {
+        SettingsError::ReadOnly
+    }
+}
+
 // ============================================================================
 // Data Types
 // ============================================================================
@@ -42,6 +56,326 @@ pub struct Setting {
     pub updated_at: String,
 }
 
+/// Setting keys the app reads or writes, on either side of the bridge
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SettingKey {
+    UserName,
+    Persona,
+    ContextTruncationStrategy,
+    NameFormat,
+    TelemetryEnabled,
+    OnboardingCompleted,
+    OnboardingStep,
+    DisclaimerAccepted,
+    DisclaimerAcceptedAt,
+    MondayDigestDismissedWeek,
+    RepresentationReportsEnabled,
+    MaxImportBytes,
+    MaxImportRows,
+    ExtractionThemes,
+    AggregateTimeoutMs,
+    DataConfidenceFloor,
+    MaxMessageTokens,
+    MilestoneYears,
+    AggregateExampleEmployees,
+    CustomPiiPatterns,
+    DiversityReportingEnabled,
+    ExtractionConcurrency,
+    NetworkRetryCount,
+    NetworkRetryBaseDelayMs,
+    ProxyUrl,
+    ProxyUsername,
+}
+
+impl SettingKey {
+    pub const ALL: [SettingKey; 26] = [
+        SettingKey::UserName,
+        SettingKey::Persona,
+        SettingKey::ContextTruncationStrategy,
+        SettingKey::NameFormat,
+        SettingKey::TelemetryEnabled,
+        SettingKey::OnboardingCompleted,
+        SettingKey::OnboardingStep,
+        SettingKey::DisclaimerAccepted,
+        SettingKey::DisclaimerAcceptedAt,
+        SettingKey::MondayDigestDismissedWeek,
+        SettingKey::RepresentationReportsEnabled,
+        SettingKey::MaxImportBytes,
+        SettingKey::MaxImportRows,
+        SettingKey::ExtractionThemes,
+        SettingKey::AggregateTimeoutMs,
+        SettingKey::DataConfidenceFloor,
+        SettingKey::MaxMessageTokens,
+        SettingKey::MilestoneYears,
+        SettingKey::AggregateExampleEmployees,
+        SettingKey::CustomPiiPatterns,
+        SettingKey::DiversityReportingEnabled,
+        SettingKey::ExtractionConcurrency,
+        SettingKey::NetworkRetryCount,
+        SettingKey::NetworkRetryBaseDelayMs,
+        SettingKey::ProxyUrl,
+        SettingKey::ProxyUsername,
+    ];
+
+    /// Key as stored in the settings table
+    pub fn as_str(self) -> &'static str {
+        match self {
+            SettingKey::UserName => "user_name",
+            SettingKey::Persona => "persona",
+            SettingKey::ContextTruncationStrategy => "context_truncation_strategy",
+            SettingKey::NameFormat => "name_format",
+            SettingKey::TelemetryEnabled => "telemetry_enabled",
+            SettingKey::OnboardingCompleted => "onboarding_completed",
+            SettingKey::OnboardingStep => "onboarding_step",
+            SettingKey::DisclaimerAccepted => "disclaimer_accepted",
+            SettingKey::DisclaimerAcceptedAt => "disclaimer_accepted_at",
+            SettingKey::MondayDigestDismissedWeek => "monday_digest_dismissed_week",
+            SettingKey::RepresentationReportsEnabled => "representation_reports_enabled",
+            SettingKey::MaxImportBytes => "max_import_bytes",
+            SettingKey::MaxImportRows => "max_import_rows",
+            SettingKey::ExtractionThemes => "extraction_themes",
+            SettingKey::AggregateTimeoutMs => "aggregate_timeout_ms",
+            SettingKey::DataConfidenceFloor => "data_confidence_floor",
+            SettingKey::MaxMessageTokens => "max_message_tokens",
+            SettingKey::MilestoneYears => "milestone_years",
+            SettingKey::AggregateExampleEmployees => "aggregate_example_employees",
+            SettingKey::CustomPiiPatterns => "custom_pii_patterns",
+            SettingKey::DiversityReportingEnabled => "diversity_reporting_enabled",
+            SettingKey::ExtractionConcurrency => "extraction_concurrency",
+            SettingKey::NetworkRetryCount => "network_retry_count",
+            SettingKey::NetworkRetryBaseDelayMs => "network_retry_base_delay_ms",
+            SettingKey::ProxyUrl => "proxy_url",
+            SettingKey::ProxyUsername => "proxy_username",
+        }
+    }
+
+    pub fn from_key(key: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|k| k.as_str() == key)
+    }
+
+    /// Whether the setting is user preference worth carrying to another device.
+    /// Onboarding, disclaimer, and digest state describe this install only,
+    /// as do the aggregate timeout, network retry and proxy settings (they
+    /// depend on the machine and its connection, and the proxy password
+    /// should never leave it);
+    /// the demographic-report opt-in is deliberately re-confirmed per device.
+    pub fn is_portable(self) -> bool {
+        matches!(
+            self,
+            SettingKey::UserName
+                | SettingKey::Persona
+                | SettingKey::ContextTruncationStrategy
+                | SettingKey::NameFormat
+                | SettingKey::TelemetryEnabled
+                | SettingKey::MaxImportBytes
+                | SettingKey::MaxImportRows
+                | SettingKey::ExtractionThemes
+                | SettingKey::DataConfidenceFloor
+                | SettingKey::MaxMessageTokens
+                | SettingKey::MilestoneYears
+                | SettingKey::AggregateExampleEmployees
+                | SettingKey::CustomPiiPatterns
+                | SettingKey::ExtractionConcurrency
+        )
+    }
+
+    /// Whether the setting only records UI state or preference, so it may
+    /// still be saved in read-only mode. Everything else, including unknown
+    /// keys, changes how data is handled and is refused there.
+    pub fn is_ui_only(self) -> bool {
+        matches!(
+            self,
+            SettingKey::UserName
+                | SettingKey::Persona
+                | SettingKey::TelemetryEnabled
+                | SettingKey::OnboardingCompleted
+                | SettingKey::OnboardingStep
+                | SettingKey::DisclaimerAccepted
+                | SettingKey::DisclaimerAcceptedAt
+                | SettingKey::MondayDigestDismissedWeek
+        )
+    }
+}
+
+/// Whether `key` may be written or deleted while read-only mode is on
+pub fn writable_in_read_only(key: &str) -> bool {
+    SettingKey::from_key(key).is_some_and(SettingKey::is_ui_only)
+}
+
+/// The values a known setting accepts. Unknown keys take any string.
+#[derive(Debug, Clone, PartialEq)]
+pub enum SettingSchema {
+    String,
+    /// "true" or "false"
+    Bool,
+    /// Whole number within the inclusive range
+    Int { min: i64, max: i64 },
+    /// Decimal number within the inclusive range
+    Float { min: f64, max: f64 },
+    /// One of a fixed list of ids
+    Enum(Vec<&'static str>),
+    /// Well-formed JSON; the owning module checks the shape
+    Json,
+}
+
+impl SettingKey {
+    /// What a valid value for this key looks like
+    pub fn schema(self) -> SettingSchema {
+        use SettingSchema::*;
+        match self {
+            SettingKey::UserName
+            | SettingKey::DisclaimerAcceptedAt
+            | SettingKey::MondayDigestDismissedWeek
+            | SettingKey::ProxyUrl
+            | SettingKey::ProxyUsername => String,
+            SettingKey::Persona => Enum(crate::context::PERSONAS.iter().map(|p| p.id).collect()),
+            SettingKey::ContextTruncationStrategy => Enum(vec!["truncate", "round_robin", "prioritized"]),
+            SettingKey::NameFormat => Enum(vec!["first_last", "last_first"]),
+            SettingKey::TelemetryEnabled
+            | SettingKey::OnboardingCompleted
+            | SettingKey::DisclaimerAccepted
+            | SettingKey::RepresentationReportsEnabled
+            | SettingKey::AggregateExampleEmployees
+            | SettingKey::DiversityReportingEnabled => Bool,
+            SettingKey::OnboardingStep => Int { min: 0, max: i64::MAX },
+            SettingKey::MaxImportBytes | SettingKey::MaxImportRows | SettingKey::MaxMessageTokens => {
+                Int { min: 1, max: i64::MAX }
+            }
+            SettingKey::AggregateTimeoutMs => Int { min: 1, max: i64::MAX },
+            SettingKey::ExtractionConcurrency => Int {
+                min: 1,
+                max: crate::highlights::MAX_EXTRACTION_CONCURRENCY as i64,
+            },
+            SettingKey::NetworkRetryCount => Int {
+                min: 0,
+                max: crate::network::MAX_RETRY_COUNT as i64,
+            },
+            SettingKey::NetworkRetryBaseDelayMs => Int {
+                min: 0,
+                max: crate::network::MAX_RETRY_DELAY.as_millis() as i64,
+            },
+            SettingKey::DataConfidenceFloor => Float { min: 0.0, max: 1.0 },
+            SettingKey::ExtractionThemes | SettingKey::MilestoneYears | SettingKey::CustomPiiPatterns => Json,
+        }
+    }
+
+    /// Check `value` against the key's schema, describing the problem if it
+    /// doesn't fit
+    pub fn validate(self, value: &str) -> Result<(), SettingsError> {
+        let invalid = |expected: String| {
+            Err(SettingsError::Invalid(format!(
+                "'{}' is not a valid value for {}: expected {}",
+                value,
+                self.as_str(),
+                expected
+            )))
+        };
+        match self.schema() {
+            SettingSchema::String => Ok(()),
+            SettingSchema::Bool => match value {
+                "true" | "false" => Ok(()),
+                _ => invalid("true or false".to_string()),
+            },
+            SettingSchema::Int { min, max } => match value.trim().parse::<i64>() {
+                Ok(n) if (min..=max).contains(&n) => Ok(()),
+                _ if max == i64::MAX => invalid(format!("a whole number of at least {}", min)),
+                _ => invalid(format!("a whole number from {} to {}", min, max)),
+            },
+            SettingSchema::Float { min, max } => match value.trim().parse::<f64>() {
+                Ok(n) if (min..=max).contains(&n) => Ok(()),
+                _ => invalid(format!("a number from {} to {}", min, max)),
+            },
+            SettingSchema::Enum(allowed) => {
+                if allowed.contains(&value) {
+                    Ok(())
+                } else {
+                    invalid(format!("one of {}", allowed.join(", ")))
+                }
+            }
+            SettingSchema::Json => {
+                if let Err(e) = serde_json::from_str::<serde_json::Value>(value) {
+                    return invalid(format!("JSON ({})", e));
+                }
+                // Parse with the reader the setting feeds, so a value accepted
+                // here isn't silently dropped at load time
+                match self {
+                    SettingKey::CustomPiiPatterns => {
+                        let patterns: Vec<crate::pii::CustomPiiPattern> = match serde_json::from_str(value) {
+                            Ok(patterns) => patterns,
+                            Err(e) => return invalid(format!("a list of {{label, regex}} patterns ({})", e)),
+                        };
+                        match crate::pii::compile_custom_patterns(&patterns) {
+                            Ok(_) => Ok(()),
+                            Err(e) => invalid(format!("valid patterns ({})", e)),
+                        }
+                    }
+                    SettingKey::MilestoneYears => match crate::tenure::parse_milestone_years(value) {
+                        Some(_) => Ok(()),
+                        None => invalid("a JSON array of positive whole years".to_string()),
+                    },
+                    SettingKey::ExtractionThemes => match crate::highlights::parse_theme_vocabulary(value) {
+                        Some(_) => Ok(()),
+                        None => invalid("a JSON array with at least one non-blank theme".to_string()),
+                    },
+                    _ => Ok(()),
+                }
+            }
+        }
+    }
+}
+
+/// Conversion from a stored setting string for `get_typed_setting`
+pub trait FromSetting: Sized {
+    fn from_setting(value: &str) -> Option<Self>;
+}
+
+impl FromSetting for String {
+    fn from_setting(value: &str) -> Option<Self> {
+        Some(value.to_string())
+    }
+}
+
+impl FromSetting for bool {
+    fn from_setting(value: &str) -> Option<Self> {
+        match value {
+            "true" => Some(true),
+            "false" => Some(false),
+            _ => None,
+        }
+    }
+}
+
+macro_rules! from_setting_parse {
+    ($($ty:ty),*) => {
+        $(impl FromSetting for $ty {
+            fn from_setting(value: &str) -> Option<Self> {
+                value.trim().parse().ok()
+            }
+        })*
+    };
+}
+
+from_setting_parse!(i64, u32, u64, usize, f64);
+
+/// Current config export format version
+pub const CONFIG_FORMAT_VERSION: u32 = 1;
+
+/// Plaintext config export: preferences only, no employee data
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConfigExport {
+    pub version: u32,
+    pub exported_at: String,
+    pub settings: BTreeMap<String, String>,
+}
+
+/// Result of applying an exported config
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ConfigImportResult {
+    pub imported: usize,
+    /// Keys that were skipped, with the reason
+    pub warnings: Vec<String>,
+}
+
 // ============================================================================
 // CRUD Operations
 // ============================================================================
@@ -78,6 +412,51 @@ pub async fn set_setting(pool: &DbPool, key: &str, value: &str) -> Result<(), Se
     Ok(())
 }
 
+/// Secrets kept out of the settings table (and so out of backups and
+/// `get_setting`); they live in keyring.rs
+pub const SECRET_KEYS: &[&str] = &["proxy_password"];
+
+/// Set a setting after checking it against the key's schema.
+/// Unknown keys are stored as free-form strings.
+pub async fn set_setting_validated(pool: &DbPool, key: &str, value: &str) -> Result<(), SettingsError> {
+    if SECRET_KEYS.contains(&key) {
+        return Err(SettingsError::Invalid(format!("{} is kept in secure key storage, not in settings", key)));
+    }
+    if let Some(known) = SettingKey::from_key(key) {
+        known.validate(value)?;
+    }
+    set_setting(pool, key, value).await
+}
+
+/// Read a known setting as `T`. Ok(None) when unset; a stored value that
+/// fails the schema (e.g. edited by hand) is `SettingsError::Invalid`.
+pub async fn get_typed_setting<T: FromSetting>(pool: &DbPool, key: SettingKey) -> Result<Option<T>, SettingsError> {
+    let Some(value) = get_setting(pool, key.as_str()).await? else {
+        return Ok(None);
+    };
+    key.validate(&value)?;
+    T::from_setting(&value).map(Some).ok_or_else(|| {
+        SettingsError::Invalid(format!(
+            "{} value '{}' can't be read as {}",
+            key.as_str(),
+            value,
+            std::any::type_name::<T>()
+        ))
+    })
+}
+
+/// `get_typed_setting` for callers with a default to fall back on: unset,
+/// invalid and unreadable values are all None, with the latter two logged
+pub async fn load_typed_setting<T: FromSetting>(pool: &DbPool, key: SettingKey) -> Option<T> {
+    match get_typed_setting(pool, key).await {
+        Ok(value) => value,
+        Err(e) => {
+            eprintln!("[Settings] Ignoring {}: {}", key.as_str(), e);
+            None
+        }
+    }
+}
+
 /// Delete a setting by key
 /// Does nothing if the setting doesn't exist
 pub async fn delete_setting(pool: &DbPool, key: &str) -> Result<(), SettingsError> {
@@ -112,8 +491,319 @@ pub async fn list_settings(pool: &DbPool) -> Result<Vec<Setting>, SettingsError>
     Ok(settings)
 }
 
+// ============================================================================
+// Key Migrations
+// ============================================================================
+
+/// A renamed or retired setting key
+pub struct SettingMigration {
+    pub old_key: &'static str,
+    /// Key the value moves to; None retires the old key outright
+    pub new_key: Option<&'static str>,
+    /// Optional value rewrite; returning None drops the value
+    pub transform: Option<fn(&str) -> Option<String>>,
+}
+
+/// Declared key migrations, applied in order at startup.
+/// Append entries here when a key is renamed or retired; never reorder.
+pub const SETTING_MIGRATIONS: &[SettingMigration] = &[];
+
+/// Apply `SETTING_MIGRATIONS`. Safe to run on every launch.
+pub async fn migrate_settings(pool: &DbPool) -> Result<usize, SettingsError> {
+    apply_setting_migrations(pool, SETTING_MIGRATIONS).await
+}
+
+/// Apply key migrations in one transaction, returning how many old keys were
+/// migrated. A value only moves when the new key isn't already set, so a
+/// preference saved under the new name always wins; the old key is removed
+/// either way.
+pub async fn apply_setting_migrations(
+    pool: &DbPool,
+    migrations: &[SettingMigration],
+) -> Result<usize, SettingsError> {
+    let mut tx = pool.begin().await?;
+    let mut migrated = 0;
+
+    for migration in migrations {
+        let old_value: Option<(String,)> = sqlx::query_as("SELECT value FROM settings WHERE key = ?")
+            .bind(migration.old_key)
+            .fetch_optional(&mut *tx)
+            .await?;
+        let Some((old_value,)) = old_value else {
+            continue;
+        };
+
+        if let Some(new_key) = migration.new_key {
+            let new_exists: Option<(i64,)> = sqlx::query_as("SELECT 1 FROM settings WHERE key = ?")
+                .bind(new_key)
+                .fetch_optional(&mut *tx)
+                .await?;
+
+            let value = match migration.transform {
+                Some(transform) => transform(&old_value),
+                None => Some(old_value),
+            };
+
+            if let (None, Some(value)) = (new_exists, value) {
+                sqlx::query("INSERT INTO settings (key, value, updated_at) VALUES (?, ?, datetime('now'))")
+                    .bind(new_key)
+                    .bind(value)
+                    .execute(&mut *tx)
+                    .await?;
+            }
+        }
+
+        sqlx::query("DELETE FROM settings WHERE key = ?")
+            .bind(migration.old_key)
+            .execute(&mut *tx)
+            .await?;
+        migrated += 1;
+    }
+
+    tx.commit().await?;
+    Ok(migrated)
+}
+
+// ============================================================================
+// Config Export / Import
+// ============================================================================
+
+/// Serialize portable settings (persona choice, display name, context
+/// preferences) to plaintext JSON for moving a setup to another device.
+/// Device-specific and unrecognized keys are left out.
+pub async fn export_config(pool: &DbPool) -> Result<String, SettingsError> {
+    let settings = list_settings(pool)
+        .await?
+        .into_iter()
+        .filter(|s| SettingKey::from_key(&s.key).is_some_and(SettingKey::is_portable))
+        .map(|s| (s.key, s.value))
+        .collect();
+
+    let export = ConfigExport {
+        version: CONFIG_FORMAT_VERSION,
+        exported_at: chrono::Utc::now().to_rfc3339(),
+        settings,
+    };
+
+    serde_json::to_string_pretty(&export).map_err(|e| SettingsError::InvalidConfig(e.to_string()))
+}
+
+/// Apply a config produced by `export_config`.
+/// Unknown or device-specific keys are skipped and reported as warnings;
+/// only malformed JSON or an unsupported version fails the import.
+pub async fn import_config(pool: &DbPool, json: &str) -> Result<ConfigImportResult, SettingsError> {
+    let export: ConfigExport =
+        serde_json::from_str(json).map_err(|e| SettingsError::InvalidConfig(e.to_string()))?;
+
+    if export.version > CONFIG_FORMAT_VERSION {
+        return Err(SettingsError::InvalidConfig(format!(
+            "Config version {} is newer than supported version {}",
+            export.version, CONFIG_FORMAT_VERSION
+        )));
+    }
+
+    let mut result = ConfigImportResult::default();
+    for (key, value) in &export.settings {
+        match SettingKey::from_key(key) {
+            Some(known) if known.is_portable() => match known.validate(value) {
+                Ok(()) => {
+                    set_setting(pool, key, value).await?;
+                    result.imported += 1;
+                }
+                Err(e) => result.warnings.push(format!("Skipped {}", e)),
+            },
+            Some(_) => result
+                .warnings
+                .push(format!("Skipped device-specific setting '{}'", key)),
+            None => result.warnings.push(format!("Skipped unknown setting '{}'", key)),
+        }
+    }
+
+    Ok(result)
+}
+
 #[cfg(test)]
 mod tests {
-    // Integration tests would require database setup
-    // Unit tests for this module are minimal since it's mostly DB operations
+    use super::*;
+
+    #[test]
+    fn test_setting_key_round_trip() {
+        for key in SettingKey::ALL {
+            assert_eq!(SettingKey::from_key(key.as_str()), Some(key));
+        }
+        assert_eq!(SettingKey::from_key("not_a_setting"), None);
+    }
+
+    #[test]
+    fn test_only_ui_settings_are_writable_in_read_only() {
+        assert!(writable_in_read_only("persona"));
+        assert!(writable_in_read_only("onboarding_step"));
+        assert!(!writable_in_read_only("custom_pii_patterns"));
+        assert!(!writable_in_read_only("diversity_reporting_enabled"));
+        assert!(!writable_in_read_only("representation_reports_enabled"));
+        assert!(!writable_in_read_only("not_a_setting"));
+    }
+
+    fn uppercase(value: &str) -> Option<String> {
+        Some(value.to_uppercase())
+    }
+
+    const TEST_MIGRATIONS: &[SettingMigration] = &[
+        SettingMigration { old_key: "old_persona", new_key: Some("persona"), transform: None },
+        SettingMigration { old_key: "old_name", new_key: Some("user_name"), transform: Some(uppercase) },
+        SettingMigration { old_key: "retired_flag", new_key: None, transform: None },
+    ];
+
+    #[tokio::test]
+    async fn test_migrate_settings_moves_old_keys() {
+        let pool = crate::db::test_pool().await;
+        set_setting(&pool, "old_persona", "morgan").await.unwrap();
+        set_setting(&pool, "old_name", "pat").await.unwrap();
+        set_setting(&pool, "retired_flag", "true").await.unwrap();
+
+        let migrated = apply_setting_migrations(&pool, TEST_MIGRATIONS).await.unwrap();
+        assert_eq!(migrated, 3);
+        assert_eq!(get_setting(&pool, "persona").await.unwrap().as_deref(), Some("morgan"));
+        assert_eq!(get_setting(&pool, "user_name").await.unwrap().as_deref(), Some("PAT"));
+        for old in ["old_persona", "old_name", "retired_flag"] {
+            assert!(!has_setting(&pool, old).await.unwrap(), "{}", old);
+        }
+
+        // Second run finds nothing to do
+        assert_eq!(apply_setting_migrations(&pool, TEST_MIGRATIONS).await.unwrap(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_migrate_settings_keeps_existing_new_value() {
+        let pool = crate::db::test_pool().await;
+        set_setting(&pool, "persona", "taylor").await.unwrap();
+        set_setting(&pool, "old_persona", "morgan").await.unwrap();
+
+        apply_setting_migrations(&pool, TEST_MIGRATIONS).await.unwrap();
+        assert_eq!(get_setting(&pool, "persona").await.unwrap().as_deref(), Some("taylor"));
+        assert!(!has_setting(&pool, "old_persona").await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_config_round_trip_with_persona() {
+        let source = crate::db::test_pool().await;
+        set_setting(&source, "persona", "jordan").await.unwrap();
+        set_setting(&source, "user_name", "Pat").await.unwrap();
+        set_setting(&source, "context_truncation_strategy", "round_robin").await.unwrap();
+        set_setting(&source, "onboarding_completed", "true").await.unwrap();
+
+        let json = export_config(&source).await.unwrap();
+        assert!(!json.contains("onboarding_completed"));
+
+        let target = crate::db::test_pool().await;
+        set_setting(&target, "persona", "alex").await.unwrap();
+        let result = import_config(&target, &json).await.unwrap();
+
+        assert_eq!(result.imported, 3);
+        assert!(result.warnings.is_empty());
+        assert_eq!(get_setting(&target, "persona").await.unwrap().as_deref(), Some("jordan"));
+        assert_eq!(get_setting(&target, "user_name").await.unwrap().as_deref(), Some("Pat"));
+        assert_eq!(
+            get_setting(&target, "context_truncation_strategy").await.unwrap().as_deref(),
+            Some("round_robin")
+        );
+        assert!(!has_setting(&target, "onboarding_completed").await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_import_config_warns_on_unknown_keys() {
+        let pool = crate::db::test_pool().await;
+        let json = r#"{
+            "version": 1,
+            "exported_at": "2025-01-01T00:00:00Z",
+            "settings": {
+                "persona": "sam",
+                "favorite_color": "teal",
+                "onboarding_step": "3"
+            }
+        }"#;
+
+        let result = import_config(&pool, json).await.unwrap();
+        assert_eq!(result.imported, 1);
+        assert_eq!(result.warnings.len(), 2);
+        assert!(result.warnings.iter().any(|w| w.contains("favorite_color")));
+        assert!(!has_setting(&pool, "favorite_color").await.unwrap());
+        assert!(!has_setting(&pool, "onboarding_step").await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_import_config_rejects_bad_input() {
+        let pool = crate::db::test_pool().await;
+        assert!(matches!(
+            import_config(&pool, "not json").await,
+            Err(SettingsError::InvalidConfig(_))
+        ));
+        let future = r#"{"version": 99, "exported_at": "", "settings": {}}"#;
+        assert!(matches!(
+            import_config(&pool, future).await,
+            Err(SettingsError::InvalidConfig(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_set_setting_validated_rejects_unknown_persona() {
+        let pool = crate::db::test_pool().await;
+        set_setting_validated(&pool, "persona", "morgan").await.unwrap();
+
+        let err = set_setting_validated(&pool, "persona", "dr_evil").await.unwrap_err();
+        assert!(matches!(&err, SettingsError::Invalid(msg) if msg.contains("alex, jordan")), "{}", err);
+        assert_eq!(get_setting(&pool, "persona").await.unwrap().as_deref(), Some("morgan"));
+
+        assert!(set_setting_validated(&pool, "telemetry_enabled", "yes").await.is_err());
+        // Unknown keys stay free-form
+        set_setting_validated(&pool, "sidebar_width", "wide-ish").await.unwrap();
+        assert_eq!(get_setting(&pool, "sidebar_width").await.unwrap().as_deref(), Some("wide-ish"));
+    }
+
+    #[tokio::test]
+    async fn test_typed_settings_enforce_integer_range() {
+        let pool = crate::db::test_pool().await;
+        for bad in ["0", "17", "four", "2.5"] {
+            let err = set_setting_validated(&pool, "extraction_concurrency", bad).await.unwrap_err();
+            assert!(matches!(err, SettingsError::Invalid(_)), "{}", bad);
+        }
+        assert_eq!(get_typed_setting::<usize>(&pool, SettingKey::ExtractionConcurrency).await.unwrap(), None);
+
+        set_setting_validated(&pool, "extraction_concurrency", "16").await.unwrap();
+        assert_eq!(get_typed_setting::<usize>(&pool, SettingKey::ExtractionConcurrency).await.unwrap(), Some(16));
+        assert!(set_setting_validated(&pool, "data_confidence_floor", "1.5").await.is_err());
+
+        // A value written around validation is an error on typed reads
+        set_setting(&pool, "network_retry_count", "99").await.unwrap();
+        assert!(get_typed_setting::<u32>(&pool, SettingKey::NetworkRetryCount).await.is_err());
+        assert_eq!(load_typed_setting::<u32>(&pool, SettingKey::NetworkRetryCount).await, None);
+        set_setting_validated(&pool, "diversity_reporting_enabled", "true").await.unwrap();
+        assert_eq!(load_typed_setting(&pool, SettingKey::DiversityReportingEnabled).await, Some(true));
+    }
+
+    #[tokio::test]
+    async fn test_json_settings_validated_by_their_parsers() {
+        let pool = crate::db::test_pool().await;
+        for (key, bad) in [
+            ("custom_pii_patterns", r#"[{"label":"x","regex":"("}]"#),
+            ("custom_pii_patterns", r#"{"label":"x"}"#),
+            ("milestone_years", "[0, -5]"),
+            ("milestone_years", r#"["five"]"#),
+            ("extraction_themes", r#"["  "]"#),
+        ] {
+            let err = set_setting_validated(&pool, key, bad).await.unwrap_err();
+            assert!(matches!(err, SettingsError::Invalid(_)), "{} {}", key, bad);
+        }
+
+        set_setting_validated(&pool, "custom_pii_patterns", r#"[{"label":"Badge","regex":"B-\\d{6}"}]"#)
+            .await
+            .unwrap();
+        set_setting_validated(&pool, "milestone_years", "[1, 5, 10]").await.unwrap();
+        set_setting_validated(&pool, "extraction_themes", r#"["leadership"]"#).await.unwrap();
+
+        let config = r#"{"version": 1, "exported_at": "2024-01-01T00:00:00Z", "settings": {"milestone_years": "[0]"}}"#;
+        let result = import_config(&pool, config).await.unwrap();
+        assert_eq!(result.imported, 0);
+        assert_eq!(get_setting(&pool, "milestone_years").await.unwrap().as_deref(), Some("[1, 5, 10]"));
+    }
 }
@@ -0,0 +1,414 @@
+// HR Command Center - Tenure Module
+// One hire_date-to-today calculation shared by the digest, org aggregates,
+// and the tenure stats command, so the numbers agree everywhere.
+
+use chrono::{Datelike, NaiveDate};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::db::DbPool;
+use crate::settings::{self, SettingKey};
+
+// ============================================================================
+// Error Types
+// ============================================================================
+
+#[derive(Error, Debug, Serialize)]
+pub enum TenureError {
+    #[error("Database error: {0}")]
+    Database(String),
+}
+
+impl From<sqlx::Error> for TenureError {
+    fn from(err: sqlx::Error) -> Self {
+        TenureError::Database(err.to_string())
+    }
+}
+
+// ============================================================================
+// Data Types
+// ============================================================================
+
+/// Average year length, matching the analytics tenure-bucket SQL
+pub const DAYS_PER_YEAR: f64 = 365.25;
+
+/// Anniversary years celebrated when milestone filtering is on and the
+/// milestone_years setting is unset or invalid
+pub const DEFAULT_MILESTONE_YEARS: [i32; 3] = [1, 5, 10];
+
+/// How many longest-tenured employees to report
+const LONGEST_TENURED_LIMIT: usize = 5;
+
+/// Tenure bands as (label, exclusive upper bound in years).
+/// Labels match the analytics tenure_bucket chart.
+pub(crate) const TENURE_BANDS: [(&str, f64); 4] = [
+    ("< 1 year", 1.0),
+    ("1-3 years", 3.0),
+    ("3-5 years", 5.0),
+    ("5+ years", f64::INFINITY),
+];
+
+/// Headcount within one tenure band
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TenureBandCount {
+    pub label: String,
+    pub count: i64,
+}
+
+/// A long-tenured active employee
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TenuredEmployee {
+    pub id: String,
+    pub full_name: String,
+    pub department: Option<String>,
+    pub hire_date: String,
+    pub tenure_years: f64,
+}
+
+/// Tenure statistics for active employees with a usable hire_date
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TenureStats {
+    /// Active employees included in the stats
+    pub employees_counted: i64,
+    /// Active employees left out for a missing or unparseable hire_date
+    pub employees_without_hire_date: i64,
+    pub avg_tenure_years: Option<f64>,
+    pub median_tenure_years: Option<f64>,
+    /// Every band in order, including empty ones
+    pub bands: Vec<TenureBandCount>,
+    /// Longest-tenured first
+    pub longest_tenured: Vec<TenuredEmployee>,
+}
+
+// ============================================================================
+// Tenure Math
+// ============================================================================
+
+/// Today's date in the machine's configured local timezone
+pub fn local_today() -> NaiveDate {
+    chrono::Local::now().date_naive()
+}
+
+/// Parse a stored hire_date ("YYYY-MM-DD", optionally followed by a time)
+pub fn parse_hire_date(raw: &str) -> Option<NaiveDate> {
+    let date_part = raw.trim().get(..10)?;
+    NaiveDate::parse_from_str(date_part, "%Y-%m-%d").ok()
+}
+
+/// Days from hire to `today`; future hire dates count as zero
+pub fn tenure_days(hire: NaiveDate, today: NaiveDate) -> i64 {
+    (today - hire).num_days().max(0)
+}
+
+/// Fractional years from hire to `today`
+pub fn tenure_years(hire: NaiveDate, today: NaiveDate) -> f64 {
+    tenure_days(hire, today) as f64 / DAYS_PER_YEAR
+}
+
+/// Fractional months from `start` to `end`, on the same average year
+pub fn months_between(start: NaiveDate, end: NaiveDate) -> f64 {
+    tenure_days(start, end) as f64 * 12.0 / DAYS_PER_YEAR
+}
+
+/// Whole work anniversaries reached by `on`
+pub fn completed_years(hire: NaiveDate, on: NaiveDate) -> i32 {
+    let mut years = on.year() - hire.year();
+    if (on.month(), on.day()) < (hire.month(), hire.day()) {
+        years -= 1;
+    }
+    years.max(0)
+}
+
+/// Next anniversary of `hire` on or after `today` (handles the year
+/// boundary). None for a Feb 29 hire date when that day doesn't exist.
+pub fn next_anniversary(hire: NaiveDate, today: NaiveDate) -> Option<NaiveDate> {
+    let this_year = NaiveDate::from_ymd_opt(today.year(), hire.month(), hire.day())?;
+    if this_year >= today {
+        Some(this_year)
+    } else {
+        NaiveDate::from_ymd_opt(today.year() + 1, hire.month(), hire.day())
+    }
+}
+
+/// Years being celebrated at the next anniversary on or after `today`, if it
+/// falls within `window_days`. With `milestones`, anniversaries for any other
+/// year count are skipped.
+pub fn upcoming_anniversary_years(
+    hire: NaiveDate,
+    today: NaiveDate,
+    window_days: i64,
+    milestones: Option<&[i32]>,
+) -> Option<i32> {
+    let next_anniversary = next_anniversary(hire, today)?;
+
+    if (next_anniversary - today).num_days() > window_days {
+        return None;
+    }
+
+    let years = completed_years(hire, next_anniversary);
+    match milestones {
+        Some(milestones) if !milestones.contains(&years) => None,
+        _ => Some(years),
+    }
+}
+
+/// Parse the milestone_years setting: a JSON array of positive year counts
+pub fn parse_milestone_years(raw: &str) -> Option<Vec<i32>> {
+    let mut years: Vec<i32> = serde_json::from_str(raw).ok()?;
+    years.retain(|y| *y > 0);
+    years.sort_unstable();
+    years.dedup();
+    if years.is_empty() {
+        None
+    } else {
+        Some(years)
+    }
+}
+
+/// Configured milestone years, or the defaults when unset or invalid
+pub async fn load_milestone_years(pool: &DbPool) -> Vec<i32> {
+    settings::get_setting(pool, SettingKey::MilestoneYears.as_str())
+        .await
+        .ok()
+        .flatten()
+        .and_then(|raw| parse_milestone_years(&raw))
+        .unwrap_or_else(|| DEFAULT_MILESTONE_YEARS.to_vec())
+}
+
+pub(crate) fn band_label(years: f64) -> &'static str {
+    TENURE_BANDS
+        .iter()
+        .find(|(_, upper)| years < *upper)
+        .map(|(label, _)| *label)
+        .unwrap_or(TENURE_BANDS[TENURE_BANDS.len() - 1].0)
+}
+
+fn median(sorted: &[f64]) -> Option<f64> {
+    if sorted.is_empty() {
+        return None;
+    }
+    let mid = sorted.len() / 2;
+    if sorted.len().is_multiple_of(2) {
+        Some((sorted[mid - 1] + sorted[mid]) / 2.0)
+    } else {
+        Some(sorted[mid])
+    }
+}
+
+// ============================================================================
+// Stats
+// ============================================================================
+
+/// Compute tenure statistics for active employees as of today (local time),
+/// for one company or across all of them
+pub async fn compute_tenure_stats(pool: &DbPool, company_id: Option<&str>) -> Result<TenureStats, TenureError> {
+    compute_tenure_stats_as_of(pool, company_id, local_today()).await
+}
+
+/// Active employees with a usable hire_date, longest-tenured first, plus how
+/// many were left out for a missing or unparseable one
+async fn load_active_tenures(
+    pool: &DbPool,
+    company_id: Option<&str>,
+    today: NaiveDate,
+) -> Result<(Vec<TenuredEmployee>, i64), TenureError> {
+    let rows: Vec<(String, String, Option<String>, Option<String>)> = sqlx::query_as(
+        "SELECT id, full_name, department, hire_date FROM employees WHERE status = 'active' AND (? IS NULL OR company_id = ?)",
+    )
+    .bind(company_id)
+    .bind(company_id)
+    .fetch_all(pool)
+    .await?;
+
+    let mut without_hire_date = 0;
+    let mut tenured: Vec<TenuredEmployee> = Vec::new();
+    for (id, full_name, department, hire_date) in rows {
+        match hire_date.as_deref().and_then(parse_hire_date) {
+            Some(hire) => tenured.push(TenuredEmployee {
+                id,
+                full_name,
+                department,
+                hire_date: hire.format("%Y-%m-%d").to_string(),
+                tenure_years: tenure_years(hire, today),
+            }),
+            None => without_hire_date += 1,
+        }
+    }
+
+    tenured.sort_by(|a, b| b.tenure_years.total_cmp(&a.tenure_years));
+    Ok((tenured, without_hire_date))
+}
+
+/// Up to `limit` longest-tenured active employees as of today (local time)
+pub async fn longest_tenured(
+    pool: &DbPool,
+    company_id: Option<&str>,
+    limit: usize,
+) -> Result<Vec<TenuredEmployee>, TenureError> {
+    let (mut tenured, _) = load_active_tenures(pool, company_id, local_today()).await?;
+    tenured.truncate(limit);
+    Ok(tenured)
+}
+
+/// Compute tenure statistics as of a given date
+pub async fn compute_tenure_stats_as_of(
+    pool: &DbPool,
+    company_id: Option<&str>,
+    today: NaiveDate,
+) -> Result<TenureStats, TenureError> {
+    let (tenured, without_hire_date) = load_active_tenures(pool, company_id, today).await?;
+
+    let mut years: Vec<f64> = tenured.iter().map(|t| t.tenure_years).collect();
+    years.sort_by(f64::total_cmp);
+
+    let bands = TENURE_BANDS
+        .iter()
+        .map(|(label, _)| TenureBandCount {
+            label: label.to_string(),
+            count: years.iter().filter(|y| band_label(**y) == *label).count() as i64,
+        })
+        .collect();
+
+    let avg_tenure_years = if years.is_empty() {
+        None
+    } else {
+        Some(years.iter().sum::<f64>() / years.len() as f64)
+    };
+
+    Ok(TenureStats {
+        employees_counted: years.len() as i64,
+        employees_without_hire_date: without_hire_date,
+        avg_tenure_years,
+        median_tenure_years: median(&years),
+        bands,
+        longest_tenured: tenured.into_iter().take(LONGEST_TENURED_LIMIT).collect(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(s: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(s, "%Y-%m-%d").unwrap()
+    }
+
+    #[test]
+    fn test_completed_years_respects_anniversary() {
+        let hire = date("2020-06-15");
+        assert_eq!(completed_years(hire, date("2024-06-14")), 3);
+        assert_eq!(completed_years(hire, date("2024-06-15")), 4);
+        assert_eq!(completed_years(hire, date("2019-01-01")), 0);
+    }
+
+    #[test]
+    fn test_upcoming_anniversary_milestone_filter() {
+        let today = date("2024-06-10");
+        let hires = [
+            ("2023-06-12", 1),
+            ("2021-06-13", 3),
+            ("2019-06-11", 5),
+            ("2022-06-14", 2),
+            ("2014-06-16", 10),
+        ];
+
+        // Default: every anniversary in the window
+        let all: Vec<i32> = hires
+            .iter()
+            .filter_map(|(h, _)| upcoming_anniversary_years(date(h), today, 7, None))
+            .collect();
+        assert_eq!(all, vec![1, 3, 5, 2, 10]);
+
+        let milestones = [1, 5, 10];
+        let celebrated: Vec<i32> = hires
+            .iter()
+            .filter_map(|(h, _)| upcoming_anniversary_years(date(h), today, 7, Some(&milestones)))
+            .collect();
+        assert_eq!(celebrated, vec![1, 5, 10]);
+        assert!(hires.iter().all(|(h, years)| {
+            upcoming_anniversary_years(date(h), today, 7, None) == Some(*years)
+        }));
+
+        // Outside the window
+        assert_eq!(upcoming_anniversary_years(date("2023-06-30"), today, 7, None), None);
+        // Year boundary
+        assert_eq!(upcoming_anniversary_years(date("2019-01-02"), date("2024-12-30"), 7, None), Some(6));
+        assert_eq!(next_anniversary(date("2019-01-02"), date("2024-12-30")), Some(date("2025-01-02")));
+        assert_eq!(next_anniversary(date("2020-02-29"), date("2025-01-10")), None);
+    }
+
+    #[test]
+    fn test_months_between() {
+        assert!((months_between(date("2025-01-01"), date("2026-01-01")) - 12.0).abs() < 0.05);
+        assert!((months_between(date("2025-01-01"), date("2025-07-02")) - 5.98).abs() < 0.01);
+        assert_eq!(months_between(date("2025-06-01"), date("2025-01-01")), 0.0);
+    }
+
+    #[test]
+    fn test_parse_milestone_years() {
+        assert_eq!(parse_milestone_years("[10, 1, 5, 5, 0]"), Some(vec![1, 5, 10]));
+        assert_eq!(parse_milestone_years("[]"), None);
+        assert_eq!(parse_milestone_years("five"), None);
+    }
+
+    #[tokio::test]
+    async fn test_load_milestone_years_uses_setting() {
+        let pool = crate::db::test_pool().await;
+        assert_eq!(load_milestone_years(&pool).await, DEFAULT_MILESTONE_YEARS.to_vec());
+
+        settings::set_setting(&pool, SettingKey::MilestoneYears.as_str(), "[1,3,5,10]").await.unwrap();
+        assert_eq!(load_milestone_years(&pool).await, vec![1, 3, 5, 10]);
+    }
+
+    #[test]
+    fn test_parse_hire_date_accepts_datetime_suffix() {
+        assert_eq!(parse_hire_date("2021-03-04 09:00:00"), Some(date("2021-03-04")));
+        assert_eq!(parse_hire_date("03/04/2021"), None);
+        assert_eq!(parse_hire_date(""), None);
+    }
+
+    #[tokio::test]
+    async fn test_tenure_stats_median_and_bands() {
+        let pool = crate::db::test_pool().await;
+        sqlx::query(
+            r#"INSERT INTO employees (id, email, full_name, hire_date, status) VALUES
+                ('a', 'a@example.com', 'Ana Half', '2024-07-01', 'active'),
+                ('b', 'b@example.com', 'Bo Two', '2023-01-01', 'active'),
+                ('c', 'c@example.com', 'Cy Four', '2021-01-01', 'active'),
+                ('d', 'd@example.com', 'Di Ten', '2015-01-01', 'active'),
+                ('e', 'e@example.com', 'Ed Null', NULL, 'active'),
+                ('f', 'f@example.com', 'Fay Gone', '2010-01-01', 'terminated')"#,
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        let stats = compute_tenure_stats_as_of(&pool, None, date("2025-01-01")).await.unwrap();
+
+        assert_eq!(stats.employees_counted, 4);
+        assert_eq!(stats.employees_without_hire_date, 1);
+
+        // Sorted tenures ≈ 0.5, 2.0, 4.0, 10.0 → median is the mean of 2.0 and 4.0
+        let median = stats.median_tenure_years.unwrap();
+        assert!((median - 3.0).abs() < 0.01, "median was {}", median);
+
+        let counts: Vec<(&str, i64)> = stats.bands.iter().map(|b| (b.label.as_str(), b.count)).collect();
+        assert_eq!(
+            counts,
+            vec![("< 1 year", 1), ("1-3 years", 1), ("3-5 years", 1), ("5+ years", 1)]
+        );
+
+        assert_eq!(stats.longest_tenured[0].id, "d");
+        assert!(stats.longest_tenured.iter().all(|t| t.id != "e" && t.id != "f"));
+    }
+
+    #[tokio::test]
+    async fn test_tenure_stats_empty() {
+        let pool = crate::db::test_pool().await;
+        let stats = compute_tenure_stats_as_of(&pool, None, date("2025-01-01")).await.unwrap();
+        assert_eq!(stats.employees_counted, 0);
+        assert!(stats.avg_tenure_years.is_none());
+        assert!(stats.median_tenure_years.is_none());
+        assert_eq!(stats.bands.len(), 4);
+    }
+}
@@ -0,0 +1,175 @@
+// HR Command Center - XLSX Export Module
+// Writes a single-sheet workbook of inline-string cells. Enough for roster
+// exports that round-trip through the importer; no styles or formulas.
+
+use std::io::Write;
+
+use flate2::write::DeflateEncoder;
+use flate2::Compression;
+
+const CONTENT_TYPES: &str = "<?xml version=\"1.0\" encoding=\"UTF-8\" standalone=\"yes\"?><Types xmlns=\"http://schemas.openxmlformats.org/package/2006/content-types\"><Default Extension=\"rels\" ContentType=\"application/vnd.openxmlformats-package.relationships+xml\"/><Default Extension=\"xml\" ContentType=\"application/xml\"/><Override PartName=\"/xl/workbook.xml\" ContentType=\"application/vnd.openxmlformats-officedocument.spreadsheetml.sheet.main+xml\"/><Override PartName=\"/xl/worksheets/sheet1.xml\" ContentType=\"application/vnd.openxmlformats-officedocument.spreadsheetml.worksheet+xml\"/></Types>";
+const ROOT_RELS: &str = "<?xml version=\"1.0\" encoding=\"UTF-8\" standalone=\"yes\"?><Relationships xmlns=\"http://schemas.openxmlformats.org/package/2006/relationships\"><Relationship Id=\"rId1\" Type=\"http://schemas.openxmlformats.org/officeDocument/2006/relationships/officeDocument\" Target=\"xl/workbook.xml\"/></Relationships>";
+const WORKBOOK_RELS: &str = "<?xml version=\"1.0\" encoding=\"UTF-8\" standalone=\"yes\"?><Relationships xmlns=\"http://schemas.openxmlformats.org/package/2006/relationships\"><Relationship Id=\"rId1\" Type=\"http://schemas.openxmlformats.org/officeDocument/2006/relationships/worksheet\" Target=\"worksheets/sheet1.xml\"/></Relationships>";
+
+/// Build an XLSX workbook with one sheet. The first row is usually the
+/// header. Empty strings become empty (absent) cells.
+pub fn build_workbook<S: AsRef<str>>(sheet_name: &str, rows: &[Vec<S>]) -> Vec<u8> {
+    let workbook = format!(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\" standalone=\"yes\"?><workbook xmlns=\"http://schemas.openxmlformats.org/spreadsheetml/2006/main\" xmlns:r=\"http://schemas.openxmlformats.org/officeDocument/2006/relationships\"><sheets><sheet name=\"{}\" sheetId=\"1\" r:id=\"rId1\"/></sheets></workbook>",
+        escape_xml(sheet_name)
+    );
+
+    let files: [(&str, &[u8]); 5] = [
+        ("[Content_Types].xml", CONTENT_TYPES.as_bytes()),
+        ("_rels/.rels", ROOT_RELS.as_bytes()),
+        ("xl/workbook.xml", workbook.as_bytes()),
+        ("xl/_rels/workbook.xml.rels", WORKBOOK_RELS.as_bytes()),
+        ("xl/worksheets/sheet1.xml", &sheet_xml(rows).into_bytes()),
+    ];
+    write_zip(&files)
+}
+
+fn sheet_xml<S: AsRef<str>>(rows: &[Vec<S>]) -> String {
+    let width = rows.iter().map(|r| r.len()).max().unwrap_or(0);
+    let mut xml = String::from(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\" standalone=\"yes\"?><worksheet xmlns=\"http://schemas.openxmlformats.org/spreadsheetml/2006/main\">",
+    );
+    if width > 0 {
+        xml.push_str(&format!("<dimension ref=\"A1:{}{}\"/>", column_name(width - 1), rows.len()));
+    }
+    xml.push_str("<sheetData>");
+    for (r, row) in rows.iter().enumerate() {
+        xml.push_str(&format!("<row r=\"{}\">", r + 1));
+        for (c, value) in row.iter().enumerate() {
+            let value = value.as_ref();
+            if value.is_empty() {
+                continue;
+            }
+            xml.push_str(&format!(
+                "<c r=\"{}{}\" t=\"inlineStr\"><is><t xml:space=\"preserve\">{}</t></is></c>",
+                column_name(c),
+                r + 1,
+                escape_xml(value)
+            ));
+        }
+        xml.push_str("</row>");
+    }
+    xml.push_str("</sheetData></worksheet>");
+    xml
+}
+
+/// Zero-based column index to a spreadsheet letter: 0 -> A, 26 -> AA
+fn column_name(mut index: usize) -> String {
+    let mut name = Vec::new();
+    loop {
+        name.push(b'A' + (index % 26) as u8);
+        if index < 26 {
+            break;
+        }
+        index = index / 26 - 1;
+    }
+    name.reverse();
+    String::from_utf8(name).unwrap_or_default()
+}
+
+/// Escape markup characters and drop control characters XML 1.0 forbids
+fn escape_xml(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\t' | '\n' | '\r' => out.push(c),
+            c if c.is_control() => {}
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+/// Minimal ZIP archive with deflated entries and no timestamps
+fn write_zip(files: &[(&str, &[u8])]) -> Vec<u8> {
+    let mut zip = Vec::new();
+    let mut central = Vec::new();
+    for (name, content) in files {
+        let mut crc = flate2::Crc::new();
+        crc.update(content);
+        let crc = crc.sum();
+
+        let mut encoder = DeflateEncoder::new(Vec::new(), Compression::default());
+        // Writing to a Vec can't fail
+        let compressed = encoder
+            .write_all(content)
+            .and_then(|_| encoder.finish())
+            .unwrap_or_default();
+
+        let offset = zip.len() as u32;
+        let sizes = [compressed.len() as u32, content.len() as u32];
+        let name_len = (name.len() as u16).to_le_bytes();
+
+        // Local file header: version 2.0, no flags, deflate, zeroed time/date
+        zip.extend_from_slice(&[0x50, 0x4b, 0x03, 0x04, 20, 0, 0, 0, 8, 0, 0, 0, 0, 0]);
+        zip.extend_from_slice(&crc.to_le_bytes());
+        zip.extend_from_slice(&sizes[0].to_le_bytes());
+        zip.extend_from_slice(&sizes[1].to_le_bytes());
+        zip.extend_from_slice(&name_len);
+        zip.extend_from_slice(&[0, 0]);
+        zip.extend_from_slice(name.as_bytes());
+        zip.extend_from_slice(&compressed);
+
+        central.extend_from_slice(&[0x50, 0x4b, 0x01, 0x02, 20, 0, 20, 0, 0, 0, 8, 0, 0, 0, 0, 0]);
+        central.extend_from_slice(&crc.to_le_bytes());
+        central.extend_from_slice(&sizes[0].to_le_bytes());
+        central.extend_from_slice(&sizes[1].to_le_bytes());
+        central.extend_from_slice(&name_len);
+        // Extra and comment lengths, disk number, attributes
+        central.extend_from_slice(&[0u8; 12]);
+        central.extend_from_slice(&offset.to_le_bytes());
+        central.extend_from_slice(name.as_bytes());
+    }
+
+    let central_offset = zip.len() as u32;
+    let count = (files.len() as u16).to_le_bytes();
+    zip.extend_from_slice(&central);
+    zip.extend_from_slice(&[0x50, 0x4b, 0x05, 0x06, 0, 0, 0, 0]);
+    zip.extend_from_slice(&count);
+    zip.extend_from_slice(&count);
+    zip.extend_from_slice(&(central.len() as u32).to_le_bytes());
+    zip.extend_from_slice(&central_offset.to_le_bytes());
+    zip.extend_from_slice(&[0, 0]);
+    zip
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_column_name() {
+        assert_eq!(column_name(0), "A");
+        assert_eq!(column_name(25), "Z");
+        assert_eq!(column_name(26), "AA");
+        assert_eq!(column_name(27), "AB");
+        assert_eq!(column_name(701), "ZZ");
+        assert_eq!(column_name(702), "AAA");
+    }
+
+    #[test]
+    fn test_workbook_parses_back() {
+        let rows = vec![
+            vec!["name", "note"],
+            vec!["R&D <team>", ""],
+            vec!["  padded  ", "quote \" here"],
+        ];
+        let data = build_workbook("Sheet & Co", &rows);
+        let parsed = crate::file_parser::parse_file(&data, "book.xlsx", &Default::default()).unwrap();
+
+        assert_eq!(parsed.headers, vec!["name", "note"]);
+        assert_eq!(parsed.rows.len(), 2);
+        assert_eq!(parsed.rows[0]["name"], "R&D <team>");
+        assert_eq!(parsed.rows[0].get("note").map(String::as_str).unwrap_or(""), "");
+        assert_eq!(parsed.rows[1]["note"], "quote \" here");
+    }
+}
@@ -61,6 +61,11 @@ export function ErrorMessage({
 
         {/* Error details */}
         <p className="text-sm text-red-700 mb-3">{error.details}</p>
+        {error.requestId && (
+          <p className="text-xs text-red-600 mb-3 font-mono select-all">
+            Request ID: {error.requestId}
+          </p>
+        )}
 
         {/* Action buttons and timestamp */}
         <div className="flex items-center justify-between gap-3">
@@ -16,6 +16,7 @@ const EMPLOYEE_FIELD_LABELS: Record<string, string> = {
   email: 'Email',
   first_name: 'First Name',
   last_name: 'Last Name',
+  full_name: 'Full Name',
   department: 'Department',
   title: 'Job Title',
   hire_date: 'Hire Date',
@@ -191,7 +192,8 @@ function transformRowToEmployee(
   // Build full_name from first + last, or use as-is if full_name column exists
   const firstName = getValue('first_name') || '';
   const lastName = getValue('last_name') || '';
-  const fullName = [firstName, lastName].filter(Boolean).join(' ') || email.split('@')[0];
+  const fullName =
+    [firstName, lastName].filter(Boolean).join(' ') || getValue('full_name') || email.split('@')[0];
 
   // Normalize status: accept various formats
   const rawStatus = getValue('status')?.toLowerCase();
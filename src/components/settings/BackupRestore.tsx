@@ -84,7 +84,12 @@ export function BackupRestore({ onImportComplete }: BackupRestoreProps) {
       // Clear success message after 5 seconds
       setTimeout(() => setSuccess(null), 5000);
     } catch (err) {
-      setError(err instanceof Error ? err.message : 'Export failed');
+      const weak = (err as { WeakPassword?: { feedback: string } })?.WeakPassword;
+      if (weak) {
+        setError(`Password is too weak. ${weak.feedback}`);
+      } else {
+        setError(err instanceof Error ? err.message : 'Export failed');
+      }
     } finally {
       setStatus('idle');
     }
@@ -30,11 +30,12 @@ import {
   generateConversationSummary,
   saveConversationSummary,
   scanPii,
-  createAuditEntry,
   executeAnalytics,
   type ConversationListItem,
   type ChatMessage,
   type StreamChunk,
+  type ChatComplete,
+  type StreamStatus,
 } from '../lib/tauri-commands';
 
 // =============================================================================
@@ -307,10 +308,30 @@ export function ConversationProvider({ children }: ConversationProviderProps) {
     };
     setMessages((prev) => [...prev, assistantMessage]);
 
-    // Set up stream event listener
+    // Set up stream event listeners
     let unlisten: UnlistenFn | null = null;
+    let unlistenComplete: UnlistenFn | null = null;
+    let unlistenCancelled: UnlistenFn | null = null;
 
     try {
+      // Turn metadata arrives after the final chunk. The backend emits it
+      // before the command returns, so it lands before the loading -> idle
+      // transition below triggers the auto-save.
+      unlistenComplete = await listen<ChatComplete>('chat-complete', (event) => {
+        const { turn } = event.payload;
+        setMessages((prev) =>
+          prev.map((msg) =>
+            msg.id === assistantId ? { ...msg, ...turn } : msg
+          )
+        );
+      });
+
+      // A cancelled stream never completes; stop the spinner right away
+      unlistenCancelled = await listen<StreamStatus>('chat-stream-cancelled', () => {
+        streamingMessageId.current = null;
+        setIsLoading(false);
+      });
+
       unlisten = await listen<StreamChunk>('chat-stream', (event) => {
         const { chunk, done, verification } = event.payload;
 
@@ -363,16 +384,7 @@ export function ConversationProvider({ children }: ConversationProviderProps) {
               });
           }
 
-          // Create audit entry (fire-and-forget, don't block on errors)
-          createAuditEntry({
-            conversation_id: conversationId,
-            request_redacted: redactedMessageRef.current ?? '',
-            response_text: fullResponse,
-            employee_ids_used: employeeIdsRef.current,
-          }).catch((err) => {
-            // Log but don't fail - audit is non-critical
-            console.error('[Audit] Failed to create entry:', err);
-          });
+          // Audit entry is written by the backend from the assembled stream
 
           // Reset refs for next message
           redactedMessageRef.current = null;
@@ -380,7 +392,6 @@ export function ConversationProvider({ children }: ConversationProviderProps) {
           accumulatedResponseRef.current = '';
 
           streamingMessageId.current = null;
-          setIsLoading(false);
         } else {
           // Accumulate response for audit logging
           accumulatedResponseRef.current += chunk;
@@ -424,8 +435,17 @@ export function ConversationProvider({ children }: ConversationProviderProps) {
         apiMessages,
         promptResult.system_prompt,
         promptResult.aggregates,
-        promptResult.query_type
+        promptResult.query_type,
+        {
+          conversation_id: conversationId,
+          request_redacted: redactedMessageRef.current ?? '',
+          employee_ids_used: employeeIdsRef.current,
+          persona_id: promptResult.persona_id,
+        }
       );
+      // Also covers streams that end without completing (cancelled, or
+      // closed before message_stop)
+      setIsLoading(false);
     } catch (error) {
       // Categorize error for user-friendly display
       const chatError = categorizeError(error);
@@ -448,6 +468,12 @@ export function ConversationProvider({ children }: ConversationProviderProps) {
       if (unlisten) {
         unlisten();
       }
+      if (unlistenComplete) {
+        unlistenComplete();
+      }
+      if (unlistenCancelled) {
+        unlistenCancelled();
+      }
     }
   }, []);
 
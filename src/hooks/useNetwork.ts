@@ -54,6 +54,7 @@ export function useNetwork(options: UseNetworkOptions = {}): UseNetworkResult {
     is_online: navigator.onLine, // Start with browser's best guess
     api_reachable: navigator.onLine, // Assume reachable if browser says online
     error_message: null,
+    retried: false,
   });
   const [isChecking, setIsChecking] = useState(false);
   const [lastChecked, setLastChecked] = useState<Date | null>(null);
@@ -79,6 +80,7 @@ export function useNetwork(options: UseNetworkOptions = {}): UseNetworkResult {
           is_online: false,
           api_reachable: false,
           error_message: 'Failed to check network status',
+          retried: false,
         });
       }
     } finally {
@@ -106,6 +108,7 @@ export function useNetwork(options: UseNetworkOptions = {}): UseNetworkResult {
       is_online: false,
       api_reachable: false,
       error_message: 'Network connection lost',
+      retried: false,
     });
   }, []);
 
@@ -33,6 +33,13 @@ const ERROR_PATTERNS: ErrorPattern[] = [
     details: 'Too many requests. Please wait a moment and try again.',
     retryable: true,
   },
+  {
+    pattern: /Message is too long/i,
+    type: 'message_too_long',
+    message: 'Message Too Long',
+    details: 'This message is over the size limit. Split it into smaller parts and send them one at a time.',
+    retryable: false,
+  },
   {
     pattern: /API request failed|connection|timeout|network|unable to connect/i,
     type: 'network_error',
@@ -55,6 +62,7 @@ const ERROR_PATTERNS: ErrorPattern[] = [
  */
 export function categorizeError(error: unknown): ChatError {
   const errorStr = error instanceof Error ? error.message : String(error);
+  const requestId = errorStr.match(/request id: ([\w-]+)/)?.[1];
 
   for (const pattern of ERROR_PATTERNS) {
     const matches =
@@ -68,6 +76,7 @@ export function categorizeError(error: unknown): ChatError {
         message: pattern.message,
         details: pattern.details,
         retryable: pattern.retryable,
+        ...(requestId && { requestId }),
       };
     }
   }
@@ -78,5 +87,6 @@ export function categorizeError(error: unknown): ChatError {
     message: 'Something Went Wrong',
     details: 'An unexpected error occurred. Please try again.',
     retryable: true,
+    ...(requestId && { requestId }),
   };
 }
@@ -5,16 +5,24 @@ import { invoke } from '@tauri-apps/api/core';
 import type {
   Employee,
   ReviewCycle,
+  CycleEvent,
+  CycleReminder,
   PerformanceRating,
+  RatingHistoryPoint,
   PerformanceReview,
+  ReviewTemplate,
+  TemplateSection,
   EnpsResponse,
   ParseResult,
   ParsePreview,
+  ParsedRow,
   ColumnMapping,
   Company,
   UpsertCompany,
   EmployeeStatesSummary,
   VerificationResult,
+  VerificationStatus,
+  ClaimType,
   OrgAggregates,
   QueryType,
   SystemPromptResult,
@@ -22,6 +30,9 @@ import type {
   ReviewHighlight,
   EmployeeSummary,
   BatchExtractionResult,
+  ExtractionEstimate,
+  BatchSummaryResult,
+  TenureStats,
 } from './types';
 
 /**
@@ -57,6 +68,37 @@ export async function deleteApiKey(): Promise<void> {
   return invoke('delete_api_key');
 }
 
+/**
+ * Store the Voyage AI key used for semantic memory search
+ * Keys start with "pa-"; without one, memory search matches keywords
+ */
+export async function storeEmbeddingsApiKey(apiKey: string): Promise<void> {
+  return invoke('store_embeddings_api_key', { apiKey });
+}
+
+export async function hasEmbeddingsApiKey(): Promise<boolean> {
+  return invoke('has_embeddings_api_key');
+}
+
+export async function deleteEmbeddingsApiKey(): Promise<void> {
+  return invoke('delete_embeddings_api_key');
+}
+
+/**
+ * Store the HTTP proxy password (kept out of the settings table and backups)
+ */
+export async function storeProxyPassword(password: string): Promise<void> {
+  return invoke('store_proxy_password', { password });
+}
+
+export async function hasProxyPassword(): Promise<boolean> {
+  return invoke('has_proxy_password');
+}
+
+export async function deleteProxyPassword(): Promise<void> {
+  return invoke('delete_proxy_password');
+}
+
 /**
  * Validate API key format without storing it
  * Returns true if the key has the correct prefix and length
@@ -87,36 +129,91 @@ export interface ChatResponse {
  */
 export async function sendChatMessage(
   messages: ChatMessage[],
-  systemPrompt?: string
+  systemPrompt?: string,
+  model?: string | null
 ): Promise<ChatResponse> {
   return invoke('send_chat_message', {
     messages,
-    systemPrompt: systemPrompt ?? null
+    systemPrompt: systemPrompt ?? null,
+    model: model ?? null
   });
 }
 
+/** Audit details for a streamed exchange (the response is assembled server-side) */
+export interface StreamAuditInput {
+  conversation_id: string | null;
+  request_redacted: string;
+  employee_ids_used: string[];
+  /** Persona used for the system prompt (SystemPromptResult.persona_id) */
+  persona_id?: string | null;
+}
+
 /**
  * Send messages to Claude with streaming response
- * Listen for "chat-stream" events for response chunks
+ * Listen for "chat-stream" events for response chunks, "chat-correction"
+ * for mismatched numbers in aggregate answers, "chat-stream-usage" with the
+ * token counts, and "chat-complete" once the backend has verified and
+ * audited the full response. "chat-stream-started"
+ * carries the stream id for cancelChatStream; a cancelled stream ends with
+ * "chat-stream-cancelled" instead of "chat-complete".
  * @param messages Array of conversation messages
  * @param systemPrompt Optional system prompt for context
  * @param aggregates V2.1.4: Optional org aggregates for answer verification
  * @param queryType V2.1.4: Optional query type for answer verification
+ * @param audit Redacted request and context used, for the audit entry
+ * @param model Model id from listAvailableModels; omit for the default
  */
 export async function sendChatMessageStreaming(
   messages: ChatMessage[],
-  systemPrompt?: string,
-  aggregates?: OrgAggregates | null,
-  queryType?: QueryType | null
-): Promise<void> {
+  systemPrompt: string | undefined,
+  aggregates: OrgAggregates | null | undefined,
+  queryType: QueryType | null | undefined,
+  audit: StreamAuditInput,
+  model?: string | null
+): Promise<string> {
   return invoke('send_chat_message_streaming', {
     messages,
     systemPrompt: systemPrompt ?? null,
     aggregates: aggregates ?? null,
     queryType: queryType ?? null,
+    audit,
+    model: model ?? null,
   });
 }
 
+/** A Claude model the user can pick for a conversation */
+export interface ModelInfo {
+  id: string;
+  display_name: string;
+  /** Maximum input tokens */
+  context_window: number;
+  is_default: boolean;
+}
+
+/** Models available for chat, default first */
+export async function listAvailableModels(): Promise<ModelInfo[]> {
+  return invoke('list_available_models');
+}
+
+/** Payload of the "chat-stream-usage" event */
+export interface TokenUsage {
+  input_tokens: number;
+  output_tokens: number;
+}
+
+/** Payload of "chat-stream-started" and "chat-stream-cancelled" events */
+export interface StreamStatus {
+  stream_id: string;
+}
+
+/**
+ * Stop a streamed response and close its connection
+ * @returns false if the stream had already finished
+ */
+export async function cancelChatStream(streamId: string): Promise<boolean> {
+  return invoke('cancel_chat_stream', { streamId });
+}
+
 /** Event payload for streaming chunks */
 export interface StreamChunk {
   chunk: string;
@@ -125,6 +222,38 @@ export interface StreamChunk {
   verification?: VerificationResult;
 }
 
+/** A mismatched aggregate claim with the value from the user's data */
+export interface ClaimCorrection {
+  claim_type: ClaimType;
+  claimed: number;
+  actual: number;
+  /** Ready-to-display text, e.g. "Claude said 95 employees; your data shows 100 employees." */
+  message: string;
+}
+
+/** Event payload for "chat-correction", emitted after the final chunk */
+export interface ChatCorrection {
+  corrections: ClaimCorrection[];
+}
+
+/** Event payload for "chat-complete", emitted after the response is audited */
+export interface ChatComplete {
+  /** null if the audit write failed */
+  audit_entry_id: string | null;
+  verification: VerificationResult | null;
+  /** Model, persona, and token usage to store on the assistant message */
+  turn: TurnMetadata;
+}
+
+/** Per-turn metadata; fields are omitted when unknown */
+export interface TurnMetadata {
+  model?: string;
+  persona_id?: string;
+  input_tokens?: number;
+  output_tokens?: number;
+  verification_status?: VerificationStatus;
+}
+
 // =============================================================================
 // Phase 1.5 - Network Detection
 // =============================================================================
@@ -139,6 +268,8 @@ export interface NetworkStatus {
   api_reachable: boolean;
   /** Optional error message if offline */
   error_message: string | null;
+  /** Whether the first attempt failed and the check was retried */
+  retried: boolean;
 }
 
 /**
@@ -157,6 +288,46 @@ export async function isOnline(): Promise<boolean> {
   return invoke('is_online');
 }
 
+/** Result of a successful proxy connection test */
+export interface ProxyCheck {
+  /** Proxy URL without credentials */
+  proxy_url: string;
+  /** Whether the proxy came from settings or the HTTPS_PROXY variable */
+  source: 'settings' | 'environment';
+  /** Whether a username was sent to the proxy */
+  authenticated: boolean;
+  latency_ms: number;
+}
+
+/**
+ * Check that the Anthropic API is reachable through the configured proxy
+ * (proxy_url / proxy_username settings plus the stored proxy password, or
+ * HTTPS_PROXY).
+ * Rejects with a readable message when there is no proxy or auth fails.
+ */
+export async function testProxyConnection(): Promise<ProxyCheck> {
+  return invoke('test_proxy_connection');
+}
+
+// =============================================================================
+// Read-Only (Demo) Mode
+// =============================================================================
+
+/**
+ * Whether read-only mode is on
+ */
+export async function getReadOnlyMode(): Promise<boolean> {
+  return invoke('get_read_only_mode');
+}
+
+/**
+ * Turn read-only mode on or off (resets to off on restart).
+ * While on, HR data writes and imports fail with a ReadOnly error.
+ */
+export async function setReadOnlyMode(enabled: boolean): Promise<void> {
+  return invoke('set_read_only_mode', { enabled });
+}
+
 // =============================================================================
 // Phase 2.2 - Company Profile
 // =============================================================================
@@ -170,16 +341,23 @@ export async function hasCompany(): Promise<boolean> {
 }
 
 /**
- * Get the company profile
+ * Get a company profile (the primary one when no ID is given)
  * @throws Error if company profile doesn't exist
  */
-export async function getCompany(): Promise<Company> {
-  return invoke('get_company');
+export async function getCompany(companyId?: string): Promise<Company> {
+  return invoke('get_company', { companyId });
+}
+
+/**
+ * List all company profiles (legal entities), primary first
+ */
+export async function listCompanies(): Promise<Company[]> {
+  return invoke('list_companies');
 }
 
 /**
- * Create or update the company profile (upsert)
- * @param input Company name, state (2-letter code), and optional industry
+ * Create or update a company profile (upsert)
+ * @param input Optional ID, company name, state (2-letter code), and optional industry
  */
 export async function upsertCompany(input: UpsertCompany): Promise<Company> {
   return invoke('upsert_company', { input });
@@ -216,6 +394,7 @@ export interface CreateEmployeeInput {
   termination_date?: string;
   termination_reason?: string;
   extra_fields?: string;
+  company_id?: string;
 }
 
 /**
@@ -236,6 +415,7 @@ export interface UpdateEmployeeInput {
   termination_date?: string;
   termination_reason?: string;
   extra_fields?: string;
+  company_id?: string;
 }
 
 /**
@@ -246,6 +426,8 @@ export interface EmployeeFilter {
   department?: string;
   work_state?: string;
   search?: string;
+  /** Only employees of this company; all companies when omitted */
+  company_id?: string;
 }
 
 /**
@@ -314,6 +496,34 @@ export async function listEmployees(
   return invoke('list_employees', { filter, limit, offset });
 }
 
+/** Result of a roster export; data is the file's bytes */
+export interface EmployeeExport {
+  data: number[];
+  row_count: number;
+  /** Matching employees left out because they're under legal hold */
+  legal_hold_excluded: number;
+  filename: string;
+}
+
+/** Options for a roster export */
+export interface EmployeeExportOptions {
+  /** Include employees under legal hold (default: false) */
+  include_legal_hold?: boolean;
+}
+
+/**
+ * Export the roster as CSV or XLSX with the same columns the employee
+ * import maps, so the file can be edited and imported again. Demographic
+ * columns are only included when diversity reporting is enabled.
+ */
+export async function exportEmployees(
+  filter: EmployeeFilter = {},
+  format: 'csv' | 'xlsx' = 'csv',
+  options?: EmployeeExportOptions
+): Promise<EmployeeExport> {
+  return invoke('export_employees', { filter, format, options });
+}
+
 /**
  * Get all unique departments
  */
@@ -351,6 +561,9 @@ export interface CreateReviewCycleInput {
   start_date: string;
   end_date: string;
   status?: 'active' | 'closed';
+  /** Allow overlapping an active cycle of the same type */
+  force?: boolean;
+  template_id?: string;
 }
 
 /**
@@ -362,6 +575,10 @@ export interface UpdateReviewCycleInput {
   start_date?: string;
   end_date?: string;
   status?: 'active' | 'closed';
+  /** Allow overlapping an active cycle of the same type */
+  force?: boolean;
+  /** Attach a review template; omit to keep the current one */
+  template_id?: string;
 }
 
 /**
@@ -413,6 +630,63 @@ export async function closeReviewCycle(id: string): Promise<ReviewCycle> {
   return invoke('close_review_cycle', { id });
 }
 
+/**
+ * Close every active cycle whose end date has passed.
+ * Returns the ids of the cycles that were closed.
+ */
+export async function closeExpiredCycles(): Promise<string[]> {
+  return invoke('close_expired_cycles');
+}
+
+/**
+ * Status transitions for a review cycle, oldest first
+ */
+export async function getCycleHistory(cycleId: string): Promise<CycleEvent[]> {
+  return invoke('get_cycle_history', { cycleId });
+}
+
+/**
+ * Active review cycles that open or close within 7 days, are past their
+ * end date, or have misconfigured dates. Most urgent first.
+ */
+export async function getCycleReminders(): Promise<CycleReminder[]> {
+  return invoke('get_cycle_reminders');
+}
+
+// =============================================================================
+// Review Templates
+// =============================================================================
+
+/**
+ * Input for creating a review template
+ */
+export interface CreateReviewTemplateInput {
+  name: string;
+  description?: string;
+  sections: TemplateSection[];
+}
+
+/**
+ * Create a review template (names are unique)
+ */
+export async function createReviewTemplate(input: CreateReviewTemplateInput): Promise<ReviewTemplate> {
+  return invoke('create_review_template', { input });
+}
+
+/**
+ * List review templates by name
+ */
+export async function listReviewTemplates(): Promise<ReviewTemplate[]> {
+  return invoke('list_review_templates');
+}
+
+/**
+ * Get the template attached to a review cycle, if any
+ */
+export async function getTemplateForCycle(reviewCycleId: string): Promise<ReviewTemplate | null> {
+  return invoke('get_template_for_cycle', { reviewCycleId });
+}
+
 // =============================================================================
 // Phase 2.1 - Performance Ratings
 // =============================================================================
@@ -474,6 +748,13 @@ export async function getRatingsForEmployee(employeeId: string): Promise<Perform
   return invoke('get_ratings_for_employee', { employeeId });
 }
 
+/**
+ * Get an employee's ratings per cycle, oldest first
+ */
+export async function getRatingHistory(employeeId: string): Promise<RatingHistoryPoint[]> {
+  return invoke('get_rating_history', { employeeId });
+}
+
 /**
  * Get all ratings for a review cycle
  */
@@ -516,6 +797,28 @@ export async function getAverageRating(reviewCycleId: string): Promise<number |
   return invoke('get_average_rating', { reviewCycleId });
 }
 
+export type NormalizationFlag = 'no_reviewer' | 'too_few_ratings' | 'zero_variance';
+
+export interface NormalizedRating {
+  rating_id: string;
+  employee_id: string;
+  reviewer_id: string | null;
+  raw_rating: number;
+  /** Standard deviations from the reviewer's mean; null when not normalized */
+  z_score: number | null;
+  reviewer_mean: number | null;
+  reviewer_std_dev: number | null;
+  flag: NormalizationFlag | null;
+}
+
+/**
+ * Get a cycle's ratings with z-scores against each reviewer's own mean and spread
+ * Reviewers with fewer than 3 ratings are flagged and left un-normalized
+ */
+export async function normalizeCycleRatings(reviewCycleId: string): Promise<NormalizedRating[]> {
+  return invoke('normalize_cycle_ratings', { reviewCycleId });
+}
+
 // =============================================================================
 // Phase 2.1 - Performance Reviews
 // =============================================================================
@@ -624,6 +927,21 @@ export interface EnpsAggregate {
   response_rate: number;
 }
 
+export type CompareMetric = 'latest_rating' | 'avg_rating' | 'latest_enps';
+
+/**
+ * One employee in a side-by-side comparison, highest value first
+ */
+export interface CompareRow {
+  employee_id: string;
+  full_name: string;
+  department: string | null;
+  value: number | null;
+  /** 1-based; tied values share a rank. Null when the metric is missing */
+  rank: number | null;
+  missing: boolean;
+}
+
 /**
  * Full chat context for building system prompt
  */
@@ -639,14 +957,17 @@ export interface ChatContext {
  * Extracts mentions, finds relevant employees, and gathers company data
  * @param userMessage - The user's message to analyze
  * @param selectedEmployeeId - Optional employee ID to prioritize (always included first)
+ * @param companyId - Optional company ID to scope the company profile and aggregates
  */
 export async function buildChatContext(
   userMessage: string,
-  selectedEmployeeId?: string | null
+  selectedEmployeeId?: string | null,
+  companyId?: string | null
 ): Promise<ChatContext> {
   return invoke('build_chat_context', {
     userMessage,
-    selectedEmployeeId: selectedEmployeeId ?? null
+    selectedEmployeeId: selectedEmployeeId ?? null,
+    companyId: companyId ?? null
   });
 }
 
@@ -655,15 +976,18 @@ export async function buildChatContext(
  * V2.1.4: Now returns SystemPromptResult with aggregates and query_type for verification
  * @param userMessage - The user's message to analyze
  * @param selectedEmployeeId - Optional employee ID to prioritize (always included first)
+ * @param companyId - Optional company ID to scope the company profile and aggregates
  * @returns SystemPromptResult containing prompt, employee IDs, aggregates, and query type
  */
 export async function getSystemPrompt(
   userMessage: string,
-  selectedEmployeeId?: string | null
+  selectedEmployeeId?: string | null,
+  companyId?: string | null
 ): Promise<SystemPromptResult> {
   return invoke('get_system_prompt', {
     userMessage,
-    selectedEmployeeId: selectedEmployeeId ?? null
+    selectedEmployeeId: selectedEmployeeId ?? null,
+    companyId: companyId ?? null
   });
 }
 
@@ -679,16 +1003,26 @@ export async function getEmployeeContext(employeeId: string): Promise<EmployeeCo
 /**
  * Get company context (name, state, employee/department counts)
  */
-export async function getCompanyContext(): Promise<CompanyContext | null> {
-  return invoke('get_company_context');
+export async function getCompanyContext(companyId?: string): Promise<CompanyContext | null> {
+  return invoke('get_company_context', { companyId });
 }
 
 /**
- * Get aggregate eNPS score for the organization
+ * Get aggregate eNPS score for the organization, or one company
  * Calculates promoters, passives, detractors, and overall score
  */
-export async function getAggregateEnps(): Promise<EnpsAggregate> {
-  return invoke('get_aggregate_enps');
+export async function getAggregateEnps(companyId?: string): Promise<EnpsAggregate> {
+  return invoke('get_aggregate_enps', { companyId });
+}
+
+/**
+ * Rank employees side by side; employees missing the metric come last
+ */
+export async function compareEmployees(
+  employeeIds: string[],
+  metric: CompareMetric
+): Promise<CompareRow[]> {
+  return invoke('compare_employees', { employeeIds, metric });
 }
 
 // =============================================================================
@@ -719,7 +1053,9 @@ export async function getSetting(key: string): Promise<string | null> {
 }
 
 /**
- * Set a setting value (creates or updates)
+ * Set a setting value (creates or updates). Known keys are validated
+ * (booleans, integer ranges, persona ids...) and an invalid value is
+ * rejected; unknown keys are stored as-is.
  * @param key - The setting key
  * @param value - The value to store
  */
@@ -742,6 +1078,29 @@ export async function hasSetting(key: string): Promise<boolean> {
   return invoke('has_setting', { key });
 }
 
+/** Result of applying an exported config */
+export interface ConfigImportResult {
+  imported: number;
+  /** Skipped keys, with the reason */
+  warnings: string[];
+}
+
+/**
+ * Export portable settings (persona, display name, preferences) as JSON.
+ * Contains no employee data.
+ */
+export async function exportConfig(): Promise<string> {
+  return invoke('export_config');
+}
+
+/**
+ * Apply a config exported from another device.
+ * Unknown or device-specific keys are skipped and reported as warnings.
+ */
+export async function importConfig(json: string): Promise<ConfigImportResult> {
+  return invoke('import_config', { json });
+}
+
 // =============================================================================
 // V2.1.3 - Personas
 // =============================================================================
@@ -805,9 +1164,19 @@ export interface DigestData {
 /**
  * Get Monday Digest data (anniversaries and new hires)
  * Returns employees with anniversaries within 7 days and new hires within 90 days
+ * @param milestonesOnly - Only list anniversaries in the milestone_years setting
+ * @param companyId - Only include this company's employees
+ */
+export async function getDigestData(milestonesOnly?: boolean, companyId?: string): Promise<DigestData> {
+  return invoke('get_digest_data', { milestonesOnly, companyId });
+}
+
+/**
+ * Get tenure statistics for active employees (average, median, bands,
+ * longest-tenured). Employees without a hire date are excluded.
  */
-export async function getDigestData(): Promise<DigestData> {
-  return invoke('get_digest_data');
+export async function getTenureStats(companyId?: string): Promise<TenureStats> {
+  return invoke('get_tenure_stats', { companyId });
 }
 
 // =============================================================================
@@ -856,6 +1225,20 @@ export async function searchMemories(
   return invoke('search_memories', { query, limit });
 }
 
+/** Result of reindexMemoryEmbeddings */
+export interface ReindexResult {
+  embedded: number;
+  /** Summaries still without an embedding */
+  remaining: number;
+}
+
+/**
+ * Embed conversation summaries saved before an embeddings key was configured
+ */
+export async function reindexMemoryEmbeddings(): Promise<ReindexResult> {
+  return invoke('reindex_memory_embeddings');
+}
+
 // =============================================================================
 // Phase 2.5 - Conversation Management
 // =============================================================================
@@ -870,6 +1253,15 @@ export interface ConversationRecord {
   messages_json: string;
   created_at: string;
   updated_at: string;
+  /** JSON array of tag strings */
+  tags: string;
+  archived: boolean;
+  /** Listed above unpinned conversations (unless archived) */
+  pinned: boolean;
+  /** Never surfaced by memory retrieval */
+  exclude_from_memory: boolean;
+  /** Model chosen for this conversation; null uses the default */
+  model: string | null;
 }
 
 /**
@@ -883,6 +1275,12 @@ export interface ConversationListItem {
   first_message_preview: string | null;
   created_at: string;
   updated_at: string;
+  /** JSON array of tag strings */
+  tags: string;
+  /** Listed above unpinned conversations */
+  pinned: boolean;
+  /** Only returned when archived conversations are requested */
+  archived: boolean;
 }
 
 /**
@@ -901,6 +1299,9 @@ export interface UpdateConversationInput {
   title?: string;
   messages_json?: string;
   summary?: string;
+  exclude_from_memory?: boolean;
+  /** Model id from listAvailableModels; empty string resets to the default */
+  model?: string;
 }
 
 /**
@@ -936,15 +1337,17 @@ export async function updateConversation(
 
 /**
  * List conversations for sidebar display
- * Returns lightweight items sorted by updated_at (most recent first)
+ * Returns lightweight items, pinned first, then by updated_at (most recent first)
  * @param limit - Max results (default: 50)
  * @param offset - Pagination offset (default: 0)
+ * @param includeArchived - Also return archived conversations (default: false)
  */
 export async function listConversations(
   limit?: number,
-  offset?: number
+  offset?: number,
+  includeArchived?: boolean
 ): Promise<ConversationListItem[]> {
-  return invoke('list_conversations', { limit, offset });
+  return invoke('list_conversations', { limit, offset, includeArchived });
 }
 
 /**
@@ -968,6 +1371,60 @@ export async function deleteConversation(id: string): Promise<void> {
   return invoke('delete_conversation', { id });
 }
 
+/**
+ * Render a conversation as Markdown (title, created date, and each message
+ * under a "**You:**" / "**<persona>:**" label) for pasting into a doc or ticket
+ */
+export async function exportConversationMarkdown(id: string): Promise<string> {
+  return invoke('export_conversation_markdown', { id });
+}
+
+/**
+ * Pin a conversation to the top of the sidebar (no-op if already pinned)
+ */
+export async function pinConversation(id: string): Promise<void> {
+  return invoke('pin_conversation', { id });
+}
+
+/**
+ * Unpin a conversation
+ */
+export async function unpinConversation(id: string): Promise<void> {
+  return invoke('unpin_conversation', { id });
+}
+
+/**
+ * Archive a conversation; archived conversations are hidden from
+ * listConversations unless includeArchived is set, even when pinned
+ */
+export async function archiveConversation(id: string): Promise<void> {
+  return invoke('archive_conversation', { id });
+}
+
+/** Operation applied by bulkConversationOp */
+export type ConversationOp =
+  | { AddTag: string }
+  | { RemoveTag: string }
+  | 'Archive'
+  | 'Delete';
+
+/** Per-id outcome of a bulk conversation operation */
+export interface ConversationBatchResult {
+  succeeded: string[];
+  failed: { id: string; error: string }[];
+}
+
+/**
+ * Tag, untag, archive, or delete several conversations in one transaction.
+ * Unknown ids are reported in `failed`; a database error rolls back the batch.
+ */
+export async function bulkConversationOp(
+  ids: string[],
+  op: ConversationOp
+): Promise<ConversationBatchResult> {
+  return invoke('bulk_conversation_op', { ids, op });
+}
+
 /**
  * Generate a title for a conversation using Claude
  * Falls back to truncated first message if Claude fails
@@ -1036,6 +1493,48 @@ export async function mapEmployeeColumns(headers: string[]): Promise<ColumnMappi
   return invoke('map_employee_columns', { headers });
 }
 
+/** One header mapped to a standard field */
+export interface ColumnMatch {
+  field: string;
+  header: string;
+  /** 1.0 exact, 0.9 specific alias, 0.6 weak alias, 0.4 partial */
+  map_confidence: number;
+}
+
+/** Matches below this confidence should be confirmed by the user */
+export const LOW_CONFIDENCE_THRESHOLD = 0.7;
+
+/**
+ * Map parsed headers to employee fields with a confidence per match,
+ * best matches first
+ */
+export async function mapEmployeeColumnsScored(headers: string[]): Promise<ColumnMatch[]> {
+  return invoke('map_employee_columns_scored', { headers });
+}
+
+/** How to read numeric dates like 1/2/2020: month first (us) or day first (eu) */
+export type DateLocale = 'us' | 'eu';
+
+export interface DateCheck {
+  /** Spreadsheet row number (the header is row 1) */
+  row: number;
+  raw: string;
+  /** ISO date, or null when the value couldn't be parsed */
+  normalized: string | null;
+}
+
+/**
+ * Normalize a date column from parsed rows to ISO dates, flagging
+ * values that can't be parsed. Blank cells are skipped.
+ */
+export async function checkImportDates(
+  rows: ParsedRow[],
+  column: string,
+  locale?: DateLocale
+): Promise<DateCheck[]> {
+  return invoke('check_import_dates', { rows, column, locale });
+}
+
 /**
  * Map parsed headers to performance rating fields
  */
@@ -1067,7 +1566,26 @@ export async function readFileAsBytes(file: File): Promise<Uint8Array> {
  */
 export interface BulkImportResult {
   inserted: number;
+  /** "<id>: <reason>" for each skipped row (best-effort imports only) */
   errors: string[];
+  /** The same failures with their position in the input */
+  row_errors: BulkImportError[];
+}
+
+/** A row that failed to import */
+export interface BulkImportError {
+  /** Zero-based position in the submitted batch */
+  index: number;
+  id: string;
+  reason: string;
+}
+
+/**
+ * Bulk imports are atomic by default: one failing row rolls back the batch
+ * and the call rejects. Set continue_on_error to skip failing rows instead.
+ */
+export interface BulkImportOptions {
+  continue_on_error?: boolean;
 }
 
 /**
@@ -1167,36 +1685,51 @@ export async function bulkClearData(): Promise<void> {
 /**
  * Bulk import review cycles with predefined IDs
  */
-export async function bulkImportReviewCycles(cycles: ImportReviewCycle[]): Promise<BulkImportResult> {
-  return invoke('bulk_import_review_cycles', { cycles });
+export async function bulkImportReviewCycles(
+  cycles: ImportReviewCycle[],
+  options?: BulkImportOptions
+): Promise<BulkImportResult> {
+  return invoke('bulk_import_review_cycles', { cycles, options });
 }
 
 /**
  * Bulk import employees with predefined IDs
  */
-export async function bulkImportEmployees(employees: ImportEmployee[]): Promise<BulkImportResult> {
-  return invoke('bulk_import_employees', { employees });
+export async function bulkImportEmployees(
+  employees: ImportEmployee[],
+  options?: BulkImportOptions
+): Promise<BulkImportResult> {
+  return invoke('bulk_import_employees', { employees, options });
 }
 
 /**
  * Bulk import performance ratings with predefined IDs
  */
-export async function bulkImportRatings(ratings: ImportRating[]): Promise<BulkImportResult> {
-  return invoke('bulk_import_ratings', { ratings });
+export async function bulkImportRatings(
+  ratings: ImportRating[],
+  options?: BulkImportOptions
+): Promise<BulkImportResult> {
+  return invoke('bulk_import_ratings', { ratings, options });
 }
 
 /**
  * Bulk import performance reviews with predefined IDs
  */
-export async function bulkImportReviews(reviews: ImportReview[]): Promise<BulkImportResult> {
-  return invoke('bulk_import_reviews', { reviews });
+export async function bulkImportReviews(
+  reviews: ImportReview[],
+  options?: BulkImportOptions
+): Promise<BulkImportResult> {
+  return invoke('bulk_import_reviews', { reviews, options });
 }
 
 /**
  * Bulk import eNPS responses with predefined IDs
  */
-export async function bulkImportEnps(responses: ImportEnps[]): Promise<BulkImportResult> {
-  return invoke('bulk_import_enps', { responses });
+export async function bulkImportEnps(
+  responses: ImportEnps[],
+  options?: BulkImportOptions
+): Promise<BulkImportResult> {
+  return invoke('bulk_import_enps', { responses, options });
 }
 
 /**
@@ -1211,13 +1744,15 @@ export async function verifyDataIntegrity(): Promise<IntegrityCheckResult[]> {
 // =============================================================================
 
 /** Types of PII that can be detected */
-export type PiiType = 'ssn' | 'credit_card' | 'bank_account';
+export type PiiType = 'ssn' | 'credit_card' | 'bank_account' | 'email' | 'custom';
 
 /** A single PII match found in text */
 export interface PiiMatch {
   pii_type: PiiType;
   start: number;
   end: number;
+  /** Label of the custom pattern that matched (custom matches only) */
+  custom_label?: string;
   // Note: matched_text is not serialized for security
 }
 
@@ -1242,6 +1777,110 @@ export async function scanPii(text: string): Promise<RedactionResult> {
   return invoke('scan_pii', { text });
 }
 
+export interface TokenizedResult {
+  /** Text with each PII value replaced by a token like [SSN_1] */
+  tokenized_text: string;
+  /** Token → original value; keep local, never send to the API */
+  tokens: Record<string, string>;
+  /** List of detected PII instances */
+  matches: PiiMatch[];
+  /** Whether any PII was found */
+  had_pii: boolean;
+  /** Human-readable summary (e.g., "Redacted: 1 SSN, 2 credit cards") */
+  summary: string | null;
+}
+
+/**
+ * Scan text for PII and replace each value with a stable token
+ * Send tokenized_text to Claude; use tokens to restore the user's own view
+ * @param text - The text to scan
+ * @returns Tokenized text plus the token → original map
+ */
+export async function scanAndTokenize(text: string): Promise<TokenizedResult> {
+  return invoke('scan_and_tokenize', { text });
+}
+
+/** A user-defined PII pattern applied on top of the built-in detectors */
+export interface CustomPiiPattern {
+  /** Shown in placeholders and summaries, e.g. "Employee badge" */
+  label: string;
+  /** Rust regex syntax, e.g. "EMP-\\d{6}" */
+  regex: string;
+}
+
+/**
+ * Get the saved custom PII patterns
+ */
+export async function getCustomPiiPatterns(): Promise<CustomPiiPattern[]> {
+  return invoke('get_custom_pii_patterns');
+}
+
+/**
+ * Replace the saved custom PII patterns
+ * Rejects if any regex is invalid or matches empty text
+ * @param patterns - The full list of patterns to keep
+ */
+export async function setCustomPiiPatterns(patterns: CustomPiiPattern[]): Promise<void> {
+  return invoke('set_custom_pii_patterns', { patterns });
+}
+
+export interface PiiCategoryCount {
+  pii_type: PiiType;
+  label: string;
+  count: number;
+}
+
+export interface MessagePiiBreakdown {
+  /** 0-based position in the thread (all roles counted) */
+  message_index: number;
+  message_id: string | null;
+  /** Only categories present in this message */
+  counts: PiiCategoryCount[];
+}
+
+/** PII exposure across an entire conversation */
+export interface PiiReport {
+  user_messages_scanned: number;
+  messages_with_pii: number;
+  total_matches: number;
+  /** Every category, including zero counts */
+  by_category: PiiCategoryCount[];
+  /** Only messages that contained PII */
+  messages: MessagePiiBreakdown[];
+}
+
+/**
+ * Preview PII found across every user message in a conversation
+ * Read-only and local; nothing is redacted or stored
+ * @param messagesJson - The conversation's messages_json
+ */
+export async function auditPiiReport(messagesJson: string): Promise<PiiReport> {
+  return invoke('audit_pii_report', { messagesJson });
+}
+
+export interface StoredPiiFinding {
+  /** e.g. "company.industry" or "settings.<key>" */
+  location: string;
+  /** Only categories present in this field */
+  counts: PiiCategoryCount[];
+}
+
+/** PII found in the company profile and settings */
+export interface StoredPiiReport {
+  locations_scanned: number;
+  total_matches: number;
+  /** Only locations that contained PII */
+  findings: StoredPiiFinding[];
+}
+
+/**
+ * Scan the company profile and settings for PII (including emails)
+ * Read-only; matched values are never returned
+ */
+export async function scanStoredPii(): Promise<StoredPiiReport> {
+  return invoke('scan_stored_pii');
+}
+
 // =============================================================================
 // Phase 3.4 - Audit Logging
 // =============================================================================
@@ -1256,6 +1895,11 @@ export interface AuditEntry {
   response_text: string;
   context_used: string | null; // JSON array of employee IDs
   created_at: string;
+  /** Null for entries written before usage was tracked */
+  input_tokens: number | null;
+  output_tokens: number | null;
+  /** 'cancelled' when the user stopped the answer mid-stream */
+  request_status: 'completed' | 'cancelled';
 }
 
 /**
@@ -1295,6 +1939,27 @@ export interface AuditFilter {
 export interface ExportResult {
   csv_content: string;
   row_count: number;
+  /** Suggested download filename (timestamp only) */
+  filename: string;
+}
+
+/**
+ * Audit export options
+ */
+export interface AuditExportOptions {
+  /** Scrub PII from free-text and context columns (default: true) */
+  scrub_pii: boolean;
+  /** Replace known employee names with "[emp:<id>]" tokens (default: false) */
+  pseudonymize_names?: boolean;
+}
+
+/** CSV formatting for exports (defaults: comma, no BOM, LF) */
+export interface CsvExportOptions {
+  /** Field separator, e.g. ',', ';', or '\t' */
+  delimiter?: string;
+  /** Prefix a UTF-8 byte-order mark (helps Excel detect encoding) */
+  include_bom?: boolean;
+  line_ending?: 'lf' | 'crlf';
 }
 
 /**
@@ -1343,9 +2008,99 @@ export async function countAuditEntries(filter?: AuditFilter): Promise<number> {
  * Export audit log to CSV format
  * Response is truncated to first 500 chars per entry
  * @param filter - Optional filter by conversation_id or date range
+ * @param options - Optional export options (PII scrubbing is on by default)
+ */
+export async function exportAuditLog(
+  filter?: AuditFilter,
+  options?: AuditExportOptions,
+  csvOptions?: CsvExportOptions
+): Promise<ExportResult> {
+  return invoke('export_audit_log', { filter, options, csvOptions });
+}
+
+/** Dry-run preview of an audit retention purge */
+export interface PurgePreview {
+  cutoff: string;
+  entry_count: number;
+  /** Pass to purgeAuditEntriesBefore; stops matching if the count changes */
+  confirmation_token: string;
+}
+
+export interface PurgeResult {
+  cutoff: string;
+  deleted: number;
+  /** The audit entry recording the purge */
+  meta_entry_id: string;
+}
+
+/**
+ * Count audit entries created before the cutoff (no deletion)
+ * @param cutoff - ISO 8601 timestamp
+ */
+export async function countAuditEntriesBefore(cutoff: string): Promise<PurgePreview> {
+  return invoke('count_audit_entries_before', { cutoff });
+}
+
+/**
+ * Delete audit entries created before the cutoff. Requires the token from
+ * countAuditEntriesBefore for the same cutoff; the purge itself is logged.
+ */
+export async function purgeAuditEntriesBefore(
+  cutoff: string,
+  confirmationToken: string
+): Promise<PurgeResult> {
+  return invoke('purge_audit_entries_before', { cutoff, confirmationToken });
+}
+
+/** Result of a PDF audit log export */
+export interface PdfExportResult {
+  pdf_data: number[];
+  entry_count: number;
+  page_count: number;
+  filename: string;
+}
+
+/**
+ * Export audit log as a paginated PDF with a header block and one section
+ * per entry. Long responses are truncated.
+ * @param filter - Optional filter by conversation_id or date range
+ * @param options - Optional export options (PII scrubbing is on by default)
+ */
+export async function exportAuditLogPdf(
+  filter?: AuditFilter,
+  options?: AuditExportOptions
+): Promise<PdfExportResult> {
+  return invoke('export_audit_log_pdf', { filter, options });
+}
+
+/** Outcome of re-verifying a batch of audit entries */
+export interface ReverifyReport {
+  entries_checked: number;
+  reverified: number;
+  /** Entries written before context snapshots were stored */
+  skipped_without_snapshot: number;
+  /** Re-verified entries whose stored status changed */
+  status_changed: number;
+  /** Entries that couldn't be re-verified, as "<id>: <reason>" */
+  errors: string[];
+}
+
+/**
+ * Re-run answer verification for one audit entry against the aggregates
+ * stored with it, and store the new status
+ * @param auditId - The audit entry ID
+ */
+export async function reverifyAuditEntry(auditId: string): Promise<VerificationResult> {
+  return invoke('reverify_audit_entry', { auditId });
+}
+
+/**
+ * Re-verify every audit entry matching the filter
+ * Entries without a stored snapshot are skipped
+ * @param filter - Optional filter by conversation_id or date range
  */
-export async function exportAuditLog(filter?: AuditFilter): Promise<ExportResult> {
-  return invoke('export_audit_log', { filter });
+export async function reverifyAuditLog(filter?: AuditFilter): Promise<ReverifyReport> {
+  return invoke('reverify_audit_log', { filter });
 }
 
 // =============================================================================
@@ -1361,10 +2116,13 @@ export interface BackupTableCounts {
   company: number;
   settings: number;
   audit_log: number;
+  review_templates: number;
   review_cycles: number;
+  review_cycle_events: number;
   performance_ratings: number;
   performance_reviews: number;
   enps_responses: number;
+  department_changes: number;
 }
 
 /**
@@ -1375,6 +2133,19 @@ export interface BackupMetadata {
   created_at: string; // ISO 8601 format
   app_version: string;
   table_counts: BackupTableCounts;
+  /** Set on incremental backups: rows written at or after this time (ISO 8601) */
+  incremental_since: string | null;
+  /** Argon2id parameters the file was encrypted with (null before version 1.1) */
+  kdf_params: BackupKdfParams | null;
+}
+
+/**
+ * Argon2id cost parameters recorded in a backup
+ */
+export interface BackupKdfParams {
+  memory_kib: number;
+  iterations: number;
+  parallelism: number;
 }
 
 /**
@@ -1389,12 +2160,55 @@ export interface BackupExportResult {
   table_counts: BackupTableCounts;
 }
 
+/**
+ * Options for exporting a backup
+ */
+export interface BackupExportOptions {
+  /** Minimum password strength score, 0-4 (default: 3) */
+  min_password_score?: number;
+}
+
+/**
+ * Estimated strength of a backup password
+ */
+export interface PasswordStrength {
+  /** 0 = very weak, 1 = weak, 2 = fair, 3 = strong, 4 = very strong */
+  score: number;
+  /** Suggestions for making the password stronger */
+  feedback: string[];
+}
+
+/** A backed-up table, for restoring part of a backup */
+export type BackupTableName =
+  | 'company'
+  | 'settings'
+  | 'review_templates'
+  | 'review_cycles'
+  | 'review_cycle_events'
+  | 'employees'
+  | 'performance_ratings'
+  | 'performance_reviews'
+  | 'enps_responses'
+  | 'department_changes'
+  | 'conversations'
+  | 'audit_log';
+
+/**
+ * Options for importing a backup
+ */
+export interface BackupImportOptions {
+  /** Tables to clear and restore; omitted or empty restores every table */
+  tables?: BackupTableName[];
+}
+
 /**
  * Result from importing a backup
  */
 export interface BackupImportResult {
   /** Count of records restored per table */
   restored_counts: BackupTableCounts;
+  /** Backup records skipped per table (merge imports only) */
+  skipped_counts: BackupTableCounts;
   /** Any warnings encountered during import */
   warnings: string[];
 }
@@ -1403,10 +2217,56 @@ export interface BackupImportResult {
  * Export all database tables to an encrypted backup
  * Uses AES-256-GCM encryption with Argon2 key derivation
  * @param password - Password for encryption (minimum 8 characters)
+ * @param options - Export options (legal hold employees are skipped by default)
+ * @returns Export result with encrypted data and table counts
+ */
+export async function exportBackup(
+  password: string,
+  options?: BackupExportOptions
+): Promise<BackupExportResult> {
+  return invoke('export_backup', { password, options });
+}
+
+/**
+ * Export only rows written since a point in time to an encrypted incremental backup
+ * Importing it merges into existing data instead of replacing it
+ * @param password - Password for encryption (minimum 8 characters)
+ * @param since - ISO 8601 timestamp, typically when the last full backup was taken
  * @returns Export result with encrypted data and table counts
  */
-export async function exportBackup(password: string): Promise<BackupExportResult> {
-  return invoke('export_backup', { password });
+export async function exportIncrementalBackup(
+  password: string,
+  since: string
+): Promise<BackupExportResult> {
+  return invoke('export_incremental_backup', { password, since });
+}
+
+/**
+ * Estimate backup password strength so the UI can show guidance before export
+ * @param password - Candidate backup password
+ */
+export async function estimateBackupPasswordStrength(password: string): Promise<PasswordStrength> {
+  return invoke('estimate_backup_password_strength', { password });
+}
+
+/**
+ * Re-encrypt a backup file under a new password (fresh salt and nonce)
+ * @param encryptedData - The encrypted backup data as Uint8Array
+ * @param oldPassword - Password the backup is currently encrypted with
+ * @param newPassword - New password (must pass the strength check)
+ * @returns The re-encrypted backup data as byte array
+ * @throws Error if the old password is wrong or the new one is too weak
+ */
+export async function rotateBackupPassword(
+  encryptedData: Uint8Array,
+  oldPassword: string,
+  newPassword: string
+): Promise<number[]> {
+  return invoke('rotate_backup_password', {
+    encryptedData: Array.from(encryptedData),
+    oldPassword,
+    newPassword
+  });
 }
 
 /**
@@ -1429,17 +2289,34 @@ export async function validateBackup(
 
 /**
  * Import data from an encrypted backup, replacing all existing data
- * WARNING: This deletes all current data before restoring!
+ * WARNING: This deletes all current data in the restored tables before restoring!
  * @param encryptedData - The encrypted backup data as Uint8Array
  * @param password - Password to decrypt the backup
+ * @param options - Restore only some tables (default: all)
  * @returns Import result with restored counts and any warnings
- * @throws Error if password is wrong or backup is invalid
+ * @throws Error if password is wrong, backup is invalid, or the table selection would orphan data
  */
 export async function importBackup(
   encryptedData: Uint8Array,
-  password: string
+  password: string,
+  options?: BackupImportOptions
 ): Promise<BackupImportResult> {
   return invoke('import_backup', {
+    encryptedData: Array.from(encryptedData),
+    password,
+    options
+  });
+}
+
+/**
+ * Merge a backup into existing data without clearing anything
+ * Rows that collide with existing ones, or whose employee is missing, are skipped
+ */
+export async function importBackupMerge(
+  encryptedData: Uint8Array,
+  password: string
+): Promise<BackupImportResult> {
+  return invoke('import_backup_merge', {
     encryptedData: Array.from(encryptedData),
     password
   });
@@ -1470,6 +2347,56 @@ export function downloadBackupFile(data: number[], filename: string): void {
   URL.revokeObjectURL(url);
 }
 
+// =============================================================================
+// Database Maintenance
+// =============================================================================
+
+/** Database size before and after VACUUM */
+export interface VacuumReport {
+  size_before_bytes: number;
+  size_after_bytes: number;
+}
+
+/** Outcome of SQLite's integrity check */
+export interface IntegrityReport {
+  ok: boolean;
+  /** Problems as SQLite reports them; empty when ok */
+  problems: string[];
+}
+
+export interface TableStats {
+  name: string;
+  rows: number;
+}
+
+export interface DatabaseStats {
+  size_bytes: number;
+  /** Free-list space a VACUUM would give back */
+  reclaimable_bytes: number;
+  tables: TableStats[];
+}
+
+/**
+ * Rebuild the database file to reclaim space after large imports or purges
+ */
+export async function vacuumDatabase(): Promise<VacuumReport> {
+  return invoke('vacuum_database');
+}
+
+/**
+ * Check the database file for corruption
+ */
+export async function checkDatabaseIntegrity(): Promise<IntegrityReport> {
+  return invoke('check_database_integrity');
+}
+
+/**
+ * Database size, reclaimable space, and per-table row counts
+ */
+export async function getDatabaseStats(): Promise<DatabaseStats> {
+  return invoke('get_database_stats');
+}
+
 // =============================================================================
 // V2.2.1 - Review Highlights
 // =============================================================================
@@ -1502,9 +2429,29 @@ export async function extractReviewHighlight(reviewId: string): Promise<ReviewHi
 /**
  * Extract highlights for multiple reviews in batch
  * @param reviewIds - Array of review IDs to process
+ * @param jobId - Optional job ID from a previous run to resume from its checkpoint
+ */
+export async function extractHighlightsBatch(
+  reviewIds: string[],
+  jobId?: string
+): Promise<BatchExtractionResult> {
+  return invoke('extract_highlights_batch', { reviewIds, jobId });
+}
+
+/**
+ * Stop the running batch extraction after the review in flight
+ * The batch resolves with cancelled: true; pass its job_id to resume later
  */
-export async function extractHighlightsBatch(reviewIds: string[]): Promise<BatchExtractionResult> {
-  return invoke('extract_highlights_batch', { reviewIds });
+export async function cancelHighlightsExtraction(): Promise<void> {
+  return invoke('cancel_highlights_extraction');
+}
+
+/**
+ * Estimate tokens, cost and duration of extracting highlights for these reviews
+ * @param reviewIds - Review IDs that would be passed to extractHighlightsBatch
+ */
+export async function estimateExtractionCost(reviewIds: string[]): Promise<ExtractionEstimate> {
+  return invoke('estimate_extraction_cost', { reviewIds });
 }
 
 /**
@@ -1533,6 +2480,15 @@ export async function generateEmployeeSummary(employeeId: string): Promise<Emplo
   return invoke('generate_employee_summary', { employeeId });
 }
 
+/**
+ * Regenerate career summaries for many employees (e.g. after a big import)
+ * Listen for "summary-batch-progress" events to track progress
+ * @param employeeIds - Employees to summarize; those without highlights are skipped
+ */
+export async function generateSummariesBatch(employeeIds: string[]): Promise<BatchSummaryResult> {
+  return invoke('generate_summaries_batch', { employeeIds });
+}
+
 /**
  * Invalidate highlight and summary when a review is updated
  * Call this after updating a performance review to trigger re-extraction
@@ -1546,6 +2502,36 @@ export async function invalidateReviewHighlight(
   return invoke('invalidate_review_highlight', { reviewId, employeeId });
 }
 
+/**
+ * IDs of highlights whose review or employee no longer exists
+ */
+export async function findOrphanedHighlights(): Promise<string[]> {
+  return invoke('find_orphaned_highlights');
+}
+
+/**
+ * Delete orphaned highlights
+ * @returns Number of highlights removed
+ */
+export async function purgeOrphanedHighlights(): Promise<number> {
+  return invoke('purge_orphaned_highlights');
+}
+
+/**
+ * IDs of employee summaries whose employee no longer exists
+ */
+export async function findOrphanedSummaries(): Promise<string[]> {
+  return invoke('find_orphaned_summaries');
+}
+
+/**
+ * Delete orphaned employee summaries
+ * @returns Number of summaries removed
+ */
+export async function purgeOrphanedSummaries(): Promise<number> {
+  return invoke('purge_orphaned_summaries');
+}
+
 // =============================================================================
 // V2.3.2g-l - Insight Canvas
 // =============================================================================
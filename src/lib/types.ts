@@ -9,6 +9,8 @@ export interface Employee {
   id: string;
   email: string;
   full_name: string;
+  /** Name exactly as it appeared in the import file */
+  original_full_name?: string;
   department?: string;
   job_title?: string;
   manager_id?: string;
@@ -26,6 +28,10 @@ export interface Employee {
   termination_reason?: 'voluntary' | 'involuntary' | 'retirement' | 'other';
 
   extra_fields?: Record<string, unknown>;
+  /** Under litigation/investigation hold: flagged in AI context, skipped from exports */
+  legal_hold: boolean;
+  /** Legal entity the employee belongs to ('default' is the primary company) */
+  company_id?: string;
   created_at: string;
   updated_at: string;
 }
@@ -53,6 +59,12 @@ export interface Message {
   chartData?: ChartData;
   /** V2.3.2h: Analytics request for pinning to insight canvas */
   analyticsRequest?: AnalyticsRequest;
+  /** Turn metadata for assistant messages (from "chat-complete") */
+  model?: string;
+  persona_id?: string;
+  input_tokens?: number;
+  output_tokens?: number;
+  verification_status?: VerificationStatus;
 }
 
 // =============================================================================
@@ -63,6 +75,7 @@ export type ChatErrorType =
   | 'no_api_key'
   | 'auth_error'
   | 'rate_limit'
+  | 'message_too_long'
   | 'network_error'
   | 'api_error'
   | 'unknown';
@@ -73,6 +86,8 @@ export interface ChatError {
   details: string;
   retryable: boolean;
   originalContent?: string;
+  /** Anthropic request id, for support reports */
+  requestId?: string;
 }
 
 // =============================================================================
@@ -115,6 +130,7 @@ export type QueryType =
   | 'Individual'
   | 'Comparison'
   | 'Attrition'
+  | 'Compensation'
   | 'General';
 
 /** Organization aggregates for verification ground truth */
@@ -129,12 +145,39 @@ export interface OrgAggregates {
   employees_with_no_rating: number;
   enps: EnpsAggregate;
   attrition: AttritionStats;
+  tenure: TenureStats;
+  /** Only present when diversity_reporting_enabled is on */
+  diversity: DiversityStats | null;
+  /** Sections that timed out; their fields hold defaults */
+  missing_sections: AggregateSection[];
 }
 
+export type AggregateSection = 'departments' | 'performance' | 'enps' | 'attrition' | 'tenure' | 'diversity';
+
 export interface DepartmentCount {
   name: string;
   count: number;
   percentage: number;
+  /** Average latest rating of active employees; null when none are rated */
+  avg_rating: number | null;
+  /** eNPS from active employees' latest responses; null when none responded */
+  enps_score: number | null;
+}
+
+/** Count and percentage are null when the category is suppressed */
+export interface CategoryCount {
+  name: string;
+  count: number | null;
+  percentage: number | null;
+  suppressed: boolean;
+}
+
+/** Self-reported gender/ethnicity of active employees; small groups fold into "Other" (suppressed when still under 5) */
+export interface DiversityStats {
+  by_gender: CategoryCount[];
+  by_ethnicity: CategoryCount[];
+  /** Active employees with neither gender nor ethnicity on file */
+  not_disclosed: number;
 }
 
 export interface RatingDistribution {
@@ -142,6 +185,10 @@ export interface RatingDistribution {
   exceeds: number;
   meets: number;
   needs_improvement: number;
+  /** Active employees with a rating; bucket shares are of this count */
+  rated_count: number;
+  /** All active employees, rated or not */
+  active_count: number;
 }
 
 export interface EnpsAggregate {
@@ -160,6 +207,31 @@ export interface AttritionStats {
   turnover_rate_annualized: number | null;
 }
 
+export interface TenureBandCount {
+  label: string;
+  count: number;
+}
+
+export interface TenuredEmployee {
+  id: string;
+  full_name: string;
+  department: string | null;
+  hire_date: string;
+  tenure_years: number;
+}
+
+/** Tenure of active employees with a usable hire_date */
+export interface TenureStats {
+  employees_counted: number;
+  /** Active employees excluded for a missing or unparseable hire_date */
+  employees_without_hire_date: number;
+  avg_tenure_years: number | null;
+  median_tenure_years: number | null;
+  bands: TenureBandCount[];
+  /** Longest-tenured first */
+  longest_tenured: TenuredEmployee[];
+}
+
 /** Result from get_system_prompt command (V2.1.4, V2.2.2) */
 export interface SystemPromptResult {
   system_prompt: string;
@@ -167,6 +239,8 @@ export interface SystemPromptResult {
   aggregates: OrgAggregates | null;
   query_type: QueryType;
   metrics: RetrievalMetrics; // V2.2.2: retrieval observability
+  /** Persona the prompt was built with */
+  persona_id: string;
 }
 
 // =============================================================================
@@ -201,6 +275,8 @@ export interface TokenUsage {
 export interface RetrievalMetrics {
   /** Query type classification */
   query_type: QueryType;
+  /** Secondary intent for compound queries */
+  secondary_query_type: QueryType | null;
   /** Number of employees matched by query */
   employees_found: number;
   /** Number of employees included in context */
@@ -227,8 +303,10 @@ export interface Company {
   created_at: string;
 }
 
-/** Input for creating or updating the company profile */
+/** Input for creating or updating a company profile */
 export interface UpsertCompany {
+  /** Company to write; the primary ('default') profile when omitted */
+  id?: string;
   name: string;
   state: string; // 2-letter US state code (e.g., CA, NY, TX)
   industry?: string;
@@ -275,6 +353,50 @@ export interface ReviewCycle {
   end_date: string;
   status: 'active' | 'closed';
   created_at: string;
+  template_id?: string;
+}
+
+export type ReviewSection =
+  | 'strengths'
+  | 'areas_for_improvement'
+  | 'accomplishments'
+  | 'goals_next_period'
+  | 'manager_comments'
+  | 'self_assessment';
+
+export interface TemplateSection {
+  section: ReviewSection;
+  prompt?: string;               // guidance shown when pre-populating
+  required: boolean;
+}
+
+export interface ReviewTemplate {
+  id: string;
+  name: string;
+  description?: string;
+  sections: TemplateSection[];   // display order
+  created_at: string;
+}
+
+/** One status transition in a review cycle's history */
+export interface CycleEvent {
+  id: string;
+  cycle_id: string;
+  old_status: 'active' | 'closed' | null; // null when the cycle was created
+  new_status: 'active' | 'closed';
+  created_at: string;
+}
+
+export interface CycleReminder {
+  cycle_id: string;
+  name: string;
+  kind: 'overdue' | 'ending_soon' | 'starting_soon' | 'misconfigured';
+  start_date: string;
+  end_date: string;
+  days_until: number | null; // negative when overdue, null when misconfigured
+  missing_ratings: number;
+  missing_reviews: number;
+  reason: string | null;
 }
 
 export interface PerformanceRating {
@@ -290,6 +412,17 @@ export interface PerformanceRating {
   updated_at: string;
 }
 
+export interface RatingHistoryPoint {
+  review_cycle_id: string;
+  cycle_name: string;
+  cycle_start_date: string;
+  rating_date?: string;
+  overall_rating: number;
+  goals_rating?: number;
+  competencies_rating?: number;
+  delta?: number;                // vs previous rated cycle; absent on the first point
+}
+
 export interface PerformanceReview {
   id: string;
   employee_id: string;
@@ -399,6 +532,10 @@ export interface ParseResult {
   total_rows: number;
   /** Detected file format (CSV, TSV, XLSX, XLS) */
   file_format: string;
+  /** Text encoding the file was read as (CSV/TSV only), e.g. "UTF-16LE" */
+  encoding: string | null;
+  /** Field delimiter that was used (CSV/TSV only) */
+  delimiter: string | null;
   /** Warnings during parsing (e.g., skipped rows) */
   warnings: string[];
 }
@@ -415,6 +552,9 @@ export interface ParsePreview {
   total_rows: number;
   /** Detected file format */
   file_format: string;
+  /** Text encoding and delimiter, for CSV/TSV */
+  encoding: string | null;
+  delimiter: string | null;
 }
 
 /**
@@ -479,10 +619,50 @@ export interface EmployeeSummary {
  * Result of batch extraction operation
  */
 export interface BatchExtractionResult {
+  /** Pass back to extractHighlightsBatch to resume an interrupted run */
+  job_id: string;
   total: number;
   succeeded: number;
   failed: number;
   errors: string[];
+  /** The run was cancelled before every review was attempted */
+  cancelled: boolean;
+}
+
+/**
+ * Projected usage of a batch extraction (no API calls are made)
+ */
+export interface ExtractionEstimate {
+  model: string;
+  /** Reviews that would be sent to the API */
+  review_count: number;
+  /** Skipped because highlights already exist */
+  already_extracted: number;
+  not_found: number;
+  input_tokens: number;
+  output_tokens: number;
+  estimated_cost_usd: number;
+  estimated_seconds: number;
+}
+
+export interface BatchSummaryResult {
+  total: number;
+  succeeded: number;
+  failed: number;
+  /** Employees with no highlights to summarize */
+  skipped_employee_ids: string[];
+  errors: string[];
+}
+
+/** Payload of "summary-batch-progress" events */
+export interface ExtractionProgress {
+  total: number;
+  completed: number;
+  failed: number;
+  is_running: boolean;
+  /** Review that just finished (highlight extraction only) */
+  last_review_id: string | null;
+  last_succeeded: boolean | null;
 }
 
 /**